@@ -1,17 +1,50 @@
 //! The application.
 
-use std::path::{Path, PathBuf};
+use std::{borrow::Cow, collections::BTreeSet, path::PathBuf};
 
-use clap::{Args, Parser, Subcommand};
+use clap::{ArgAction, Args, CommandFactory, Parser, Subcommand};
 use miette::Diagnostic;
 use thiserror::Error;
+use tracing::{debug, info};
 
+#[cfg(feature = "links")]
+use crate::commands::check::Links;
+#[cfg(feature = "forge")]
+use crate::commands::{create::from_pull_request, import::import};
 use crate::{
-    commands::{build::build, create::create, preview::preview},
-    discover::discover,
+    commands::{
+        build::{build, Format as ReportFormat, Options, Target},
+        cache::clear,
+        check::{check, Age, Format, Options as CheckOptions},
+        config::{show, validate},
+        create::{
+            add_counter, auto_name, create, from_template, is_interactive, warn_unknown_type,
+        },
+        doctor::doctor,
+        fixtures::{generate, DEFAULT_COUNT, DEFAULT_SEED},
+        list::{list, Format as ListFormat},
+        man::man,
+        migrate::split,
+        normalize::{normalize, Options as NormalizeOptions},
+        open::{open, Target as OpenTarget},
+        preview::{preview, watch, Mode},
+        render::render,
+        resolve::resolve,
+        rollback::{rollback, Options as RollbackOptions},
+        variables::variables,
+        verify::{verify, Options as VerifyOptions},
+    },
+    config::{Overrides, Wrap},
+    discover::{discover, discover_with_context, discover_with_path},
+    exit::Code,
+    fragment::{Id, Selection},
     init::init,
     load::load,
-    workspace::Workspace,
+    network::offline_enabled,
+    pretty::color_enabled,
+    report::{self, level},
+    version,
+    workspace::{Source, Workspace},
 };
 
 /// Represents global options of `changelogging`.
@@ -36,6 +69,189 @@ pub struct Globals {
         help = "Use the config from this file"
     )]
     pub config: Option<PathBuf>,
+
+    /// The path to the context file to use instead of `context.toml` next to the config file.
+    #[arg(
+        long,
+        global = true,
+        name = "CONTEXT",
+        help = "Supply the context from this file"
+    )]
+    pub context: Option<PathBuf>,
+
+    /// The level of verbosity.
+    #[arg(
+        short = 'v',
+        long,
+        global = true,
+        action = ArgAction::Count,
+        conflicts_with = "quiet",
+        help = "Show more information (can be repeated)"
+    )]
+    pub verbose: u8,
+
+    /// Whether to hide everything except for errors.
+    #[arg(
+        short = 'q',
+        long,
+        global = true,
+        action,
+        help = "Hide everything except for errors"
+    )]
+    pub quiet: bool,
+
+    /// The profile to apply.
+    #[arg(
+        short = 'P',
+        long,
+        global = true,
+        name = "PROFILE",
+        help = "Apply the profile with this name"
+    )]
+    pub profile: Option<String>,
+
+    /// The fragments directory to use instead of `paths.directory`.
+    #[arg(
+        long,
+        global = true,
+        name = "FRAGMENTS",
+        help = "Use this fragments directory instead of the configured one"
+    )]
+    pub fragments_dir: Option<PathBuf>,
+
+    /// The changelog file to use instead of `paths.output`.
+    #[arg(
+        long,
+        global = true,
+        name = "CHANGELOG",
+        help = "Use this changelog instead of the configured one"
+    )]
+    pub changelog: Option<PathBuf>,
+
+    /// The line length to use instead of `wrap`.
+    #[arg(
+        long,
+        global = true,
+        name = "WRAP",
+        help = "Wrap entries at this line length instead of the configured one"
+    )]
+    pub wrap: Option<Wrap>,
+
+    /// The start marker to use instead of `start`.
+    #[arg(
+        long,
+        global = true,
+        name = "START",
+        help = "Use this start marker instead of the configured one"
+    )]
+    pub start: Option<String>,
+
+    /// Whether to never access the network.
+    #[arg(
+        long,
+        global = true,
+        action,
+        help = "Never access the network, skipping network-backed features"
+    )]
+    pub offline: bool,
+}
+
+impl Globals {
+    /// Checks whether offline mode is enabled, see [`offline_enabled`].
+    pub fn offline(&self) -> bool {
+        offline_enabled(self.offline)
+    }
+
+    /// Loads the workspace, applying the profile and the overrides specified by these options.
+    ///
+    /// When the config file is given, paths are resolved against its directory,
+    /// see [`Workspace::resolve`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] when discovering or loading the workspace
+    /// or applying the profile fails, as well as if types or the version are invalid.
+    pub fn workspace(&self) -> Result<Workspace<'static>, Error> {
+        let mut workspace = if let Some(path) = self.config.as_ref() {
+            let source = Source::Config(path.clone());
+
+            info!("using {source}");
+
+            let mut workspace: Workspace<'static> = match self.context.as_ref() {
+                Some(context) => Workspace::load_with_context(path, context),
+                None => load(path),
+            }
+            .map_err(Error::workspace)?;
+
+            workspace.source = Some(source);
+
+            // paths are relative to the config file, unless it is in the current directory
+
+            if let Some(root) = path.parent().filter(|root| !root.as_os_str().is_empty()) {
+                debug!("resolving paths against `{}`", root.display());
+
+                workspace.resolve(root.to_owned());
+            }
+
+            workspace
+        } else if let Some(context) = self.context.as_ref() {
+            info!("using the context from `{}`", context.display());
+
+            discover_with_context(context).map_err(Error::discover)?
+        } else {
+            discover().map_err(Error::discover)?
+        };
+
+        if let Some(name) = self.profile.as_ref() {
+            info!("applying profile `{name}`");
+
+            workspace = workspace.with_profile(name).map_err(Error::profile)?;
+        }
+
+        let overrides = self.overrides();
+
+        for key in overrides.keys() {
+            debug!("overriding `{key}`");
+        }
+
+        workspace.config.apply(overrides);
+
+        let warnings = workspace.config.check_types().map_err(Error::types)?;
+
+        for warning in warnings {
+            report::warn(warning);
+        }
+
+        version::check(&workspace.context.version, workspace.config.version)
+            .map_err(Error::version)?;
+
+        Ok(workspace)
+    }
+
+    /// Returns the path to the config file, either the one given or the discovered one.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] when discovering the workspace fails.
+    pub fn config_path(&self) -> Result<PathBuf, Error> {
+        if let Some(path) = self.config.as_ref() {
+            return Ok(path.clone());
+        }
+
+        discover_with_path()
+            .map(|(_, path)| path)
+            .map_err(Error::discover)
+    }
+
+    /// Returns [`Overrides`] specified by these options.
+    pub fn overrides(&self) -> Overrides<'static> {
+        Overrides {
+            directory: self.fragments_dir.clone().map(Cow::Owned),
+            output: self.changelog.clone().map(Cow::Owned),
+            start: self.start.clone().map(Cow::Owned),
+            wrap: self.wrap,
+        }
+    }
 }
 
 /// Represents the `changelogging` application.
@@ -67,12 +283,51 @@ pub enum ErrorSource {
     Discover(#[from] crate::discover::Error),
     /// Workspace loading errors.
     Workspace(#[from] crate::workspace::Error),
+    /// Profile errors.
+    Profile(#[from] crate::workspace::ProfileError),
+    /// Types errors.
+    Types(#[from] crate::config::TypesError),
+    /// Version errors.
+    Version(#[from] crate::version::InvalidError),
     /// `build` errors.
     Build(#[from] crate::commands::build::Error),
+    /// `rollback` errors.
+    Rollback(#[from] crate::commands::rollback::Error),
+    /// `resolve` errors.
+    Resolve(#[from] crate::commands::resolve::Error),
+    /// `cache` errors.
+    Cache(#[from] crate::commands::cache::Error),
+    /// `check` errors.
+    Check(#[from] crate::commands::check::Error),
     /// `preview` errors.
     Preview(#[from] crate::commands::preview::Error),
     /// `create` errors.
     Create(#[from] crate::commands::create::Error),
+    /// `config` errors.
+    Config(#[from] crate::commands::config::Error),
+    /// `doctor` errors.
+    Doctor(#[from] crate::commands::doctor::Error),
+    /// `man` errors.
+    Man(#[from] crate::commands::man::Error),
+    /// `generate-fixtures` errors.
+    GenerateFixtures(#[from] crate::commands::fixtures::Error),
+    /// `variables` errors.
+    Variables(#[from] crate::commands::variables::Error),
+    /// `render` errors.
+    Render(#[from] crate::commands::render::Error),
+    /// `normalize` errors.
+    Normalize(#[from] crate::commands::normalize::Error),
+    /// `list` errors.
+    List(#[from] crate::commands::list::Error),
+    /// `open` errors.
+    Open(#[from] crate::commands::open::Error),
+    /// `import-prs` errors.
+    #[cfg(feature = "forge")]
+    Import(#[from] crate::commands::import::Error),
+    /// `migrate` errors.
+    Migrate(#[from] crate::commands::migrate::Error),
+    /// `verify` errors.
+    Verify(#[from] crate::commands::verify::Error),
 }
 
 /// Represents errors that can occur during application runs.
@@ -95,6 +350,38 @@ impl Error {
         Self { source }
     }
 
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Init(_) => Code::Io,
+            ErrorSource::Discover(_)
+            | ErrorSource::Workspace(_)
+            | ErrorSource::Profile(_)
+            | ErrorSource::Types(_)
+            | ErrorSource::Version(_) => Code::Config,
+            ErrorSource::Build(error) => error.exit_code(),
+            ErrorSource::Rollback(error) => error.exit_code(),
+            ErrorSource::Resolve(error) => error.exit_code(),
+            ErrorSource::Cache(error) => error.exit_code(),
+            ErrorSource::Check(error) => error.exit_code(),
+            ErrorSource::Preview(error) => error.exit_code(),
+            ErrorSource::Create(error) => error.exit_code(),
+            ErrorSource::Config(error) => error.exit_code(),
+            ErrorSource::Doctor(error) => error.exit_code(),
+            ErrorSource::Man(error) => error.exit_code(),
+            ErrorSource::GenerateFixtures(error) => error.exit_code(),
+            ErrorSource::Variables(error) => error.exit_code(),
+            ErrorSource::Render(error) => error.exit_code(),
+            ErrorSource::Normalize(error) => error.exit_code(),
+            ErrorSource::List(error) => error.exit_code(),
+            ErrorSource::Open(error) => error.exit_code(),
+            #[cfg(feature = "forge")]
+            ErrorSource::Import(error) => error.exit_code(),
+            ErrorSource::Migrate(error) => error.exit_code(),
+            ErrorSource::Verify(error) => error.exit_code(),
+        }
+    }
+
     /// Constructs [`Self`] from [`Error`].
     ///
     /// [`Error`]: crate::init::Error
@@ -116,6 +403,27 @@ impl Error {
         Self::new(error.into())
     }
 
+    /// Constructs [`Self`] from [`ProfileError`].
+    ///
+    /// [`ProfileError`]: crate::workspace::ProfileError
+    pub fn profile(error: crate::workspace::ProfileError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`TypesError`].
+    ///
+    /// [`TypesError`]: crate::config::TypesError
+    pub fn types(error: crate::config::TypesError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`InvalidError`].
+    ///
+    /// [`InvalidError`]: crate::version::InvalidError
+    pub fn version(error: crate::version::InvalidError) -> Self {
+        Self::new(error.into())
+    }
+
     /// Constructs [`Self`] from [`Error`].
     ///
     /// [`Error`]: crate::commands::build::Error
@@ -123,6 +431,34 @@ impl Error {
         Self::new(error.into())
     }
 
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::rollback::Error
+    pub fn rollback(error: crate::commands::rollback::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::resolve::Error
+    pub fn resolve(error: crate::commands::resolve::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::cache::Error
+    pub fn cache(error: crate::commands::cache::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::check::Error
+    pub fn check(error: crate::commands::check::Error) -> Self {
+        Self::new(error.into())
+    }
+
     /// Constructs [`Self`] from [`Error`]
     ///
     /// [`Error`]: crate::commands::preview::Error
@@ -136,6 +472,91 @@ impl Error {
     pub fn create(error: crate::commands::create::Error) -> Self {
         Self::new(error.into())
     }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::config::Error
+    pub fn config(error: crate::commands::config::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::doctor::Error
+    pub fn doctor(error: crate::commands::doctor::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::man::Error
+    pub fn man(error: crate::commands::man::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::fixtures::Error
+    pub fn generate_fixtures(error: crate::commands::fixtures::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::variables::Error
+    pub fn variables(error: crate::commands::variables::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::render::Error
+    pub fn render(error: crate::commands::render::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::normalize::Error
+    pub fn normalize(error: crate::commands::normalize::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::list::Error
+    pub fn list(error: crate::commands::list::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::open::Error
+    pub fn open(error: crate::commands::open::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::import::Error
+    #[cfg(feature = "forge")]
+    pub fn import(error: crate::commands::import::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::migrate::Error
+    pub fn migrate(error: crate::commands::migrate::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::commands::verify::Error
+    pub fn verify(error: crate::commands::verify::Error) -> Self {
+        Self::new(error.into())
+    }
 }
 
 impl App {
@@ -147,26 +568,89 @@ impl App {
     pub fn run(self) -> Result<(), Error> {
         let globals = self.globals;
 
-        init(globals.directory).map_err(Error::init)?;
+        report::init(level(globals.verbose, globals.quiet));
 
-        let workspace = if let Some(path) = globals.config {
-            load(path).map_err(Error::workspace)?
-        } else {
-            discover().map_err(Error::discover)?
-        };
+        // generate manual pages relative to where the application is invoked
+
+        if let Command::Man(man) = self.command {
+            return man.run().map_err(Error::man);
+        }
+
+        init(globals.directory.as_ref()).map_err(Error::init)?;
+
+        let loaded = globals.workspace();
+
+        if let Command::Doctor(doctor) = self.command {
+            return doctor.run(loaded).map_err(Error::doctor);
+        }
+
+        let workspace = loaded?;
+
+        let overrides = globals.overrides();
 
         match self.command {
             Command::Build(build) => {
                 build.run(workspace).map_err(Error::build)?;
             }
+            Command::Rollback(rollback) => {
+                rollback.run(workspace).map_err(Error::rollback)?;
+            }
+            Command::Resolve(resolve) => {
+                resolve.run(workspace).map_err(Error::resolve)?;
+            }
+            Command::Check(check) => {
+                check
+                    .run(workspace, globals.offline())
+                    .map_err(Error::check)?;
+            }
             Command::Preview(preview) => {
-                preview.run(workspace).map_err(Error::preview)?;
+                preview.run(workspace, &globals).map_err(Error::preview)?;
             }
             Command::Create(create) => {
-                let directory = workspace.config.paths.directory;
+                create
+                    .run(&workspace, globals.offline())
+                    .map_err(Error::create)?;
+            }
+            Command::Config(config) => {
+                config.run(workspace, &overrides).map_err(Error::config)?;
+            }
+            Command::Variables(variables) => {
+                variables.run(workspace).map_err(Error::variables)?;
+            }
+            Command::Render(render) => {
+                render.run(workspace).map_err(Error::render)?;
+            }
+            Command::Normalize(normalize) => {
+                normalize.run(workspace).map_err(Error::normalize)?;
+            }
+            Command::List(list) => {
+                list.run(workspace).map_err(Error::list)?;
+            }
+            Command::Open(open) => {
+                let target = open.target(&globals)?;
 
-                create.run(directory).map_err(Error::create)?;
+                open.run(workspace, target).map_err(Error::open)?;
+            }
+            #[cfg(feature = "forge")]
+            Command::ImportPrs(import) => {
+                import
+                    .run(&workspace, globals.offline())
+                    .map_err(Error::import)?;
             }
+            Command::Migrate(migrate) => {
+                migrate.run(workspace).map_err(Error::migrate)?;
+            }
+            Command::Verify(verify) => {
+                verify.run(workspace).map_err(Error::verify)?;
+            }
+            Command::GenerateFixtures(fixtures) => {
+                fixtures.run(&workspace).map_err(Error::generate_fixtures)?;
+            }
+            Command::Doctor(_) => unreachable!("`doctor` is handled before loading"),
+            Command::Cache(cache) => {
+                cache.run(&workspace).map_err(Error::cache)?;
+            }
+            Command::Man(_) => unreachable!("`man` is handled before initialization"),
         };
 
         Ok(())
@@ -179,20 +663,70 @@ pub enum Command {
     /// The `build` subcommand.
     #[command(about = "Build changelogs from fragments")]
     Build(BuildCommand),
+    /// The `rollback` subcommand.
+    #[command(about = "Roll back the last build")]
+    Rollback(RollbackCommand),
+    /// The `resolve` subcommand.
+    #[command(about = "Resolve merge conflicts in the changelog")]
+    Resolve(ResolveCommand),
+    /// The `check` subcommand.
+    #[command(about = "Check changelog fragments")]
+    Check(CheckCommand),
     /// The `preview` subcommand.
     #[command(about = "Preview changelog entries")]
     Preview(PreviewCommand),
     /// The `create` subcommand.
     #[command(about = "Create changelog fragments")]
     Create(CreateCommand),
+    /// The `config` subcommand.
+    #[command(about = "Inspect the configuration")]
+    Config(ConfigCommand),
+    /// The `doctor` subcommand.
+    #[command(about = "Diagnose setup problems")]
+    Doctor(DoctorCommand),
+    /// The `variables` subcommand.
+    #[command(about = "List variables available to templates")]
+    Variables(VariablesCommand),
+    /// The `render` subcommand.
+    #[command(about = "Render the entry with the example fragment")]
+    Render(RenderCommand),
+    /// The `normalize` subcommand.
+    #[command(about = "Rewrite fragments into their canonical form")]
+    Normalize(NormalizeCommand),
+    /// The `list` subcommand.
+    #[command(about = "List pending fragments")]
+    List(ListCommand),
+    /// The `open` subcommand.
+    #[command(about = "Open the changelog, the config or fragments in the editor")]
+    Open(OpenCommand),
+    /// The `cache` subcommand.
+    #[command(about = "Manage the cache of rendered fragments")]
+    Cache(CacheCommand),
+    /// The `import-prs` subcommand.
+    #[cfg(feature = "forge")]
+    #[command(about = "Import merged pull requests as fragments")]
+    ImportPrs(ImportPrsCommand),
+    /// The `migrate` subcommand.
+    #[command(about = "Migrate changelogs to fragments")]
+    Migrate(MigrateCommand),
+    /// The `verify` subcommand.
+    #[command(
+        about = "Verify that released entries match their fragments",
+        disable_version_flag = true
+    )]
+    Verify(VerifyCommand),
+    /// The `man` subcommand.
+    #[command(about = "Generate manual pages", hide = true)]
+    Man(ManCommand),
+    /// The `generate-fixtures` subcommand.
+    #[command(about = "Generate synthesized fragments for benchmarking", hide = true)]
+    GenerateFixtures(GenerateFixturesCommand),
 }
 
 /// Represents the `build` subcommand.
 #[derive(Debug, Args)]
 pub struct BuildCommand {
-    /// The date to use. If not provided, [`today`] is used.
-    ///
-    /// [`today`]: crate::date::today
+    /// The date to use, defaulting to today.
     #[arg(
         short = 'd',
         long,
@@ -208,89 +742,1164 @@ pub struct BuildCommand {
     /// Whether to remove fragments.
     #[arg(short = 'r', long, action, help = "Remove the fragments")]
     pub remove: bool,
-}
-
-impl BuildCommand {
-    /// Runs the `build` subcommand.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`Error`] when any error is encountered.
-    ///
-    /// [`Error`]: crate::commands::build::Error
-    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::build::Error> {
-        build(workspace, self.date, self.stage, self.remove)
-    }
-}
 
-/// Represents the `preview` subcommand.
-#[derive(Debug, Args)]
-pub struct PreviewCommand {
-    /// The date to use. If not provided, [`today`] is used.
-    ///
-    /// [`today`]: crate::date::today
+    /// Whether to insert the start marker if it is missing.
     #[arg(
-        short = 'd',
         long,
-        name = "DATE",
-        help = "Use the date provided instead of today"
+        action,
+        help = "Insert the start marker into the changelog if it is missing"
     )]
-    pub date: Option<String>,
-}
-
-impl PreviewCommand {
-    /// Runs the `preview` subcommand.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`Error`] when any error is encountered.
-    ///
-    /// [`Error`]: crate::commands::preview::Error
-    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::preview::Error> {
-        preview(workspace, self.date)
-    }
-}
+    pub insert_marker: bool,
 
-/// Represents the `create` subcommand.
-#[derive(Debug, Args)]
-#[command(about = "Create changelog fragments")]
-pub struct CreateCommand {
-    /// The name of the fragment.
-    #[arg(name = "NAME", help = "Write to the directory with this file name")]
-    pub name: String,
+    /// Whether to fail if there are no fragments.
+    #[arg(long, action, help = "Fail if there are no fragments to build")]
+    pub require_fragments: bool,
 
-    /// The fragment content, if it is passed as the argument.
+    /// Whether to fold prerelease entries into the final release entry.
     #[arg(
-        short = 'c',
         long,
-        name = "TEXT",
-        help = "Pass the fragment content as this argument"
+        action,
+        help = "Merge prerelease entries of the version into the final entry"
     )]
-    pub content: Option<String>,
+    pub consolidate_prereleases: bool,
 
-    /// Whether to open the default editor to edit the fragment content.
+    /// Whether to replace the existing entry of the version being built, if any.
     #[arg(
-        short = 'e',
+        short = 'a',
         long,
         action,
-        help = "Open the default editor to edit the content"
+        help = "Replace the existing entry of the version instead of adding another one"
     )]
-    pub edit: bool,
+    pub amend: bool,
 
-    /// Whether to add the fragment via `git`.
+    /// The path to write the changelog to instead of `paths.output` (`-` means `stdout`).
+    #[arg(
+        short = 'o',
+        long,
+        name = "PATH",
+        conflicts_with = "remove",
+        help = "Write the changelog to this path instead (`-` for stdout)"
+    )]
+    pub output: Option<PathBuf>,
+
+    /// The path to write release notes (that is, the entry alone) to.
+    #[arg(
+        short = 'n',
+        long,
+        name = "NOTES",
+        help = "Also write the entry alone to this path"
+    )]
+    pub notes_file: Option<PathBuf>,
+
+    /// Whether to write release notes only.
+    #[arg(
+        long,
+        action,
+        requires = "NOTES",
+        conflicts_with_all = ["PATH", "remove"],
+        help = "Write release notes only, leaving the changelog intact"
+    )]
+    pub notes_only: bool,
+
+    /// The format to print the report of what happened in.
+    #[arg(
+        long,
+        value_enum,
+        name = "FORMAT",
+        help = "Print the report of what happened in this format"
+    )]
+    pub report: Option<ReportFormat>,
+
+    /// Whether to build without acquiring the lock.
+    #[arg(long, action, help = "Do not guard against concurrent builds")]
+    pub no_lock: bool,
+
+    /// The revision to read fragments from instead of the working tree.
+    #[arg(
+        long,
+        name = "REF",
+        conflicts_with = "remove",
+        help = "Read fragments from this revision (for instance, the release branch) via git"
+    )]
+    pub fragments_ref: Option<String>,
+
+    /// The IDs of fragments to build, if given.
+    #[arg(
+        long,
+        name = "IDS",
+        value_delimiter = ',',
+        help = "Only build (and remove) the fragments with these comma-separated IDs"
+    )]
+    pub only: Option<Vec<Id<'static>>>,
+
+    /// The IDs of fragments to leave out.
+    #[arg(
+        long,
+        name = "EXCLUDED",
+        value_delimiter = ',',
+        help = "Leave out the fragments with these comma-separated IDs"
+    )]
+    pub exclude: Vec<Id<'static>>,
+
+    /// Whether to print how long building took.
+    #[arg(
+        long,
+        action,
+        help = "Print how long collecting, rendering and writing took"
+    )]
+    pub timings: bool,
+}
+
+impl BuildCommand {
+    /// Runs the `build` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::build::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::build::Error> {
+        let options = Options {
+            stage: self.stage,
+            remove: self.remove,
+            insert_marker: self.insert_marker,
+            require_fragments: self.require_fragments,
+            consolidate_prereleases: self.consolidate_prereleases,
+            amend: self.amend,
+            output: self.output.map(Target::from),
+            notes: self.notes_file,
+            notes_only: self.notes_only,
+            report: self.report,
+            no_lock: self.no_lock,
+            fragments_ref: self.fragments_ref,
+            selection: Selection::new(
+                self.only.map(BTreeSet::from_iter),
+                BTreeSet::from_iter(self.exclude),
+            ),
+            timings: self.timings,
+        };
+
+        build(workspace, self.date, options)?;
+
+        Ok(())
+    }
+}
+
+/// Represents the `rollback` subcommand.
+#[derive(Debug, Args)]
+pub struct RollbackCommand {
+    /// Whether to stage the changelog.
+    #[arg(short = 's', long, action, help = "Stage the changelog")]
+    pub stage: bool,
+
+    /// Whether to leave fragments as they are.
+    #[arg(short = 'k', long, action, help = "Do not restore the fragments")]
+    pub keep_fragments: bool,
+
+    /// The revision to restore fragments from, defaulting to `HEAD`.
+    #[arg(
+        long,
+        name = "REVISION",
+        conflicts_with = "keep_fragments",
+        help = "Restore the fragments from this revision instead of HEAD"
+    )]
+    pub revision: Option<String>,
+
+    /// Whether to remove the newest entries from changelogs without backups.
+    #[arg(
+        long,
+        action,
+        help = "Remove the newest entry from changelogs that are not backed up"
+    )]
+    pub remove_newest: bool,
+}
+
+impl RollbackCommand {
+    /// Runs the `rollback` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::rollback::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::rollback::Error> {
+        let options = RollbackOptions {
+            stage: self.stage,
+            keep_fragments: self.keep_fragments,
+            revision: self.revision,
+            remove_newest: self.remove_newest,
+        };
+
+        rollback(workspace, options)
+    }
+}
+
+/// Represents the `resolve` subcommand.
+#[derive(Debug, Args)]
+pub struct ResolveCommand {
+    /// Whether to stage the changelog.
+    #[arg(short = 's', long, action, help = "Stage the changelog")]
+    pub stage: bool,
+}
+
+impl ResolveCommand {
+    /// Runs the `resolve` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::resolve::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::resolve::Error> {
+        resolve(workspace, self.stage)
+    }
+}
+
+/// Represents the `preview` subcommand.
+#[derive(Debug, Args)]
+pub struct PreviewCommand {
+    /// The date to use, defaulting to today.
+    #[arg(
+        short = 'd',
+        long,
+        name = "DATE",
+        help = "Use the date provided instead of today"
+    )]
+    pub date: Option<String>,
+
+    /// Whether to render the entry with terminal styling.
+    #[arg(
+        short = 'p',
+        long,
+        action,
+        help = "Render the entry with terminal styling"
+    )]
+    pub pretty: bool,
+
+    /// Whether to disable colors.
+    #[arg(long, action, help = "Disable terminal styling")]
+    pub no_color: bool,
+
+    /// Whether to watch for changes and preview again on every change.
+    #[arg(
+        short = 'w',
+        long,
+        action,
+        help = "Watch for changes and preview again on every change"
+    )]
+    pub watch: bool,
+
+    /// The version of the released entry to compare the pending entry against.
+    #[arg(
+        short = 'a',
+        long,
+        name = "VERSION",
+        help = "Compare the pending entry against the released entry of this version"
+    )]
+    pub against: Option<String>,
+
+    /// Whether to show the number of fragments per section and the kind of release suggested.
+    #[arg(
+        short = 'c',
+        long,
+        visible_alias = "count",
+        action,
+        conflicts_with = "VERSION",
+        help = "Show the number of fragments per section and the kind of release suggested"
+    )]
+    pub summary: bool,
+
+    /// The name of the GitHub Actions output to set to the entry.
+    #[arg(
+        long,
+        name = "NAME",
+        conflicts_with_all = ["VERSION", "summary", "watch"],
+        help = "Set the GitHub Actions output with this name to the entry"
+    )]
+    pub github_output: Option<String>,
+
+    /// Whether to copy the entry to the clipboard.
+    #[cfg(feature = "clipboard")]
+    #[arg(
+        long,
+        action,
+        conflicts_with_all = ["VERSION", "summary", "watch", "NAME"],
+        help = "Copy the entry to the clipboard"
+    )]
+    pub clipboard: bool,
+
+    /// Whether to print how long previewing took.
+    #[arg(long, action, help = "Print how long collecting and rendering took")]
+    pub timings: bool,
+}
+
+impl PreviewCommand {
+    /// Runs the `preview` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::preview::Error
+    pub fn run(
+        self,
+        workspace: Workspace<'_>,
+        globals: &Globals,
+    ) -> Result<(), crate::commands::preview::Error> {
+        let color = color_enabled(self.no_color);
+
+        let mode = if self.summary {
+            Mode::Summary
+        } else if let Some(version) = self.against.as_deref() {
+            Mode::Against(version)
+        } else {
+            self.github_output
+                .as_deref()
+                .map_or(Mode::Entry, Mode::GitHub)
+        };
+
+        #[cfg(feature = "clipboard")]
+        let mode = if self.clipboard {
+            Mode::Clipboard
+        } else {
+            mode
+        };
+
+        if self.watch {
+            let load = || globals.workspace();
+
+            watch(
+                load,
+                globals.config.as_deref(),
+                self.date,
+                self.pretty,
+                color,
+                mode,
+                self.timings,
+            )
+        } else {
+            preview(workspace, self.date, self.pretty, color, mode, self.timings)
+        }
+    }
+}
+
+/// Represents the `check` subcommand.
+#[derive(Debug, Args)]
+pub struct CheckCommand {
+    /// The paths to check.
+    #[arg(name = "PATHS", help = "Check these files instead of every fragment")]
+    pub paths: Vec<PathBuf>,
+
+    /// Whether to check staged files.
+    #[arg(short = 's', long, action, help = "Check staged files")]
+    pub staged: bool,
+
+    /// The format to output findings in.
+    #[arg(
+        short = 'f',
+        long,
+        value_enum,
+        default_value_t,
+        help = "Output findings in this format"
+    )]
+    pub format: Format,
+
+    /// Whether to check that fragment IDs reference existing pull requests or issues.
+    #[cfg(feature = "forge")]
+    #[arg(
+        long,
+        action,
+        help = "Check that IDs reference existing pull requests or issues"
+    )]
+    pub references: bool,
+
+    /// Where to check links in, if anywhere.
+    #[cfg(feature = "links")]
+    #[arg(
+        long,
+        value_enum,
+        name = "IN",
+        num_args = 0..=1,
+        default_missing_value = "entry",
+        help = "Check links in the entry (default) or the changelog"
+    )]
+    pub links: Option<Links>,
+
+    /// The age past which fragments are considered stale, if any.
+    #[arg(
+        long,
+        name = "AGE",
+        help = "Flag fragments at least this old (for instance, `90d` or `12w`)"
+    )]
+    pub stale: Option<Age>,
+
+    /// Whether to check ordering and headings of changelog entries.
+    #[arg(
+        short = 'e',
+        long,
+        action,
+        help = "Check ordering and headings of changelog entries"
+    )]
+    pub entries: bool,
+}
+
+impl CheckCommand {
+    /// Runs the `check` subcommand.
+    ///
+    /// If `offline` is set, neither `--references` nor `--links` request anything.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered, including problems found.
+    ///
+    /// [`Error`]: crate::commands::check::Error
+    pub fn run(
+        self,
+        workspace: Workspace<'_>,
+        offline: bool,
+    ) -> Result<(), crate::commands::check::Error> {
+        #[cfg(feature = "forge")]
+        let references = self.references;
+
+        #[cfg(not(feature = "forge"))]
+        let references = false;
+
+        #[cfg(feature = "links")]
+        let links = self.links;
+
+        #[cfg(not(feature = "links"))]
+        let links = None;
+
+        let options = CheckOptions {
+            staged: self.staged,
+            paths: self.paths,
+            format: self.format,
+            references,
+            links,
+            offline,
+            stale: self.stale,
+            entries: self.entries,
+        };
+
+        check(workspace, options)
+    }
+}
+
+/// Represents the `create` subcommand.
+#[derive(Debug, Args)]
+#[command(about = "Create changelog fragments")]
+pub struct CreateCommand {
+    /// The name of the fragment.
+    #[arg(name = "NAME", help = "Write to the directory with this file name")]
+    pub name: String,
+
+    /// The fragment content, if it is passed as the argument.
+    #[arg(
+        short = 'c',
+        long,
+        name = "TEXT",
+        help = "Pass the fragment content as this argument"
+    )]
+    pub content: Option<String>,
+
+    /// Whether to open the editor to edit the fragment content.
+    #[arg(
+        short = 'e',
+        long,
+        action,
+        help = "Open the editor to edit the content"
+    )]
+    pub edit: bool,
+
+    /// Whether to never open the editor, even if `create.edit` is set.
+    #[arg(
+        long,
+        action,
+        conflicts_with = "edit",
+        help = "Never open the editor, even if configured to"
+    )]
+    pub no_edit: bool,
+
+    /// Whether to add the fragment via `git`.
     #[arg(short = 'a', long, action, help = "Add the fragment via `git`")]
     pub add: bool,
+
+    /// Whether to assign the fragment ID from the counter.
+    #[arg(
+        long,
+        action,
+        help = "Assign the next ID from the counter, passing `{type}` or `{type}.{extension}`"
+    )]
+    pub auto: bool,
+
+    /// Whether to fetch the content from the pull request with the fragment ID.
+    #[cfg(feature = "forge")]
+    #[arg(
+        long,
+        action,
+        conflicts_with_all = ["TEXT", "auto"],
+        help = "Use the title of the pull request with the fragment ID as the content"
+    )]
+    pub from_pr: bool,
 }
 
 impl CreateCommand {
     /// Runs the `create` subcommand.
     ///
+    /// If `offline` is set, `--from-pr` does not fetch anything, falling back to the placeholder.
+    ///
     /// # Errors
     ///
     /// Returns [`Error`] when any error is encountered.
     ///
     /// [`Error`]: crate::commands::create::Error
-    pub fn run<D: AsRef<Path>>(self, directory: D) -> Result<(), crate::commands::create::Error> {
-        create(directory, self.name, self.content, self.edit, self.add)
+    pub fn run(
+        self,
+        workspace: &Workspace<'_>,
+        offline: bool,
+    ) -> Result<(), crate::commands::create::Error> {
+        let config = &workspace.config;
+
+        let name = if self.auto {
+            auto_name(config, &self.name)?
+        } else {
+            self.name
+        };
+
+        #[cfg(feature = "forge")]
+        let (name, content) = if self.from_pr {
+            from_pull_request(workspace, name, offline)?
+        } else {
+            (name, self.content)
+        };
+
+        // there is nothing to fetch without the `forge` feature
+
+        #[cfg(not(feature = "forge"))]
+        let (name, content) = {
+            let _ = offline;
+
+            (name, self.content)
+        };
+
+        warn_unknown_type(config, &name);
+
+        // only `--edit` fails fast in non-interactive sessions, while `create.edit`
+        // merely opens the editor when there is no content given and it can be used
+
+        let edit = !self.no_edit
+            && (self.edit || (config.create.edit && content.is_none() && is_interactive()));
+
+        // pre-populate the editor from the template of the type, if there is one
+
+        let content = match content {
+            None if edit => from_template(workspace, &name)?,
+            content => content,
+        };
+
+        let directory = config.paths.directory.as_ref();
+
+        create(
+            directory,
+            name,
+            content,
+            edit,
+            config.create.editor.as_deref(),
+            self.add,
+            &config.git,
+        )?;
+
+        // the counter is shared, so it is added along with the fragment
+
+        if self.auto && self.add {
+            add_counter(&config.git, directory)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents the `config` subcommand.
+#[derive(Debug, Args)]
+pub struct ConfigCommand {
+    /// The `config` subcommand to run.
+    #[command(subcommand)]
+    pub command: ConfigSubcommand,
+}
+
+impl ConfigCommand {
+    /// Runs the `config` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::config::Error
+    pub fn run(
+        self,
+        workspace: Workspace<'_>,
+        overrides: &Overrides<'_>,
+    ) -> Result<(), crate::commands::config::Error> {
+        match self.command {
+            ConfigSubcommand::Show(show) => show.run(workspace, overrides),
+            ConfigSubcommand::Validate(validate) => validate.run(workspace),
+        }
+    }
+}
+
+/// Represents `config` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum ConfigSubcommand {
+    /// The `config show` subcommand.
+    #[command(about = "Show the effective configuration")]
+    Show(ShowCommand),
+    /// The `config validate` subcommand.
+    #[command(about = "Validate the configuration")]
+    Validate(ValidateCommand),
+}
+
+/// Represents the `config show` subcommand.
+#[derive(Debug, Args)]
+pub struct ShowCommand {
+    /// Whether to show only the values that are set or overridden.
+    #[arg(long, action, help = "Show only the values that are set or overridden")]
+    pub only_changed: bool,
+
+    /// Whether to annotate values with their origins.
+    #[arg(long, action, help = "Annotate values with where they came from")]
+    pub origin: bool,
+}
+
+impl ShowCommand {
+    /// Runs the `config show` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::config::Error
+    pub fn run(
+        self,
+        workspace: Workspace<'_>,
+        overrides: &Overrides<'_>,
+    ) -> Result<(), crate::commands::config::Error> {
+        show(workspace, overrides, self.only_changed, self.origin)
+    }
+}
+
+/// Represents the `config validate` subcommand.
+#[derive(Debug, Args)]
+pub struct ValidateCommand {}
+
+impl ValidateCommand {
+    /// Runs the `config validate` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any problems are found.
+    ///
+    /// [`Error`]: crate::commands::config::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::config::Error> {
+        validate(workspace)
+    }
+}
+
+/// Represents the `doctor` subcommand.
+#[derive(Debug, Args)]
+pub struct DoctorCommand {}
+
+impl DoctorCommand {
+    /// Runs the `doctor` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any problems are found.
+    ///
+    /// [`Error`]: crate::commands::doctor::Error
+    pub fn run(
+        self,
+        loaded: Result<Workspace<'_>, Error>,
+    ) -> Result<(), crate::commands::doctor::Error> {
+        doctor(loaded)
+    }
+}
+
+/// Represents the `variables` subcommand.
+#[derive(Debug, Args)]
+pub struct VariablesCommand {}
+
+impl VariablesCommand {
+    /// Runs the `variables` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::variables::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::variables::Error> {
+        variables(workspace)
+    }
+}
+
+/// Represents the `render` subcommand.
+#[derive(Debug, Args)]
+pub struct RenderCommand {
+    /// The name of the fragment, if provided.
+    #[arg(
+        short = 'f',
+        long,
+        name = "NAME",
+        help = "Use this name (`{id}.{type}`) for the fragment"
+    )]
+    pub fragment: Option<String>,
+
+    /// The content of the fragment, if provided.
+    #[arg(
+        short = 'c',
+        long,
+        name = "TEXT",
+        help = "Use this content for the fragment"
+    )]
+    pub content: Option<String>,
+
+    /// The date to use, defaulting to today.
+    #[arg(
+        short = 'd',
+        long,
+        name = "DATE",
+        help = "Use the date provided instead of today"
+    )]
+    pub date: Option<String>,
+}
+
+impl RenderCommand {
+    /// Runs the `render` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::render::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::render::Error> {
+        render(
+            workspace,
+            self.fragment.as_deref(),
+            self.content.as_deref(),
+            self.date.as_deref(),
+        )
+    }
+}
+
+/// Represents the `normalize` subcommand.
+#[derive(Debug, Args)]
+pub struct NormalizeCommand {
+    /// The extension to enforce, defaulting to `md`.
+    #[arg(
+        short = 'e',
+        long,
+        name = "EXTENSION",
+        help = "Enforce this extension instead of `md`"
+    )]
+    pub extension: Option<String>,
+
+    /// Whether to only report fragments that are not normalized.
+    #[arg(
+        long,
+        action,
+        help = "Only report fragments that are not normalized, failing if there are any"
+    )]
+    pub check: bool,
+}
+
+impl NormalizeCommand {
+    /// Runs the `normalize` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::normalize::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::normalize::Error> {
+        let options = NormalizeOptions {
+            extension: self.extension,
+            check: self.check,
+        };
+
+        normalize(workspace, options)
+    }
+}
+
+/// Represents the `list` subcommand.
+#[derive(Debug, Args)]
+pub struct ListCommand {
+    /// The format to list fragments in.
+    #[arg(
+        short = 'f',
+        long,
+        value_enum,
+        default_value_t,
+        help = "List fragments in this format"
+    )]
+    pub format: ListFormat,
+}
+
+impl ListCommand {
+    /// Runs the `list` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::list::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::list::Error> {
+        list(workspace, self.format)
+    }
+}
+
+/// Represents the `cache` subcommand.
+#[derive(Debug, Args)]
+pub struct CacheCommand {
+    /// The `cache` subcommand to run.
+    #[command(subcommand)]
+    pub command: CacheSubcommand,
+}
+
+impl CacheCommand {
+    /// Runs the `cache` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::cache::Error
+    pub fn run(self, workspace: &Workspace<'_>) -> Result<(), crate::commands::cache::Error> {
+        match self.command {
+            CacheSubcommand::Clear(clear) => clear.run(workspace),
+        }
+    }
+}
+
+/// Represents `cache` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum CacheSubcommand {
+    /// The `cache clear` subcommand.
+    #[command(about = "Remove the cache")]
+    Clear(ClearCommand),
+}
+
+/// Represents the `cache clear` subcommand.
+#[derive(Debug, Args)]
+pub struct ClearCommand {}
+
+impl ClearCommand {
+    /// Runs the `cache clear` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when removing the cache fails.
+    ///
+    /// [`Error`]: crate::commands::cache::Error
+    pub fn run(self, workspace: &Workspace<'_>) -> Result<(), crate::commands::cache::Error> {
+        clear(workspace)
+    }
+}
+
+/// Represents the `open` subcommand.
+#[derive(Debug, Args)]
+pub struct OpenCommand {
+    /// The `open` subcommand to run.
+    #[command(subcommand)]
+    pub command: OpenSubcommand,
+}
+
+impl OpenCommand {
+    /// Resolves the target to open.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] when discovering the config file fails.
+    pub fn target(&self, globals: &Globals) -> Result<OpenTarget, Error> {
+        let target = match &self.command {
+            OpenSubcommand::Changelog(_) => OpenTarget::Changelog,
+            OpenSubcommand::Config(_) => OpenTarget::Config(globals.config_path()?),
+            OpenSubcommand::Fragment(fragment) => OpenTarget::Fragment(fragment.id.clone()),
+        };
+
+        Ok(target)
+    }
+
+    /// Runs the `open` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::open::Error
+    pub fn run(
+        self,
+        workspace: Workspace<'_>,
+        target: OpenTarget,
+    ) -> Result<(), crate::commands::open::Error> {
+        open(workspace, target)
+    }
+}
+
+/// Represents `open` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum OpenSubcommand {
+    /// The `open changelog` subcommand.
+    #[command(about = "Open the changelog")]
+    Changelog(OpenChangelogCommand),
+    /// The `open config` subcommand.
+    #[command(about = "Open the config file")]
+    Config(OpenConfigCommand),
+    /// The `open fragment` subcommand.
+    #[command(about = "Open the fragment with the given ID")]
+    Fragment(OpenFragmentCommand),
+}
+
+/// Represents the `open changelog` subcommand.
+#[derive(Debug, Args)]
+pub struct OpenChangelogCommand {}
+
+/// Represents the `open config` subcommand.
+#[derive(Debug, Args)]
+pub struct OpenConfigCommand {}
+
+/// Represents the `open fragment` subcommand.
+#[derive(Debug, Args)]
+pub struct OpenFragmentCommand {
+    /// The ID of the fragment.
+    #[arg(
+        name = "ID",
+        help = "The ID of the fragment, for instance `13` or `~cool`"
+    )]
+    pub id: String,
+}
+
+/// Represents the `import-prs` subcommand.
+#[cfg(feature = "forge")]
+#[derive(Debug, Args)]
+pub struct ImportPrsCommand {
+    /// The tag (or any other revision) or the date to import pull requests since.
+    #[arg(
+        short = 's',
+        long,
+        name = "SINCE",
+        help = "Import pull requests merged since this tag or date (defaults to the last tag)"
+    )]
+    pub since: Option<String>,
+
+    /// Whether to add fragments via `git`.
+    #[arg(short = 'a', long, action, help = "Add the fragments via `git`")]
+    pub add: bool,
+}
+
+#[cfg(feature = "forge")]
+impl ImportPrsCommand {
+    /// Runs the `import-prs` subcommand.
+    ///
+    /// If `offline` is set, nothing is imported.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::import::Error
+    pub fn run(
+        self,
+        workspace: &Workspace<'_>,
+        offline: bool,
+    ) -> Result<(), crate::commands::import::Error> {
+        import(workspace, self.since.as_deref(), self.add, offline)
+    }
+}
+
+/// Represents the `migrate` subcommand.
+#[derive(Debug, Args)]
+pub struct MigrateCommand {
+    /// The `migrate` subcommand to run.
+    #[command(subcommand)]
+    pub command: MigrateSubcommand,
+}
+
+impl MigrateCommand {
+    /// Runs the `migrate` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::migrate::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::migrate::Error> {
+        match self.command {
+            MigrateSubcommand::Split(split) => split.run(workspace),
+        }
+    }
+}
+
+/// Represents `migrate` subcommands.
+#[derive(Debug, Subcommand)]
+pub enum MigrateSubcommand {
+    /// The `migrate split` subcommand.
+    #[command(about = "Recreate fragments from the changelog entry of the version")]
+    Split(SplitCommand),
+}
+
+/// Represents the `migrate split` subcommand.
+#[derive(Debug, Args)]
+pub struct SplitCommand {
+    /// The version of the entry to split into fragments.
+    #[arg(name = "VERSION", help = "Split the entry of this version")]
+    pub version: String,
+
+    /// Whether to remove the entry from the changelog.
+    #[arg(
+        short = 'r',
+        long,
+        action,
+        help = "Remove the entry from the changelog, so that it can be rebuilt"
+    )]
+    pub remove: bool,
+}
+
+impl SplitCommand {
+    /// Runs the `migrate split` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::migrate::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::migrate::Error> {
+        let paths = split(workspace, &self.version, self.remove)?;
+
+        println!("created {} fragment(s)", paths.len());
+
+        Ok(())
+    }
+}
+
+/// Represents the `verify` subcommand.
+#[derive(Debug, Args)]
+pub struct VerifyCommand {
+    /// The version of the entry to verify, defaulting to the one of the context.
+    #[arg(
+        long,
+        name = "VERSION",
+        help = "Verify the entry of this version instead of the current one"
+    )]
+    pub version: Option<String>,
+
+    /// The date to rebuild the entry with, defaulting to the one of the released entry.
+    #[arg(
+        short = 'd',
+        long,
+        name = "DATE",
+        help = "Rebuild the entry with this date instead of the released one"
+    )]
+    pub date: Option<String>,
+
+    /// The revision to read fragments from instead of the working tree.
+    #[arg(
+        long,
+        name = "REF",
+        help = "Read fragments from this revision (for instance, the one before the release) via git"
+    )]
+    pub fragments_ref: Option<String>,
+}
+
+impl VerifyCommand {
+    /// Runs the `verify` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when any error is encountered.
+    ///
+    /// [`Error`]: crate::commands::verify::Error
+    pub fn run(self, workspace: Workspace<'_>) -> Result<(), crate::commands::verify::Error> {
+        let options = VerifyOptions {
+            version: self.version,
+            date: self.date,
+            fragments_ref: self.fragments_ref,
+        };
+
+        verify(workspace, options)
+    }
+}
+
+/// Represents the `man` subcommand.
+#[derive(Debug, Args)]
+pub struct ManCommand {
+    /// The directory to write manual pages into.
+    #[arg(name = "DIRECTORY", help = "The directory to write manual pages into")]
+    pub directory: PathBuf,
+}
+
+impl ManCommand {
+    /// Runs the `man` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when generating manual pages fails.
+    ///
+    /// [`Error`]: crate::commands::man::Error
+    pub fn run(self) -> Result<(), crate::commands::man::Error> {
+        man(App::command(), self.directory)
+    }
+}
+
+/// Represents the `generate-fixtures` subcommand.
+#[derive(Debug, Args)]
+pub struct GenerateFixturesCommand {
+    /// The directory to generate fragments into, defaulting to `paths.directory`.
+    #[arg(
+        name = "TARGET",
+        help = "Generate fragments into this directory instead of the fragments one"
+    )]
+    pub directory: Option<PathBuf>,
+
+    /// The number of fragments to generate.
+    #[arg(
+        short = 'n',
+        long,
+        default_value_t = DEFAULT_COUNT,
+        help = "Generate this many fragments"
+    )]
+    pub count: usize,
+
+    /// The seed to synthesize fragments from.
+    #[arg(
+        long,
+        default_value_t = DEFAULT_SEED,
+        help = "Synthesize fragments from this seed"
+    )]
+    pub seed: u64,
+}
+
+impl GenerateFixturesCommand {
+    /// Runs the `generate-fixtures` subcommand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when generating fragments fails.
+    ///
+    /// [`Error`]: crate::commands::fixtures::Error
+    pub fn run(self, workspace: &Workspace<'_>) -> Result<(), crate::commands::fixtures::Error> {
+        let config = &workspace.config;
+
+        let directory = self
+            .directory
+            .unwrap_or_else(|| config.paths.directory.clone().into_owned());
+
+        let paths = generate(config, &directory, self.count, self.seed)?;
+
+        println!(
+            "generated {} fragment(s) into `{}`",
+            paths.len(),
+            directory.display()
+        );
+
+        Ok(())
     }
 }
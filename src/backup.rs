@@ -0,0 +1,289 @@
+//! Backing up changelogs.
+//!
+//! Before `build` writes changelogs, their previous contents are copied to the [`DIRECTORY`]
+//! (in the root of the project), so that the `rollback` command can restore them,
+//! see [`Backup::restore`]. Fragments removed by `build` are recorded there as well,
+//! so that only they are restored, see [`Backup::record`].
+//!
+//! Only the last build is backed up, and backups are removed once restored.
+//! The directory contains the [`IGNORE`] file ignoring everything in it,
+//! so that backups are never committed by accident.
+
+use std::{
+    fs::{copy, create_dir_all, read, remove_file, write},
+    io::ErrorKind,
+    path::{absolute, Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+use tracing::debug;
+
+use crate::{cache::hash, config::normalize_path};
+
+/// The directory to store the backup in, relative to the project.
+pub const DIRECTORY: &str = ".changelogging/backup";
+
+/// The name of the file recording fragments removed by the last build.
+pub const REMOVED: &str = "removed.json";
+
+/// The name of the file ignoring the contents of the backup directory.
+pub const IGNORE: &str = ".gitignore";
+
+/// The contents of the [`IGNORE`] file.
+pub const IGNORE_ALL: &str = "*\n";
+
+/// The suffix of markers recording that files did not exist when backed up.
+pub const ABSENT: &str = ".absent";
+
+const SEPARATOR: char = '-';
+
+/// Represents errors that can occur when saving backups.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to back up `{path}`")]
+#[diagnostic(
+    code(changelogging::backup::save),
+    help("check whether the backup directory is accessible")
+)]
+pub struct SaveError {
+    /// The underlying I/O error.
+    pub source: std::io::Error,
+    /// The path to the file backed up.
+    pub path: PathBuf,
+}
+
+impl SaveError {
+    /// Constructs [`Self`].
+    pub fn new(source: std::io::Error, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+}
+
+/// Represents errors that can occur when restoring backups.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to restore `{path}` from the backup")]
+#[diagnostic(
+    code(changelogging::backup::restore),
+    help("check whether the file and the backup directory are accessible")
+)]
+pub struct RestoreError {
+    /// The underlying I/O error.
+    pub source: std::io::Error,
+    /// The path to the file restored.
+    pub path: PathBuf,
+}
+
+impl RestoreError {
+    /// Constructs [`Self`].
+    pub fn new(source: std::io::Error, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+}
+
+/// Represents backups of changelogs, stored in one directory.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Backup {
+    /// The directory to store the backup in.
+    pub directory: PathBuf,
+}
+
+impl Default for Backup {
+    fn default() -> Self {
+        Self::new(PathBuf::from(DIRECTORY))
+    }
+}
+
+impl Backup {
+    /// Constructs [`Self`].
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Returns the path to the backup of the changelog at the given path.
+    ///
+    /// Backups are named after hashes of the normalized absolute paths (followed by file names),
+    /// so that different paths to the same file share backups, while different files never do.
+    pub fn path(&self, path: &Path) -> PathBuf {
+        let key = hash(resolve(path).as_os_str().as_encoded_bytes());
+
+        let name = match path.file_name() {
+            Some(name) => format!("{key}{SEPARATOR}{}", name.to_string_lossy()),
+            None => key,
+        };
+
+        self.directory.join(name)
+    }
+
+    /// Returns the path to the marker recording that the file at the given path
+    /// did not exist when backed up.
+    pub fn absent_path(&self, path: &Path) -> PathBuf {
+        let mut marker = self.path(path).into_os_string();
+
+        marker.push(ABSENT);
+
+        marker.into()
+    }
+
+    /// Returns the path to the record of fragments removed by the last build.
+    pub fn removed_path(&self) -> PathBuf {
+        self.directory.join(REMOVED)
+    }
+
+    /// Checks whether the backup of the changelog at the given path exists.
+    pub fn exists(&self, path: &Path) -> bool {
+        self.path(path).is_file() || self.absent_path(path).is_file()
+    }
+
+    /// Backs up the changelog at the given path, returning whether there was anything to back up.
+    ///
+    /// If the changelog does not exist, the previous backup is replaced with the marker
+    /// recording that, so that restoring removes the changelog created afterwards.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SaveError`] if copying the changelog, writing the marker
+    /// or removing the previous backup fails.
+    pub fn save(&self, path: &Path) -> Result<bool, SaveError> {
+        let backup = self.path(path);
+        let absent = self.absent_path(path);
+
+        let exists = path.exists();
+
+        self.create_directory()
+            .and_then(|()| {
+                if exists {
+                    remove(&absent).and_then(|()| copy(path, &backup).map(drop))
+                } else {
+                    remove(&backup).and_then(|()| write(&absent, ""))
+                }
+            })
+            .map_err(|error| SaveError::new(error, path.to_owned()))?;
+
+        if !exists {
+            debug!("recorded `{}` as absent", path.display());
+
+            return Ok(false);
+        }
+
+        debug!("backed up `{}`", path.display());
+
+        Ok(true)
+    }
+
+    /// Restores the changelog at the given path from the backup and removes the backup,
+    /// returning whether there was anything to restore.
+    ///
+    /// If the changelog was recorded as absent (see [`save`]), it is removed instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RestoreError`] if copying the backup, removing the changelog
+    /// or removing the backup fails.
+    ///
+    /// [`save`]: Self::save
+    pub fn restore(&self, path: &Path) -> Result<bool, RestoreError> {
+        let backup = self.path(path);
+        let absent = self.absent_path(path);
+
+        if absent.is_file() {
+            remove(path)
+                .and_then(|()| remove(&absent))
+                .map_err(|error| RestoreError::new(error, path.to_owned()))?;
+
+            debug!("removed `{}`, as it was absent", path.display());
+
+            return Ok(true);
+        }
+
+        if !backup.is_file() {
+            return Ok(false);
+        }
+
+        copy(&backup, path)
+            .and_then(|_| remove(&backup))
+            .map_err(|error| RestoreError::new(error, path.to_owned()))?;
+
+        debug!("restored `{}`", path.display());
+
+        Ok(true)
+    }
+
+    /// Records the given fragments as removed by the last build, replacing the previous record.
+    ///
+    /// Paths are recorded as normalized absolute ones. If there are no fragments given,
+    /// the previous record is removed instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SaveError`] if writing the record or removing the previous one fails.
+    pub fn record(&self, fragments: &[PathBuf]) -> Result<(), SaveError> {
+        let path = self.removed_path();
+
+        if fragments.is_empty() {
+            return remove(&path).map_err(|error| SaveError::new(error, path));
+        }
+
+        let paths: Vec<PathBuf> = fragments.iter().map(|fragment| resolve(fragment)).collect();
+
+        self.create_directory()
+            .and_then(|()| serde_json::to_vec_pretty(&paths).map_err(Into::into))
+            .and_then(|bytes| write(&path, bytes))
+            .map_err(|error| SaveError::new(error, path.clone()))?;
+
+        debug!("recorded {} removed fragment(s)", paths.len());
+
+        Ok(())
+    }
+
+    /// Returns fragments recorded as removed by the last build (see [`record`])
+    /// and removes the record.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RestoreError`] if reading, parsing or removing the record fails.
+    ///
+    /// [`record`]: Self::record
+    pub fn take_removed(&self) -> Result<Vec<PathBuf>, RestoreError> {
+        let path = self.removed_path();
+
+        let bytes = match read(&path) {
+            Ok(bytes) => bytes,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(error) => return Err(RestoreError::new(error, path)),
+        };
+
+        let fragments = serde_json::from_slice(&bytes)
+            .map_err(Into::into)
+            .and_then(|fragments| remove(&path).map(|()| fragments))
+            .map_err(|error| RestoreError::new(error, path))?;
+
+        Ok(fragments)
+    }
+
+    // creates the backup directory along with the file ignoring everything in it
+
+    fn create_directory(&self) -> std::io::Result<()> {
+        create_dir_all(&self.directory)?;
+
+        let ignore = self.directory.join(IGNORE);
+
+        if !ignore.exists() {
+            write(ignore, IGNORE_ALL)?;
+        }
+
+        Ok(())
+    }
+}
+
+// makes the given path absolute (if possible) and normalizes it
+
+fn resolve(path: &Path) -> PathBuf {
+    normalize_path(&absolute(path).unwrap_or_else(|_| path.to_owned()))
+}
+
+fn remove(path: &Path) -> std::io::Result<()> {
+    match remove_file(path) {
+        Err(error) if error.kind() != ErrorKind::NotFound => Err(error),
+        _ => Ok(()),
+    }
+}
@@ -2,27 +2,208 @@
 
 use std::{
     borrow::Cow,
-    fs::{read_dir, File},
-    io::{read_to_string, Write},
-    iter::{once, repeat},
-    path::PathBuf,
+    cell::OnceCell,
+    collections::{BTreeMap, HashMap},
+    iter::{once, repeat_n},
+    ops::Deref,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+#[cfg(feature = "fs")]
+use std::{
+    fs::{metadata, read_dir, DirEntry, File},
+    io::{read_to_string, ErrorKind, Write},
 };
 
-use handlebars::{no_escape, Handlebars, RenderError, TemplateError};
+use handlebars::{
+    html_escape, no_escape, to_json, Context as TemplateContext, Handlebars, Helper, HelperDef,
+    JsonValue, RenderContext, RenderError, RenderErrorReason, ScopedJson, TemplateError,
+};
+#[cfg(feature = "fs")]
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
 use itertools::Itertools;
-use miette::Diagnostic;
+use miette::{Diagnostic, SourceSpan};
 use serde::Serialize;
 use textwrap::{fill, Options as WrapOptions, WordSeparator, WordSplitter};
 use thiserror::Error;
 use time::Date;
+#[cfg(feature = "git")]
+use time::OffsetDateTime;
+use tracing::{debug, info, warn};
 
+#[cfg(feature = "git")]
 use crate::{
-    config::{Config, Level},
-    context::Context,
-    fragment::{is_valid_path, Fragment, Fragments, Sections},
+    config::{Git, Since},
+    git::{authors, first_time, head, last_tag, HEAD},
+};
+
+#[cfg(feature = "async")]
+use std::time::Instant;
+
+#[cfg(feature = "async")]
+use crate::load::load_async;
+#[cfg(feature = "fs")]
+use crate::{
+    cache::{self, Cache, Key},
+    fragment::{is_hidden, is_valid_path, path_name},
     load::load,
-    workspace::Workspace,
 };
+use crate::{
+    changelog::{
+        definition_label, entries, find, reference_label, references, remove, split_definitions,
+        Entry,
+    },
+    config::{Collapse, Config, Dates, Escape, Grouping, Level, LineEnding, Placement, Sort, Type},
+    context::Context,
+    exit::Code,
+    fragment::{self, Fragment, FragmentFile, Fragments, Partial, Sections, Selection, DATE},
+    git::{self, Author},
+    report::chain,
+    text::{is_crlf, normalize, strip_bom, to_crlf},
+    timings::{Phase, Timings},
+    version::{build_metadata, is_prerelease, parse, prerelease, slug},
+    workspace::{project_path, Workspace},
+};
+
+/// The name of the ignore file placed in the fragments directory.
+#[cfg(feature = "fs")]
+pub const IGNORE: &str = ".changeloggingignore";
+
+/// Builds the matcher from the ignore file in the given directory.
+///
+/// If the ignore file does not exist, nothing is ignored.
+///
+/// # Errors
+///
+/// Returns [`CollectError`] if the ignore file can not be read or contains invalid globs.
+#[cfg(feature = "fs")]
+pub fn ignore_in(directory: &Path) -> Result<Gitignore, CollectError> {
+    let mut builder = GitignoreBuilder::new(directory);
+
+    let path = directory.join(IGNORE);
+
+    if path.is_file() {
+        if let Some(error) = builder.add(&path) {
+            return Err(CollectError::new_ignore(error, path));
+        }
+    }
+
+    builder
+        .build()
+        .map_err(|error| CollectError::new_ignore(error, path))
+}
+
+/// Builds the matcher from the ignore file in the given directory asynchronously.
+///
+/// If the ignore file does not exist, nothing is ignored.
+///
+/// # Errors
+///
+/// Returns [`CollectError`] if the ignore file can not be read or contains invalid globs.
+#[cfg(feature = "async")]
+pub async fn ignore_in_async(directory: &Path) -> Result<Gitignore, CollectError> {
+    let mut builder = GitignoreBuilder::new(directory);
+
+    let path = directory.join(IGNORE);
+
+    if tokio::fs::metadata(&path)
+        .await
+        .is_ok_and(|metadata| metadata.is_file())
+    {
+        let string = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|error| CollectError::new_ignore(error.into(), path.clone()))?;
+
+        for line in string.lines() {
+            builder
+                .add_line(Some(path.clone()), line)
+                .map_err(|error| CollectError::new_ignore(error, path.clone()))?;
+        }
+    }
+
+    builder
+        .build()
+        .map_err(|error| CollectError::new_ignore(error, path))
+}
+
+#[cfg(feature = "fs")]
+fn is_ignored(ignore: &Gitignore, path: &Path) -> bool {
+    let ignored = ignore.matched(path, false).is_ignore();
+
+    if ignored {
+        debug!("ignored `{}`", path.display());
+    }
+
+    ignored
+}
+
+#[cfg(feature = "git")]
+fn find_added_dates(config: &Git<'_>, directory: &Path) -> Option<HashMap<PathBuf, Date>> {
+    let added = match git::added(config, directory) {
+        Ok(added) => added,
+        Err(error) => {
+            warn!("failed to find dates of fragments: {error}");
+
+            return None;
+        }
+    };
+
+    let dates = added
+        .into_iter()
+        .filter_map(|(path, timestamp)| {
+            let date = OffsetDateTime::from_unix_timestamp(timestamp).ok()?.date();
+
+            Some((path, date))
+        })
+        .collect();
+
+    Some(dates)
+}
+
+/// Represents fragment files collected by builders, which keep them alive
+/// while lending fragments to sections, see [`sections`].
+///
+/// [`sections`]: Self::sections
+#[cfg(feature = "fs")]
+#[derive(Debug, Clone)]
+pub struct Collection<'c, 'b> {
+    builder: &'c Builder<'b>,
+    files: Vec<FragmentFile>,
+    added: Option<HashMap<PathBuf, Date>>,
+}
+
+#[cfg(feature = "fs")]
+impl<'c, 'b> Collection<'c, 'b> {
+    fn new(
+        builder: &'c Builder<'b>,
+        files: Vec<FragmentFile>,
+        added: Option<HashMap<PathBuf, Date>>,
+    ) -> Self {
+        Self {
+            builder,
+            files,
+            added,
+        }
+    }
+
+    /// Returns the fragment files collected.
+    pub fn files(&self) -> &[FragmentFile] {
+        &self.files
+    }
+
+    /// Consumes [`Self`], returning the fragment files collected.
+    pub fn into_files(self) -> Vec<FragmentFile> {
+        self.files
+    }
+
+    /// Groups fragments borrowed from the files into sections, skipping the ones
+    /// that can not be parsed, see [`Builder::collect_from`].
+    ///
+    /// Dates of fragments (if `dates` is `git`) are found once, when collecting.
+    pub fn sections(&self) -> Sections<'_> {
+        self.builder.collect_dated(&self.files, self.added.as_ref())
+    }
+}
 
 /// Represents errors that can occur during builder initialization.
 #[derive(Debug, Error, Diagnostic)]
@@ -33,6 +214,113 @@ use crate::{
 )]
 pub struct InitError(#[from] pub TemplateError);
 
+/// Represents errors that can occur when rendering templates.
+///
+/// The diagnostic points into the template, labeling the offending placeholder
+/// and listing the variables available to the template.
+///
+/// The details are boxed, since they are large and errors are moved around a lot.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(forward(0))]
+pub struct RenderTemplateError(pub Box<RenderTemplateDetails>);
+
+/// Represents details of [`RenderTemplateError`].
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to render the `{name}` template")]
+#[diagnostic(code(changelogging::builder::render))]
+pub struct RenderTemplateDetails {
+    /// The underlying render error.
+    #[source]
+    pub source: RenderError,
+    /// The name of the template.
+    pub name: String,
+    /// The source of the template.
+    #[source_code]
+    pub template: String,
+    /// The location of the offending placeholder, if known.
+    #[label("{reason}")]
+    pub span: Option<SourceSpan>,
+    /// The reason of this error, used to label the placeholder.
+    pub reason: String,
+    /// The help message listing the available variables.
+    #[help]
+    pub help: Option<String>,
+}
+
+impl RenderTemplateError {
+    /// Constructs [`Self`].
+    pub fn new(source: RenderError, name: String, template: String, variables: &[String]) -> Self {
+        let span = source
+            .line_no
+            .zip(source.column_no)
+            .and_then(|(line, column)| placeholder_span(&template, line, column));
+
+        let reason = match source.reason() {
+            RenderErrorReason::MissingVariable(Some(variable)) => {
+                format!("unknown variable `{variable}`")
+            }
+            RenderErrorReason::MissingVariable(None) => "unknown variable".to_owned(),
+            reason => reason.to_string(),
+        };
+
+        let help = if variables.is_empty() {
+            None
+        } else {
+            let available = variables
+                .iter()
+                .map(|variable| format!("`{variable}`"))
+                .join(", ");
+
+            Some(format!("available variables: {available}"))
+        };
+
+        Self(Box::new(RenderTemplateDetails {
+            source,
+            name,
+            template,
+            span,
+            reason,
+            help,
+        }))
+    }
+}
+
+const OPEN: &str = "{{";
+const CLOSE: &str = "}}";
+
+fn placeholder_span(template: &str, line: usize, column: usize) -> Option<SourceSpan> {
+    let line_start: usize = template
+        .split_inclusive(NEW_LINE)
+        .take(line.checked_sub(1)?)
+        .map(str::len)
+        .sum();
+
+    let offset = line_start
+        + template[line_start..]
+            .char_indices()
+            .nth(column.checked_sub(1)?)
+            .map(|(index, _)| index)?;
+
+    let rest = &template[offset..];
+
+    let length = if rest.starts_with(OPEN) {
+        rest.find(CLOSE)
+            .map_or(rest.len(), |index| index + CLOSE.len())
+    } else {
+        0
+    };
+
+    Some(SourceSpan::new(offset.into(), length))
+}
+
+fn variables<T: Serialize>(data: &T) -> Vec<String> {
+    match to_json(data) {
+        JsonValue::Object(object) => object.keys().cloned().sorted().collect(),
+        _ => Vec::new(),
+    }
+}
+
 /// Represents errors that can occur when building titles.
 #[derive(Debug, Error, Diagnostic)]
 #[error("failed to build the title")]
@@ -40,7 +328,11 @@ pub struct InitError(#[from] pub TemplateError);
     code(changelogging::builder::build_title),
     help("make sure the formats configuration is valid")
 )]
-pub struct BuildTitleError(#[from] pub RenderError);
+pub struct BuildTitleError(
+    #[from]
+    #[diagnostic_source]
+    pub RenderTemplateError,
+);
 
 /// Represents errors that can occur when building fragments.
 #[derive(Debug, Error, Diagnostic)]
@@ -49,7 +341,11 @@ pub struct BuildTitleError(#[from] pub RenderError);
     code(changelogging::builder::build_fragment),
     help("make sure the formats configuration is valid")
 )]
-pub struct BuildFragmentError(#[from] pub RenderError);
+pub struct BuildFragmentError(
+    #[from]
+    #[diagnostic_source]
+    pub RenderTemplateError,
+);
 
 /// Represents errors that can occur when reading from files.
 #[derive(Debug, Error, Diagnostic)]
@@ -123,6 +419,35 @@ impl OpenFileError {
 )]
 pub struct ReadDirectoryError(#[from] std::io::Error);
 
+/// Represents errors that occur when the fragments directory does not exist.
+#[derive(Debug, Error, Diagnostic)]
+#[error("fragments directory `{directory}` does not exist (resolved to `{absolute}`)")]
+#[diagnostic(
+    code(changelogging::builder::directory_not_found),
+    help("create the directory, or check `paths.directory` (and `--fragments-dir`, if given)")
+)]
+pub struct DirectoryNotFoundError {
+    /// The underlying I/O error.
+    pub source: std::io::Error,
+    /// The configured directory.
+    pub directory: PathBuf,
+    /// The absolute path to the directory, resolved against the current directory.
+    pub absolute: PathBuf,
+}
+
+impl DirectoryNotFoundError {
+    /// Constructs [`Self`], resolving the absolute path to the given directory.
+    pub fn new(source: std::io::Error, directory: PathBuf) -> Self {
+        let absolute = std::path::absolute(&directory).unwrap_or_else(|_| directory.clone());
+
+        Self {
+            source,
+            directory,
+            absolute,
+        }
+    }
+}
+
 /// Represents errors that can occur during iterating over directories.
 #[derive(Debug, Error, Diagnostic)]
 #[error("failed to iterate directory")]
@@ -132,6 +457,16 @@ pub struct ReadDirectoryError(#[from] std::io::Error);
 )]
 pub struct IterDirectoryError(#[from] std::io::Error);
 
+/// Represents errors that can occur when reading ignore files.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to read the ignore file")]
+#[diagnostic(
+    code(changelogging::builder::ignore),
+    help("make sure `.changeloggingignore` is accessible and uses the gitignore syntax")
+)]
+#[cfg(feature = "fs")]
+pub struct IgnoreError(#[from] pub ignore::Error);
+
 /// Represents sources of errors that can occur during fragment collection.
 #[derive(Debug, Error, Diagnostic)]
 #[error(transparent)]
@@ -139,8 +474,16 @@ pub struct IterDirectoryError(#[from] std::io::Error);
 pub enum CollectErrorSource {
     /// Read directory errors.
     ReadDirectory(#[from] ReadDirectoryError),
+    /// Directory not found errors.
+    DirectoryNotFound(#[from] DirectoryNotFoundError),
     /// Iterate directory errors.
     IterDirectory(#[from] IterDirectoryError),
+    /// Ignore errors.
+    #[cfg(feature = "fs")]
+    Ignore(#[from] IgnoreError),
+    /// `git` errors.
+    #[cfg(feature = "git")]
+    Git(#[from] git::Error),
 }
 
 /// Represents errors that can occur during fragment collection.
@@ -170,13 +513,40 @@ impl CollectError {
         Self::new(error.into(), path)
     }
 
+    /// Constructs [`Self`] from [`DirectoryNotFoundError`].
+    pub fn directory_not_found(error: DirectoryNotFoundError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
     /// Constructs [`Self`] from [`IterDirectoryError`].
     pub fn iter_directory(error: IterDirectoryError, path: PathBuf) -> Self {
         Self::new(error.into(), path)
     }
 
+    /// Constructs [`Self`] from [`IgnoreError`].
+    #[cfg(feature = "fs")]
+    pub fn ignore(error: IgnoreError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::git::Error
+    #[cfg(feature = "git")]
+    pub fn git(error: git::Error, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
     /// Constructs [`ReadDirectoryError`] and constructs [`Self`] from it.
+    ///
+    /// If the directory does not exist, [`DirectoryNotFoundError`] is constructed instead.
     pub fn new_read_directory(error: std::io::Error, path: PathBuf) -> Self {
+        if error.kind() == std::io::ErrorKind::NotFound {
+            let error = DirectoryNotFoundError::new(error, path.clone());
+
+            return Self::directory_not_found(error, path);
+        }
+
         Self::read_directory(ReadDirectoryError(error), path)
     }
 
@@ -184,8 +554,35 @@ impl CollectError {
     pub fn new_iter_directory(error: std::io::Error, path: PathBuf) -> Self {
         Self::iter_directory(IterDirectoryError(error), path)
     }
+
+    /// Constructs [`IgnoreError`] and constructs [`Self`] from it.
+    #[cfg(feature = "fs")]
+    pub fn new_ignore(error: ignore::Error, path: PathBuf) -> Self {
+        Self::ignore(IgnoreError(error), path)
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match self.source {
+            #[cfg(feature = "fs")]
+            CollectErrorSource::Ignore(_) => Code::Config,
+            CollectErrorSource::DirectoryNotFound(_) => Code::Config,
+            #[cfg(feature = "git")]
+            CollectErrorSource::Git(_) => Code::Git,
+            _ => Code::Io,
+        }
+    }
 }
 
+/// Represents errors that can occur when finding contributors.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to find contributors")]
+#[diagnostic(
+    code(changelogging::builder::contributors),
+    help("make sure `git` is present and the project is a repository")
+)]
+pub struct ContributorsError(#[from] pub git::Error);
+
 /// Represents sources of errors that can occur when building.
 #[derive(Debug, Error, Diagnostic)]
 #[error(transparent)]
@@ -197,6 +594,8 @@ pub enum BuildErrorSource {
     BuildFragment(#[from] BuildFragmentError),
     /// Collect errors.
     Collect(#[from] CollectError),
+    /// Contributors errors.
+    Contributors(#[from] ContributorsError),
 }
 
 /// Represents errors that can occur when building.
@@ -234,17 +633,62 @@ impl BuildError {
         Self::new(error.into())
     }
 
+    /// Constructs [`Self`] from [`ContributorsError`].
+    pub fn contributors(error: ContributorsError) -> Self {
+        Self::new(error.into())
+    }
+
     /// Constructs [`BuildTitleError`] and constructs [`Self`] from it.
-    pub fn new_build_title(error: RenderError) -> Self {
+    pub fn new_build_title(error: RenderTemplateError) -> Self {
         Self::build_title(BuildTitleError(error))
     }
 
     /// Constructs [`BuildFragmentError`] and constructs [`Self`] from it.
-    pub fn new_build_fragment(error: RenderError) -> Self {
+    pub fn new_build_fragment(error: RenderTemplateError) -> Self {
         Self::build_fragment(BuildFragmentError(error))
     }
+
+    /// Constructs [`ContributorsError`] and constructs [`Self`] from it.
+    pub fn new_contributors(error: git::Error) -> Self {
+        Self::contributors(ContributorsError(error))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            BuildErrorSource::BuildTitle(_) | BuildErrorSource::BuildFragment(_) => Code::Config,
+            BuildErrorSource::Collect(error) => error.exit_code(),
+            BuildErrorSource::Contributors(_) => Code::Git,
+        }
+    }
 }
 
+/// Represents errors that can occur when building skeletons of new changelogs.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to build the skeleton")]
+#[diagnostic(
+    code(changelogging::builder::build_skeleton),
+    help("make sure the formats configuration is valid")
+)]
+pub struct BuildSkeletonError(
+    #[from]
+    #[diagnostic_source]
+    pub RenderTemplateError,
+);
+
+/// Represents errors that can occur when building link reference definitions of entries.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to build link reference definitions")]
+#[diagnostic(
+    code(changelogging::builder::build_links),
+    help("make sure the formats configuration is valid")
+)]
+pub struct BuildLinksError(
+    #[from]
+    #[diagnostic_source]
+    pub RenderTemplateError,
+);
+
 /// Represents sources of errors that can occur when writing entries.
 #[derive(Debug, Error, Diagnostic)]
 #[error(transparent)]
@@ -258,6 +702,10 @@ pub enum WriteErrorSource {
     Build(#[from] BuildError),
     /// Write file errors.
     WriteFile(#[from] WriteFileError),
+    /// Build skeleton errors.
+    BuildSkeleton(#[from] BuildSkeletonError),
+    /// Build links errors.
+    BuildLinks(#[from] BuildLinksError),
 }
 
 /// Represents errors that can occur when writing entries.
@@ -300,6 +748,16 @@ impl WriteError {
         Self::new(error.into())
     }
 
+    /// Constructs [`Self`] from [`BuildSkeletonError`].
+    pub fn build_skeleton(error: BuildSkeletonError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`BuildLinksError`].
+    pub fn build_links(error: BuildLinksError) -> Self {
+        Self::new(error.into())
+    }
+
     /// Constructs [`OpenFileError`] and constructs [`Self`] from it.
     pub fn new_open_file(error: std::io::Error, path: PathBuf) -> Self {
         Self::open_file(OpenFileError::new(error, path))
@@ -314,101 +772,674 @@ impl WriteError {
     pub fn new_write_file(error: std::io::Error, path: PathBuf) -> Self {
         Self::write_file(WriteFileError::new(error, path))
     }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            WriteErrorSource::Build(error) => error.exit_code(),
+            WriteErrorSource::BuildSkeleton(_) | WriteErrorSource::BuildLinks(_) => Code::Config,
+            _ => Code::Io,
+        }
+    }
+}
+
+/// Represents sources of errors that can occur when rendering entries.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum RenderEntryErrorSource {
+    /// Init errors.
+    Init(#[from] InitError),
+    /// Build errors.
+    Build(#[from] BuildError),
+}
+
+/// Represents errors that can occur when rendering entries.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to render the entry")]
+#[diagnostic(
+    code(changelogging::builder::render_entry),
+    help("see the report for more information")
+)]
+pub struct RenderEntryError {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: RenderEntryErrorSource,
+}
+
+impl RenderEntryError {
+    /// Constructs [`Self`].
+    pub fn new(source: RenderEntryErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`BuildError`].
+    pub fn build(error: BuildError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            RenderEntryErrorSource::Init(_) => Code::Config,
+            RenderEntryErrorSource::Build(error) => error.exit_code(),
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+/// Renders the entry from the given fragments, without touching the filesystem.
+///
+/// This is the in-memory equivalent of building entries, useful for tests and bots
+/// that already have fragments at hand (see [`FragmentFile::from_string`]).
+///
+/// Contributors are only found if they are enabled in the configuration,
+/// which requires the `git` feature and the fragments directory.
+///
+/// # Errors
+///
+/// Returns [`RenderEntryError`] if compiling templates or rendering fails.
+pub fn render_entry<'f, I: IntoIterator<Item = Fragment<'f>>>(
+    config: Config<'_>,
+    context: Context<'_>,
+    fragments: I,
+    date: Date,
+) -> Result<String, RenderEntryError> {
+    let builder = Builder::new(context, config, date).map_err(RenderEntryError::init)?;
+
+    builder
+        .build_from(&builder.sections(fragments), &[])
+        .map_err(RenderEntryError::build)
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 struct RenderTitleData<'t> {
     #[serde(flatten)]
     context: &'t Context<'t>,
     date: Cow<'t, str>,
+    prerelease: Option<&'t str>,
+    is_prerelease: bool,
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    build_metadata: Option<&'t str>,
+    commit: Option<&'t str>,
 }
 
 impl<'t> RenderTitleData<'t> {
-    fn new(context: &'t Context<'_>, date: Date) -> Self {
+    fn new(context: &'t Context<'_>, date: Date, commit: Option<&'t str>) -> Self {
+        let version = context.version.as_ref();
+
+        let prerelease = prerelease(version);
+
+        let parsed = parse(version).ok();
+
         Self {
             context,
             date: Cow::Owned(date.to_string()),
+            prerelease,
+            is_prerelease: prerelease.is_some(),
+            major: parsed.as_ref().map(|parsed| parsed.major),
+            minor: parsed.as_ref().map(|parsed| parsed.minor),
+            patch: parsed.as_ref().map(|parsed| parsed.patch),
+            build_metadata: build_metadata(version),
+            commit,
         }
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct RenderSkeletonData<'s> {
+    #[serde(flatten)]
+    context: &'s Context<'s>,
+    start: &'s str,
+}
+
+impl<'s> RenderSkeletonData<'s> {
+    fn new(context: &'s Context<'_>, start: &'s str) -> Self {
+        Self { context, start }
+    }
+}
+
+/// Converts the given front matter value into JSON, as available to templates.
+///
+/// Unlike serializing values directly, dates and times are converted into strings
+/// (like `2024-01-15`) instead of special tables.
+pub fn meta_json(value: &toml::Value) -> JsonValue {
+    match value {
+        toml::Value::String(string) => JsonValue::String(string.clone()),
+        toml::Value::Integer(integer) => JsonValue::from(*integer),
+        toml::Value::Float(float) => JsonValue::from(*float),
+        toml::Value::Boolean(boolean) => JsonValue::Bool(*boolean),
+        toml::Value::Datetime(datetime) => JsonValue::String(datetime.to_string()),
+        toml::Value::Array(array) => array.iter().map(meta_json).collect(),
+        toml::Value::Table(table) => table
+            .iter()
+            .map(|(key, value)| (key.clone(), meta_json(value)))
+            .collect(),
+    }
+}
+
+// fragments are flattened field by field, so that `meta` is converted (see `meta_json`)
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
 struct RenderFragmentData<'f> {
     #[serde(flatten)]
     context: &'f Context<'f>,
     #[serde(flatten)]
-    fragment: &'f Fragment<'f>,
+    partial: &'f Partial<'f>,
+    content: &'f str,
+    meta: JsonValue,
+    fragment_date: Option<String>,
+    has_id: bool,
+    has_area: bool,
+    has_author: bool,
+    has_date: bool,
+    is_breaking: bool,
+    has_cve: bool,
+    has_severity: bool,
+    has_advisory_url: bool,
 }
 
 impl<'f> RenderFragmentData<'f> {
     fn new(context: &'f Context<'_>, fragment: &'f Fragment<'_>) -> Self {
-        Self { context, fragment }
+        let fragment_date = fragment.date().map(|date| date.to_string());
+
+        let has_id = fragment.partial.id.is_integer();
+        let has_area = fragment.area().is_some();
+        let has_author = fragment.author().is_some();
+        let has_date = fragment_date.is_some();
+        let is_breaking = fragment.is_breaking();
+        let has_cve = fragment.cve().is_some();
+        let has_severity = fragment.severity().is_some();
+        let has_advisory_url = fragment.advisory_url().is_some();
+
+        let meta = fragment
+            .meta
+            .iter()
+            .map(|(key, value)| (key.clone(), meta_json(value)))
+            .collect();
+
+        Self {
+            context,
+            partial: &fragment.partial,
+            content: &fragment.content,
+            meta,
+            fragment_date,
+            has_id,
+            has_area,
+            has_author,
+            has_date,
+            is_breaking,
+            has_cve,
+            has_severity,
+            has_advisory_url,
+        }
     }
 }
 
-/// Represents changelog builders.
-#[derive(Debug, Clone)]
-pub struct Builder<'b> {
-    /// The context of the project.
-    pub context: Context<'b>,
-    /// The configuration to use.
-    pub config: Config<'b>,
-    /// The date to use.
-    pub date: Date,
-    /// The renderer to use.
-    pub renderer: Handlebars<'b>,
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct RenderLinkData<'l> {
+    #[serde(flatten)]
+    context: &'l Context<'l>,
+    label: &'l str,
+    previous: Option<&'l str>,
+    has_previous: bool,
 }
 
-/// The `title` literal.
-pub const TITLE: &str = "title";
+impl<'l> RenderLinkData<'l> {
+    fn new(context: &'l Context<'_>, label: &'l str, previous: Option<&'l str>) -> Self {
+        Self {
+            context,
+            label,
+            previous,
+            has_previous: previous.is_some(),
+        }
+    }
+}
 
-/// The `fragment` literal.
-pub const FRAGMENT: &str = "fragment";
+#[derive(Debug, Clone, PartialEq, Serialize)]
+struct RenderContributorData<'c> {
+    #[serde(flatten)]
+    context: &'c Context<'c>,
+    #[serde(flatten)]
+    contributor: &'c Contributor,
+}
 
-impl<'b> Builder<'b> {
-    /// Constructs [`Self`] from [`Workspace`].
-    ///
-    /// # Errors
-    ///
-    /// Returns [`InitError`] if initializing the renderer fails.
-    pub fn from_workspace(workspace: Workspace<'b>, date: Date) -> Result<Self, InitError> {
-        Self::new(workspace.context, workspace.config, date)
+impl<'c> RenderContributorData<'c> {
+    fn new(context: &'c Context<'_>, contributor: &'c Contributor) -> Self {
+        Self {
+            context,
+            contributor,
+        }
     }
+}
 
-    /// Constructs [`Self`].
+/// Represents contributors, as returned by [`Builder::contributors`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Contributor {
+    /// The author.
     ///
-    /// # Errors
-    ///
-    /// Returns [`InitError`] if initializing the renderer fails.
-    pub fn new(context: Context<'b>, config: Config<'b>, date: Date) -> Result<Self, InitError> {
-        let mut renderer = Handlebars::new();
-
-        let formats = config.formats();
-
-        renderer.set_strict_mode(true);
+    /// This field is flattened during serialization.
+    #[serde(flatten)]
+    pub author: Author,
+    /// Whether the author makes their first contribution.
+    pub first: bool,
+}
 
-        renderer.register_escape_fn(no_escape);
+impl Contributor {
+    /// Constructs [`Self`].
+    pub fn new(author: Author, first: bool) -> Self {
+        Self { author, first }
+    }
+}
 
-        renderer.register_template_string(TITLE, formats.title.as_ref())?;
-        renderer.register_template_string(FRAGMENT, formats.fragment.as_ref())?;
+/// Represents fragments skipped during building, along with the reasons why.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Skipped {
+    /// The path to the fragment.
+    pub path: PathBuf,
+    /// The reason why the fragment is skipped.
+    pub reason: String,
+}
 
-        Ok(Self {
-            context,
-            config,
-            date,
-            renderer,
-        })
+impl Skipped {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, reason: String) -> Self {
+        Self { path, reason }
+    }
+}
+
+/// Represents reports of building, as returned by [`Builder::build_report`]
+/// and [`Builder::write`].
+///
+/// Building happens in two phases: the entry is built first, without touching anything,
+/// and then it is written, which is when `written` and `removed` are filled in.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize)]
+pub struct BuildReport {
+    /// The entry built.
+    pub entry: String,
+    /// The paths to fragments included in the entry.
+    pub included: Vec<PathBuf>,
+    /// The fragments skipped.
+    pub skipped: Vec<Skipped>,
+    /// The paths written to.
+    pub written: Vec<PathBuf>,
+    /// The paths removed.
+    pub removed: Vec<PathBuf>,
+}
+
+impl BuildReport {
+    /// Constructs [`Self`] before anything is written or removed.
+    pub fn new(entry: String, included: Vec<PathBuf>, skipped: Vec<Skipped>) -> Self {
+        Self {
+            entry,
+            included,
+            skipped,
+            written: Vec::new(),
+            removed: Vec::new(),
+        }
+    }
+}
+
+/// Represents changelog contents, as returned by [`Builder::read_changelog`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contents {
+    /// The contents, with normalized line endings.
+    pub string: String,
+    /// The line ending to write the contents with.
+    pub line_ending: LineEnding,
+}
+
+impl Contents {
+    /// Constructs [`Self`].
+    pub fn new(string: String, line_ending: LineEnding) -> Self {
+        Self {
+            string,
+            line_ending,
+        }
+    }
+}
+
+/// Represents renderers, that is, registries of templates compiled from configurations.
+///
+/// Renderers share compiled templates, so cloning them is cheap. This allows compiling templates
+/// once and reusing them across multiple builders, see [`Builder::with_renderer`].
+#[derive(Debug, Clone)]
+pub struct Renderer<'r> {
+    /// The registry of compiled templates.
+    pub registry: Arc<Handlebars<'r>>,
+}
+
+impl<'r> Renderer<'r> {
+    /// Constructs [`Self`].
+    pub fn new(registry: Handlebars<'r>) -> Self {
+        Self {
+            registry: Arc::new(registry),
+        }
+    }
+
+    /// Compiles templates of the given configuration into [`Self`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InitError`] if compiling any of the templates fails.
+    pub fn compile(config: &Config<'_>) -> Result<Self, InitError> {
+        let mut registry = Handlebars::new();
+
+        let formats = config.formats();
+
+        registry.set_strict_mode(true);
+
+        match config.escape.clone() {
+            Escape::None => registry.register_escape_fn(no_escape),
+            Escape::Html => registry.register_escape_fn(html_escape),
+            escape @ Escape::Custom(_) => {
+                registry.register_escape_fn(move |string| escape.replace(string));
+            }
+        }
+
+        registry.register_helper(SLUG, Box::new(SlugHelper));
+        registry.register_helper(DEFAULT, Box::new(DefaultHelper));
+
+        registry.register_template_string(TITLE, formats.title.as_ref())?;
+        registry.register_template_string(FRAGMENT, config.fragment_format())?;
+        registry.register_template_string(SKELETON, formats.skeleton.as_ref())?;
+
+        if let Some(reference) = formats.reference.as_ref() {
+            registry.register_template_string(REFERENCE, reference.as_ref())?;
+        }
+
+        if let Some(security) = formats.security.as_ref() {
+            registry.register_template_string(SECURITY, security.as_ref())?;
+        }
+
+        for (name, type_) in &config.types {
+            if let Some(format) = type_.format.as_ref() {
+                registry.register_template_string(&type_template(name), format.as_ref())?;
+            }
+        }
+
+        if let Some(compare) = formats.compare.as_ref() {
+            registry.register_template_string(COMPARE, compare.as_ref())?;
+        }
+
+        if let Some(unreleased) = formats.unreleased.as_ref() {
+            registry.register_template_string(UNRELEASED, unreleased.as_ref())?;
+        }
+
+        if config.contributors.enabled {
+            registry.register_template_string(CONTRIBUTOR, config.contributors.format.as_ref())?;
+
+            if let Some(first) = config.contributors.first.as_ref() {
+                registry.register_template_string(FIRST, first.as_ref())?;
+            }
+        }
+
+        Ok(Self::new(registry))
+    }
+}
+
+impl<'r> Deref for Renderer<'r> {
+    type Target = Handlebars<'r>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.registry
+    }
+}
+
+/// The `slug` literal, used as the name of the helper.
+pub const SLUG: &str = "slug";
+
+/// The `default` literal, used as the name of the helper.
+pub const DEFAULT: &str = "default";
+
+/// The `version` literal.
+pub const VERSION: &str = "version";
+
+/// The separator between `fragment` and type names in names of per-type templates.
+pub const TYPE_SEPARATOR: char = '.';
+
+/// Returns the name of the template of the given type (for instance, `fragment.fix`).
+pub fn type_template(name: &str) -> String {
+    format!("{FRAGMENT}{TYPE_SEPARATOR}{name}")
+}
+
+/// Represents the `slug` helper, which derives slugs from versions (see [`slug`]).
+///
+/// The helper takes the version as its only parameter, defaulting to the `version` variable,
+/// so that both `{{slug}}` and `{{slug "1.2.0"}}` work.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct SlugHelper;
+
+impl HelperDef for SlugHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        helper: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        context: &'rc TemplateContext,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        let value = match helper.param(0) {
+            Some(param) => param.value(),
+            None => context
+                .data()
+                .get(VERSION)
+                .ok_or_else(|| RenderErrorReason::MissingVariable(Some(VERSION.to_owned())))?,
+        };
+
+        let version = value
+            .as_str()
+            .ok_or(RenderErrorReason::InvalidParamType("string"))?;
+
+        Ok(ScopedJson::Derived(JsonValue::String(slug(version))))
+    }
+}
+
+/// Represents the `default` helper, which gives the value of its first parameter unless
+/// it is missing or `null`, falling back to the second parameter (or the empty string).
+///
+/// Unlike referring to missing variables, passing them to this helper is not an error
+/// in strict mode, so that `{{default meta.issue "none"}}` works for every fragment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DefaultHelper;
+
+impl HelperDef for DefaultHelper {
+    fn call_inner<'reg: 'rc, 'rc>(
+        &self,
+        helper: &Helper<'rc>,
+        _: &'reg Handlebars<'reg>,
+        _: &'rc TemplateContext,
+        _: &mut RenderContext<'reg, 'rc>,
+    ) -> Result<ScopedJson<'rc>, RenderError> {
+        let value = helper
+            .param(0)
+            .filter(|param| !param.is_value_missing() && !param.value().is_null())
+            .or_else(|| helper.param(1))
+            .map_or_else(
+                || JsonValue::String(String::new()),
+                |param| param.value().clone(),
+            );
+
+        Ok(ScopedJson::Derived(value))
+    }
+}
+
+/// Represents changelog builders.
+///
+/// Cloning builders does not recompile templates, since renderers are shared.
+#[derive(Debug, Clone)]
+pub struct Builder<'b> {
+    /// The context of the project.
+    pub context: Context<'b>,
+    /// The configuration to use.
+    pub config: Config<'b>,
+    /// The date to use.
+    pub date: Date,
+    /// The renderer to use.
+    pub renderer: Renderer<'b>,
+    /// The selection of fragments to build, every fragment by default.
+    pub selection: Selection,
+    /// The short hash of the current commit, found once needed (see [`commit`]).
+    ///
+    /// [`commit`]: Self::commit
+    pub commit: OnceCell<Option<String>>,
+    /// The timings to record, if any (see [`with_timings`]).
+    ///
+    /// [`with_timings`]: Self::with_timings
+    pub timings: Option<Timings>,
+    /// The root of the project, if not the current directory (see [`Workspace::root`]).
+    pub root: Option<PathBuf>,
+}
+
+/// The `title` literal.
+pub const TITLE: &str = "title";
+
+/// The `fragment` literal.
+pub const FRAGMENT: &str = "fragment";
+
+/// The `skeleton` literal.
+pub const SKELETON: &str = "skeleton";
+
+/// The `content` literal.
+pub const CONTENT: &str = "content";
+
+/// The `reference` literal.
+pub const REFERENCE: &str = "reference";
+
+/// The `security` literal.
+pub const SECURITY: &str = "security";
+
+/// The `compare` literal.
+pub const COMPARE: &str = "compare";
+
+/// The `unreleased` literal.
+pub const UNRELEASED: &str = "unreleased";
+
+/// The label of the link reference definition of unreleased changes.
+pub const UNRELEASED_LABEL: &str = "Unreleased";
+
+/// The `contributor` literal.
+pub const CONTRIBUTOR: &str = "contributor";
+
+/// The `first` literal.
+pub const FIRST: &str = "first";
+
+/// The `template` literal.
+pub const TEMPLATE: &str = "template";
+
+impl<'b> Builder<'b> {
+    /// Constructs [`Self`] from [`Workspace`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InitError`] if initializing the renderer fails.
+    pub fn from_workspace(workspace: Workspace<'b>, date: Date) -> Result<Self, InitError> {
+        let root = workspace.root;
+
+        let builder = Self::new(workspace.context, workspace.config, date)?;
+
+        Ok(Self { root, ..builder })
+    }
+
+    /// Constructs [`Self`], compiling templates of the given configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InitError`] if initializing the renderer fails.
+    pub fn new(context: Context<'b>, config: Config<'b>, date: Date) -> Result<Self, InitError> {
+        let renderer = Renderer::compile(&config)?;
+
+        Ok(Self::with_renderer(context, config, date, renderer))
+    }
+
+    /// Constructs [`Self`] with the given renderer, which avoids compiling templates again.
+    ///
+    /// The renderer must be compiled from the same configuration (see [`Renderer::compile`]).
+    pub fn with_renderer(
+        context: Context<'b>,
+        config: Config<'b>,
+        date: Date,
+        renderer: Renderer<'b>,
+    ) -> Self {
+        let selection = Selection::default();
+
+        let commit = OnceCell::new();
+
+        let timings = None;
+
+        let root = None;
+
+        Self {
+            context,
+            config,
+            date,
+            renderer,
+            selection,
+            commit,
+            timings,
+            root,
+        }
+    }
+
+    /// Returns [`Self`] building only the fragments selected by the given selection.
+    #[must_use]
+    pub fn with_selection(self, selection: Selection) -> Self {
+        Self { selection, ..self }
+    }
+
+    /// Returns [`Self`] recording how long collecting, rendering and writing take,
+    /// see [`Timings`].
+    #[must_use]
+    pub fn with_timings(self) -> Self {
+        let timings = Some(Timings::new());
+
+        Self { timings, ..self }
+    }
+
+    // only measure when timings are recorded, as measuring is not supported everywhere
+
+    fn timed<T, F: FnOnce() -> T>(&self, phase: Phase, function: F) -> T {
+        match self.timings.as_ref() {
+            Some(timings) => timings.time(phase, function),
+            None => function(),
+        }
+    }
+
+    /// Resolves the given path, relative to the project, against the root, if any.
+    pub fn project_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        project_path(self.root.as_deref(), path.as_ref())
+    }
+
+    /// Returns the copy of [`Self`] using the given date, sharing the renderer.
+    #[must_use]
+    pub fn at(&self, date: Date) -> Self {
+        Self {
+            date,
+            ..self.clone()
+        }
     }
 }
 
 const SPACE: char = ' ';
 const NEW_LINE: char = '\n';
+const NEW_LINE_STR: &str = "\n";
 const DOUBLE_NEW_LINE: &str = "\n\n";
-const NO_SIGNIFICANT_CHANGES: &str = "No significant changes.";
+const NESTED: &str = "  ";
+const BOLD: &str = "**";
+const COLON: char = ':';
+const DETAILS_START: &str = "<details>";
+const DETAILS_END: &str = "</details>";
+const CHANGE: &str = "change";
+const CHANGES: &str = "changes";
 
 fn heading(character: char, level: Level) -> String {
-    repeat(character)
-        .take(level.into())
+    repeat_n(character, level.into())
         .chain(once(SPACE))
         .collect()
 }
@@ -417,6 +1448,31 @@ fn indent(character: char) -> String {
     once(character).chain(once(SPACE)).collect()
 }
 
+/// Converts line endings of the given string (with normalized line endings)
+/// to `\r\n` if the given line ending is [`LineEnding::Crlf`].
+pub fn convert(string: &str, line_ending: LineEnding) -> Cow<'_, str> {
+    match line_ending {
+        LineEnding::Crlf => Cow::Owned(to_crlf(string)),
+        LineEnding::Lf | LineEnding::Preserve => Cow::Borrowed(string),
+    }
+}
+
+#[cfg(feature = "fs")]
+fn write_file(path: &Path, string: &str, line_ending: LineEnding) -> Result<(), WriteError> {
+    let mut file = File::options()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(path)
+        .map_err(|error| WriteError::new_open_file(error, path.to_owned()))?;
+
+    let string = convert(string, line_ending);
+
+    write!(file, "{string}").map_err(|error| WriteError::new_write_file(error, path.to_owned()))?;
+
+    Ok(())
+}
+
 impl Builder<'_> {
     /// Returns [`Context`] reference.
     pub fn context(&self) -> &Context<'_> {
@@ -430,338 +1486,2098 @@ impl Builder<'_> {
 
     // BUILDING
 
-    /// Builds entries and writes them to the changelog.
+    /// Builds entries and writes them to the changelog, returning [`BuildReport`].
     ///
     /// # Errors
     ///
     /// Returns [`WriteError`] when building fails, as well as when I/O operations fail.
-    pub fn write(&self) -> Result<(), WriteError> {
-        let entry = self.build().map_err(WriteError::build)?;
+    ///
+    /// If `fold_prereleases` is set, prerelease entries are folded into the new entry
+    /// and removed from the changelog, see [`prereleases`].
+    ///
+    /// If `retain` is set, entries beyond the newest ones are dropped, see [`retain`].
+    ///
+    /// [`prereleases`]: Self::prereleases
+    /// [`retain`]: Self::retain
+    #[cfg(feature = "fs")]
+    pub fn write(&self) -> Result<BuildReport, WriteError> {
+        let contents = self.read_changelog()?;
+
+        let prereleases = self.prereleases(&contents.string);
+
+        let mut report = self.build_report(&prereleases).map_err(WriteError::build)?;
+
+        let remaining = remove(&contents.string, &prereleases);
+
+        let string = self.splice(&remaining, &report.entry);
+
+        let string = self.link(&string).map_err(WriteError::build_links)?;
+
+        let archive = self.write_retained(&string, contents.line_ending)?;
+
+        report.written.extend(archive);
+
+        report
+            .written
+            .push(self.config.paths.output.clone().into_owned());
+
+        Ok(report)
+    }
+
+    /// Keeps the newest `retain` entries in the given changelog contents, returning
+    /// the resulting string along with the dropped entries joined together, if any.
+    ///
+    /// If link reference definitions are maintained (see [`link`]), they are kept
+    /// at the end of the changelog.
+    ///
+    /// [`link`]: Self::link
+    pub fn retain(&self, contents: &str) -> (String, Option<String>) {
+        let Some(retain) = self.config.retain else {
+            return (contents.to_owned(), None);
+        };
+
+        let (body, definitions) = if self.config.formats.compare.is_some() {
+            split_definitions(contents)
+        } else {
+            (contents, "")
+        };
+
+        let found = entries(body, &self.entry_heading());
+
+        let Some(dropped) = found
+            .get(retain.get()..)
+            .filter(|dropped| !dropped.is_empty())
+        else {
+            return (contents.to_owned(), None);
+        };
+
+        debug!("dropping {} entries", dropped.len());
+
+        let mut string = remove(body, dropped).trim_end().to_owned();
+
+        string.push(NEW_LINE);
+
+        if !definitions.is_empty() {
+            string.push(NEW_LINE);
+            string.push_str(definitions);
+            string.push(NEW_LINE);
+        }
+
+        let joined = dropped
+            .iter()
+            .map(|entry| contents[entry.start..entry.end].trim_end())
+            .join(DOUBLE_NEW_LINE);
+
+        (string, Some(joined))
+    }
+
+    /// Removes the newest entry from the given changelog contents, returning the resulting string,
+    /// or [`None`] if there are no entries.
+    pub fn remove_newest(&self, contents: &str) -> Option<String> {
+        let newest = entries(contents, &self.entry_heading())
+            .into_iter()
+            .next()?;
+
+        Some(remove(contents, &[newest]))
+    }
+
+    /// Reads the archive, returning its contents with normalized line endings,
+    /// or [`None`] if the archive is not configured or does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WriteError`] when opening or reading the archive fails.
+    #[cfg(feature = "fs")]
+    pub fn read_archive(&self) -> Result<Option<String>, WriteError> {
+        let Some(path) = self.config.paths.archive.as_deref() else {
+            return Ok(None);
+        };
+
+        let string = match File::options().read(true).open(path) {
+            Ok(file) => read_to_string(file)
+                .map_err(|error| WriteError::new_read_file(error, path.to_owned()))?,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(WriteError::new_open_file(error, path.to_owned())),
+        };
+
+        Ok(Some(normalize(strip_bom(&string)).into_owned()))
+    }
+
+    /// Moves the given entries to the archive, that is, splices them into `paths.archive`,
+    /// creating it if needed, and returns the path to it.
+    /// Does nothing if the archive is not configured.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WriteError`] when opening, reading or writing the archive fails.
+    #[cfg(feature = "fs")]
+    pub fn archive(&self, entries: &str) -> Result<Option<PathBuf>, WriteError> {
+        let Some(path) = self.config.paths.archive.as_deref() else {
+            return Ok(None);
+        };
+
+        info!("archiving entries to `{}`", path.display());
+
+        let string = match File::options().read(true).open(path) {
+            Ok(file) => read_to_string(file)
+                .map_err(|error| WriteError::new_read_file(error, path.to_owned()))?,
+            Err(error) if error.kind() == ErrorKind::NotFound => String::new(),
+            Err(error) => return Err(WriteError::new_open_file(error, path.to_owned())),
+        };
+
+        let contents = strip_bom(&string);
+
+        let line_ending = self.resolve_line_ending(contents);
+
+        let string = self.splice(&normalize(contents), entries);
+
+        write_file(path, &string, line_ending)?;
+
+        Ok(Some(path.to_owned()))
+    }
+
+    /// Applies [`retain`] to the given changelog contents, writing the resulting string
+    /// to the changelog and moving the dropped entries to the archive (see [`archive`]),
+    /// returning the path to the archive if anything was archived.
+    ///
+    /// The changelog is written first, so that entries never end up in both files
+    /// in case archiving fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WriteError`] when writing the changelog or archiving fails.
+    ///
+    /// [`retain`]: Self::retain
+    /// [`archive`]: Self::archive
+    #[cfg(feature = "fs")]
+    pub fn write_retained(
+        &self,
+        contents: &str,
+        line_ending: LineEnding,
+    ) -> Result<Option<PathBuf>, WriteError> {
+        let (string, dropped) = self.retain(contents);
+
+        self.write_changelog(&string, line_ending)?;
+
+        match dropped {
+            Some(dropped) => self.archive(&dropped),
+            None => Ok(None),
+        }
+    }
+
+    /// Reads the contents of the changelog.
+    ///
+    /// If the changelog does not exist and `create_output` is set,
+    /// the skeleton of the new changelog is returned instead.
+    ///
+    /// The byte order mark is stripped and line endings are normalized, while the line ending
+    /// to write the changelog with is resolved according to `line_ending`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WriteError`] when opening or reading the changelog,
+    /// or building the skeleton fails.
+    #[cfg(feature = "fs")]
+    pub fn read_changelog(&self) -> Result<Contents, WriteError> {
+        let path = self.config.paths.output.as_ref();
+
+        let file = match File::options().read(true).open(path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::NotFound && self.config.create_output => {
+                let string = self.build_skeleton().map_err(WriteError::build_skeleton)?;
+
+                return Ok(Contents::new(string, self.resolve_line_ending("")));
+            }
+            Err(error) => return Err(WriteError::new_open_file(error, path.to_owned())),
+        };
+
+        let string = read_to_string(file)
+            .map_err(|error| WriteError::new_read_file(error, path.to_owned()))?;
+
+        let contents = strip_bom(&string);
+
+        let line_ending = self.resolve_line_ending(contents);
+
+        Ok(Contents::new(normalize(contents).into_owned(), line_ending))
+    }
+
+    /// Resolves the line ending to use for the given changelog contents.
+    ///
+    /// Unless `line_ending` is set to `preserve`, it is returned as-is.
+    /// Otherwise, the dominant line ending of the contents is returned.
+    pub fn resolve_line_ending(&self, contents: &str) -> LineEnding {
+        match self.config.line_ending {
+            LineEnding::Preserve if is_crlf(contents) => LineEnding::Crlf,
+            LineEnding::Preserve => LineEnding::Lf,
+            line_ending => line_ending,
+        }
+    }
+
+    /// Writes the given string to the changelog, replacing its contents.
+    ///
+    /// Line endings are converted to `\r\n` if the given line ending is [`LineEnding::Crlf`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WriteError`] when opening or writing to the changelog fails.
+    #[cfg(feature = "fs")]
+    pub fn write_changelog(&self, string: &str, line_ending: LineEnding) -> Result<(), WriteError> {
+        self.write_changelog_to(self.config.paths.output.as_ref(), string, line_ending)
+    }
+
+    /// Similar to [`write_changelog`], except the string is written to the given path.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WriteError`] when opening or writing to the file fails.
+    ///
+    /// [`write_changelog`]: Self::write_changelog
+    #[cfg(feature = "fs")]
+    pub fn write_changelog_to(
+        &self,
+        path: &Path,
+        string: &str,
+        line_ending: LineEnding,
+    ) -> Result<(), WriteError> {
+        self.timed(Phase::Writing, || write_file(path, string, line_ending))
+    }
+
+    /// Splices the given entry into the changelog contents, returning the resulting string.
+    ///
+    /// The entry is written after the start marker, if present, or at the beginning otherwise.
+    pub fn splice(&self, contents: &str, entry: &str) -> String {
+        let start = self.config.start.as_ref();
+
+        let mut string = String::new();
+
+        if let Some((before, after)) = contents.split_once(start) {
+            string.push_str(before);
+
+            string.push_str(start);
+
+            string.push_str(DOUBLE_NEW_LINE);
+
+            string.push_str(entry);
+
+            string.push(NEW_LINE);
+
+            let trimmed = after.trim_start();
+
+            if !trimmed.is_empty() {
+                string.push(NEW_LINE);
+
+                string.push_str(trimmed);
+            }
+        } else {
+            string.push_str(entry);
+
+            string.push(NEW_LINE);
+
+            let trimmed = contents.trim_start();
+
+            if !trimmed.is_empty() {
+                string.push(NEW_LINE);
+
+                string.push_str(trimmed);
+            }
+        };
+
+        string
+    }
+
+    fn render_link(
+        &self,
+        name: &str,
+        format: &str,
+        label: &str,
+        previous: Option<&str>,
+    ) -> Result<String, BuildLinksError> {
+        let data = RenderLinkData::new(self.context(), label, previous);
+
+        let string = self.renderer.render(name, &data).map_err(|error| {
+            RenderTemplateError::new(error, name.to_owned(), format.to_owned(), &variables(&data))
+        })?;
+
+        Ok(string)
+    }
+
+    /// Maintains link reference definitions at the end of the given changelog contents,
+    /// returning the resulting string.
+    ///
+    /// Does nothing unless the `compare` format is provided and the title of the newest entry
+    /// starts with the reference link (see [`reference_label`]). Otherwise, the definition
+    /// of the newest entry is added (replacing the existing one, if any), comparing it
+    /// with the previous entry, and so is the [`UNRELEASED_LABEL`] one if the `unreleased`
+    /// format is provided. Other definitions are kept as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildLinksError`] when rendering any of the definitions fails.
+    pub fn link(&self, contents: &str) -> Result<String, BuildLinksError> {
+        let Some(compare) = self.config.formats.compare.as_ref() else {
+            return Ok(contents.to_owned());
+        };
+
+        let (body, block) = split_definitions(contents);
+
+        let found = entries(body, &self.entry_heading());
+
+        let Some(label) = found.first().and_then(|entry| reference_label(entry.title)) else {
+            return Ok(contents.to_owned());
+        };
+
+        let previous = found.get(1).and_then(|entry| reference_label(entry.title));
+
+        let url = self.render_link(COMPARE, compare, label, previous)?;
+
+        let is_label = |line: &str, other: &str| {
+            definition_label(line).is_some_and(|existing| existing.eq_ignore_ascii_case(other))
+        };
+
+        let unreleased = match self.config.formats.unreleased.as_ref() {
+            Some(unreleased) => {
+                let url =
+                    self.render_link(UNRELEASED, unreleased, UNRELEASED_LABEL, Some(label))?;
+
+                Some(format!("[{UNRELEASED_LABEL}]: {url}"))
+            }
+            None => block
+                .lines()
+                .find(|line| is_label(line, UNRELEASED_LABEL))
+                .map(str::to_owned),
+        };
+
+        let rest = block
+            .lines()
+            .filter(|line| !is_label(line, label) && !is_label(line, UNRELEASED_LABEL))
+            .map(str::to_owned);
+
+        let definitions = unreleased
+            .into_iter()
+            .chain(once(format!("[{label}]: {url}")))
+            .chain(rest)
+            .join(NEW_LINE_STR);
+
+        let mut string = body.trim_end().to_owned();
+
+        string.push_str(DOUBLE_NEW_LINE);
+        string.push_str(&definitions);
+        string.push(NEW_LINE);
+
+        Ok(string)
+    }
+
+    // MARKING
+
+    /// Checks whether the given changelog contents contain the start marker.
+    pub fn contains_start(&self, contents: &str) -> bool {
+        contents.contains(self.config.start.as_ref())
+    }
+
+    /// Inserts the start marker into the given changelog contents, returning the resulting string.
+    ///
+    /// The marker is inserted after the leading top-level heading, if any,
+    /// or at the beginning otherwise.
+    pub fn insert_start(&self, contents: &str) -> String {
+        let start = self.config.start.as_ref();
+
+        let heading = heading(self.config.indents.heading, Level::MIN);
+
+        let (before, after) = if contents.starts_with(&heading) {
+            contents.split_once(NEW_LINE).unwrap_or((contents, ""))
+        } else {
+            ("", contents)
+        };
+
+        let mut string = String::new();
+
+        if !before.is_empty() {
+            string.push_str(before);
+
+            string.push_str(DOUBLE_NEW_LINE);
+        }
+
+        string.push_str(start);
+
+        string.push(NEW_LINE);
+
+        let trimmed = after.trim_start();
+
+        if !trimmed.is_empty() {
+            string.push(NEW_LINE);
+
+            string.push_str(trimmed);
+        }
+
+        string
+    }
+
+    /// Builds and previews (prints) entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] when building fails.
+    #[cfg(feature = "fs")]
+    pub fn preview(&self) -> Result<(), BuildError> {
+        let string = self.build()?;
+
+        println!("{string}");
+
+        Ok(())
+    }
+
+    /// Builds and returns entries.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] when rendering titles and fragments, collecting fragments
+    /// or finding contributors fails.
+    #[cfg(feature = "fs")]
+    pub fn build(&self) -> Result<String, BuildError> {
+        self.build_with(&[])
+    }
+
+    /// Checks whether prerelease entries are folded, that is, whether `fold_prereleases`
+    /// is set and the version being built is not the prerelease itself.
+    pub fn folds_prereleases(&self) -> bool {
+        self.config.fold_prereleases && !is_prerelease(self.context.version.as_ref())
+    }
+
+    /// Finds prerelease entries of the version being built in the given changelog contents.
+    ///
+    /// Returns no entries unless prerelease entries are folded, see [`folds_prereleases`].
+    ///
+    /// [`folds_prereleases`]: Self::folds_prereleases
+    pub fn prereleases<'c>(&self, contents: &'c str) -> Vec<Entry<'c>> {
+        if !self.folds_prereleases() {
+            return Vec::new();
+        }
+
+        let version = self.context.version.as_ref();
+
+        let found: Vec<Entry<'_>> = entries(contents, &self.entry_heading())
+            .into_iter()
+            .filter(|entry| entry.is_prerelease_of(version))
+            .collect();
+
+        debug!("found {} prerelease entries", found.len());
+
+        found
+    }
+
+    /// Finds the existing entry of the version being built in the given changelog contents,
+    /// which is replaced when amending builds.
+    pub fn existing<'c>(&self, contents: &'c str) -> Option<Entry<'c>> {
+        find(
+            contents,
+            &self.entry_heading(),
+            self.context.version.as_ref(),
+        )
+    }
+
+    /// Builds and returns entries, folding prerelease entries of the version being built
+    /// found in the changelog, in case they are folded (see [`folds_prereleases`]).
+    ///
+    /// Unlike [`write`], the changelog is left intact.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`WriteError`] when reading the changelog or building fails.
+    ///
+    /// [`folds_prereleases`]: Self::folds_prereleases
+    /// [`write`]: Self::write
+    #[cfg(feature = "fs")]
+    pub fn build_entry(&self) -> Result<String, WriteError> {
+        // there is nothing to fold if the changelog does not exist
+
+        if !self.folds_prereleases() || !self.config.paths.output.exists() {
+            return self.build().map_err(WriteError::build);
+        }
+
+        let contents = self.read_changelog()?;
+
+        let prereleases = self.prereleases(&contents.string);
+
+        self.build_with(&prereleases).map_err(WriteError::build)
+    }
+
+    /// Builds and returns entries, folding the given prerelease entries into them.
+    ///
+    /// Bullets of prerelease entries are merged into sections with matching titles,
+    /// oldest entries first, followed by the bullets built from fragments.
+    /// Link reference definitions of prerelease entries are kept as well.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] when rendering titles and fragments, collecting fragments
+    /// or finding contributors fails.
+    #[cfg(feature = "fs")]
+    pub fn build_with(&self, prereleases: &[Entry<'_>]) -> Result<String, BuildError> {
+        let files = self.read_files().map_err(BuildError::collect)?;
+
+        self.build_from(&self.collect_from(&files), prereleases)
+    }
+
+    /// Similar to [`build_with`], except [`BuildReport`] is returned, which also tells
+    /// which fragments are included in the entry and which ones are skipped (and why).
+    ///
+    /// This is the first phase of [`write`], so nothing is written or removed yet.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] when rendering titles and fragments, collecting fragments
+    /// or finding contributors fails.
+    ///
+    /// [`build_with`]: Self::build_with
+    /// [`write`]: Self::write
+    #[cfg(feature = "fs")]
+    pub fn build_report(&self, prereleases: &[Entry<'_>]) -> Result<BuildReport, BuildError> {
+        let mut skipped = Vec::new();
+
+        let files = self
+            .read_files_skipping(&mut skipped)
+            .map_err(BuildError::collect)?;
+
+        self.report_from(&files, skipped, prereleases)
+    }
+
+    /// Similar to [`build_report`], except fragments are read from the fragments directory
+    /// as of the given revision via `git` (see [`read_files_at`]), for instance,
+    /// to build entries from fragments that only exist on release branches.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] when rendering titles and fragments, collecting fragments
+    /// or finding contributors fails.
+    ///
+    /// [`build_report`]: Self::build_report
+    /// [`read_files_at`]: Self::read_files_at
+    #[cfg(feature = "git")]
+    pub fn build_report_at(
+        &self,
+        revision: &str,
+        prereleases: &[Entry<'_>],
+    ) -> Result<BuildReport, BuildError> {
+        let mut skipped = Vec::new();
+
+        let files = self
+            .read_files_at_skipping(revision, &mut skipped)
+            .map_err(BuildError::collect)?;
+
+        self.report_from(&files, skipped, prereleases)
+    }
+
+    #[cfg(feature = "fs")]
+    fn report_from(
+        &self,
+        files: &[FragmentFile],
+        mut skipped: Vec<Skipped>,
+        prereleases: &[Entry<'_>],
+    ) -> Result<BuildReport, BuildError> {
+        let (included, unused) = self.classify(files);
+
+        skipped.extend(unused);
+
+        skipped.sort_by(|this, that| this.path.file_name().cmp(&that.path.file_name()));
+
+        let entry = self.build_from(&self.collect_from(files), prereleases)?;
+
+        Ok(BuildReport::new(entry, included, skipped))
+    }
+
+    /// Finds which of the given fragment files are included in entries, that is, the ones
+    /// that can be parsed and are of types that are known and included in `order`.
+    ///
+    /// Returns paths to the included fragments, along with the skipped ones.
+    pub fn classify(&self, files: &[FragmentFile]) -> (Vec<PathBuf>, Vec<Skipped>) {
+        let types = self.config.types_with_defaults();
+
+        let mut included = Vec::new();
+        let mut skipped = Vec::new();
+
+        for file in files {
+            let reason = match file.fragment() {
+                Ok(fragment) => {
+                    let type_name = self.config.canonical_type(&fragment.partial.type_name);
+
+                    if !self.selection.includes(&fragment.partial.id) {
+                        Some("not selected".to_owned())
+                    } else if !types.contains_key(type_name) {
+                        Some(format!("unknown type `{type_name}`"))
+                    } else if !self.config.order.iter().any(|name| name == type_name) {
+                        Some(format!("type `{type_name}` is not included in `order`"))
+                    } else {
+                        None
+                    }
+                }
+                Err(error) => Some(chain(&error.source)),
+            };
+
+            match reason {
+                Some(reason) => skipped.push(Skipped::new(file.path.clone(), reason)),
+                None => included.push(file.path.clone()),
+            }
+        }
+
+        (included, skipped)
+    }
+
+    /// Builds and returns entries from the given sections, folding the given prerelease
+    /// entries into them, without touching the filesystem.
+    ///
+    /// This is what [`build_with`] does once fragments are collected.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] when rendering titles and fragments or finding contributors fails.
+    ///
+    /// [`build_with`]: Self::build_with
+    pub fn build_from(
+        &self,
+        sections: &Sections<'_>,
+        prereleases: &[Entry<'_>],
+    ) -> Result<String, BuildError> {
+        self.build_from_with(sections, prereleases, true)
+    }
+
+    /// Builds and returns entries from the given fragments, guaranteeing byte-stable output,
+    /// which is designed for golden (snapshot) tests.
+    ///
+    /// The date is the one the builder is constructed with, fragment contents are normalized
+    /// (byte order marks are stripped and line endings are converted to `\n`) and fragments
+    /// are sorted regardless of the order they are given in. Contributors are never included,
+    /// as finding them depends on the history of the repository, and the output always
+    /// uses `\n` line endings, regardless of `line_ending`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] when rendering titles and fragments fails.
+    pub fn build_deterministic<'f, I: IntoIterator<Item = Fragment<'f>>>(
+        &self,
+        fragments: I,
+    ) -> Result<String, BuildError> {
+        let mut fragments: Vec<_> = fragments
+            .into_iter()
+            .map(|mut fragment| {
+                fragment.content = Cow::Owned(normalize(strip_bom(&fragment.content)).into_owned());
+
+                fragment
+            })
+            .collect();
+
+        fragments.sort_by(Fragment::compare);
+
+        let string = self.build_from_with(&self.sections(fragments), &[], false)?;
+
+        Ok(normalize(&string).into_owned())
+    }
+
+    fn build_from_with(
+        &self,
+        sections: &Sections<'_>,
+        prereleases: &[Entry<'_>],
+        contributors: bool,
+    ) -> Result<String, BuildError> {
+        self.timed(Phase::Rendering, || {
+            self.render_from_with(sections, prereleases, contributors)
+        })
+    }
+
+    fn render_from_with(
+        &self,
+        sections: &Sections<'_>,
+        prereleases: &[Entry<'_>],
+        contributors: bool,
+    ) -> Result<String, BuildError> {
+        info!(
+            "building the entry for `{}` ({})",
+            self.context.version, self.date
+        );
+
+        let mut string = self.build_title().map_err(BuildError::build_title)?;
+
+        string.push_str(DOUBLE_NEW_LINE);
+
+        let built = if prereleases.is_empty() {
+            self.build_sections(sections)
+        } else {
+            self.fold_sections(sections, prereleases)
+        }
+        .map_err(BuildError::build_fragment)?;
+
+        let contents = if built.is_empty() {
+            self.config.no_changes_or_default()
+        } else {
+            &built
+        };
+
+        string.push_str(contents);
+
+        let contributors = if contributors {
+            self.build_contributors()?
+        } else {
+            String::new()
+        };
+
+        if !contributors.is_empty() {
+            string.push_str(DOUBLE_NEW_LINE);
+
+            string.push_str(&contributors);
+        }
+
+        let built = self
+            .build_references(sections)
+            .map_err(BuildError::build_fragment)?;
+
+        // link reference definitions of prerelease entries come first, as they are older
+
+        let references = prereleases
+            .iter()
+            .rev()
+            .flat_map(Entry::references)
+            .chain(references(&built))
+            .unique()
+            .join(NEW_LINE_STR);
+
+        if !references.is_empty() {
+            string.push_str(DOUBLE_NEW_LINE);
+
+            string.push_str(&references);
+        }
+
+        Ok(string)
+    }
+
+    /// Builds entry titles.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildTitleError`] when rendering fails.
+    pub fn build_title(&self) -> Result<String, BuildTitleError> {
+        let mut string = self.entry_heading();
+
+        let title = self.render_title().map_err(|error| {
+            let data = RenderTitleData::new(self.context(), self.date, self.commit());
+
+            RenderTemplateError::new(
+                error,
+                TITLE.to_owned(),
+                self.config.formats.title.clone().into_owned(),
+                &variables(&data),
+            )
+        })?;
+
+        string.push_str(&title);
+
+        if let Some(anchor) = self.anchor() {
+            string.push(SPACE);
+
+            string.push_str(&anchor);
+        }
+
+        Ok(string)
+    }
+
+    /// Returns the anchor to append to entry titles, if any (see `anchors` in [`config`]).
+    ///
+    /// [`config`]: crate::config
+    pub fn anchor(&self) -> Option<String> {
+        self.config.anchors.anchor(&slug(&self.context.version))
+    }
+
+    /// Returns the short hash of the current commit (that is, `HEAD`), available to titles
+    /// as `commit`, or [`None`] if it can not be found.
+    ///
+    /// The commit is only found once needed, and then cached.
+    pub fn commit(&self) -> Option<&str> {
+        self.commit.get_or_init(|| self.find_commit()).as_deref()
+    }
+
+    #[cfg(not(feature = "git"))]
+    fn find_commit(&self) -> Option<String> {
+        None
+    }
+
+    #[cfg(feature = "git")]
+    fn find_commit(&self) -> Option<String> {
+        match head(&self.config.git) {
+            Ok(commit) => commit,
+            Err(error) => {
+                debug!("failed to find the current commit: {error}");
+
+                None
+            }
+        }
+    }
+
+    /// Builds skeletons of new changelogs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildSkeletonError`] when rendering fails.
+    pub fn build_skeleton(&self) -> Result<String, BuildSkeletonError> {
+        let string = self.render_skeleton().map_err(|error| {
+            let data = RenderSkeletonData::new(self.context(), self.config.start.as_ref());
+
+            RenderTemplateError::new(
+                error,
+                SKELETON.to_owned(),
+                self.config.formats.skeleton.clone().into_owned(),
+                &variables(&data),
+            )
+        })?;
+
+        Ok(string)
+    }
+
+    /// Builds section titles.
+    pub fn build_section_title_str(&self, title: &str) -> String {
+        let mut string = self.section_heading();
+
+        string.push_str(title);
+
+        string
+    }
+
+    /// Similar to [`build_section_title_str`], except the input is [`AsRef<str>`].
+    ///
+    /// [`build_section_title_str`]: Self::build_section_title_str
+    pub fn build_section_title<S: AsRef<str>>(&self, title: S) -> String {
+        self.build_section_title_str(title.as_ref())
+    }
+
+    /// Builds fragments.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when rendering fails.
+    pub fn build_fragment(&self, fragment: &Fragment<'_>) -> Result<String, BuildFragmentError> {
+        self.build_bullet(fragment).map(|string| self.wrap(string))
+    }
+
+    /// Builds bullets of fragments, that is, fragments without wrapping them.
+    ///
+    /// If `cache` is set, bullets are looked up in the cache before rendering them,
+    /// and cached afterwards (see [`cache`]). Without the `fs` feature, `cache` is ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when rendering fails.
+    ///
+    /// [`cache`]: crate::cache
+    pub fn build_bullet(&self, fragment: &Fragment<'_>) -> Result<String, BuildFragmentError> {
+        #[cfg(feature = "fs")]
+        if self.config.cache {
+            return self.build_cached_bullet(fragment);
+        }
+
+        self.render_bullet(fragment)
+    }
+
+    #[cfg(feature = "fs")]
+    fn build_cached_bullet(&self, fragment: &Fragment<'_>) -> Result<String, BuildFragmentError> {
+        let Some(key) = self.cache_key(fragment) else {
+            return self.render_bullet(fragment);
+        };
+
+        let cache = Cache::new(self.project_path(cache::DIRECTORY));
+
+        if let Some(string) = cache.get(&key) {
+            return Ok(string);
+        }
+
+        let string = self.render_bullet(fragment)?;
+
+        cache.set(&key, &string);
+
+        Ok(string)
+    }
+
+    /// Returns the cache key of the given fragment, that is, the hash of the data available
+    /// to its templates combined with the templates themselves, if it can be computed
+    /// (see [`cache::key`]).
+    #[cfg(feature = "fs")]
+    pub fn cache_key(&self, fragment: &Fragment<'_>) -> Option<Key> {
+        let data = self.fragment_data(fragment).to_string();
+
+        let template = (
+            self.fragment_format(fragment),
+            self.config.render_content,
+            &self.config.escape,
+            self.bullet_prefix(fragment),
+        );
+
+        cache::key(&(data, template))
+    }
+
+    // fragments are rendered with formats of their types or the `security` format, if configured
+
+    fn fragment_template(&self, fragment: &Fragment<'_>) -> (Cow<'static, str>, &str) {
+        let type_name = fragment.partial.type_name.as_ref();
+
+        if let Some(format) = self.config.type_format(type_name) {
+            let name = type_template(self.config.canonical_type(type_name));
+
+            return (Cow::Owned(name), format);
+        }
+
+        match self.config.security_format_for(type_name) {
+            Some(security) => (Cow::Borrowed(SECURITY), security),
+            None => (Cow::Borrowed(FRAGMENT), self.config.fragment_format()),
+        }
+    }
+
+    #[cfg(feature = "fs")]
+    fn fragment_format(&self, fragment: &Fragment<'_>) -> &str {
+        self.fragment_template(fragment).1
+    }
+
+    fn bullet_prefix(&self, fragment: &Fragment<'_>) -> Option<&str> {
+        // only configured types can have prefixes, as default ones do not
+
+        self.config
+            .types
+            .get(fragment.partial.type_name.as_ref())
+            .and_then(|type_| type_.prefix_for(Placement::Bullets))
+    }
+
+    fn render_bullet(&self, fragment: &Fragment<'_>) -> Result<String, BuildFragmentError> {
+        let rendered;
+
+        let fragment = if self.config.render_content {
+            let content = self.render_content(fragment).map_err(|error| {
+                let data = RenderFragmentData::new(self.context(), fragment);
+
+                RenderTemplateError::new(
+                    error,
+                    CONTENT.to_owned(),
+                    fragment.content.clone().into_owned(),
+                    &variables(&data),
+                )
+            })?;
+
+            rendered = Fragment {
+                content: Cow::Owned(content),
+                ..fragment.clone()
+            };
+
+            &rendered
+        } else {
+            fragment
+        };
+
+        let string = self.render_fragment(fragment).map_err(|error| {
+            let data = RenderFragmentData::new(self.context(), fragment);
+
+            let (name, format) = self.fragment_template(fragment);
+
+            RenderTemplateError::new(
+                error,
+                name.into_owned(),
+                format.to_owned(),
+                &variables(&data),
+            )
+        })?;
+
+        if let Some(prefix) = self.bullet_prefix(fragment) {
+            return Ok(format!("{prefix} {string}"));
+        }
+
+        Ok(string)
+    }
+
+    /// Builds multiple fragments and joins them together.
+    ///
+    /// Fragments are grouped by their areas according to `areas`, see [`build_areas`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when building any of the fragments fails.
+    ///
+    /// [`build_areas`]: Self::build_areas
+    pub fn build_fragments(&self, fragments: &Fragments<'_>) -> Result<String, BuildFragmentError> {
+        if self.config.areas != Grouping::None {
+            return self.build_areas(fragments);
+        }
+
+        let string = fragments
+            .iter()
+            .map(|fragment| self.build_fragment(fragment))
+            .process_results(|iterator| iterator.into_iter().join(DOUBLE_NEW_LINE))?;
+
+        Ok(string)
+    }
+
+    /// Builds multiple fragments of the given type and joins them together,
+    /// collapsing them according to the type's `collapse`.
+    ///
+    /// With [`Collapse::Bullet`], bullets are joined into one. If the type is collapsed
+    /// (see [`Type::is_collapsed`]), the result is wrapped in the `<details>` block,
+    /// summarized by the type's title and the number of changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when building any of the fragments fails.
+    ///
+    /// [`build_fragments`]: Self::build_fragments
+    pub fn build_type_fragments(
+        &self,
+        type_: &Type<'_>,
+        fragments: &Fragments<'_>,
+    ) -> Result<String, BuildFragmentError> {
+        let built = match type_.collapse {
+            Collapse::None | Collapse::Details => self.build_fragments(fragments)?,
+            Collapse::Bullet => {
+                let string = fragments
+                    .iter()
+                    .map(|fragment| self.build_bullet(fragment))
+                    .process_results(|iterator| iterator.into_iter().join(&SPACE.to_string()))?;
+
+                self.wrap(string)
+            }
+        };
+
+        if !type_.is_collapsed() {
+            return Ok(built);
+        }
+
+        let count = fragments.len();
+
+        let changes = if count == 1 { CHANGE } else { CHANGES };
+
+        let summary = format!(
+            "<summary>{title} ({count} {changes})</summary>",
+            title = type_.title
+        );
+
+        Ok(format!(
+            "{DETAILS_START}{NEW_LINE}{summary}{DOUBLE_NEW_LINE}{built}{DOUBLE_NEW_LINE}{DETAILS_END}"
+        ))
+    }
+
+    /// Builds multiple fragments grouped by their areas and joins them together.
+    ///
+    /// Fragments without areas come first, followed by the ones of each area
+    /// (sorted alphabetically), either preceded by bold sub-labels or nested.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when building any of the fragments fails.
+    pub fn build_areas(&self, fragments: &Fragments<'_>) -> Result<String, BuildFragmentError> {
+        let mut areas: BTreeMap<&str, Vec<&Fragment<'_>>> = BTreeMap::new();
+
+        let mut items = Vec::new();
+
+        for fragment in fragments {
+            match fragment.area() {
+                Some(area) => areas.entry(area).or_default().push(fragment),
+                None => items.push(self.build_fragment(fragment)?),
+            }
+        }
+
+        for (area, fragments) in areas {
+            match self.config.areas {
+                Grouping::Nested => {
+                    items.push(self.wrap(format!("{area}{COLON}")));
+
+                    for fragment in fragments {
+                        items.push(self.wrap_nested(self.build_bullet(fragment)?));
+                    }
+                }
+                Grouping::None | Grouping::Labels => {
+                    items.push(format!("{BOLD}{area}{COLON}{BOLD}"));
+
+                    for fragment in fragments {
+                        items.push(self.build_fragment(fragment)?);
+                    }
+                }
+            }
+        }
+
+        Ok(items.join(DOUBLE_NEW_LINE))
+    }
+
+    /// Builds sections.
+    ///
+    /// This is essentially the same as calling [`build_section_title`] and [`build_fragments`],
+    /// joining the results together.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when building any of the fragments fails.
+    ///
+    /// [`build_section_title`]: Self::build_section_title
+    /// [`build_fragments`]: Self::build_fragments
+    pub fn build_section_str(
+        &self,
+        title: &str,
+        fragments: &Fragments<'_>,
+    ) -> Result<String, BuildFragmentError> {
+        let mut string = self.build_section_title(title);
+
+        let built = self.build_fragments(fragments)?;
+
+        string.push_str(DOUBLE_NEW_LINE);
+        string.push_str(&built);
+
+        Ok(string)
+    }
+
+    /// Similar to [`build_section_str`], except the input is [`AsRef<str>`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when building any of the fragments fails.
+    ///
+    /// [`build_section_str`]: Self::build_section_str
+    pub fn build_section<S: AsRef<str>>(
+        &self,
+        title: S,
+        fragments: &Fragments<'_>,
+    ) -> Result<String, BuildFragmentError> {
+        self.build_section_str(title.as_ref(), fragments)
+    }
+
+    /// Builds sections of the given type.
+    ///
+    /// This is the same as [`build_section`], except fragments are built
+    /// via [`build_type_fragments`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when building any of the fragments fails.
+    ///
+    /// [`build_section`]: Self::build_section
+    /// [`build_type_fragments`]: Self::build_type_fragments
+    pub fn build_type_section(
+        &self,
+        type_: &Type<'_>,
+        fragments: &Fragments<'_>,
+    ) -> Result<String, BuildFragmentError> {
+        let mut string = self.build_section_title(type_.heading());
+
+        let built = self.build_type_fragments(type_, fragments)?;
+
+        string.push_str(DOUBLE_NEW_LINE);
+        string.push_str(&built);
+
+        Ok(string)
+    }
+
+    /// Builds link reference definitions of fragments included in sections.
+    ///
+    /// Returns the empty string unless the `reference` format is provided.
+    /// Only fragments with integer IDs are referenced, and duplicate definitions are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when rendering any of the definitions fails.
+    pub fn build_references(&self, sections: &Sections<'_>) -> Result<String, BuildFragmentError> {
+        let Some(reference) = self.config.formats.reference.as_ref() else {
+            return Ok(String::new());
+        };
+
+        let types = self.config.types_with_defaults();
+
+        let string = self
+            .config
+            .order
+            .iter()
+            .filter(|name| types.contains_key(*name))
+            .filter_map(|name| sections.get(name))
+            .flatten()
+            .filter(|fragment| fragment.partial.id.is_integer())
+            .map(|fragment| {
+                let data = RenderFragmentData::new(self.context(), fragment);
+
+                self.renderer.render(REFERENCE, &data).map_err(|error| {
+                    RenderTemplateError::new(
+                        error,
+                        REFERENCE.to_owned(),
+                        reference.clone().into_owned(),
+                        &variables(&data),
+                    )
+                })
+            })
+            .process_results(|iterator| iterator.unique().join(NEW_LINE_STR))?;
+
+        Ok(string)
+    }
+
+    /// Finds contributors, according to the `contributors` section.
+    ///
+    /// Returns no contributors unless the section is enabled. First-time contributors
+    /// are only detected if the `first` format is provided.
+    ///
+    /// Without the `git` feature, no contributors are found and the warning is logged instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] when collecting fragment paths or running `git` fails.
+    pub fn contributors(&self) -> Result<Vec<Contributor>, BuildError> {
+        if !self.config.contributors.enabled {
+            return Ok(Vec::new());
+        }
+
+        self.find_contributors()
+    }
+
+    #[cfg(not(feature = "git"))]
+    fn find_contributors(&self) -> Result<Vec<Contributor>, BuildError> {
+        warn!("finding contributors requires the `git` feature");
+
+        Ok(Vec::new())
+    }
+
+    #[cfg(feature = "git")]
+    fn find_contributors(&self) -> Result<Vec<Contributor>, BuildError> {
+        let contributors = &self.config.contributors;
+
+        let git = &self.config.git;
+
+        let tag = last_tag(git).map_err(BuildError::new_contributors)?;
+
+        let authors = match contributors.since {
+            Since::Fragments => {
+                let paths = self.collect_paths().map_err(BuildError::collect)?;
+
+                // without paths, `git log` would consider the entire history
+
+                if paths.is_empty() {
+                    return Ok(Vec::new());
+                }
+
+                authors(git, None, paths)
+            }
+            Since::Tag => {
+                let range = tag.as_ref().map(|tag| format!("{tag}..{HEAD}"));
+
+                authors::<&Path, _>(git, range.as_deref(), [])
+            }
+        }
+        .map_err(BuildError::new_contributors)?;
+
+        debug!("found {} contributor(s)", authors.len());
+
+        let first = if contributors.first.is_some() {
+            first_time(git, &authors, tag.as_deref()).map_err(BuildError::new_contributors)?
+        } else {
+            vec![false; authors.len()]
+        };
+
+        let contributors = authors
+            .into_iter()
+            .zip(first)
+            .map(|(author, first)| Contributor::new(author, first))
+            .collect();
+
+        Ok(contributors)
+    }
+
+    /// Builds the *Contributors* section.
+    ///
+    /// Returns the empty string if the section is disabled or there are no contributors.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] when finding contributors or rendering any of them fails.
+    pub fn build_contributors(&self) -> Result<String, BuildError> {
+        let found = self.contributors()?;
+
+        if found.is_empty() {
+            return Ok(String::new());
+        }
+
+        let contributors = &self.config.contributors;
+
+        let built = found
+            .iter()
+            .map(|contributor| {
+                let data = RenderContributorData::new(self.context(), contributor);
+
+                let (name, format) = match contributors.first.as_ref() {
+                    Some(first) if contributor.first => (FIRST, first),
+                    _ => (CONTRIBUTOR, &contributors.format),
+                };
+
+                self.renderer
+                    .render(name, &data)
+                    .map(|string| self.wrap(string))
+                    .map_err(|error| {
+                        RenderTemplateError::new(
+                            error,
+                            name.to_owned(),
+                            format.clone().into_owned(),
+                            &variables(&data),
+                        )
+                    })
+            })
+            .process_results(|iterator| iterator.into_iter().join(NEW_LINE_STR))
+            .map_err(BuildError::new_build_fragment)?;
+
+        let mut string = self.build_section_title(&contributors.title);
+
+        string.push_str(DOUBLE_NEW_LINE);
+        string.push_str(&built);
+
+        Ok(string)
+    }
+
+    /// Builds multiple sections and joins them together.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when building any of the sections fails.
+    pub fn build_sections(&self, sections: &Sections<'_>) -> Result<String, BuildFragmentError> {
+        let types = self.config.types_with_defaults();
+
+        let string = self
+            .config
+            .order
+            .iter()
+            .filter_map(|name| types.get(name).zip(sections.get(name)))
+            .map(|(type_, fragments)| self.build_type_section(type_, fragments))
+            .process_results(|iterator| iterator.into_iter().join(DOUBLE_NEW_LINE))?;
+
+        Ok(string)
+    }
+
+    /// Builds multiple sections, merging bullets of the given prerelease entries into them,
+    /// and joins them together.
+    ///
+    /// Bullets of prerelease entries are kept verbatim (see [`blocks`]).
+    ///
+    /// [`blocks`]: crate::changelog::blocks
+    ///
+    /// Sections of prerelease entries without matching types are appended to the end,
+    /// except for the *Contributors* one when it is enabled, as it is built anew.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildFragmentError`] when building any of the fragments fails.
+    pub fn fold_sections(
+        &self,
+        sections: &Sections<'_>,
+        prereleases: &[Entry<'_>],
+    ) -> Result<String, BuildFragmentError> {
+        let bullet = self.config.indents.bullet;
+
+        let heading = self.section_heading();
+
+        let contributors = &self.config.contributors;
+
+        let skipped = contributors.enabled.then_some(contributors.title.as_ref());
+
+        let mut folded: Vec<(&str, Vec<&str>)> = Vec::new();
+
+        // changelogs are ordered from the newest entries to the oldest ones
+
+        for entry in prereleases.iter().rev() {
+            for section in entry.sections(&heading) {
+                if skipped == Some(section.title) {
+                    continue;
+                }
+
+                // blocks are folded verbatim, keeping continuations and paragraphs intact
+
+                let blocks = section.blocks(bullet);
+
+                match folded.iter_mut().find(|(title, _)| *title == section.title) {
+                    Some((_, existing)) => existing.extend(blocks),
+                    None => folded.push((section.title, blocks)),
+                }
+            }
+        }
+
+        let types = self.config.types_with_defaults();
+
+        let mut built = Vec::new();
+
+        for name in self.config.order.iter() {
+            let Some(type_) = types.get(name) else {
+                continue;
+            };
+
+            let title = type_.heading();
+
+            let previous = folded
+                .iter()
+                .position(|(other, _)| *other == title)
+                .map(|index| folded.remove(index).1)
+                .unwrap_or_default();
+
+            let fragments = sections.get(name).filter(|fragments| !fragments.is_empty());
+
+            if previous.is_empty() && fragments.is_none() {
+                continue;
+            }
+
+            let mut items: Vec<String> = previous.into_iter().map(str::to_owned).collect();
+
+            if let Some(fragments) = fragments {
+                items.push(self.build_type_fragments(type_, fragments)?);
+            }
+
+            built.push(self.build_folded_section(&title, &items));
+        }
+
+        for (title, blocks) in folded {
+            if blocks.is_empty() {
+                continue;
+            }
+
+            let items: Vec<String> = blocks.into_iter().map(str::to_owned).collect();
+
+            built.push(self.build_folded_section(title, &items));
+        }
+
+        Ok(built.join(DOUBLE_NEW_LINE))
+    }
+
+    fn build_folded_section(&self, title: &str, items: &[String]) -> String {
+        let mut string = self.build_section_title(title);
+
+        string.push_str(DOUBLE_NEW_LINE);
+        string.push_str(&items.join(DOUBLE_NEW_LINE));
+
+        string
+    }
+
+    // WRAPPING
+
+    /// Wraps the given string.
+    pub fn wrap_str(&self, string: &str) -> String {
+        self.wrap_at(string, "")
+    }
+
+    /// Wraps the given string, nesting it one level deeper, that is, within another bullet.
+    pub fn wrap_nested_str(&self, string: &str) -> String {
+        self.wrap_at(string, NESTED)
+    }
+
+    /// Similar to [`wrap_nested_str`], except the input is [`AsRef<str>`].
+    ///
+    /// [`wrap_nested_str`]: Self::wrap_nested_str
+    pub fn wrap_nested<S: AsRef<str>>(&self, string: S) -> String {
+        self.wrap_nested_str(string.as_ref())
+    }
+
+    fn wrap_at(&self, string: &str, nested: &str) -> String {
+        let initial_indent = format!("{nested}{}", indent(self.config.indents.bullet));
+        let subsequent_indent = format!("{nested}{}", indent(SPACE));
+
+        let options = WrapOptions::new(self.config.wrap.get())
+            .break_words(false)
+            .word_separator(WordSeparator::AsciiSpace)
+            .word_splitter(WordSplitter::NoHyphenation)
+            .initial_indent(&initial_indent)
+            .subsequent_indent(&subsequent_indent);
+
+        fill(string, options)
+    }
+
+    /// Similar to [`wrap_str`], except the input is [`AsRef<str>`].
+    ///
+    /// [`wrap_str`]: Self::wrap_str
+    pub fn wrap<S: AsRef<str>>(&self, string: S) -> String {
+        self.wrap_str(string.as_ref())
+    }
+
+    // RENDERING
+
+    /// Renders entry titles.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderError`] if rendering the title fails.
+    pub fn render_title(&self) -> Result<String, RenderError> {
+        let data = RenderTitleData::new(self.context(), self.date, self.commit());
+
+        self.renderer.render(TITLE, &data)
+    }
+
+    /// Renders skeletons of new changelogs.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderError`] if rendering the skeleton fails.
+    pub fn render_skeleton(&self) -> Result<String, RenderError> {
+        let data = RenderSkeletonData::new(self.context(), self.config.start.as_ref());
+
+        self.renderer.render(SKELETON, &data)
+    }
+
+    /// Renders the content of the given fragment as the template.
+    ///
+    /// The same variables as in the `fragment` format are available.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderError`] if rendering the content fails.
+    pub fn render_content(&self, fragment: &Fragment<'_>) -> Result<String, RenderError> {
+        let data = RenderFragmentData::new(self.context(), fragment);
+
+        self.renderer
+            .render_template(fragment.content.as_ref(), &data)
+    }
+
+    /// Renders the given fragment template (see [`create`]) for the given fragment.
+    ///
+    /// The same variables as in the `fragment` format are available.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderTemplateError`] if rendering the template fails.
+    ///
+    /// [`create`]: crate::commands::create
+    pub fn render_fragment_template(
+        &self,
+        template: &str,
+        fragment: &Fragment<'_>,
+    ) -> Result<String, RenderTemplateError> {
+        let data = RenderFragmentData::new(self.context(), fragment);
+
+        self.renderer
+            .render_template(template, &data)
+            .map_err(|error| {
+                RenderTemplateError::new(
+                    error,
+                    TEMPLATE.to_owned(),
+                    template.to_owned(),
+                    &variables(&data),
+                )
+            })
+    }
+
+    /// Renders fragments.
+    ///
+    /// Fragments of types with formats are rendered with them, and security fragments are
+    /// rendered with the `security` format, if configured, while other fragments with
+    /// string IDs are rendered as they are.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RenderError`] if rendering the given fragment fails.
+    pub fn render_fragment(&self, fragment: &Fragment<'_>) -> Result<String, RenderError> {
+        let (name, _) = self.fragment_template(fragment);
+
+        if name != FRAGMENT || fragment.partial.id.is_integer() {
+            let data = RenderFragmentData::new(self.context(), fragment);
+
+            self.renderer.render(&name, &data)
+        } else {
+            Ok(fragment.content.as_ref().to_owned())
+        }
+    }
+
+    // VARIABLES
+
+    /// Returns the data available to the `title` template.
+    pub fn title_data(&self) -> JsonValue {
+        to_json(RenderTitleData::new(
+            self.context(),
+            self.date,
+            self.commit(),
+        ))
+    }
+
+    /// Returns the data available to the `fragment` and `reference` templates,
+    /// as well as to fragment contents, for the given fragment.
+    pub fn fragment_data(&self, fragment: &Fragment<'_>) -> JsonValue {
+        to_json(RenderFragmentData::new(self.context(), fragment))
+    }
+
+    // COLLECTING
+
+    /// Checks whether the given entry of the fragments directory is a file.
+    ///
+    /// Directories and special files are never considered files, while symbolic links
+    /// are only followed if `follow_symlinks` is set.
+    #[cfg(feature = "fs")]
+    pub fn is_file(&self, entry: &DirEntry) -> bool {
+        let path = entry.path();
+
+        let Ok(file_type) = entry.file_type() else {
+            return false;
+        };
+
+        if file_type.is_file() {
+            return true;
+        }
+
+        if file_type.is_symlink() {
+            if self.config.follow_symlinks {
+                return metadata(&path).is_ok_and(|metadata| metadata.is_file());
+            }
+
+            debug!("skipped `{}`: symbolic link", path.display());
+        } else {
+            debug!("skipped `{}`: not a file", path.display());
+        }
+
+        false
+    }
+
+    /// Returns paths to files in the fragments directory, except for the ignored ones.
+    ///
+    /// The paths are sorted by file name, so that the order does not depend on the filesystem.
+    /// See [`is_file`] for which entries are considered files.
+    ///
+    /// [`is_file`]: Self::is_file
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CollectError`] if reading the ignore file, reading or iterating
+    /// the fragments directory fails.
+    #[cfg(feature = "fs")]
+    pub fn entries(&self) -> Result<Vec<PathBuf>, CollectError> {
+        let directory = self.config.paths.directory.as_ref();
+
+        let ignore = ignore_in(directory)?;
+
+        let mut paths: Vec<PathBuf> = read_dir(directory)
+            .map_err(|error| CollectError::new_read_directory(error, directory.to_owned()))?
+            .map(|result| {
+                result
+                    .map_err(|error| CollectError::new_iter_directory(error, directory.to_owned()))
+            })
+            .process_results(|iterator| {
+                iterator
+                    .filter(|entry| self.is_file(entry))
+                    .map(|entry| entry.path())
+                    .filter(|path| !is_ignored(&ignore, path))
+                    .collect()
+            })?;
+
+        paths.sort_by(|this, that| this.file_name().cmp(&that.file_name()));
+
+        Ok(paths)
+    }
+
+    /// Collects fragment files, returning [`Collection`] that keeps them alive
+    /// and lends fragments to sections (see [`Collection::sections`]), so that
+    /// their contents are never copied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CollectError`] when reading the ignore file, reading or iterating
+    /// the fragments directory fails.
+    #[cfg(feature = "fs")]
+    pub fn collect(&self) -> Result<Collection<'_, '_>, CollectError> {
+        let files = self.read_files()?;
+
+        let added = self.timed(Phase::Collecting, || self.added_dates());
+
+        Ok(Collection::new(self, files, added))
+    }
+
+    /// Reads fragment files from the fragments directory, skipping the ones that can not be read.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CollectError`] when reading the ignore file, reading or iterating
+    /// the fragments directory fails.
+    #[cfg(feature = "fs")]
+    pub fn read_files(&self) -> Result<Vec<FragmentFile>, CollectError> {
+        self.read_files_skipping(&mut Vec::new())
+    }
+
+    /// Reads fragment files from the fragments directory as of the given revision via `git`,
+    /// skipping the ones that can not be read. The ignore file is read from the working tree.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CollectError`] when reading the ignore file or running `git` fails,
+    /// for instance, if the revision does not exist.
+    #[cfg(feature = "git")]
+    pub fn read_files_at(&self, revision: &str) -> Result<Vec<FragmentFile>, CollectError> {
+        self.read_files_at_skipping(revision, &mut Vec::new())
+    }
+
+    #[cfg(feature = "git")]
+    fn read_files_at_skipping(
+        &self,
+        revision: &str,
+        skipped: &mut Vec<Skipped>,
+    ) -> Result<Vec<FragmentFile>, CollectError> {
+        self.timed(Phase::Collecting, || {
+            self.load_files_at_skipping(revision, skipped)
+        })
+    }
+
+    #[cfg(feature = "git")]
+    fn load_files_at_skipping(
+        &self,
+        revision: &str,
+        skipped: &mut Vec<Skipped>,
+    ) -> Result<Vec<FragmentFile>, CollectError> {
+        let directory = self.config.paths.directory.as_ref();
+
+        let ignore = ignore_in(directory)?;
+
+        let git = &self.config.git;
+
+        let blobs = git::tree(git, revision, directory)
+            .map_err(|error| CollectError::git(error, directory.to_owned()))?;
 
-        let path = self.config.paths.output.as_ref();
+        info!("reading fragments from `{revision}`");
 
-        let file = File::options()
-            .read(true)
-            .open(path)
-            .map_err(|error| WriteError::new_open_file(error, path.to_owned()))?;
+        let mut files = Vec::new();
 
-        let contents = read_to_string(file)
-            .map_err(|error| WriteError::new_read_file(error, path.to_owned()))?;
+        for (path, object) in blobs {
+            if is_hidden(&path) || is_ignored(&ignore, &path) {
+                debug!("skipped `{}`: hidden or ignored", path.display());
 
-        let mut file = File::options()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(path)
-            .map_err(|error| WriteError::new_open_file(error, path.to_owned()))?;
+                continue;
+            }
 
-        let start = self.config.start.as_ref();
+            let bytes = git::blob(git, &object)
+                .map_err(|error| CollectError::git(error, directory.to_owned()))?;
 
-        let mut string = String::new();
+            let result = String::from_utf8(bytes)
+                .map_err(|_| "invalid UTF-8 contents".to_owned())
+                .and_then(|string| {
+                    FragmentFile::from_string(&path, string).map_err(|error| chain(&error.source))
+                });
 
-        if let Some((before, after)) = contents.split_once(start) {
-            string.push_str(before);
+            match result {
+                Ok(file) => files.push(file),
+                Err(reason) => {
+                    debug!("skipped `{}`: {reason}", path.display());
 
-            string.push_str(start);
+                    skipped.push(Skipped::new(path, reason));
+                }
+            }
+        }
 
-            string.push_str(DOUBLE_NEW_LINE);
+        files.sort_by(|this, that| this.path.file_name().cmp(&that.path.file_name()));
 
-            string.push_str(&entry);
+        Ok(files)
+    }
 
-            string.push(NEW_LINE);
+    // hidden files, like `.gitkeep`, are skipped silently, as they are never fragments
 
-            let trimmed = after.trim_start();
+    #[cfg(feature = "fs")]
+    fn read_files_skipping(
+        &self,
+        skipped: &mut Vec<Skipped>,
+    ) -> Result<Vec<FragmentFile>, CollectError> {
+        self.timed(Phase::Collecting, || self.load_files_skipping(skipped))
+    }
 
-            if !trimmed.is_empty() {
-                string.push(NEW_LINE);
+    #[cfg(feature = "fs")]
+    fn load_files_skipping(
+        &self,
+        skipped: &mut Vec<Skipped>,
+    ) -> Result<Vec<FragmentFile>, CollectError> {
+        let files = self
+            .entries()?
+            .into_iter()
+            .filter_map(|path| match load::<FragmentFile, _>(&path) {
+                Ok(file) => Some(file),
+                Err(error) => {
+                    debug!("skipped `{}`: {}", path.display(), error.source);
+
+                    if !is_hidden(&path) {
+                        skipped.push(Skipped::new(path, chain(&error.source)));
+                    }
+
+                    None
+                }
+            })
+            .collect();
 
-                string.push_str(trimmed);
+        Ok(files)
+    }
+
+    /// Collects fragments borrowed from the given files into sections,
+    /// skipping the ones that can not be parsed.
+    ///
+    /// If `dates` is `git`, fragments without dates are dated by the commits that added them,
+    /// or by the date of [`Self`] if they are not committed yet.
+    pub fn collect_from<'f>(&self, files: &'f [FragmentFile]) -> Sections<'f> {
+        let added = self.timed(Phase::Collecting, || self.added_dates());
+
+        self.collect_dated(files, added.as_ref())
+    }
+
+    fn collect_dated<'f>(
+        &self,
+        files: &'f [FragmentFile],
+        added: Option<&HashMap<PathBuf, Date>>,
+    ) -> Sections<'f> {
+        let fragments = files.iter().filter_map(|file| match file.fragment() {
+            Ok(fragment) if !self.selection.includes(&fragment.partial.id) => {
+                debug!("skipped `{}`: not selected", file.path.display());
+
+                None
             }
-        } else {
-            string.push_str(&entry);
+            Ok(mut fragment) => {
+                debug!("loaded `{}`", file.path.display());
 
-            string.push(NEW_LINE);
+                if let Some(added) = added.filter(|_| fragment.date().is_none()) {
+                    let date = added.get(&file.path).copied().unwrap_or(self.date);
 
-            let trimmed = contents.trim_start();
+                    fragment
+                        .meta
+                        .insert(DATE.to_owned(), date.to_string().into());
+                }
 
-            if !trimmed.is_empty() {
-                string.push(NEW_LINE);
+                Some(fragment)
+            }
+            Err(error) => {
+                debug!("skipped `{}`: {}", file.path.display(), error.source);
 
-                string.push_str(trimmed);
+                None
             }
-        };
+        });
 
-        write!(file, "{string}")
-            .map_err(|error| WriteError::new_write_file(error, path.to_owned()))?;
+        // fragments are parsed lazily, that is, while grouping them into sections
 
-        Ok(())
-    }
+        let sections = self.timed(Phase::Collecting, || self.sections(fragments));
 
-    /// Builds and previews (prints) entries.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`BuildError`] when building fails.
-    pub fn preview(&self) -> Result<(), BuildError> {
-        let string = self.build()?;
+        let count = sections.values().map(Vec::len).sum();
 
-        println!("{string}");
+        if let Some(timings) = self.timings.as_ref() {
+            timings.count(count);
+        }
 
-        Ok(())
+        info!(
+            "collected {count} fragment(s) from `{}`",
+            self.config.paths.directory.display()
+        );
+
+        sections
     }
 
-    /// Builds and returns entries.
-    ///
-    /// # Errors
+    /// Groups the given fragments into sections, sorting them according to `sort`.
     ///
-    /// Returns [`BuildError`] when rendering titles and fragments or collecting fragments fails.
-    pub fn build(&self) -> Result<String, BuildError> {
-        let mut string = self.build_title().map_err(BuildError::build_title)?;
+    /// Fragments of aliased types are grouped into the sections of the types they alias.
+    pub fn sections<'f, I: IntoIterator<Item = Fragment<'f>>>(&self, fragments: I) -> Sections<'f> {
+        let fragments = fragments.into_iter().map(|mut fragment| {
+            if let Some(name) = self.config.alias_of(&fragment.partial.type_name) {
+                fragment.partial.type_name = Cow::Owned(name.to_owned());
+            }
 
-        string.push_str(DOUBLE_NEW_LINE);
+            fragment
+        });
 
-        let sections = self.collect().map_err(BuildError::collect)?;
+        let mut sections = fragment::sections(fragments);
 
-        let built = self
-            .build_sections(&sections)
-            .map_err(BuildError::build_fragment)?;
+        if self.config.sort == Sort::Date {
+            sections
+                .values_mut()
+                .for_each(|section| section.sort_by(Fragment::compare_by_date));
+        }
 
-        let contents = if built.is_empty() {
-            NO_SIGNIFICANT_CHANGES
-        } else {
-            &built
-        };
+        sections
+    }
 
-        string.push_str(contents);
+    fn added_dates(&self) -> Option<HashMap<PathBuf, Date>> {
+        if self.config.dates != Dates::Git {
+            return None;
+        }
 
-        Ok(string)
+        self.find_added_dates()
     }
 
-    /// Builds entry titles.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`BuildTitleError`] when rendering fails.
-    pub fn build_title(&self) -> Result<String, BuildTitleError> {
-        let mut string = self.entry_heading();
-
-        let title = self.render_title()?;
+    #[cfg(not(feature = "git"))]
+    fn find_added_dates(&self) -> Option<HashMap<PathBuf, Date>> {
+        warn!("finding dates of fragments requires the `git` feature");
 
-        string.push_str(&title);
+        None
+    }
 
-        Ok(string)
+    #[cfg(feature = "git")]
+    fn find_added_dates(&self) -> Option<HashMap<PathBuf, Date>> {
+        find_added_dates(&self.config.git, self.config.paths.directory.as_ref())
     }
 
-    /// Builds section titles.
-    pub fn build_section_title_str(&self, title: &str) -> String {
-        let mut string = self.section_heading();
+    #[cfg(feature = "async")]
+    async fn added_dates_async(&self) -> Option<HashMap<PathBuf, Date>> {
+        if self.config.dates != Dates::Git {
+            return None;
+        }
 
-        string.push_str(title);
+        self.find_added_dates_async().await
+    }
 
-        string
+    #[cfg(all(feature = "async", not(feature = "git")))]
+    async fn find_added_dates_async(&self) -> Option<HashMap<PathBuf, Date>> {
+        self.find_added_dates()
     }
 
-    /// Similar to [`build_section_title_str`], except the input is [`AsRef<str>`].
-    ///
-    /// [`build_section_title_str`]: Self::build_section_title_str
-    pub fn build_section_title<S: AsRef<str>>(&self, title: S) -> String {
-        self.build_section_title_str(title.as_ref())
+    // `git` is run on the blocking thread pool, so that the runtime is not blocked on it
+
+    #[cfg(all(feature = "async", feature = "git"))]
+    async fn find_added_dates_async(&self) -> Option<HashMap<PathBuf, Date>> {
+        let config = self.config.git.clone().into_owned();
+        let directory = self.config.paths.directory.clone().into_owned();
+
+        tokio::task::spawn_blocking(move || find_added_dates(&config, &directory))
+            .await
+            .unwrap_or_else(|error| {
+                warn!("failed to find dates of fragments: {error}");
+
+                None
+            })
     }
 
-    /// Builds fragments.
+    /// Collects paths to fragments, except for the ones not selected (see [`selection`]).
+    ///
+    /// [`selection`]: Self::selection
     ///
     /// # Errors
     ///
-    /// Returns [`BuildFragmentError`] when rendering fails.
-    pub fn build_fragment(&self, fragment: &Fragment<'_>) -> Result<String, BuildFragmentError> {
-        let string = self.render_fragment(fragment)?;
+    /// Returns [`CollectError`] if reading the ignore file, reading or iterating
+    /// the fragments directory fails.
+    #[cfg(feature = "fs")]
+    pub fn collect_paths(&self) -> Result<Vec<PathBuf>, CollectError> {
+        let paths = self
+            .entries()?
+            .into_iter()
+            .filter(|path| is_valid_path(path) && self.is_selected(path))
+            .collect();
 
-        Ok(self.wrap(string))
+        Ok(paths)
     }
 
-    /// Builds multiple fragments and joins them together.
+    /// Checks whether the fragment at the given path is selected (see [`selection`]).
     ///
-    /// # Errors
+    /// Paths that are not fragments are only selected if every fragment is.
     ///
-    /// Returns [`BuildFragmentError`] when building any of the fragments fails.
-    pub fn build_fragments(&self, fragments: &Fragments<'_>) -> Result<String, BuildFragmentError> {
-        let string = fragments
-            .iter()
-            .map(|fragment| self.build_fragment(fragment))
-            .process_results(|iterator| iterator.into_iter().join(DOUBLE_NEW_LINE))?;
+    /// [`selection`]: Self::selection
+    #[cfg(feature = "fs")]
+    pub fn is_selected(&self, path: &Path) -> bool {
+        if self.selection.is_all() {
+            return true;
+        }
 
-        Ok(string)
+        path_name(path)
+            .and_then(|name| Partial::parse_borrowed(name).ok())
+            .is_some_and(|partial| self.selection.includes(&partial.id))
     }
 
-    /// Builds sections.
-    ///
-    /// This is essentially the same as calling [`build_section_title`] and [`build_fragments`],
-    /// joining the results together.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`BuildFragmentError`] when building any of the fragments fails.
+    // ASYNC
+
+    /// Similar to [`is_file`], except the metadata is fetched asynchronously.
     ///
-    /// [`build_section_title`]: Self::build_section_title
-    /// [`build_fragments`]: Self::build_fragments
-    pub fn build_section_str(
-        &self,
-        title: &str,
-        fragments: &Fragments<'_>,
-    ) -> Result<String, BuildFragmentError> {
-        let mut string = self.build_section_title(title);
+    /// [`is_file`]: Self::is_file
+    #[cfg(feature = "async")]
+    pub async fn is_file_async(&self, entry: &tokio::fs::DirEntry) -> bool {
+        let path = entry.path();
 
-        let built = self.build_fragments(fragments)?;
+        let Ok(file_type) = entry.file_type().await else {
+            return false;
+        };
 
-        string.push_str(DOUBLE_NEW_LINE);
-        string.push_str(&built);
+        if file_type.is_file() {
+            return true;
+        }
 
-        Ok(string)
+        if file_type.is_symlink() {
+            if self.config.follow_symlinks {
+                return tokio::fs::metadata(&path)
+                    .await
+                    .is_ok_and(|metadata| metadata.is_file());
+            }
+
+            debug!("skipped `{}`: symbolic link", path.display());
+        } else {
+            debug!("skipped `{}`: not a file", path.display());
+        }
+
+        false
     }
 
-    /// Similar to [`build_section_str`], except the input is [`AsRef<str>`].
+    /// Similar to [`entries`], except the fragments directory is read asynchronously.
     ///
     /// # Errors
     ///
-    /// Returns [`BuildFragmentError`] when building any of the fragments fails.
+    /// Returns [`CollectError`] if reading the ignore file, reading or iterating
+    /// the fragments directory fails.
     ///
-    /// [`build_section_str`]: Self::build_section_str
-    pub fn build_section<S: AsRef<str>>(
-        &self,
-        title: S,
-        fragments: &Fragments<'_>,
-    ) -> Result<String, BuildFragmentError> {
-        self.build_section_str(title.as_ref(), fragments)
-    }
+    /// [`entries`]: Self::entries
+    #[cfg(feature = "async")]
+    pub async fn entries_async(&self) -> Result<Vec<PathBuf>, CollectError> {
+        let directory = self.config.paths.directory.as_ref();
 
-    /// Builds multiple sections and joins them together.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`BuildFragmentError`] when building any of the sections fails.
-    pub fn build_sections(&self, sections: &Sections<'_>) -> Result<String, BuildFragmentError> {
-        let types = self.config.types_with_defaults();
+        let ignore = ignore_in_async(directory).await?;
 
-        let string = self
-            .config
-            .order
-            .iter()
-            .filter_map(|name| types.get(name).zip(sections.get(name)))
-            .map(|(title, fragments)| self.build_section(title, fragments))
-            .process_results(|iterator| iterator.into_iter().join(DOUBLE_NEW_LINE))?;
+        let mut iterator = tokio::fs::read_dir(directory)
+            .await
+            .map_err(|error| CollectError::new_read_directory(error, directory.to_owned()))?;
 
-        Ok(string)
-    }
+        let mut paths = Vec::new();
 
-    // WRAPPING
+        while let Some(entry) = iterator
+            .next_entry()
+            .await
+            .map_err(|error| CollectError::new_iter_directory(error, directory.to_owned()))?
+        {
+            if !self.is_file_async(&entry).await {
+                continue;
+            }
 
-    /// Wraps the given string.
-    pub fn wrap_str(&self, string: &str) -> String {
-        let initial_indent = indent(self.config.indents.bullet);
-        let subsequent_indent = indent(SPACE);
+            let path = entry.path();
 
-        let options = WrapOptions::new(self.config.wrap.get())
-            .break_words(false)
-            .word_separator(WordSeparator::AsciiSpace)
-            .word_splitter(WordSplitter::NoHyphenation)
-            .initial_indent(&initial_indent)
-            .subsequent_indent(&subsequent_indent);
+            if !is_ignored(&ignore, &path) {
+                paths.push(path);
+            }
+        }
 
-        fill(string, options)
-    }
+        paths.sort_by(|this, that| this.file_name().cmp(&that.file_name()));
 
-    /// Similar to [`wrap_str`], except the input is [`AsRef<str>`].
-    ///
-    /// [`wrap_str`]: Self::wrap_str
-    pub fn wrap<S: AsRef<str>>(&self, string: S) -> String {
-        self.wrap_str(string.as_ref())
+        Ok(paths)
     }
 
-    // RENDERING
-
-    /// Renders entry titles.
+    /// Similar to [`read_files`], except the files are read asynchronously.
     ///
     /// # Errors
     ///
-    /// Returns [`RenderError`] if rendering the title fails.
-    pub fn render_title(&self) -> Result<String, RenderError> {
-        let data = RenderTitleData::new(self.context(), self.date);
-
-        self.renderer.render(TITLE, &data)
-    }
-
-    /// Renders fragments.
-    ///
-    /// # Errors
+    /// Returns [`CollectError`] when reading the ignore file, reading or iterating
+    /// the fragments directory fails.
     ///
-    /// Returns [`RenderError`] if rendering the given fragment fails.
-    pub fn render_fragment(&self, fragment: &Fragment<'_>) -> Result<String, RenderError> {
-        if fragment.partial.id.is_integer() {
-            let data = RenderFragmentData::new(self.context(), fragment);
-
-            self.renderer.render(FRAGMENT, &data)
-        } else {
-            Ok(fragment.content.as_ref().to_owned())
+    /// [`read_files`]: Self::read_files
+    #[cfg(feature = "async")]
+    pub async fn read_files_async(&self) -> Result<Vec<FragmentFile>, CollectError> {
+        let mut files = Vec::new();
+
+        for path in self.entries_async().await? {
+            match load_async::<FragmentFile, _>(&path).await {
+                Ok(file) => files.push(file),
+                Err(error) => debug!("skipped `{}`: {}", path.display(), error.source),
+            }
         }
-    }
 
-    // COLLECTING
+        Ok(files)
+    }
 
-    /// Collects fragments into sections.
+    /// Similar to [`collect`], except the fragments are read asynchronously,
+    /// so that the runtime is never blocked on file I/O.
+    ///
+    /// If `dates` is `git`, `git` is run on the blocking thread pool of the runtime,
+    /// which therefore has to be the `tokio` one.
     ///
     /// # Errors
     ///
-    /// Returns [`CollectError`] when reading or iterating the fragments directory fails.
-    pub fn collect(&self) -> Result<Sections<'_>, CollectError> {
-        let directory = self.config.paths.directory.as_ref();
-
-        let mut sections = Sections::new();
-
-        read_dir(directory)
-            .map_err(|error| CollectError::new_read_directory(error, directory.to_owned()))?
-            .map(|result| {
-                result
-                    .map(|entry| entry.path())
-                    .map_err(|error| CollectError::new_iter_directory(error, directory.to_owned()))
-            })
-            .process_results(|iterator| {
-                iterator
-                    .into_iter()
-                    .filter_map(|path| load::<Fragment<'_>, _>(path).ok()) // ignore errors
-                    .for_each(|fragment| {
-                        sections
-                            .entry(fragment.partial.type_name.clone())
-                            .or_default()
-                            .push(fragment);
-                    });
-            })?;
+    /// Returns [`CollectError`] when reading the ignore file, reading or iterating
+    /// the fragments directory fails.
+    ///
+    /// [`collect`]: Self::collect
+    #[cfg(feature = "async")]
+    pub async fn collect_async(&self) -> Result<Collection<'_, '_>, CollectError> {
+        let files = self.read_files_async().await?;
 
-        sections.values_mut().for_each(|section| section.sort());
+        let start = Instant::now();
 
-        Ok(sections)
-    }
+        let added = self.added_dates_async().await;
 
-    /// Collects paths to fragments.
-    ///
-    /// # Errors
-    ///
-    /// Returns [`CollectError`] if reading or iterating the fragments directory fails.
-    pub fn collect_paths(&self) -> Result<Vec<PathBuf>, CollectError> {
-        let directory = self.config.paths.directory.as_ref();
+        if let Some(timings) = self.timings.as_ref() {
+            timings.record(Phase::Collecting, start.elapsed());
+        }
 
-        read_dir(directory)
-            .map_err(|error| CollectError::new_read_directory(error, directory.to_owned()))?
-            .map(|result| {
-                result
-                    .map(|entry| entry.path())
-                    .map_err(|error| CollectError::new_iter_directory(error, directory.to_owned()))
-            })
-            .process_results(|iterator| {
-                iterator
-                    .into_iter()
-                    .filter(|path| is_valid_path(path))
-                    .collect()
-            })
+        Ok(Collection::new(self, files, added))
     }
 
     // HEADING
@@ -781,3 +3597,61 @@ impl Builder<'_> {
         self.level_heading(self.config.levels.section)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use time::macros::date;
+
+    use super::Builder;
+    use crate::{changelog::entries, config::Config, context::Context, fragment::Sections};
+
+    fn builder() -> Builder<'static> {
+        let context = Context {
+            name: Cow::Borrowed("changelogging"),
+            version: Cow::Borrowed("1.0.0"),
+            url: None,
+            extra: Default::default(),
+        };
+
+        Builder::new(context, Config::default(), date!(2024 - 01 - 02)).unwrap()
+    }
+
+    #[test]
+    fn fold_sections_keeps_paragraphs() {
+        let builder = builder();
+
+        let changelog = "\
+## 1.0.0-rc.1 (2024-01-01)
+
+### Features
+
+- Added some features.
+
+  Perhaps let us add some more lines, just in case.
+  ([#64](https://example.com/64))
+
+- Added other features.
+";
+
+        let prereleases = entries(changelog, &builder.entry_heading());
+
+        let folded = builder
+            .fold_sections(&Sections::default(), &prereleases)
+            .unwrap();
+
+        assert_eq!(
+            folded,
+            "\
+### Features
+
+- Added some features.
+
+  Perhaps let us add some more lines, just in case.
+  ([#64](https://example.com/64))
+
+- Added other features."
+        );
+    }
+}
@@ -0,0 +1,138 @@
+//! Caching rendered fragments.
+//!
+//! When `cache` is set (see [`config`]), bullets rendered from fragments are stored in
+//! the [`DIRECTORY`], keyed by the SHA-256 hash of the fragment (along with the context it is
+//! rendered in) and the templates used to render it, see [`key`]. Unchanged fragments
+//! are therefore not rendered again, which speeds up repeated previews of large fragment sets,
+//! for instance, in watch mode.
+//!
+//! The same directory is used to remember references to pull requests and issues found
+//! by `check --references`.
+//!
+//! The cache is best-effort: failing to read from or write to it is never an error.
+//! Keys also depend on the version of `changelogging`, so upgrading it invalidates the cache.
+//!
+//! The cache can be removed with the `cache clear` command, see [`Cache::clear`].
+//!
+//! [`config`]: crate::config
+
+use std::{
+    fs::{create_dir_all, read_to_string, remove_dir_all, write},
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tracing::debug;
+
+/// The directory to store the cache in, relative to the project.
+pub const DIRECTORY: &str = ".changelogging/cache";
+
+/// Represents cache keys, that is, hexadecimal SHA-256 hashes.
+pub type Key = String;
+
+const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Hashes the given value serialized into JSON, along with the version of `changelogging`,
+/// into [`Key`], which is stable across runs and platforms.
+///
+/// Returns [`None`] if serializing the value fails, in which case nothing should be cached.
+pub fn key<S: Serialize + ?Sized>(value: &S) -> Option<Key> {
+    let json = serde_json::to_vec(&(VERSION, value)).ok()?;
+
+    Some(hash(&json))
+}
+
+/// Hashes the given bytes with SHA-256, returning the digest in lowercase hexadecimal.
+pub fn hash(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Represents errors that can occur when clearing the cache.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to clear the cache in `{path}`")]
+#[diagnostic(
+    code(changelogging::cache::clear),
+    help("check whether the directory is accessible")
+)]
+pub struct ClearError {
+    /// The underlying I/O error.
+    pub source: std::io::Error,
+    /// The path to the cache.
+    pub path: PathBuf,
+}
+
+impl ClearError {
+    /// Constructs [`Self`].
+    pub fn new(source: std::io::Error, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+}
+
+/// Represents caches of rendered fragments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Cache {
+    /// The directory to store the cache in.
+    pub directory: PathBuf,
+}
+
+impl Default for Cache {
+    fn default() -> Self {
+        Self::new(PathBuf::from(DIRECTORY))
+    }
+}
+
+impl Cache {
+    /// Constructs [`Self`].
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Returns the path to the entry with the given key.
+    pub fn path(&self, key: &str) -> PathBuf {
+        self.directory.join(key)
+    }
+
+    /// Returns the cached string for the given key, if any.
+    pub fn get(&self, key: &str) -> Option<String> {
+        let path = self.path(key);
+
+        let string = read_to_string(&path).ok()?;
+
+        debug!("cache hit `{}`", path.display());
+
+        Some(string)
+    }
+
+    /// Caches the given string for the given key.
+    ///
+    /// Failures are logged and otherwise ignored.
+    pub fn set(&self, key: &str, string: &str) {
+        let path = self.path(key);
+
+        if let Err(error) = create_dir_all(&self.directory).and_then(|()| write(&path, string)) {
+            debug!("failed to cache `{}`: {error}", path.display());
+        }
+    }
+
+    /// Removes the cache, returning whether there was anything to remove.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ClearError`] if removing the cache fails.
+    pub fn clear(&self) -> Result<bool, ClearError> {
+        let path: &Path = self.directory.as_ref();
+
+        match remove_dir_all(path) {
+            Ok(()) => Ok(true),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(false),
+            Err(error) => Err(ClearError::new(error, path.to_owned())),
+        }
+    }
+}
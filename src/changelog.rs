@@ -0,0 +1,411 @@
+//! Parsing changelogs.
+//!
+//! This module provides [`entries`] that splits changelogs into [`Entry`] values,
+//! [`find`] that finds entries by their versions, [`diff`] that compares bullets of entries
+//! and [`remove`] that removes entries from changelogs, along with [`split_definitions`]
+//! that finds link reference definitions at the end of changelogs.
+
+use semver::Version;
+use time::Date;
+
+use crate::{
+    date::parse_str,
+    version::{is_prerelease_of, parse},
+};
+
+const SPACE: char = ' ';
+
+const NEW_LINE: char = '\n';
+
+const REFERENCE_START: char = '[';
+
+const REFERENCE_END: &str = "]: ";
+
+const LABEL_END: char = ']';
+
+const LINK_START: char = '(';
+
+const VERSION_PREFIX: char = 'v';
+
+/// Represents changelog entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Entry<'e> {
+    /// The title of the entry, without the heading.
+    pub title: &'e str,
+    /// The body of the entry, up until the next entry.
+    pub body: &'e str,
+    /// The byte offset of the entry (including its heading) within the contents.
+    pub start: usize,
+    /// The byte offset of the end of the entry within the contents.
+    pub end: usize,
+}
+
+impl<'e> Entry<'e> {
+    /// Constructs [`Self`].
+    pub fn new(title: &'e str, body: &'e str, start: usize, end: usize) -> Self {
+        Self {
+            title,
+            body,
+            start,
+            end,
+        }
+    }
+
+    fn words(&self) -> impl Iterator<Item = &'e str> {
+        self.title
+            .split(|character: char| !is_version_character(character))
+            .map(strip_version_prefix)
+    }
+
+    /// Checks whether the title of [`Self`] mentions the given version.
+    ///
+    /// The leading `v` is ignored both in the version and in the title.
+    pub fn is_version(&self, version: &str) -> bool {
+        let version = strip_version_prefix(version);
+
+        self.words().any(|word| word == version)
+    }
+
+    /// Checks whether the title of [`Self`] mentions any prerelease of the given version.
+    ///
+    /// The leading `v` is ignored both in the version and in the title.
+    pub fn is_prerelease_of(&self, version: &str) -> bool {
+        let version = strip_version_prefix(version);
+
+        self.words().any(|word| is_prerelease_of(word, version))
+    }
+
+    /// Returns the first version following semantic versioning mentioned in the title
+    /// of [`Self`], if any.
+    ///
+    /// The leading `v` is ignored in the title.
+    pub fn version(&self) -> Option<Version> {
+        self.words().find_map(|word| parse(word).ok())
+    }
+
+    /// Returns the first date in the `YYYY-MM-DD` format mentioned in the title
+    /// of [`Self`], if any.
+    pub fn date(&self) -> Option<Date> {
+        self.words().find_map(|word| parse_str(word).ok())
+    }
+
+    /// Returns bullets of [`Self`], see [`bullets`].
+    pub fn bullets(&self, bullet: char) -> Vec<String> {
+        bullets(self.body, bullet)
+    }
+
+    /// Returns bullet blocks of [`Self`] verbatim, see [`blocks`].
+    pub fn blocks(&self, bullet: char) -> Vec<&'e str> {
+        blocks(self.body, bullet)
+    }
+
+    /// Splits the body of [`Self`] into sections, which start with the given `heading`.
+    ///
+    /// Note that offsets of the sections are relative to the body.
+    pub fn sections(&self, heading: &str) -> Vec<Entry<'e>> {
+        entries(self.body, heading)
+    }
+
+    /// Returns link reference definitions found in the body of [`Self`], see [`references`].
+    pub fn references(&self) -> Vec<&'e str> {
+        references(self.body)
+    }
+}
+
+fn is_version_character(character: char) -> bool {
+    character.is_alphanumeric() || matches!(character, '.' | '-' | '+')
+}
+
+fn strip_version_prefix(string: &str) -> &str {
+    string.strip_prefix(VERSION_PREFIX).unwrap_or(string)
+}
+
+/// Splits the given changelog into entries, which start with the given `heading`
+/// (for instance, `## `).
+///
+/// Anything before the first entry is ignored.
+pub fn entries<'c>(contents: &'c str, heading: &str) -> Vec<Entry<'c>> {
+    let mut entries = Vec::new();
+
+    let mut current: Option<(&str, usize, usize)> = None;
+
+    let mut offset = 0;
+
+    for line in contents.split_inclusive('\n') {
+        if let Some(title) = line.strip_prefix(heading) {
+            if let Some((title, start, body)) = current.take() {
+                entries.push(Entry::new(title, &contents[body..offset], start, offset));
+            }
+
+            current = Some((title.trim(), offset, offset + line.len()));
+        }
+
+        offset += line.len();
+    }
+
+    if let Some((title, start, body)) = current {
+        entries.push(Entry::new(title, &contents[body..], start, contents.len()));
+    }
+
+    entries
+}
+
+/// Finds the entry mentioning the given version in its title, see [`Entry::is_version`].
+pub fn find<'c>(contents: &'c str, heading: &str, version: &str) -> Option<Entry<'c>> {
+    entries(contents, heading)
+        .into_iter()
+        .find(|entry| entry.is_version(version))
+}
+
+fn push_words(string: &mut String, words: &str) {
+    for word in words.split_whitespace() {
+        if !string.is_empty() {
+            string.push(SPACE);
+        }
+
+        string.push_str(word);
+    }
+}
+
+/// Collects bullets (starting with the given `bullet` character) from the given string.
+///
+/// Indented lines following bullets are considered continuations, and whitespace is collapsed,
+/// so that bullets wrapped differently compare equal.
+pub fn bullets(string: &str, bullet: char) -> Vec<String> {
+    let mut bullets = Vec::new();
+
+    let mut current: Option<String> = None;
+
+    for line in string.lines() {
+        let item = line
+            .strip_prefix(bullet)
+            .filter(|rest| rest.starts_with(SPACE));
+
+        if let Some(rest) = item {
+            bullets.extend(current.take());
+
+            let mut text = String::new();
+
+            push_words(&mut text, rest);
+
+            current = Some(text);
+        } else if line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+            if let Some(text) = current.as_mut() {
+                push_words(text, line);
+            }
+        } else {
+            bullets.extend(current.take());
+        }
+    }
+
+    bullets.extend(current);
+
+    bullets
+}
+
+fn is_bullet(line: &str, bullet: char) -> bool {
+    line.strip_prefix(bullet)
+        .is_some_and(|rest| rest.starts_with(SPACE))
+}
+
+/// Collects bullet blocks (starting with the given `bullet` character) from the given string.
+///
+/// Unlike [`bullets`], blocks are returned verbatim: each one spans the bullet line along with
+/// every continuation following it, that is, indented lines, including any blank lines
+/// separating indented paragraphs. Trailing blank lines are not included.
+pub fn blocks(string: &str, bullet: char) -> Vec<&str> {
+    let mut blocks = Vec::new();
+
+    let mut current: Option<(usize, usize)> = None;
+
+    let mut offset = 0;
+
+    for line in string.split_inclusive(NEW_LINE) {
+        let content = line.trim_end();
+
+        if is_bullet(content, bullet) {
+            blocks.extend(current.take().map(|(start, end)| &string[start..end]));
+
+            current = Some((offset, offset + content.len()));
+        } else if content.trim_start().is_empty() {
+            // blank lines belong to blocks only if indented lines follow them
+        } else if content.starts_with(char::is_whitespace) {
+            if let Some((_, end)) = current.as_mut() {
+                *end = offset + content.len();
+            }
+        } else {
+            blocks.extend(current.take().map(|(start, end)| &string[start..end]));
+        }
+
+        offset += line.len();
+    }
+
+    blocks.extend(current.map(|(start, end)| &string[start..end]));
+
+    blocks
+}
+
+/// Collects link reference definitions (like `[#13]: https://...`) from the given string.
+pub fn references(string: &str) -> Vec<&str> {
+    string
+        .lines()
+        .map(str::trim_end)
+        .filter(|line| line.starts_with(REFERENCE_START) && line.contains(REFERENCE_END))
+        .collect()
+}
+
+/// Returns the label of the given link reference definition (like `1.2.0` in `[1.2.0]: ...`),
+/// or [`None`] if the line is not a definition.
+pub fn definition_label(line: &str) -> Option<&str> {
+    line.strip_prefix(REFERENCE_START)?
+        .split_once(REFERENCE_END)
+        .map(|(label, _)| label)
+        .filter(|label| !label.is_empty())
+}
+
+/// Returns the label of the given title if it starts with the reference link
+/// (like `1.2.0` in `[1.2.0] - 2024-05-01`), or [`None`] otherwise.
+///
+/// Inline links (like `[1.2.0](https://...)`) and full references (like `[1.2.0][v1]`)
+/// are not considered reference links.
+pub fn reference_label(title: &str) -> Option<&str> {
+    let (label, after) = title.strip_prefix(REFERENCE_START)?.split_once(LABEL_END)?;
+
+    let is_link = after.starts_with([LINK_START, REFERENCE_START]);
+
+    (!label.is_empty() && !is_link).then_some(label)
+}
+
+/// Splits the given changelog into the contents and the block of link reference definitions
+/// ending it (without trailing whitespace), which is empty if there is no such block.
+pub fn split_definitions(contents: &str) -> (&str, &str) {
+    let trimmed = contents.trim_end();
+
+    let mut start = None;
+
+    let mut offset = 0;
+
+    for line in trimmed.split_inclusive('\n') {
+        if definition_label(line).is_some() {
+            start.get_or_insert(offset);
+        } else {
+            start = None;
+        }
+
+        offset += line.len();
+    }
+
+    match start {
+        Some(start) => (&contents[..start], &trimmed[start..]),
+        None => (contents, ""),
+    }
+}
+
+/// Removes the given entries from the changelog contents, returning the resulting string.
+///
+/// The entries are expected to be found in the same contents, see [`entries`].
+pub fn remove(contents: &str, entries: &[Entry<'_>]) -> String {
+    let mut string = String::new();
+
+    let mut offset = 0;
+
+    let mut spans: Vec<(usize, usize)> = entries
+        .iter()
+        .map(|entry| (entry.start, entry.end))
+        .collect();
+
+    spans.sort_unstable();
+
+    for (start, end) in spans {
+        if start >= offset {
+            string.push_str(&contents[offset..start]);
+
+            offset = end;
+        }
+    }
+
+    string.push_str(&contents[offset..]);
+
+    string
+}
+
+/// Represents changes of bullets.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Change {
+    /// The bullet is present in both entries.
+    Kept(String),
+    /// The bullet is present only in the new entry.
+    Added(String),
+    /// The bullet is present only in the old entry.
+    Removed(String),
+}
+
+/// Compares bullets of the old entry with bullets of the new one.
+///
+/// Bullets of the new entry come first, in order, followed by the ones removed.
+pub fn diff(old: &[String], new: &[String]) -> Vec<Change> {
+    let mut remaining: Vec<Option<&String>> = old.iter().map(Some).collect();
+
+    let mut changes: Vec<Change> = new
+        .iter()
+        .map(|bullet| {
+            let found = remaining
+                .iter_mut()
+                .find(|option| option.is_some_and(|other| other == bullet));
+
+            match found {
+                Some(option) => {
+                    *option = None;
+
+                    Change::Kept(bullet.clone())
+                }
+                None => Change::Added(bullet.clone()),
+            }
+        })
+        .collect();
+
+    changes.extend(
+        remaining
+            .into_iter()
+            .flatten()
+            .map(|bullet| Change::Removed(bullet.clone())),
+    );
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::blocks;
+
+    const BULLET: char = '-';
+
+    #[test]
+    fn blocks_keep_paragraphs() {
+        let string = "\
+- First line,
+  wrapped.
+
+  Second paragraph. ([#13](https://example.com/13))
+
+- Second bullet.
+
+Not a continuation.
+";
+
+        assert_eq!(
+            blocks(string, BULLET),
+            [
+                "- First line,\n  wrapped.\n\n  Second paragraph. ([#13](https://example.com/13))",
+                "- Second bullet.",
+            ]
+        );
+    }
+
+    #[test]
+    fn blocks_skip_other_lines() {
+        let string = "Some text.\n\n-not a bullet\n* another one\n";
+
+        assert!(blocks(string, BULLET).is_empty());
+    }
+}
@@ -0,0 +1,42 @@
+//! Clipboard integration.
+//!
+//! This module provides [`copy`] that copies text to the system clipboard via [`arboard`].
+//!
+//! Note that on some platforms (for instance, X11), the clipboard contents are owned
+//! by the process that set them, and may disappear once it exits, unless a clipboard
+//! manager is running.
+
+use arboard::Clipboard;
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::exit::Code;
+
+/// Represents errors that can occur when copying to the clipboard.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to copy to the clipboard")]
+#[diagnostic(
+    code(changelogging::clipboard),
+    help("make sure the system clipboard is available")
+)]
+pub struct Error(#[from] pub arboard::Error);
+
+impl Error {
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        Code::Failure
+    }
+}
+
+/// Copies the given text to the system clipboard.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if accessing the clipboard or setting its contents fails.
+pub fn copy<S: AsRef<str>>(text: S) -> Result<(), Error> {
+    let mut clipboard = Clipboard::new()?;
+
+    clipboard.set_text(text.as_ref())?;
+
+    Ok(())
+}
@@ -2,18 +2,92 @@
 //!
 //! The [`build`] function implements the `build` subcommand.
 
-use std::iter::once;
+use std::{
+    fs::create_dir_all,
+    iter::once,
+    path::{Path, PathBuf},
+};
 
+use clap::ValueEnum;
 use miette::Diagnostic;
 use thiserror::Error;
+use time::Date;
+use tracing::{debug, info};
 
 use crate::{
-    builder::{Builder, CollectError, InitError, WriteError},
+    backup::{self, Backup, SaveError},
+    builder::{convert, BuildReport, Builder, CollectError, InitError, WriteError},
+    changelog::{self, Entry},
+    config::LineEnding,
     date::{parse, today},
+    exit::Code,
+    fragment::Selection,
     git,
+    lock::{Lock, LOCK},
+    report,
+    timings::Timings,
     workspace::Workspace,
 };
 
+/// Represents errors that occur when no fragments are found, but at least one is required.
+#[derive(Debug, Error, Diagnostic)]
+#[error("no fragments found in `{path}`")]
+#[diagnostic(
+    code(changelogging::commands::build::no_fragments),
+    help("create fragments or drop `--require-fragments`")
+)]
+pub struct NoFragmentsError {
+    /// The path to the fragments directory.
+    pub path: PathBuf,
+}
+
+impl NoFragmentsError {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+/// Represents errors that occur when fragments are to be removed,
+/// but the changelog is not written to.
+#[derive(Debug, Error, Diagnostic)]
+#[error("can not remove fragments without writing the changelog")]
+#[diagnostic(
+    code(changelogging::commands::build::remove_unwritten),
+    help("drop either `--remove (-r)` or `--output (-o)` and `--notes-only`")
+)]
+pub struct RemoveUnwrittenError;
+
+/// Represents errors that can occur when creating directories of release notes.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to create `{path}`")]
+#[diagnostic(
+    code(changelogging::commands::build::create_directory),
+    help("check whether the parent directory is accessible")
+)]
+pub struct CreateDirectoryError {
+    /// The underlying I/O error.
+    pub source: std::io::Error,
+    /// The path to the directory.
+    pub path: PathBuf,
+}
+
+impl CreateDirectoryError {
+    /// Constructs [`Self`].
+    pub fn new(source: std::io::Error, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+}
+
+/// Represents errors that can occur when serializing reports.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to serialize the report")]
+#[diagnostic(
+    code(changelogging::commands::build::serialize),
+    help("this is likely a bug, please report it")
+)]
+pub struct SerializeError(#[from] pub serde_json::Error);
+
 /// Represents sources of errors that can occur during building.
 #[derive(Debug, Error, Diagnostic)]
 #[error(transparent)]
@@ -29,6 +103,20 @@ pub enum ErrorSource {
     Collect(#[from] CollectError),
     /// `git` errors.
     Git(#[from] crate::git::Error),
+    /// No fragments errors.
+    NoFragments(#[from] NoFragmentsError),
+    /// Remove unwritten errors.
+    RemoveUnwritten(#[from] RemoveUnwrittenError),
+    /// Create directory errors.
+    CreateDirectory(#[from] CreateDirectoryError),
+    /// Serialize errors.
+    Serialize(#[from] SerializeError),
+    /// Backup errors.
+    Backup(#[from] SaveError),
+    /// Lock errors.
+    Lock(#[from] crate::lock::Error),
+    /// Profile errors.
+    Profile(#[from] crate::workspace::ProfileError),
 }
 
 /// Represents errors that can occur during building.
@@ -79,40 +167,557 @@ impl Error {
     pub fn git(error: crate::git::Error) -> Self {
         Self::new(error.into())
     }
+
+    /// Constructs [`Self`] from [`NoFragmentsError`].
+    pub fn no_fragments(error: NoFragmentsError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`RemoveUnwrittenError`].
+    pub fn remove_unwritten(error: RemoveUnwrittenError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`CreateDirectoryError`].
+    pub fn create_directory(error: CreateDirectoryError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`SerializeError`].
+    pub fn serialize(error: SerializeError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`SaveError`].
+    pub fn backup(error: SaveError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::lock::Error
+    pub fn lock(error: crate::lock::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`ProfileError`].
+    ///
+    /// [`ProfileError`]: crate::workspace::ProfileError
+    pub fn profile(error: crate::workspace::ProfileError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`NoFragmentsError`] and constructs [`Self`] from it.
+    pub fn new_no_fragments(path: PathBuf) -> Self {
+        Self::no_fragments(NoFragmentsError::new(path))
+    }
+
+    /// Constructs [`RemoveUnwrittenError`] and constructs [`Self`] from it.
+    pub fn new_remove_unwritten() -> Self {
+        Self::remove_unwritten(RemoveUnwrittenError)
+    }
+
+    /// Constructs [`CreateDirectoryError`] and constructs [`Self`] from it.
+    pub fn new_create_directory(error: std::io::Error, path: PathBuf) -> Self {
+        Self::create_directory(CreateDirectoryError::new(error, path))
+    }
+
+    /// Constructs [`SerializeError`] and constructs [`Self`] from it.
+    pub fn new_serialize(error: serde_json::Error) -> Self {
+        Self::serialize(SerializeError(error))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Date(_) => Code::Usage,
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Write(error) => error.exit_code(),
+            ErrorSource::Collect(error) => error.exit_code(),
+            ErrorSource::Git(_) => Code::Git,
+            ErrorSource::NoFragments(_) => Code::NoFragments,
+            ErrorSource::RemoveUnwritten(_) => Code::Usage,
+            ErrorSource::CreateDirectory(_) => Code::Io,
+            ErrorSource::Serialize(_) => Code::Failure,
+            ErrorSource::Backup(_) => Code::Io,
+            ErrorSource::Lock(error) => error.exit_code(),
+            ErrorSource::Profile(_) => Code::Config,
+        }
+    }
+}
+
+/// Represents warnings emitted when the changelog does not contain the start marker.
+#[derive(Debug, Error, Diagnostic)]
+#[error("changelog `{path}` does not contain the start marker")]
+#[diagnostic(
+    code(changelogging::commands::build::missing_start),
+    severity(Warning),
+    help("the entry is written at the beginning; add `{start}` or use `--insert-marker`")
+)]
+pub struct MissingStartWarning {
+    /// The path to the changelog.
+    pub path: PathBuf,
+    /// The start marker.
+    pub start: String,
+}
+
+impl MissingStartWarning {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, start: String) -> Self {
+        Self { path, start }
+    }
+}
+
+/// The path that represents `stdout` when given as the output.
+pub const STDOUT: &str = "-";
+
+/// Represents targets to write changelogs to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// Write to `stdout`.
+    Stdout,
+    /// Write to the given path.
+    Path(PathBuf),
+}
+
+impl From<PathBuf> for Target {
+    fn from(path: PathBuf) -> Self {
+        if path.as_os_str() == STDOUT {
+            Self::Stdout
+        } else {
+            Self::Path(path)
+        }
+    }
+}
+
+/// Represents output formats of reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Format {
+    /// The JSON object describing the build.
+    Json,
+}
+
+/// Represents options of building changelogs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// Whether to stage the changelog.
+    pub stage: bool,
+    /// Whether to remove fragments.
+    pub remove: bool,
+    /// Whether to insert the start marker if it is missing.
+    pub insert_marker: bool,
+    /// Whether to fail if there are no fragments.
+    pub require_fragments: bool,
+    /// Whether to fold prerelease entries into the final release entry.
+    pub consolidate_prereleases: bool,
+    /// Whether to replace the existing entry of the version being built, if any.
+    pub amend: bool,
+    /// The target to write the changelog to instead of `paths.output`, if any.
+    pub output: Option<Target>,
+    /// The path to write release notes (that is, the entry alone) to, if any.
+    pub notes: Option<PathBuf>,
+    /// Whether to write release notes only, leaving the changelog intact.
+    pub notes_only: bool,
+    /// The format to print the report to `stdout` in, if any.
+    pub report: Option<Format>,
+    /// Whether to build without acquiring the lock.
+    pub no_lock: bool,
+    /// The revision to read fragments from instead of the working tree, if any.
+    pub fragments_ref: Option<String>,
+    /// The selection of fragments to build (and remove).
+    pub selection: Selection,
+    /// Whether to print how long collecting, rendering and writing took.
+    pub timings: bool,
+}
+
+const NEW_LINE: char = '\n';
+
+fn write_notes(
+    builder: &Builder<'_>,
+    path: &Path,
+    entry: &str,
+    line_ending: LineEnding,
+) -> Result<(), Error> {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty());
+
+    if let Some(parent) = parent {
+        create_dir_all(parent)
+            .map_err(|error| Error::new_create_directory(error, parent.to_owned()))?;
+    }
+
+    info!("writing release notes to `{}`", path.display());
+
+    let mut string = entry.to_owned();
+
+    string.push(NEW_LINE);
+
+    builder
+        .write_changelog_to(path, &string, line_ending)
+        .map_err(Error::write)
+}
+
+// removes folded prerelease entries and the amended entry, and inserts the start marker, if needed
+
+fn prepare(
+    builder: &Builder<'_>,
+    contents: &str,
+    prereleases: &[Entry<'_>],
+    amended: Option<Entry<'_>>,
+    insert_marker: bool,
+) -> String {
+    let mut removed = prereleases.to_vec();
+
+    if !prereleases.is_empty() {
+        info!("folding {} prerelease entries", prereleases.len());
+    }
+
+    if let Some(entry) = amended {
+        info!("amending the entry `{}`", entry.title);
+
+        removed.push(entry);
+    }
+
+    let mut string = if removed.is_empty() {
+        contents.to_owned()
+    } else {
+        changelog::remove(contents, &removed)
+    };
+
+    if !builder.contains_start(&string) {
+        if insert_marker {
+            info!("inserting the start marker");
+
+            string = builder.insert_start(&string);
+        } else {
+            let path = builder.config.paths.output.clone().into_owned();
+            let start = builder.config.start.clone().into_owned();
+
+            report::warn(MissingStartWarning::new(path, start));
+        }
+    }
+
+    string
+}
+
+// backs up and writes the changelog, returning the paths written to (the archive, if any,
+// and the changelog itself)
+
+fn write_changelog(
+    builder: &Builder<'_>,
+    string: String,
+    line_ending: LineEnding,
+) -> Result<Vec<PathBuf>, Error> {
+    let path = builder.config.paths.output.as_ref();
+
+    let backup = Backup::new(builder.project_path(backup::DIRECTORY));
+
+    backup.save(path).map_err(Error::backup)?;
+
+    if let Some(archive) = builder.config.paths.archive.as_deref() {
+        backup.save(archive).map_err(Error::backup)?;
+    }
+
+    info!("writing to `{}`", path.display());
+
+    let archive = builder
+        .write_retained(&string, line_ending)
+        .map_err(Error::write)?;
+
+    Ok(archive.into_iter().chain(once(path.to_owned())).collect())
+}
+
+// builds and writes the additional changelog (see `outputs`), returning the paths written to
+
+fn write_output(
+    workspace: Workspace<'_>,
+    date: Date,
+    insert_marker: bool,
+    amend: bool,
+    fragments_ref: Option<&str>,
+    selection: &Selection,
+    timings: Option<&Timings>,
+) -> Result<Vec<PathBuf>, Error> {
+    let mut builder = Builder::from_workspace(workspace, date)
+        .map_err(Error::init)?
+        .with_selection(selection.clone());
+
+    if timings.is_some() {
+        builder = builder.with_timings();
+    }
+
+    let contents = builder.read_changelog().map_err(Error::write)?;
+
+    let prereleases = builder.prereleases(&contents.string);
+
+    let entry = match fragments_ref {
+        Some(revision) => builder
+            .build_report_at(revision, &prereleases)
+            .map(|report| report.entry),
+        None => builder.build_with(&prereleases),
+    }
+    .map_err(|error| Error::write(WriteError::build(error)))?;
+
+    let amended = amend.then(|| builder.existing(&contents.string)).flatten();
+
+    let string = prepare(
+        &builder,
+        &contents.string,
+        &prereleases,
+        amended,
+        insert_marker,
+    );
+
+    let string = builder.splice(&string, &entry);
+
+    let string = builder
+        .link(&string)
+        .map_err(|error| Error::write(WriteError::build_links(error)))?;
+
+    let written = write_changelog(&builder, string, contents.line_ending)?;
+
+    if let Some((timings, recorded)) = timings.zip(builder.timings.as_ref()) {
+        timings.merge(recorded);
+    }
+
+    Ok(written)
 }
 
 /// Builds changelogs from fragments.
 ///
+/// If `insert_marker` is set, the start marker is inserted into the changelog
+/// in case it is missing. Otherwise, [`MissingStartWarning`] is reported.
+///
+/// If `require_fragments` is set, [`NoFragmentsError`] is returned in case there are no fragments.
+///
+/// If `consolidate_prereleases` or `fold_prereleases` is set, prerelease entries of the version
+/// being built (for instance, `1.2.0-rc.1` when building `1.2.0`) are folded into the new entry
+/// and removed from the changelog.
+///
+/// If `amend` is set, the existing entry of the version being built (if any) is replaced
+/// with the new one instead of being duplicated, for instance, when fragments are added
+/// after the initial build but before tagging (fragments should be kept then).
+///
+/// If `output` is given, the changelog is read from `paths.output` as usual, but the result
+/// is written to the given target instead, leaving the changelog (and the archive) intact.
+///
+/// If the `compare` format is provided, link reference definitions at the end of changelogs
+/// are maintained, see [`Builder::link`].
+///
+/// If `notes` is given, the entry alone is written to that path as well, creating
+/// its parent directories if needed. If `notes_only` is also set, the changelog is left intact.
+///
+/// If `outputs` is configured, additional changelogs are built from the given profiles
+/// and written along with the main one, unless either `output` is given or `notes_only` is set.
+///
+/// Before changelogs (and archives, if any) are written, they are backed up,
+/// so that the build can be rolled back, see [`Backup`].
+///
+/// Only the fragments selected by `selection` are built, and only they are removed
+/// if `remove` is set; for instance, patch releases can cherry-pick pending fragments.
+/// Fragments can only be removed when the changelog is written to, that is, when neither
+/// `output` is given nor `notes_only` is set, so that the build can be rolled back.
+///
+/// If `fragments_ref` is given, fragments are read from the fragments directory as of
+/// that revision via `git` (for instance, from the release branch), while changelogs of
+/// the current working tree are written to. Such fragments are never removed.
+///
+/// Unless `no_lock` is set, the lock is held while building, see [`Lock`].
+///
+/// If `timings` is set, how long collecting, rendering and writing took is printed
+/// to `stderr`, along with the number of fragments processed, see [`Timings`].
+///
+/// Returns [`BuildReport`] telling what happened, which is also printed if `report` is given.
+///
 /// # Errors
 ///
 /// Returns [`struct@Error`] when parsing dates, initializing the builder,
-/// building and writing the changelog or collecting paths fails. Also returned if `git` fails.
+/// building and writing the changelog, collecting paths or serializing the report fails,
+/// as well as when the lock is held by another process or when `remove` is set
+/// while the changelog is not written to. Also returned if `git` fails.
 pub fn build<S: AsRef<str>>(
-    workspace: Workspace<'_>,
+    mut workspace: Workspace<'_>,
     date: Option<S>,
-    stage: bool,
-    remove: bool,
-) -> Result<(), Error> {
+    options: Options,
+) -> Result<BuildReport, Error> {
     let date = match date {
         Some(string) => parse(string).map_err(Error::date)?,
         None => today(),
     };
 
-    let builder = Builder::from_workspace(workspace, date).map_err(Error::init)?;
+    if options.remove && (options.output.is_some() || options.notes_only) {
+        return Err(Error::new_remove_unwritten());
+    }
 
-    builder.write().map_err(Error::write)?;
+    // the lock is released once dropped, that is, when returning
 
-    if stage {
-        let path = builder.config.paths.output.as_ref();
+    let _lock = if options.no_lock {
+        None
+    } else {
+        Some(Lock::acquire(workspace.project_path(LOCK)).map_err(Error::lock)?)
+    };
 
-        git::add(once(path)).map_err(Error::git)?;
+    if options.consolidate_prereleases {
+        workspace.config.fold_prereleases = true;
     }
 
-    if remove {
-        let paths = builder.collect_paths().map_err(Error::collect)?;
+    let outputs = workspace.outputs().map_err(Error::profile)?;
+
+    let mut builder = Builder::from_workspace(workspace, date)
+        .map_err(Error::init)?
+        .with_selection(options.selection.clone());
+
+    if options.timings {
+        builder = builder.with_timings();
+    }
+
+    let contents = builder.read_changelog().map_err(Error::write)?;
+
+    let prereleases = builder.prereleases(&contents.string);
+
+    let fragments_ref = options.fragments_ref.as_deref();
+
+    let mut report = match fragments_ref {
+        Some(revision) => builder.build_report_at(revision, &prereleases),
+        None => builder.build_report(&prereleases),
+    }
+    .map_err(|error| Error::write(WriteError::build(error)))?;
+
+    // only the fragments of types included in `order` are built
+
+    if options.require_fragments && report.included.is_empty() {
+        let path = builder.config.paths.directory.clone().into_owned();
+
+        return Err(Error::new_no_fragments(path));
+    }
+
+    let entry = report.entry.as_str();
+
+    let amended = options
+        .amend
+        .then(|| builder.existing(&contents.string))
+        .flatten();
+
+    let string = prepare(
+        &builder,
+        &contents.string,
+        &prereleases,
+        amended,
+        options.insert_marker,
+    );
+
+    let mut written = Vec::new();
+
+    if let Some(path) = options.notes.as_deref() {
+        write_notes(&builder, path, entry, contents.line_ending)?;
+
+        written.push(path.to_owned());
+    }
+
+    let string = builder.splice(&string, entry);
+
+    let string = builder
+        .link(&string)
+        .map_err(|error| Error::write(WriteError::build_links(error)))?;
+
+    let mut changelogs = Vec::new();
+
+    match options.output {
+        _ if options.notes_only => {}
+        None => {
+            changelogs.extend(write_changelog(&builder, string, contents.line_ending)?);
+
+            for workspace in outputs {
+                changelogs.extend(write_output(
+                    workspace,
+                    date,
+                    options.insert_marker,
+                    options.amend,
+                    fragments_ref,
+                    &options.selection,
+                    builder.timings.as_ref(),
+                )?);
+            }
+        }
+        Some(ref target) => {
+            // the changelog is left intact, so dropped entries are not archived
+
+            let (string, _) = builder.retain(&string);
+
+            match target {
+                Target::Stdout => {
+                    print!("{}", convert(&string, contents.line_ending));
+                }
+                Target::Path(path) => {
+                    info!("writing to `{}`", path.display());
+
+                    builder
+                        .write_changelog_to(path, &string, contents.line_ending)
+                        .map_err(Error::write)?;
+
+                    changelogs.push(path.clone());
+                }
+            }
+        }
+    }
+
+    if options.stage {
+        if changelogs.is_empty() {
+            debug!("nothing to stage, as changelogs are not written to files");
+        } else {
+            info!("staging {} file(s)", changelogs.len());
+
+            git::add(&builder.config.git, &changelogs)
+                .and_then(git::success)
+                .map_err(Error::git)?;
+        }
+    }
+
+    written.extend(changelogs);
+
+    report.written = written;
+
+    let removed = if options.remove && fragments_ref.is_some() {
+        debug!("fragments read from other revisions are not removed");
+
+        Vec::new()
+    } else if options.remove {
+        builder.collect_paths().map_err(Error::collect)?
+    } else {
+        Vec::new()
+    };
+
+    // record fragments removed along with backups of changelogs, so that rollbacks
+    // restore exactly them; the record of the previous build is dropped otherwise
+
+    if options.output.is_none() && !options.notes_only {
+        Backup::new(builder.project_path(backup::DIRECTORY))
+            .record(&removed)
+            .map_err(Error::backup)?;
+    }
+
+    if !removed.is_empty() {
+        info!("removing {} fragment(s)", removed.len());
+
+        git::remove(&builder.config.git, &removed)
+            .and_then(git::success)
+            .map_err(Error::git)?;
+
+        report.removed = removed;
+    }
+
+    if let Some(format) = options.report {
+        let string = match format {
+            Format::Json => serde_json::to_string_pretty(&report).map_err(Error::new_serialize)?,
+        };
+
+        println!("{string}");
+    }
 
-        git::remove(paths).map_err(Error::git)?;
+    if let Some(timings) = builder.timings.as_ref() {
+        eprintln!("{timings}");
     }
 
-    Ok(())
+    Ok(report)
 }
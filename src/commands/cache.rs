@@ -0,0 +1,71 @@
+//! Managing the cache of rendered fragments.
+//!
+//! The [`clear`] function implements the `cache clear` subcommand.
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::{
+    cache::{Cache, ClearError, DIRECTORY},
+    exit::Code,
+    workspace::Workspace,
+};
+
+/// Represents sources of errors that can occur when managing the cache.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Clear errors.
+    Clear(#[from] ClearError),
+}
+
+/// Represents errors that can occur when managing the cache.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to manage the cache")]
+#[diagnostic(
+    code(changelogging::commands::cache),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`ClearError`].
+    pub fn clear(error: ClearError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Clear(_) => Code::Io,
+        }
+    }
+}
+
+/// Removes the cache of rendered fragments in the root of the given workspace.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if removing the cache fails.
+pub fn clear(workspace: &Workspace<'_>) -> Result<(), Error> {
+    let cache = Cache::new(workspace.project_path(DIRECTORY));
+
+    if cache.clear().map_err(Error::clear)? {
+        println!("cleared `{}`", cache.directory.display());
+    } else {
+        println!("nothing to clear");
+    }
+
+    Ok(())
+}
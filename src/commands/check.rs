@@ -0,0 +1,837 @@
+//! Checking fragments.
+//!
+//! The [`check`] function implements the `check` subcommand, which validates fragments
+//! without building anything, making it suitable for hooks (for instance, `pre-commit` ones).
+//!
+//! Optionally, integer IDs of fragments are checked to reference existing pull requests
+//! or issues on the forge (see [`Client::reference_exists`]), which requires network access.
+//! Existing references are cached (see [`cache`]), so that they are requested at most once.
+//!
+//! Links in the rendered entry (or the whole changelog) can also be checked
+//! with the `links` feature enabled (see `links`).
+//!
+//! The changelog can also be checked, verifying that entries are ordered from the newest
+//! and that their headings match the configured title format.
+//!
+//! Once the `security` format is configured, security fragments are held to stricter
+//! requirements, having to specify valid advisory information in front matter.
+//!
+//! Fragments older than the given [`Age`] can be flagged as stale, as they often indicate
+//! abandoned changes or missed releases. Fragments are aged by the commits that added them,
+//! falling back to their modification times if they are not committed yet.
+//!
+//! [`Client::reference_exists`]: crate::forge::Client::reference_exists
+//! [`cache`]: crate::cache
+
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{canonicalize, metadata},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use clap::ValueEnum;
+use miette::Diagnostic;
+use serde::Serialize;
+use thiserror::Error;
+use time::OffsetDateTime;
+#[cfg(feature = "links")]
+use tracing::info;
+use tracing::warn;
+
+#[cfg(feature = "links")]
+use crate::links::{extract, verify};
+use crate::{
+    builder::{ignore_in, Builder, CollectError, InitError, WriteError},
+    changelog::{entries, Entry},
+    config::{normalize_path, Config, CURRENT_DIRECTORY},
+    date::today,
+    exit::Code,
+    fragment::{
+        is_cve, path_name, Fragment, Partial, ADVISORY_URL, CVE, HIDDEN, SEVERITIES, SEVERITY,
+    },
+    git,
+    load::load,
+    report::chain,
+    workspace::Workspace,
+};
+#[cfg(feature = "forge")]
+use crate::{
+    cache::{self, Cache},
+    forge::Client,
+    fragment::Id,
+    network::OfflineWarning,
+    report,
+};
+
+/// Represents errors that can occur when listing staged files.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to list staged files")]
+#[diagnostic(
+    code(changelogging::commands::check::staged),
+    help("make sure `git` is present and the project is a repository")
+)]
+pub struct StagedError(#[from] pub git::Error);
+
+/// Represents errors that can occur when serializing findings.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to serialize findings")]
+#[diagnostic(
+    code(changelogging::commands::check::serialize),
+    help("this is likely a bug, please report it")
+)]
+pub struct SerializeError(#[from] pub serde_json::Error);
+
+/// Represents errors returned when checking finds problems.
+#[derive(Debug, Error, Diagnostic)]
+#[error("found {count} problem(s)")]
+#[diagnostic(
+    code(changelogging::commands::check::problems),
+    help("see the problems reported above")
+)]
+pub struct ProblemsError {
+    /// The number of problems found.
+    pub count: usize,
+}
+
+impl ProblemsError {
+    /// Constructs [`Self`].
+    pub fn new(count: usize) -> Self {
+        Self { count }
+    }
+}
+
+/// Represents sources of errors that can occur when checking fragments.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Initialization errors.
+    Init(#[from] InitError),
+    /// Collect errors.
+    Collect(#[from] CollectError),
+    /// Staged errors.
+    Staged(#[from] StagedError),
+    /// Serialize errors.
+    Serialize(#[from] SerializeError),
+    /// Problems errors.
+    Problems(#[from] ProblemsError),
+    /// Forge errors.
+    #[cfg(feature = "forge")]
+    Forge(#[from] crate::forge::Error),
+    /// Read and build errors.
+    Write(#[from] WriteError),
+}
+
+/// Represents errors that can occur when checking fragments.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to check fragments")]
+#[diagnostic(
+    code(changelogging::commands::check),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`CollectError`].
+    pub fn collect(error: CollectError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`StagedError`].
+    pub fn staged(error: StagedError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`SerializeError`].
+    pub fn serialize(error: SerializeError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`ProblemsError`].
+    pub fn problems(error: ProblemsError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::forge::Error
+    #[cfg(feature = "forge")]
+    pub fn forge(error: crate::forge::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`WriteError`].
+    pub fn write(error: WriteError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`StagedError`] and constructs [`Self`] from it.
+    pub fn new_staged(error: git::Error) -> Self {
+        Self::staged(StagedError(error))
+    }
+
+    /// Constructs [`SerializeError`] and constructs [`Self`] from it.
+    pub fn new_serialize(error: serde_json::Error) -> Self {
+        Self::serialize(SerializeError(error))
+    }
+
+    /// Constructs [`ProblemsError`] and constructs [`Self`] from it.
+    pub fn new_problems(count: usize) -> Self {
+        Self::problems(ProblemsError::new(count))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Collect(error) => error.exit_code(),
+            ErrorSource::Staged(_) => Code::Git,
+            ErrorSource::Serialize(_) => Code::Failure,
+            ErrorSource::Problems(_) => Code::Validation,
+            #[cfg(feature = "forge")]
+            ErrorSource::Forge(error) => error.exit_code(),
+            ErrorSource::Write(error) => error.exit_code(),
+        }
+    }
+}
+
+/// Represents output formats of findings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Format {
+    /// One `{path}: {message}` line per finding.
+    #[default]
+    Text,
+    /// The JSON array of findings.
+    Json,
+}
+
+/// Represents kinds of findings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Kind {
+    /// The fragment name is invalid.
+    InvalidName,
+    /// The fragment type is unknown.
+    UnknownType,
+    /// The fragment type is not included in `order`.
+    UnorderedType,
+    /// The fragment can not be loaded.
+    Load,
+    /// The fragment is empty.
+    Empty,
+    /// The security fragment lacks advisory information or specifies it incorrectly.
+    InvalidAdvisory,
+    /// The fragment ID does not reference any pull request or issue.
+    MissingReference,
+    /// The link is broken.
+    BrokenLink,
+    /// The fragment is older than the threshold.
+    Stale,
+    /// The changelog entry is not older than the previous one.
+    UnorderedEntry,
+    /// The changelog entry heading does not match the title format.
+    MalformedHeading,
+}
+
+/// Represents where to check links in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Links {
+    /// The entry that would be built.
+    #[default]
+    Entry,
+    /// The entire changelog.
+    Changelog,
+}
+
+/// Represents errors that can occur when parsing ages.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to parse `{string}` into age")]
+#[diagnostic(
+    code(changelogging::commands::check::invalid_age),
+    help("ages are numbers of days or weeks, for instance, `90d` or `12w`")
+)]
+pub struct InvalidAgeError {
+    /// The string that could not be parsed into any valid age.
+    pub string: String,
+}
+
+impl InvalidAgeError {
+    /// Constructs [`Self`].
+    pub fn new(string: String) -> Self {
+        Self { string }
+    }
+}
+
+/// The suffix of ages in days.
+pub const DAYS: char = 'd';
+
+/// The suffix of ages in weeks.
+pub const WEEKS: char = 'w';
+
+/// The number of days in one week.
+pub const DAYS_IN_WEEK: u64 = 7;
+
+/// Represents ages of fragments, in days.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Age {
+    /// The number of days.
+    pub days: u64,
+}
+
+impl Age {
+    /// Constructs [`Self`].
+    pub fn new(days: u64) -> Self {
+        Self { days }
+    }
+}
+
+impl FromStr for Age {
+    type Err = InvalidAgeError;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        let error = || InvalidAgeError::new(string.to_owned());
+
+        let (number, multiplier) = if let Some(number) = string.strip_suffix(DAYS) {
+            (number, 1)
+        } else if let Some(number) = string.strip_suffix(WEEKS) {
+            (number, DAYS_IN_WEEK)
+        } else {
+            return Err(error());
+        };
+
+        // `parse` accepts leading signs, which are not allowed here
+
+        if number.is_empty() || !number.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(error());
+        }
+
+        let days = number
+            .parse::<u64>()
+            .ok()
+            .and_then(|number| number.checked_mul(multiplier))
+            .ok_or_else(error)?;
+
+        Ok(Self::new(days))
+    }
+}
+
+impl fmt::Display for Age {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(formatter, "{}{DAYS}", self.days)
+    }
+}
+
+/// Represents options of checking.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// Whether to check staged files, along with `paths`.
+    pub staged: bool,
+    /// The paths to check, defaulting to every fragment.
+    pub paths: Vec<PathBuf>,
+    /// The format to output findings in.
+    pub format: Format,
+    /// Whether to check that fragment IDs reference existing pull requests or issues.
+    pub references: bool,
+    /// Where to check links in, if anywhere.
+    pub links: Option<Links>,
+    /// Whether offline mode is enabled, skipping network-backed checks.
+    pub offline: bool,
+    /// The age past which fragments are considered stale, if any.
+    pub stale: Option<Age>,
+    /// Whether to check ordering and headings of changelog entries.
+    pub entries: bool,
+}
+
+/// Represents findings, that is, problems with individual fragments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Finding {
+    /// The path to the fragment.
+    pub path: PathBuf,
+    /// The kind of the finding.
+    pub kind: Kind,
+    /// The message describing the finding.
+    pub message: String,
+}
+
+impl Finding {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, kind: Kind, message: String) -> Self {
+        Self {
+            path,
+            kind,
+            message,
+        }
+    }
+}
+
+const SEPARATOR: &str = ": ";
+
+fn is_in(path: &Path, directory: &Path) -> bool {
+    let parent = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(Path::new(CURRENT_DIRECTORY));
+
+    canonicalize(parent)
+        .ok()
+        .zip(canonicalize(directory).ok())
+        .is_some_and(|(parent, directory)| parent == directory)
+}
+
+fn check_path(builder: &Builder<'_>, path: &Path) -> Option<Finding> {
+    let config = builder.config();
+
+    // hidden files, like `.gitkeep`, are never fragments
+
+    let name = path_name(path).filter(|name| !name.starts_with(HIDDEN))?;
+
+    let partial = match name.parse::<Partial<'_>>() {
+        Ok(partial) => partial,
+        Err(error) => {
+            return Some(Finding::new(
+                path.to_owned(),
+                Kind::InvalidName,
+                chain(&error),
+            ));
+        }
+    };
+
+    let type_name = config.canonical_type(&partial.type_name);
+
+    if !config.types_with_defaults().contains_key(type_name) {
+        let message = match config.suggest_type(type_name) {
+            Some(suggestion) => format!("unknown type `{type_name}`; did you mean `{suggestion}`?"),
+            None => format!("unknown type `{type_name}`"),
+        };
+
+        return Some(Finding::new(path.to_owned(), Kind::UnknownType, message));
+    }
+
+    if !config.order.iter().any(|name| name == type_name) {
+        let message = format!("type `{type_name}` is not included in `order`");
+
+        return Some(Finding::new(path.to_owned(), Kind::UnorderedType, message));
+    }
+
+    match load::<Fragment<'_>, _>(path) {
+        Ok(fragment) if fragment.content.trim().is_empty() => {
+            let message = "fragment is empty".to_owned();
+
+            Some(Finding::new(path.to_owned(), Kind::Empty, message))
+        }
+        Ok(fragment) => check_advisory(config, &fragment)
+            .map(|message| Finding::new(path.to_owned(), Kind::InvalidAdvisory, message)),
+        Err(error) => Some(Finding::new(path.to_owned(), Kind::Load, chain(&error))),
+    }
+}
+
+const HTTP: &str = "http://";
+const HTTPS: &str = "https://";
+
+fn is_http(url: &str) -> bool {
+    url.starts_with(HTTP) || url.starts_with(HTTPS)
+}
+
+// security fragments are only held to stricter requirements once `formats.security` is set
+
+fn check_advisory(config: &Config<'_>, fragment: &Fragment<'_>) -> Option<String> {
+    config.security_format_for(&fragment.partial.type_name)?;
+
+    let meta = &fragment.meta;
+
+    if fragment.cve().is_none() && fragment.advisory_url().is_none() {
+        return Some(format!(
+            "security fragment specifies neither `{CVE}` nor `{ADVISORY_URL}`"
+        ));
+    }
+
+    if meta.contains_key(CVE) && !fragment.cve().is_some_and(is_cve) {
+        return Some(format!(
+            "`{CVE}` is not a valid CVE ID (like `CVE-2024-12345`)"
+        ));
+    }
+
+    if meta.contains_key(ADVISORY_URL) && !fragment.advisory_url().is_some_and(is_http) {
+        return Some(format!("`{ADVISORY_URL}` is not an HTTP(S) URL"));
+    }
+
+    if meta.contains_key(SEVERITY)
+        && !fragment
+            .severity()
+            .is_some_and(|severity| SEVERITIES.contains(&severity))
+    {
+        let expected = SEVERITIES
+            .map(|severity| format!("`{severity}`"))
+            .join(", ");
+
+        return Some(format!("`{SEVERITY}` is not one of {expected}"));
+    }
+
+    None
+}
+
+fn find_added(builder: &Builder<'_>) -> HashMap<PathBuf, OffsetDateTime> {
+    let config = builder.config();
+
+    let added = match git::added(&config.git, config.paths.directory.as_ref()) {
+        Ok(added) => added,
+        Err(error) => {
+            warn!("failed to find when fragments were added: {error}");
+
+            return HashMap::new();
+        }
+    };
+
+    added
+        .into_iter()
+        .filter_map(|(path, timestamp)| {
+            let time = OffsetDateTime::from_unix_timestamp(timestamp).ok()?;
+
+            Some((path, time))
+        })
+        .collect()
+}
+
+fn check_stale(
+    path: &Path,
+    added: &HashMap<PathBuf, OffsetDateTime>,
+    now: OffsetDateTime,
+    stale: Age,
+) -> Option<Finding> {
+    // hidden files, like `.gitkeep`, are never fragments
+
+    path_name(path).filter(|name| !name.starts_with(HIDDEN))?;
+
+    // fragments that are not committed yet are aged by their modification times
+
+    let since = added.get(path).copied().or_else(|| {
+        metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .map(OffsetDateTime::from)
+    })?;
+
+    let days = u64::try_from((now - since).whole_days()).unwrap_or_default();
+
+    if days < stale.days {
+        return None;
+    }
+
+    let message = format!("fragment is {days} day(s) old (stale after `{stale}`)");
+
+    Some(Finding::new(path.to_owned(), Kind::Stale, message))
+}
+
+fn line_of(string: &str, offset: usize) -> usize {
+    string[..offset].matches('\n').count() + 1
+}
+
+fn check_heading(builder: &Builder<'_>, heading: &str, entry: &Entry<'_>) -> Option<String> {
+    let Some(version) = entry.version() else {
+        return Some(format!(
+            "heading `{}` does not mention any version",
+            entry.title
+        ));
+    };
+
+    // titles without dates are rendered the same regardless of the date used
+
+    let mut expected = builder.at(entry.date().unwrap_or(builder.date));
+
+    expected.context.version = version.to_string().into();
+
+    let title = match expected.build_title() {
+        Ok(title) => title,
+        Err(error) => return Some(chain(&error)),
+    };
+
+    let title = title.strip_prefix(heading).unwrap_or(&title).trim();
+
+    (title != entry.title).then(|| {
+        format!(
+            "heading `{}` does not match the title format, expected `{title}`",
+            entry.title
+        )
+    })
+}
+
+// versions take precedence over dates, since backports are released after newer versions
+
+fn is_unordered(entry: &Entry<'_>, previous: &Entry<'_>) -> bool {
+    match (entry.version(), previous.version()) {
+        (Some(version), Some(previous)) => version >= previous,
+        _ => matches!(
+            (entry.date(), previous.date()),
+            (Some(date), Some(previous)) if date > previous
+        ),
+    }
+}
+
+fn check_changelog(builder: &Builder<'_>) -> Result<Vec<Finding>, Error> {
+    let path = builder.config.paths.output.as_ref();
+
+    let contents = builder.read_changelog().map_err(Error::write)?;
+
+    let string = contents.string.as_str();
+
+    let heading = builder.entry_heading();
+
+    let found = entries(string, &heading);
+
+    let mut findings = Vec::new();
+
+    let unordered = found
+        .windows(2)
+        .find(|pair| is_unordered(&pair[1], &pair[0]));
+
+    if let Some([previous, entry]) = unordered {
+        let message = format!(
+            "line {}: entry `{}` is not older than the previous entry `{}`",
+            line_of(string, entry.start),
+            entry.title,
+            previous.title
+        );
+
+        findings.push(Finding::new(path.to_owned(), Kind::UnorderedEntry, message));
+    }
+
+    let malformed = found.iter().find_map(|entry| {
+        check_heading(builder, &heading, entry)
+            .map(|message| format!("line {}: {message}", line_of(string, entry.start)))
+    });
+
+    if let Some(message) = malformed {
+        findings.push(Finding::new(
+            path.to_owned(),
+            Kind::MalformedHeading,
+            message,
+        ));
+    }
+
+    Ok(findings)
+}
+
+#[cfg(feature = "forge")]
+const EXISTS: &str = "";
+
+#[cfg(feature = "forge")]
+fn check_reference(client: &Client, cache: &Cache, path: &Path) -> Result<Option<Finding>, Error> {
+    let Some(Ok(Id::Integer(number))) =
+        path_name(path).map(|name| name.parse::<Partial<'_>>().map(|partial| partial.id))
+    else {
+        return Ok(None);
+    };
+
+    let remote = &client.remote;
+
+    let key = cache::key(&(&remote.api, &remote.repository, number));
+
+    if key.as_deref().is_some_and(|key| cache.get(key).is_some()) {
+        return Ok(None);
+    }
+
+    if client
+        .reference_exists(number.into())
+        .map_err(Error::forge)?
+    {
+        // references are never expected to disappear, so only existing ones are cached
+
+        if let Some(key) = key.as_deref() {
+            cache.set(key, EXISTS);
+        }
+
+        return Ok(None);
+    }
+
+    let message = format!(
+        "no pull request or issue `#{number}` found in `{}`",
+        remote.repository
+    );
+
+    Ok(Some(Finding::new(
+        path.to_owned(),
+        Kind::MissingReference,
+        message,
+    )))
+}
+
+#[cfg(feature = "links")]
+fn check_links(builder: &Builder<'_>, links: Links) -> Result<Vec<Finding>, Error> {
+    let path = builder.config.paths.output.as_ref();
+
+    let string = match links {
+        Links::Entry => builder.build_entry().map_err(Error::write)?,
+        Links::Changelog => builder.read_changelog().map_err(Error::write)?.string,
+    };
+
+    let urls = extract(&string);
+
+    info!("checking {} link(s)", urls.len());
+
+    let findings = verify(&urls)
+        .into_iter()
+        .map(|broken| {
+            let message = format!("broken link `{}`: {}", broken.url, broken.reason);
+
+            Finding::new(path.to_owned(), Kind::BrokenLink, message)
+        })
+        .collect();
+
+    Ok(findings)
+}
+
+/// Checks fragments, printing findings to `stdout` in the given format.
+///
+/// If `staged` is set, staged files are checked, along with the given `paths`.
+/// Otherwise, the given `paths` are checked, or every fragment if there are none.
+/// Files outside of the fragments directory and ignored ones are skipped.
+///
+/// If `references` is set, integer IDs of otherwise valid fragments are checked to reference
+/// existing pull requests or issues on the forge. If `links` is provided, links in the entry
+/// (or the changelog) are checked, reporting broken ones against the changelog.
+///
+/// Network-backed checks are skipped with [`OfflineWarning`] if `offline` is set,
+/// and ignored without the `forge` (for `references`) or `links` (for `links`) features.
+///
+/// If `stale` is provided, otherwise valid fragments at least that old are reported,
+/// along with their ages. If `entries` is set, the first changelog entry that is not older
+/// than the previous one and the first heading not matching the title format are reported.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if any problems are found, as well as if initializing the builder,
+/// collecting fragments, listing staged files, requesting the forge API, building the entry,
+/// reading the changelog or serializing findings fails.
+///
+/// [`OfflineWarning`]: crate::network::OfflineWarning
+pub fn check(workspace: Workspace<'_>, options: Options) -> Result<(), Error> {
+    let Options {
+        staged,
+        mut paths,
+        format,
+        references,
+        links,
+        offline,
+        stale,
+        entries: check_entries,
+    } = options;
+
+    #[cfg(feature = "forge")]
+    let client = if references {
+        let client = Client::from_workspace(&workspace, offline).map_err(Error::forge)?;
+
+        if client.is_offline() {
+            report::warn(OfflineWarning::new("checking references"));
+
+            None
+        } else {
+            Some(client)
+        }
+    } else {
+        None
+    };
+
+    // there is nothing to request without the `forge` feature
+
+    #[cfg(not(feature = "forge"))]
+    let _ = references;
+
+    let builder = Builder::from_workspace(workspace, today()).map_err(Error::init)?;
+
+    let directory = builder.config.paths.directory.as_ref();
+
+    if staged {
+        paths.extend(git::staged(&builder.config.git).map_err(Error::new_staged)?);
+    }
+
+    let paths = if staged || !paths.is_empty() {
+        let ignore = ignore_in(directory).map_err(Error::collect)?;
+
+        paths
+            .iter()
+            .map(|path| normalize_path(path))
+            .filter(|path| path.is_file() && is_in(path, directory))
+            .filter(|path| !ignore.matched(path, false).is_ignore())
+            .collect()
+    } else {
+        builder.entries().map_err(Error::collect)?
+    };
+
+    let added = stale.map(|_| find_added(&builder)).unwrap_or_default();
+
+    #[cfg(feature = "forge")]
+    let cache = Cache::new(builder.project_path(cache::DIRECTORY));
+
+    let now = OffsetDateTime::now_utc();
+
+    let mut findings = Vec::new();
+
+    for path in &paths {
+        if let Some(finding) = check_path(&builder, path) {
+            findings.push(finding);
+
+            continue;
+        }
+
+        if let Some(stale) = stale {
+            findings.extend(check_stale(path, &added, now, stale));
+        }
+
+        #[cfg(feature = "forge")]
+        if let Some(client) = client.as_ref() {
+            findings.extend(check_reference(client, &cache, path)?);
+        }
+    }
+
+    if check_entries {
+        findings.extend(check_changelog(&builder)?);
+    }
+
+    if let Some(links) = links {
+        #[cfg(feature = "links")]
+        if offline {
+            report::warn(OfflineWarning::new("checking links"));
+        } else {
+            findings.extend(check_links(&builder, links)?);
+        }
+
+        #[cfg(not(feature = "links"))]
+        let _ = (links, offline);
+    }
+
+    match format {
+        Format::Text => {
+            for finding in &findings {
+                println!("{}{SEPARATOR}{}", finding.path.display(), finding.message);
+            }
+        }
+        Format::Json => {
+            let string = serde_json::to_string_pretty(&findings).map_err(Error::new_serialize)?;
+
+            println!("{string}");
+        }
+    }
+
+    if !findings.is_empty() {
+        return Err(Error::new_problems(findings.len()));
+    }
+
+    Ok(())
+}
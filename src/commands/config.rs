@@ -0,0 +1,452 @@
+//! Inspecting configuration.
+//!
+//! The [`show`] function implements the `config show` subcommand,
+//! while [`validate`] implements the `config validate` subcommand.
+
+use std::{borrow::Cow, fmt::Write, path::PathBuf};
+
+use itertools::Itertools;
+use miette::{Diagnostic, Report};
+use serde::Serialize;
+use thiserror::Error;
+use toml::{Table, Value};
+
+use crate::{
+    builder::{BuildFragmentError, BuildTitleError, Builder, InitError},
+    commands::variables::example,
+    config::{Config, Overrides, DIRECTORY_KEY, OUTPUT_KEY, SECURITY_TYPE},
+    date::today,
+    exit::Code,
+    fragment::Sections,
+    workspace::Workspace,
+};
+
+/// Represents errors that can occur when serializing configuration.
+#[derive(Debug, Error, Diagnostic)]
+#[error("serializing failed")]
+#[diagnostic(
+    code(changelogging::commands::config::serialize),
+    help("this is likely a bug, please report it")
+)]
+pub struct SerializeError(#[from] pub toml::ser::Error);
+
+/// Represents errors returned when validating finds problems.
+#[derive(Debug, Error, Diagnostic)]
+#[error("found {count} problem(s)")]
+#[diagnostic(
+    code(changelogging::commands::config::invalid),
+    help("see the problems reported above")
+)]
+pub struct InvalidError {
+    /// The number of problems found.
+    pub count: usize,
+}
+
+impl InvalidError {
+    /// Constructs [`Self`].
+    pub fn new(count: usize) -> Self {
+        Self { count }
+    }
+}
+
+/// Represents sources of errors that can occur when inspecting configuration.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Serialize errors.
+    Serialize(#[from] SerializeError),
+    /// Invalid errors.
+    Invalid(#[from] InvalidError),
+}
+
+/// Represents errors that can occur when inspecting configuration.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to inspect config")]
+#[diagnostic(
+    code(changelogging::commands::config),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`SerializeError`].
+    pub fn serialize(error: SerializeError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`InvalidError`].
+    pub fn invalid(error: InvalidError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`SerializeError`] and constructs [`Self`] from it.
+    pub fn new_serialize(error: toml::ser::Error) -> Self {
+        Self::serialize(SerializeError(error))
+    }
+
+    /// Constructs [`InvalidError`] and constructs [`Self`] from it.
+    pub fn new_invalid(count: usize) -> Self {
+        Self::invalid(InvalidError::new(count))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Serialize(_) => Code::Failure,
+            ErrorSource::Invalid(_) => Code::Validation,
+        }
+    }
+}
+
+/// Represents origins of configuration values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Origin {
+    /// The value is the default one.
+    Default,
+    /// The value comes from the configuration file.
+    Config,
+    /// The value is overridden for this run.
+    Override,
+}
+
+impl Origin {
+    /// Returns the static string representation of [`Self`].
+    pub fn static_str(self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Config => "config",
+            Self::Override => "override",
+        }
+    }
+}
+
+fn into_table<S: Serialize>(value: &S, config: &Config<'_>) -> Result<Table, Error> {
+    let mut table = Table::try_from(value).map_err(Error::new_serialize)?;
+
+    // show the effective `types`, that is, including the defaults
+
+    let types = Value::try_from(config.types_with_defaults()).map_err(Error::new_serialize)?;
+
+    table.insert(TYPES.to_owned(), types);
+
+    Ok(table)
+}
+
+const TYPES: &str = "types";
+
+fn is_bare(key: &str) -> bool {
+    !key.is_empty()
+        && key
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || matches!(character, '-' | '_'))
+}
+
+fn key(key: &str) -> String {
+    if is_bare(key) {
+        key.to_owned()
+    } else {
+        Value::String(key.to_owned()).to_string()
+    }
+}
+
+/// Shows the effective configuration as TOML.
+///
+/// The `overrides` given are used to tell overridden values apart.
+///
+/// Values set in the file the workspace was loaded from (see [`Source::read_table`])
+/// come from the config, even if they are set to the defaults. Other values come from the config
+/// only if they differ from the defaults (for instance, when set by profiles or context files).
+///
+/// When `only_changed` is set, only values that do not come from the defaults are shown.
+/// When `origin` is set, every value is annotated with its [`Origin`].
+///
+/// [`Source::read_table`]: crate::workspace::Source::read_table
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if serializing the configuration fails.
+pub fn show(
+    workspace: Workspace<'_>,
+    overrides: &Overrides<'_>,
+    only_changed: bool,
+    origin: bool,
+) -> Result<(), Error> {
+    let defaults = Config::default();
+
+    let table = into_table(&workspace, &workspace.config)?;
+    let default_table = into_table(&defaults, &defaults)?;
+
+    let written = workspace
+        .source
+        .as_ref()
+        .and_then(|source| source.read_table().ok())
+        .unwrap_or_default();
+
+    let origin_of = |section: Option<&str>, name: &str, value: &Value| {
+        let dotted = section.map_or_else(|| name.to_owned(), |section| format!("{section}.{name}"));
+
+        if overrides.contains(&dotted) {
+            return Origin::Override;
+        }
+
+        let present = match section {
+            Some(section) => written
+                .get(section)
+                .and_then(Value::as_table)
+                .is_some_and(|table| table.contains_key(name)),
+            None => written.contains_key(name),
+        };
+
+        if present {
+            return Origin::Config;
+        }
+
+        let default = match section {
+            Some(section) => default_table
+                .get(section)
+                .and_then(Value::as_table)
+                .and_then(|table| table.get(name)),
+            None => default_table.get(name),
+        };
+
+        if default == Some(value) {
+            Origin::Default
+        } else {
+            Origin::Config
+        }
+    };
+
+    let line = |string: &mut String, section: Option<&str>, name: &str, value: &Value| {
+        let value_origin = origin_of(section, name, value);
+
+        if only_changed && value_origin == Origin::Default {
+            return;
+        }
+
+        let _ = write!(string, "{} = {value}", key(name));
+
+        if origin {
+            let _ = write!(string, "  # {}", value_origin.static_str());
+        }
+
+        string.push('\n');
+    };
+
+    let mut output = String::new();
+
+    for (name, value) in table.iter().filter(|(_, value)| !value.is_table()) {
+        line(&mut output, None, name, value);
+    }
+
+    for (section, value) in table.iter() {
+        let Some(inner) = value.as_table() else {
+            continue;
+        };
+
+        let mut lines = String::new();
+
+        for (name, value) in inner {
+            line(&mut lines, Some(section), name, value);
+        }
+
+        if lines.is_empty() {
+            continue;
+        }
+
+        if !output.is_empty() {
+            output.push('\n');
+        }
+
+        let _ = writeln!(output, "[{}]", key(section));
+
+        output.push_str(&lines);
+    }
+
+    print!("{output}");
+
+    Ok(())
+}
+
+/// Represents problems with `order` entries that have no titles in `types`.
+#[derive(Debug, Error, Diagnostic)]
+#[error("type `{name}` from `order` has no title")]
+#[diagnostic(
+    code(changelogging::commands::config::missing_title),
+    help("add the title to `types` or remove the type from `order`")
+)]
+pub struct MissingTitleProblem {
+    /// The name of the type.
+    pub name: String,
+}
+
+impl MissingTitleProblem {
+    /// Constructs [`Self`].
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+/// Represents problems with configured paths that do not exist.
+#[derive(Debug, Error, Diagnostic)]
+#[error("`{key}` path `{path}` does not exist")]
+#[diagnostic(
+    code(changelogging::commands::config::missing_path),
+    help("create the path or change the configuration")
+)]
+pub struct MissingPathProblem {
+    /// The configuration key of the path.
+    pub key: &'static str,
+    /// The path itself.
+    pub path: PathBuf,
+}
+
+impl MissingPathProblem {
+    /// Constructs [`Self`].
+    pub fn new(key: &'static str, path: PathBuf) -> Self {
+        Self { key, path }
+    }
+}
+
+/// Represents problems found by [`validate`].
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum Problem {
+    /// Template compilation problems.
+    Init(#[from] InitError),
+    /// `title` template problems.
+    BuildTitle(#[from] BuildTitleError),
+    /// `fragment`, `reference`, `security` and per-type template problems.
+    BuildFragment(#[from] BuildFragmentError),
+    /// Missing title problems.
+    MissingTitle(#[from] MissingTitleProblem),
+    /// Missing path problems.
+    MissingPath(#[from] MissingPathProblem),
+    /// Problems with profiles listed in `outputs`.
+    Profile(#[from] crate::workspace::ProfileError),
+}
+
+/// Validates the given configuration, returning the problems found.
+///
+/// Templates are compiled and rendered against the actual context and the example fragment,
+/// so that unknown variables are caught before building.
+pub fn problems(workspace: &Workspace<'_>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    let config = &workspace.config;
+
+    let types = config.types_with_defaults();
+
+    // templates
+
+    match Builder::new(workspace.context.clone(), config.clone(), today()) {
+        Ok(builder) => {
+            if let Err(error) = builder.build_title() {
+                problems.push(error.into());
+            }
+
+            let fragment = example();
+
+            if let Err(error) = builder.build_fragment(&fragment) {
+                problems.push(error.into());
+            }
+
+            // formats of types and the `security` format are only used for their fragments
+
+            let security = config.formats.security.is_some().then_some(SECURITY_TYPE);
+
+            let formatted = config
+                .types
+                .iter()
+                .filter(|(_, type_)| type_.format.is_some())
+                .map(|(name, _)| name.as_ref());
+
+            for name in security.into_iter().chain(formatted).unique() {
+                let mut fragment = example();
+
+                fragment.partial.type_name = Cow::Borrowed(name);
+
+                if let Err(error) = builder.build_fragment(&fragment) {
+                    problems.push(error.into());
+                }
+            }
+
+            // references are only rendered for types that are both ordered and titled
+
+            let mut sections = Sections::new();
+
+            if let Some(name) = config.order.iter().find(|name| types.contains_key(*name)) {
+                sections.insert(name.clone(), vec![fragment]);
+            }
+
+            if let Err(error) = builder.build_references(&sections) {
+                problems.push(error.into());
+            }
+        }
+        Err(error) => problems.push(error.into()),
+    }
+
+    // types
+
+    for name in &config.order {
+        if !types.contains_key(name) {
+            problems.push(MissingTitleProblem::new(name.clone().into_owned()).into());
+        }
+    }
+
+    // paths
+
+    let directory = config.paths.directory.as_ref();
+
+    if !directory.exists() {
+        problems.push(MissingPathProblem::new(DIRECTORY_KEY, directory.to_owned()).into());
+    }
+
+    let output = config.paths.output.as_ref();
+
+    if !output.exists() && !config.create_output {
+        problems.push(MissingPathProblem::new(OUTPUT_KEY, output.to_owned()).into());
+    }
+
+    // outputs
+
+    if let Err(error) = workspace.outputs() {
+        problems.push(error.into());
+    }
+
+    problems
+}
+
+/// Validates the configuration, reporting problems to `stderr`.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if any problems are found.
+pub fn validate(workspace: Workspace<'_>) -> Result<(), Error> {
+    let problems = problems(&workspace);
+
+    let count = problems.len();
+
+    for problem in problems {
+        eprintln!("{:?}", Report::new(problem));
+    }
+
+    if count > 0 {
+        return Err(Error::new_invalid(count));
+    }
+
+    println!("configuration is valid");
+
+    Ok(())
+}
@@ -3,10 +3,12 @@
 //! The [`create`] function implements the `create` subcommand.
 
 use std::{
-    fs::File,
-    io::Write,
+    borrow::Cow,
+    fs::{read_to_string, File},
+    io::{stdin, stdout, IsTerminal, Write},
     iter::once,
     path::{Path, PathBuf},
+    process::Command,
 };
 
 use edit::edit_file;
@@ -14,9 +16,17 @@ use miette::Diagnostic;
 use thiserror::Error;
 
 use crate::{
-    fragment::{validate, ParseError},
-    git,
+    builder::{Builder, InitError, RenderTemplateError},
+    config::{Config, Git},
+    counter::Counter,
+    date::today,
+    exit::Code,
+    fragment::{validate, Fragment, Integer, ParseError, Partial},
+    git, report,
+    workspace::Workspace,
 };
+#[cfg(feature = "forge")]
+use crate::{forge::Client, fragment::Id, network::OfflineWarning};
 
 /// Represents errors that can occur when opening files.
 #[derive(Debug, Error, Diagnostic)]
@@ -36,6 +46,27 @@ pub struct OpenError(#[from] pub std::io::Error);
 )]
 pub struct WriteError(#[from] pub std::io::Error);
 
+/// Represents errors that can occur when reading fragment templates.
+#[derive(Debug, Error, Diagnostic)]
+#[error("reading the template `{path}` failed")]
+#[diagnostic(
+    code(changelogging::create::read_template),
+    help("make sure the template file is accessible")
+)]
+pub struct ReadTemplateError {
+    /// The underlying I/O error.
+    pub source: std::io::Error,
+    /// The path to the template.
+    pub path: PathBuf,
+}
+
+impl ReadTemplateError {
+    /// Constructs [`Self`].
+    pub fn new(source: std::io::Error, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+}
+
 /// Represents errors that can occur when starting default editors.
 #[derive(Debug, Error, Diagnostic)]
 #[error("editing failed")]
@@ -45,6 +76,76 @@ pub struct WriteError(#[from] pub std::io::Error);
 )]
 pub struct EditError(#[from] pub std::io::Error);
 
+/// Represents errors that occur when the editor is requested in non-interactive mode.
+#[derive(Debug, Error, Diagnostic)]
+#[error("can not open the editor in non-interactive mode")]
+#[diagnostic(
+    code(changelogging::create::non_interactive),
+    help("pass the content via `--content (-c)` or use `--no-edit`")
+)]
+pub struct NonInteractiveError;
+
+/// Represents errors that occur when fragments without integer IDs are created from pull requests.
+#[derive(Debug, Error, Diagnostic)]
+#[error("fragment ID is not the pull request number")]
+#[diagnostic(
+    code(changelogging::create::not_pull_request),
+    help("use the pull request number as the fragment ID, for instance `42.feature.md`")
+)]
+pub struct NotPullRequestError;
+
+/// Represents errors that occur when types of fragments can not be inferred from pull requests.
+#[derive(Debug, Error, Diagnostic)]
+#[error("can not infer the type of pull request `#{number}`")]
+#[diagnostic(
+    code(changelogging::create::no_type),
+    help("specify the type, for instance `{number}.feature.md`, or map labels via `forge.labels`")
+)]
+pub struct NoTypeError {
+    /// The number of the pull request.
+    pub number: Integer,
+}
+
+impl NoTypeError {
+    /// Constructs [`Self`].
+    pub fn new(number: Integer) -> Self {
+        Self { number }
+    }
+}
+
+/// Represents errors that occur when IDs are assigned automatically without the counter.
+#[derive(Debug, Error, Diagnostic)]
+#[error("can not assign IDs automatically")]
+#[diagnostic(
+    code(changelogging::create::not_sequence),
+    help("set `ids = \"sequence\"` to assign IDs from the counter")
+)]
+pub struct NotSequenceError;
+
+/// Represents warnings emitted when fragments of unknown types are created.
+#[derive(Debug, Error, Diagnostic)]
+#[error("unknown type `{type_name}`")]
+#[diagnostic(code(changelogging::create::unknown_type), severity(Warning))]
+pub struct UnknownTypeWarning {
+    /// The unknown type.
+    pub type_name: String,
+    /// The help message suggesting the closest known type, if any.
+    #[help]
+    pub help: Option<String>,
+}
+
+impl UnknownTypeWarning {
+    /// Constructs [`Self`], suggesting the given type, if any.
+    pub fn new(type_name: String, suggestion: Option<String>) -> Self {
+        let help = Some(suggestion.map_or_else(
+            || "fragments of unknown types are never built".to_owned(),
+            |suggestion| format!("did you mean `{suggestion}`?"),
+        ));
+
+        Self { type_name, help }
+    }
+}
+
 /// Represents sources of errors that can occur during fragment creation.
 #[derive(Debug, Error, Diagnostic)]
 #[error(transparent)]
@@ -56,10 +157,29 @@ pub enum ErrorSource {
     Open(#[from] OpenError),
     /// Write errors.
     Write(#[from] WriteError),
+    /// Read template errors.
+    ReadTemplate(#[from] ReadTemplateError),
+    /// Initialization errors.
+    Init(#[from] InitError),
+    /// Render template errors.
+    RenderTemplate(#[from] RenderTemplateError),
     /// Edit errors.
     Edit(#[from] EditError),
+    /// Non-interactive errors.
+    NonInteractive(#[from] NonInteractiveError),
     /// `git` errors.
     Git(#[from] crate::git::Error),
+    /// Not pull request errors.
+    NotPullRequest(#[from] NotPullRequestError),
+    /// No type errors.
+    NoType(#[from] NoTypeError),
+    /// Not sequence errors.
+    NotSequence(#[from] NotSequenceError),
+    /// Counter errors.
+    Counter(#[from] crate::counter::Error),
+    /// Forge errors.
+    #[cfg(feature = "forge")]
+    Forge(#[from] crate::forge::Error),
 }
 
 /// Represents errors that can occur during fragment creation.
@@ -99,6 +219,21 @@ impl Error {
         Self::new(error.into(), path)
     }
 
+    /// Constructs [`Self`] from [`ReadTemplateError`].
+    pub fn read_template(error: ReadTemplateError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`RenderTemplateError`].
+    pub fn render_template(error: RenderTemplateError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
     /// Constructs [`Self`] from [`EditError`].
     pub fn edit(error: EditError, path: PathBuf) -> Self {
         Self::new(error.into(), path)
@@ -111,6 +246,41 @@ impl Error {
         Self::new(error.into(), path)
     }
 
+    /// Constructs [`Self`] from [`NonInteractiveError`].
+    pub fn non_interactive(error: NonInteractiveError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`NotPullRequestError`].
+    pub fn not_pull_request(error: NotPullRequestError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`NoTypeError`].
+    pub fn no_type(error: NoTypeError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`NotSequenceError`].
+    pub fn not_sequence(error: NotSequenceError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::counter::Error
+    pub fn counter(error: crate::counter::Error, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::forge::Error
+    #[cfg(feature = "forge")]
+    pub fn forge(error: crate::forge::Error, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
     /// Constructs [`OpenError`] and constructs [`Self`] from it.
     pub fn new_open(error: std::io::Error, path: PathBuf) -> Self {
         Self::open(OpenError(error), path)
@@ -121,27 +291,295 @@ impl Error {
         Self::write(WriteError(error), path)
     }
 
+    /// Constructs [`NoTypeError`] and constructs [`Self`] from it.
+    pub fn new_no_type(number: Integer, path: PathBuf) -> Self {
+        Self::no_type(NoTypeError::new(number), path)
+    }
+
     /// Constructs [`EditError`] and constructs [`Self`] from it.
     pub fn new_edit(error: std::io::Error, path: PathBuf) -> Self {
         Self::edit(EditError(error), path)
     }
+
+    /// Constructs [`ReadTemplateError`] and constructs [`Self`] from it.
+    pub fn new_read_template(error: std::io::Error, template: PathBuf, path: PathBuf) -> Self {
+        Self::read_template(ReadTemplateError::new(error, template), path)
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Parse(_) => Code::Validation,
+            ErrorSource::Open(_) | ErrorSource::Write(_) | ErrorSource::ReadTemplate(_) => Code::Io,
+            ErrorSource::Init(_) | ErrorSource::RenderTemplate(_) => Code::Config,
+            ErrorSource::Edit(_) => Code::Failure,
+            ErrorSource::NonInteractive(_)
+            | ErrorSource::NotPullRequest(_)
+            | ErrorSource::NoType(_)
+            | ErrorSource::NotSequence(_) => Code::Usage,
+            ErrorSource::Counter(error) => error.exit_code(),
+            ErrorSource::Git(_) => Code::Git,
+            #[cfg(feature = "forge")]
+            ErrorSource::Forge(error) => error.exit_code(),
+        }
+    }
 }
 
 /// The placeholder that gets written to fragment files if contents are not provided.
 pub const PLACEHOLDER: &str = "Add the fragment content here.";
 
+/// Checks whether both `stdin` and `stdout` are terminals, that is, editors can be used.
+pub fn is_interactive() -> bool {
+    stdin().is_terminal() && stdout().is_terminal()
+}
+
+/// Opens the fragment at the given path in the editor.
+///
+/// The `editor` command is split on whitespace, with the path appended to its arguments.
+/// If it is not provided, the default editor is used.
+///
+/// # Errors
+///
+/// Returns [`std::io::Error`] if starting the editor fails or it exits unsuccessfully.
+pub fn open_editor(editor: Option<&str>, path: &Path) -> std::io::Result<()> {
+    let Some(editor) = editor else {
+        return edit_file(path);
+    };
+
+    let mut parts = editor.split_whitespace();
+
+    let program = parts
+        .next()
+        .ok_or_else(|| std::io::Error::other("the editor command is empty"))?;
+
+    let status = Command::new(program).args(parts).arg(path).status()?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(std::io::Error::other(format!(
+            "`{program}` exited with {status}"
+        )))
+    }
+}
+
+/// The extension of fragments named after pull requests or with automatically assigned IDs.
+pub const EXTENSION: &str = "md";
+
+/// The directory with fragment templates, relative to the fragments directory.
+pub const TEMPLATES: &str = "templates";
+
+/// Returns the path to the template of the given type in the given fragments directory,
+/// that is, `{directory}/templates/{type}.md`.
+pub fn template_path(directory: &Path, type_name: &str) -> PathBuf {
+    directory
+        .join(TEMPLATES)
+        .join(format!("{type_name}.{EXTENSION}"))
+}
+
+/// Renders the template of the type of the given fragment name, if there is one
+/// (see [`template_path`]), returning the content to pre-populate the editor with.
+///
+/// The same variables as in the `fragment` format are available, except for `content`,
+/// which is empty.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if parsing the fragment name, reading the template,
+/// initializing the builder or rendering the template fails.
+pub fn from_template(workspace: &Workspace<'_>, name: &str) -> Result<Option<String>, Error> {
+    let directory = workspace.config.paths.directory.as_ref();
+
+    let path = directory.join(name);
+
+    let partial: Partial<'_> = name
+        .parse()
+        .map_err(|error| Error::parse(error, path.clone()))?;
+
+    // templates are looked up by type names, so that aliases share them
+
+    let type_name = workspace.config.canonical_type(&partial.type_name);
+
+    let template = template_path(directory, type_name);
+
+    if !template.is_file() {
+        return Ok(None);
+    }
+
+    let string = read_to_string(&template)
+        .map_err(|error| Error::new_read_template(error, template, path.clone()))?;
+
+    let builder = Builder::from_workspace(workspace.clone(), today())
+        .map_err(|error| Error::init(error, path.clone()))?;
+
+    let fragment = Fragment::new(partial, Cow::Borrowed(""));
+
+    let content = builder
+        .render_fragment_template(&string, &fragment)
+        .map_err(|error| Error::render_template(error, path))?;
+
+    Ok(Some(content))
+}
+
+/// Fetches the fragment content from the pull request with the ID of the given fragment name,
+/// that is, its title.
+///
+/// The name can also consist of the pull request number alone, in which case the type
+/// is inferred from labels of the pull request (see `forge.labels`), returning the full name.
+///
+/// If `offline` is set, nothing is fetched and [`OfflineWarning`] is reported instead,
+/// returning no content.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if parsing the fragment name fails, its ID is not the integer,
+/// the type can not be inferred or fetching the pull request fails.
+#[cfg(feature = "forge")]
+pub fn from_pull_request(
+    workspace: &Workspace<'_>,
+    name: String,
+    offline: bool,
+) -> Result<(String, Option<String>), Error> {
+    let config = &workspace.config;
+
+    let path = config.paths.directory.join(&name);
+
+    // either `{id}.{type}` (possibly followed by anything) or `{id}` alone
+
+    let (id, typed) = match name.parse::<Partial<'_>>() {
+        Ok(partial) => (partial.id, true),
+        Err(error) => match name.parse::<Id<'_>>() {
+            Ok(id) => (id, false),
+            Err(_) => return Err(Error::parse(error, path)),
+        },
+    };
+
+    let Id::Integer(number) = id else {
+        return Err(Error::not_pull_request(NotPullRequestError, path));
+    };
+
+    let client = Client::from_workspace(workspace, offline)
+        .map_err(|error| Error::forge(error, path.clone()))?;
+
+    if client.is_offline() {
+        report::warn(OfflineWarning::new("fetching the pull request"));
+
+        if !typed {
+            return Err(Error::new_no_type(number, path));
+        }
+
+        return Ok((name, None));
+    }
+
+    let pull_request = client
+        .pull_request(number.into())
+        .map_err(|error| Error::forge(error, path.clone()))?;
+
+    let name = if typed {
+        name
+    } else {
+        let type_name = config
+            .forge
+            .type_for(&pull_request.labels, &config.order)
+            .ok_or_else(|| Error::new_no_type(number, path))?;
+
+        format!("{number}.{type_name}.{EXTENSION}")
+    };
+
+    Ok((name, Some(pull_request.title)))
+}
+
+/// Assigns the next ID from the counter (see [`Counter`]) to the given name, which is either
+/// `{type}` or `{type}.{extension}`, returning the full name. If the name has no extension,
+/// [`EXTENSION`] is used.
+///
+/// The name is validated before assigning the ID, so that invalid names do not consume IDs.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if `ids` is not `sequence`, the name is invalid
+/// or counting fails.
+pub fn auto_name(config: &Config<'_>, name: &str) -> Result<String, Error> {
+    let directory = config.paths.directory.as_ref();
+
+    let path = directory.join(name);
+
+    if !config.ids.is_sequence() {
+        return Err(Error::not_sequence(NotSequenceError, path));
+    }
+
+    // validate the name with the placeholder ID first
+
+    let partial: Partial<'_> = format!("0.{name}")
+        .parse()
+        .map_err(|error| Error::parse(error, path.clone()))?;
+
+    let counter = Counter::new(directory.to_owned());
+
+    let id = counter
+        .next()
+        .map_err(|error| Error::counter(error, path))?;
+
+    let name = if partial.extension.is_some() {
+        format!("{id}.{name}")
+    } else {
+        format!("{id}.{name}.{EXTENSION}")
+    };
+
+    Ok(name)
+}
+
+/// Reports [`UnknownTypeWarning`] if the type of the given fragment name is not known,
+/// suggesting the closest known type (see [`Config::suggest_type`]).
+///
+/// Names that can not be parsed are left for [`create`] to reject.
+pub fn warn_unknown_type(config: &Config<'_>, name: &str) {
+    let Ok(partial) = name.parse::<Partial<'_>>() else {
+        return;
+    };
+
+    let type_name = config.canonical_type(&partial.type_name);
+
+    if !config.types_with_defaults().contains_key(type_name) {
+        report::warn(UnknownTypeWarning::new(
+            type_name.to_owned(),
+            config.suggest_type(type_name),
+        ));
+    }
+}
+
+/// Adds the counter in the given fragments directory via `git`.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if adding the counter fails.
+pub fn add_counter(git: &Git<'_>, directory: &Path) -> Result<(), Error> {
+    let path = Counter::new(directory.to_owned()).path();
+
+    git::add(git, once(&path))
+        .and_then(git::success)
+        .map_err(|error| Error::git(error, path))
+}
+
 /// Creates changelog fragments.
 ///
+/// If `edit` is set, the fragment is opened in the `editor` (or the default one).
+/// Editing fails fast with [`NonInteractiveError`] unless [`is_interactive`].
+///
+/// If `add` is set, the fragment is added via `git`, invoked according to `git`.
+///
 /// # Errors
 ///
 /// Returns [`struct@Error`] if parsing the fragment name, creating the fragment file
-/// and writing to it fails. Also returned if starting the default editor fails.
+/// and writing to it fails. Also returned if starting the editor fails.
 pub fn create<D: AsRef<Path>, S: AsRef<str>, C: AsRef<str>>(
     directory: D,
     name: S,
     content: Option<C>,
     edit: bool,
+    editor: Option<&str>,
     add: bool,
+    git: &Git<'_>,
 ) -> Result<(), Error> {
     let name = name.as_ref();
 
@@ -149,6 +587,12 @@ pub fn create<D: AsRef<Path>, S: AsRef<str>, C: AsRef<str>>(
 
     validate(name).map_err(|error| Error::parse(error, path.clone()))?;
 
+    // fail before creating the fragment instead of waiting for the editor
+
+    if edit && !is_interactive() {
+        return Err(Error::non_interactive(NonInteractiveError, path));
+    }
+
     let mut file = File::options()
         .create_new(true)
         .write(true)
@@ -162,11 +606,13 @@ pub fn create<D: AsRef<Path>, S: AsRef<str>, C: AsRef<str>>(
     writeln!(file, "{string}").map_err(|error| Error::new_write(error, path.clone()))?;
 
     if edit {
-        edit_file(&path).map_err(|error| Error::new_edit(error, path.clone()))?;
+        open_editor(editor, &path).map_err(|error| Error::new_edit(error, path.clone()))?;
     }
 
     if add {
-        git::add(once(&path)).map_err(|error| Error::git(error, path.clone()))?;
+        git::add(git, once(&path))
+            .and_then(git::success)
+            .map_err(|error| Error::git(error, path.clone()))?;
     }
 
     Ok(())
@@ -0,0 +1,416 @@
+//! Diagnosing setup problems.
+//!
+//! The [`doctor`] function implements the `doctor` subcommand.
+
+use std::{
+    fs::{read_dir, read_to_string},
+    io::ErrorKind,
+    path::PathBuf,
+};
+
+use miette::{Diagnostic, Report};
+use thiserror::Error;
+
+use crate::{
+    builder::{ignore_in, Builder, CollectError, InitError},
+    date::today,
+    exit::Code,
+    fragment::{path_name, Partial},
+    git,
+    workspace::Workspace,
+};
+
+/// Represents problems with loading workspaces.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to load the workspace")]
+#[diagnostic(
+    code(changelogging::commands::doctor::workspace),
+    help("create `changelogging.toml` (or use the `--config (-C)` option)")
+)]
+pub struct WorkspaceProblem {
+    /// The underlying diagnostic.
+    #[diagnostic_source]
+    pub source: Box<dyn Diagnostic + Send + Sync>,
+}
+
+impl WorkspaceProblem {
+    /// Constructs [`Self`].
+    pub fn new(source: Box<dyn Diagnostic + Send + Sync>) -> Self {
+        Self { source }
+    }
+}
+
+/// Represents problems with missing fragments directories.
+#[derive(Debug, Error, Diagnostic)]
+#[error("fragments directory `{path}` does not exist")]
+#[diagnostic(
+    code(changelogging::commands::doctor::directory),
+    help("create the directory or check `paths.directory`")
+)]
+pub struct DirectoryProblem {
+    /// The path to the directory.
+    pub path: PathBuf,
+}
+
+impl DirectoryProblem {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+/// Represents problems with invalid ignore files.
+#[derive(Debug, Error, Diagnostic)]
+#[error("ignore file is invalid")]
+#[diagnostic(
+    code(changelogging::commands::doctor::ignore),
+    help("fix `.changeloggingignore` in the fragments directory")
+)]
+pub struct IgnoreProblem {
+    /// The underlying collection error.
+    #[source]
+    #[diagnostic_source]
+    pub source: CollectError,
+}
+
+impl IgnoreProblem {
+    /// Constructs [`Self`].
+    pub fn new(source: CollectError) -> Self {
+        Self { source }
+    }
+}
+
+/// Represents problems with missing changelogs.
+#[derive(Debug, Error, Diagnostic)]
+#[error("changelog `{path}` does not exist")]
+#[diagnostic(
+    code(changelogging::commands::doctor::changelog),
+    help("create the changelog containing the start marker, set `create_output` or check `paths.output`")
+)]
+pub struct ChangelogProblem {
+    /// The path to the changelog.
+    pub path: PathBuf,
+}
+
+impl ChangelogProblem {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+/// Represents problems with start markers missing from changelogs.
+#[derive(Debug, Error, Diagnostic)]
+#[error("changelog `{path}` does not contain the start marker")]
+#[diagnostic(
+    code(changelogging::commands::doctor::missing_start),
+    help("add `{start}` to the changelog where entries should be written, or check `start`")
+)]
+pub struct MissingStartProblem {
+    /// The path to the changelog.
+    pub path: PathBuf,
+    /// The start marker.
+    pub start: String,
+}
+
+impl MissingStartProblem {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, start: String) -> Self {
+        Self { path, start }
+    }
+}
+
+/// Represents problems with start markers duplicated in changelogs.
+#[derive(Debug, Error, Diagnostic)]
+#[error("changelog `{path}` contains the start marker {count} times")]
+#[diagnostic(
+    code(changelogging::commands::doctor::duplicated_start),
+    help("keep exactly one `{start}` in the changelog")
+)]
+pub struct DuplicatedStartProblem {
+    /// The path to the changelog.
+    pub path: PathBuf,
+    /// The start marker.
+    pub start: String,
+    /// The number of times the start marker occurs.
+    pub count: usize,
+}
+
+impl DuplicatedStartProblem {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, start: String, count: usize) -> Self {
+        Self { path, start, count }
+    }
+}
+
+/// Represents problems with fragments referencing unknown types.
+#[derive(Debug, Error, Diagnostic)]
+#[error("fragment `{path}` has unknown type `{type_name}`")]
+#[diagnostic(
+    code(changelogging::commands::doctor::unknown_type),
+    help("add `{type_name}` to `types` and `order`, or rename the fragment")
+)]
+pub struct UnknownTypeProblem {
+    /// The path to the fragment.
+    pub path: PathBuf,
+    /// The type of the fragment.
+    pub type_name: String,
+}
+
+impl UnknownTypeProblem {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, type_name: String) -> Self {
+        Self { path, type_name }
+    }
+}
+
+/// Represents problems with fragments having types that are not included in `order`.
+#[derive(Debug, Error, Diagnostic)]
+#[error("fragment `{path}` has type `{type_name}` that is not included in `order`")]
+#[diagnostic(
+    code(changelogging::commands::doctor::unordered_type),
+    help("add `{type_name}` to `order`, otherwise the fragment is never built")
+)]
+pub struct UnorderedTypeProblem {
+    /// The path to the fragment.
+    pub path: PathBuf,
+    /// The type of the fragment.
+    pub type_name: String,
+}
+
+impl UnorderedTypeProblem {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, type_name: String) -> Self {
+        Self { path, type_name }
+    }
+}
+
+/// Represents problems with `git` being unavailable.
+#[derive(Debug, Error, Diagnostic)]
+#[error("git is not available")]
+#[diagnostic(
+    code(changelogging::commands::doctor::git),
+    severity(Warning),
+    help(
+        "install git and make sure it is on `PATH` (or set `git.executable`) to use `--add`, `--stage` and `--remove`"
+    )
+)]
+pub struct GitProblem {
+    /// The underlying `git` error, if any.
+    #[source]
+    pub source: Option<git::Error>,
+}
+
+impl GitProblem {
+    /// Constructs [`Self`].
+    pub fn new(source: Option<git::Error>) -> Self {
+        Self { source }
+    }
+}
+
+/// Represents problems with invalid templates.
+#[derive(Debug, Error, Diagnostic)]
+#[error("templates are invalid")]
+#[diagnostic(
+    code(changelogging::commands::doctor::templates),
+    help("fix the `formats` section")
+)]
+pub struct TemplatesProblem {
+    /// The underlying initialization error.
+    #[source]
+    #[diagnostic_source]
+    pub source: InitError,
+}
+
+impl TemplatesProblem {
+    /// Constructs [`Self`].
+    pub fn new(source: InitError) -> Self {
+        Self { source }
+    }
+}
+
+/// Represents problems found by [`doctor`].
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum Problem {
+    /// Workspace problems.
+    Workspace(#[from] WorkspaceProblem),
+    /// Fragments directory problems.
+    Directory(#[from] DirectoryProblem),
+    /// Ignore file problems.
+    Ignore(#[from] IgnoreProblem),
+    /// Changelog problems.
+    Changelog(#[from] ChangelogProblem),
+    /// Missing start marker problems.
+    MissingStart(#[from] MissingStartProblem),
+    /// Duplicated start marker problems.
+    DuplicatedStart(#[from] DuplicatedStartProblem),
+    /// Unknown type problems.
+    UnknownType(#[from] UnknownTypeProblem),
+    /// Unordered type problems.
+    UnorderedType(#[from] UnorderedTypeProblem),
+    /// `git` problems.
+    Git(#[from] GitProblem),
+    /// Template problems.
+    Templates(#[from] TemplatesProblem),
+}
+
+impl Problem {
+    /// Checks whether [`Self`] is merely a warning.
+    pub fn is_warning(&self) -> bool {
+        matches!(self, Self::Git(_))
+    }
+}
+
+/// Represents errors returned when the doctor finds problems.
+#[derive(Debug, Error, Diagnostic)]
+#[error("found {count} problem(s)")]
+#[diagnostic(
+    code(changelogging::commands::doctor),
+    help("see the problems reported above")
+)]
+pub struct Error {
+    /// The number of problems found.
+    pub count: usize,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(count: usize) -> Self {
+        Self { count }
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        Code::Validation
+    }
+}
+
+/// Checks the given workspace, returning the problems found.
+pub fn check(workspace: &Workspace<'_>) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    let config = &workspace.config;
+
+    // fragments directory and types
+
+    let directory = config.paths.directory.as_ref();
+
+    let ignore = match ignore_in(directory) {
+        Ok(ignore) => Some(ignore),
+        Err(error) => {
+            problems.push(IgnoreProblem::new(error).into());
+
+            None
+        }
+    };
+
+    match read_dir(directory) {
+        Ok(entries) => {
+            let types = config.types_with_defaults();
+
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(|result| result.ok().map(|entry| entry.path()))
+                .filter(|path| {
+                    ignore
+                        .as_ref()
+                        .is_none_or(|ignore| !ignore.matched(path, path.is_dir()).is_ignore())
+                })
+                .collect();
+
+            paths.sort();
+
+            for path in paths {
+                let Some(partial) =
+                    path_name(&path).and_then(|name| name.parse::<Partial<'_>>().ok())
+                else {
+                    continue;
+                };
+
+                let type_name = config.canonical_type(&partial.type_name);
+
+                if !types.contains_key(type_name) {
+                    problems.push(UnknownTypeProblem::new(path, type_name.to_owned()).into());
+                } else if !config.order.iter().any(|name| name == type_name) {
+                    problems.push(UnorderedTypeProblem::new(path, type_name.to_owned()).into());
+                }
+            }
+        }
+        Err(error) if error.kind() == ErrorKind::NotFound => {
+            problems.push(DirectoryProblem::new(directory.to_owned()).into());
+        }
+        Err(_) => {}
+    };
+
+    // changelog and start marker
+
+    let output = config.paths.output.as_ref();
+
+    let start = config.start.as_ref();
+
+    match read_to_string(output) {
+        Ok(contents) => match contents.matches(start).count() {
+            0 => {
+                problems.push(MissingStartProblem::new(output.to_owned(), start.to_owned()).into())
+            }
+            1 => {}
+            count => problems.push(
+                DuplicatedStartProblem::new(output.to_owned(), start.to_owned(), count).into(),
+            ),
+        },
+        Err(error) if error.kind() == ErrorKind::NotFound && !config.create_output => {
+            problems.push(ChangelogProblem::new(output.to_owned()).into());
+        }
+        Err(_) => {}
+    };
+
+    // templates
+
+    if let Err(error) = Builder::new(workspace.context.clone(), config.clone(), today()) {
+        problems.push(TemplatesProblem::new(error).into());
+    }
+
+    // git
+
+    match git::version(&config.git) {
+        Ok(status) if status.success() => {}
+        Ok(_) => problems.push(GitProblem::new(None).into()),
+        Err(error) => problems.push(GitProblem::new(Some(error)).into()),
+    };
+
+    problems
+}
+
+/// Diagnoses setup problems, reporting them to `stderr`.
+///
+/// The `loaded` argument is the result of loading the workspace.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if any problems (except for warnings) are found.
+pub fn doctor<E: Diagnostic + Send + Sync + 'static>(
+    loaded: Result<Workspace<'_>, E>,
+) -> Result<(), Error> {
+    let problems = match loaded {
+        Ok(workspace) => check(&workspace),
+        Err(error) => vec![WorkspaceProblem::new(Box::new(error)).into()],
+    };
+
+    let count = problems
+        .iter()
+        .filter(|problem| !problem.is_warning())
+        .count();
+
+    for problem in problems {
+        eprintln!("{:?}", Report::new(problem));
+    }
+
+    if count > 0 {
+        return Err(Error::new(count));
+    }
+
+    println!("no problems found");
+
+    Ok(())
+}
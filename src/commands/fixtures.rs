@@ -0,0 +1,288 @@
+//! Generating fixtures.
+//!
+//! The [`generate`] function implements the hidden `generate-fixtures` subcommand, which fills
+//! fragments directories with synthesized fragments, for instance, to benchmark collecting
+//! and rendering, or to reproduce performance problems without sharing actual fragments.
+//!
+//! Fragments are synthesized deterministically from the given seed, so that the same seed
+//! and count always produce the same fragments. Their types are picked from `order`,
+//! and some of them are given authors in front matter.
+
+use std::{
+    borrow::Cow,
+    fs::create_dir_all,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+use tracing::info;
+
+use crate::{
+    commands::create::EXTENSION,
+    config::Config,
+    exit::Code,
+    fragment::{Fragment, Id, Integer, Partial, SaveError, AUTHOR},
+};
+
+/// Represents errors that can occur when creating directories.
+#[derive(Debug, Error, Diagnostic)]
+#[error("creating directory failed")]
+#[diagnostic(
+    code(changelogging::commands::fixtures::create_directory),
+    help("make sure the target directory is accessible")
+)]
+pub struct CreateDirectoryError(#[from] pub std::io::Error);
+
+/// Represents errors that can occur when there are no types to generate fragments of.
+#[derive(Debug, Error, Diagnostic)]
+#[error("`order` is empty")]
+#[diagnostic(
+    code(changelogging::commands::fixtures::no_types),
+    help("include at least one type in `order`")
+)]
+pub struct NoTypesError;
+
+/// Represents sources of errors that can occur when generating fixtures.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Create directory errors.
+    CreateDirectory(#[from] CreateDirectoryError),
+    /// No types errors.
+    NoTypes(#[from] NoTypesError),
+    /// Save errors.
+    Save(#[from] SaveError),
+}
+
+/// Represents errors that can occur when generating fixtures.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to generate fixtures into `{path}`")]
+#[diagnostic(
+    code(changelogging::commands::fixtures),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+    /// The path provided.
+    pub path: PathBuf,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+
+    /// Constructs [`Self`] from [`CreateDirectoryError`].
+    pub fn create_directory(error: CreateDirectoryError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`NoTypesError`].
+    pub fn no_types(error: NoTypesError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`SaveError`].
+    pub fn save(error: SaveError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`CreateDirectoryError`] and constructs [`Self`] from it.
+    pub fn new_create_directory(error: std::io::Error, path: PathBuf) -> Self {
+        Self::create_directory(CreateDirectoryError(error), path)
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::CreateDirectory(_) | ErrorSource::Save(_) => Code::Io,
+            ErrorSource::NoTypes(_) => Code::Config,
+        }
+    }
+}
+
+/// The default number of fragments to generate.
+pub const DEFAULT_COUNT: usize = 1000;
+
+/// The default seed to generate fragments from.
+pub const DEFAULT_SEED: u64 = 0;
+
+/// The words that synthesized sentences are made of.
+pub const WORDS: [&str; 24] = [
+    "added",
+    "builder",
+    "cache",
+    "changed",
+    "changelog",
+    "command",
+    "configuration",
+    "entries",
+    "fixed",
+    "fragments",
+    "handling",
+    "improved",
+    "options",
+    "output",
+    "parsing",
+    "performance",
+    "removed",
+    "rendering",
+    "sections",
+    "support",
+    "templates",
+    "types",
+    "updated",
+    "when",
+];
+
+/// The number of authors that fragments are attributed to.
+pub const AUTHORS: u64 = 16;
+
+// one in this many fragments is attributed to the author
+
+const ATTRIBUTED: u64 = 4;
+
+// one in this many fragments spans multiple paragraphs
+
+const PARAGRAPHS: u64 = 8;
+
+const MIN_WORDS: u64 = 4;
+const MAX_WORDS: u64 = 16;
+
+/// Represents deterministic generators of pseudo-random numbers (SplitMix64).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    /// Constructs [`Self`] from the given seed.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Returns the next number.
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut value = self.state;
+
+        value = (value ^ (value >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+
+        value ^ (value >> 31)
+    }
+
+    /// Returns the next number below the given bound, which must be positive.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+
+    /// Returns the next number in the given inclusive range.
+    pub fn between(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low + 1)
+    }
+
+    /// Picks the next item of the given non-empty slice.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        // slices can not be longer than `u64::MAX`, so the index fits into `usize`
+
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
+
+/// Synthesizes the sentence from [`WORDS`].
+pub fn sentence(random: &mut Random) -> String {
+    let count = random.between(MIN_WORDS, MAX_WORDS);
+
+    let mut sentence = (0..count)
+        .map(|_| *random.pick(&WORDS))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    if let Some(first) = sentence.get_mut(..1) {
+        first.make_ascii_uppercase();
+    }
+
+    sentence.push('.');
+
+    sentence
+}
+
+/// Synthesizes the fragment with the given ID, picking its type from the given types.
+pub fn fragment(random: &mut Random, id: Integer, types: &[Cow<'_, str>]) -> Fragment<'static> {
+    let type_name = random.pick(types).clone().into_owned();
+
+    let mut content = sentence(random);
+
+    if random.below(PARAGRAPHS) == 0 {
+        content.push_str("\n\n");
+        content.push_str(&sentence(random));
+    }
+
+    let partial = Partial::new(Id::integer(id), Cow::Owned(type_name))
+        .with_extension(Some(Cow::Borrowed(EXTENSION)));
+
+    let mut fragment = Fragment::owned(partial, content);
+
+    if random.below(ATTRIBUTED) == 0 {
+        let author = format!("author-{}", random.below(AUTHORS));
+
+        fragment.meta.insert(AUTHOR.to_owned(), author.into());
+    }
+
+    fragment
+}
+
+/// Generates the given number of fragments from the given seed, writing them into the given
+/// directory (created if needed). IDs start from one, and types are picked from `order`.
+///
+/// Returns the paths to the generated fragments.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if `order` is empty, creating the directory fails or any
+/// of the fragments can not be saved (including when it already exists).
+pub fn generate<P: AsRef<Path>>(
+    config: &Config<'_>,
+    directory: P,
+    count: usize,
+    seed: u64,
+) -> Result<Vec<PathBuf>, Error> {
+    let directory = directory.as_ref();
+
+    let types = config.order.as_slice();
+
+    if types.is_empty() {
+        return Err(Error::no_types(NoTypesError, directory.to_owned()));
+    }
+
+    create_dir_all(directory)
+        .map_err(|error| Error::new_create_directory(error, directory.to_owned()))?;
+
+    let mut random = Random::new(seed);
+
+    let mut paths = Vec::with_capacity(count);
+
+    for id in (1..).take(count) {
+        let path = fragment(&mut random, id, types)
+            .save(directory)
+            .map_err(|error| Error::save(error, directory.to_owned()))?;
+
+        paths.push(path);
+    }
+
+    info!(
+        "generated {} fragment(s) into `{}`",
+        paths.len(),
+        directory.display()
+    );
+
+    Ok(paths)
+}
@@ -0,0 +1,303 @@
+//! Importing merged pull requests as fragments.
+//!
+//! The [`import`] function implements the `import-prs` subcommand.
+
+use std::{borrow::Cow, collections::HashSet, fs::read_dir, iter::once, path::Path};
+
+use miette::Diagnostic;
+use thiserror::Error;
+use time::OffsetDateTime;
+use tracing::{debug, info};
+
+use crate::{
+    commands::create::EXTENSION,
+    config::Git,
+    date,
+    exit::Code,
+    forge::{format_time, Client, PullRequest},
+    fragment::{Fragment, Id, Integer, Partial, SaveError},
+    git,
+    network::OfflineWarning,
+    report,
+    workspace::Workspace,
+};
+
+/// Represents errors that occur when no tags are found and `--since` is not provided.
+#[derive(Debug, Error, Diagnostic)]
+#[error("no tags found")]
+#[diagnostic(
+    code(changelogging::commands::import::no_tag),
+    help("pass the tag or the date to import pull requests since via `--since`")
+)]
+pub struct NoTagError;
+
+/// Represents errors that occur when `--since` is neither the date nor the revision.
+#[derive(Debug, Error, Diagnostic)]
+#[error("`{since}` is neither the date nor the revision")]
+#[diagnostic(
+    code(changelogging::commands::import::since),
+    help("pass either the tag (for instance, `v1.4.0`) or the date in `YYYY-MM-DD` format")
+)]
+pub struct SinceError {
+    /// The value provided.
+    pub since: String,
+}
+
+impl SinceError {
+    /// Constructs [`Self`].
+    pub fn new(since: String) -> Self {
+        Self { since }
+    }
+}
+
+/// Represents sources of errors that can occur during importing.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// No tag errors.
+    NoTag(#[from] NoTagError),
+    /// Since errors.
+    Since(#[from] SinceError),
+    /// `git` errors.
+    Git(#[from] crate::git::Error),
+    /// Forge errors.
+    Forge(#[from] crate::forge::Error),
+    /// Save errors.
+    Save(#[from] SaveError),
+}
+
+/// Represents errors that can occur during importing.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to import pull requests")]
+#[diagnostic(
+    code(changelogging::commands::import),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`NoTagError`].
+    pub fn no_tag(error: NoTagError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`SinceError`].
+    pub fn since(error: SinceError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::git::Error
+    pub fn git(error: crate::git::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::forge::Error
+    pub fn forge(error: crate::forge::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`SaveError`].
+    pub fn save(error: SaveError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`SinceError`] and constructs [`Self`] from it.
+    pub fn new_since(since: String) -> Self {
+        Self::since(SinceError::new(since))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::NoTag(_) | ErrorSource::Since(_) => Code::Usage,
+            ErrorSource::Git(_) => Code::Git,
+            ErrorSource::Forge(error) => error.exit_code(),
+            ErrorSource::Save(_) => Code::Io,
+        }
+    }
+}
+
+/// Resolves the time to import pull requests since.
+///
+/// The `since` value is either the date in `YYYY-MM-DD` format (meaning its midnight in UTC)
+/// or the revision, such as the tag (meaning its committer time). If it is not provided,
+/// the last tag is used.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if there are no tags, `since` is invalid or `git` fails.
+pub fn resolve_since(git: &Git<'_>, since: Option<&str>) -> Result<OffsetDateTime, Error> {
+    let since = match since {
+        Some(since) => since.to_owned(),
+        None => git::last_tag(git)
+            .map_err(Error::git)?
+            .ok_or_else(|| Error::no_tag(NoTagError))?,
+    };
+
+    if let Ok(date) = date::parse(&since) {
+        return Ok(date.midnight().assume_utc());
+    }
+
+    let timestamp = git::timestamp(git, &since)
+        .map_err(Error::git)?
+        .ok_or_else(|| Error::new_since(since.clone()))?;
+
+    OffsetDateTime::from_unix_timestamp(timestamp).map_err(|_| Error::new_since(since))
+}
+
+/// Returns integer IDs of fragments in the given directory.
+///
+/// Fragments that can not be read are ignored, as creating them is going to fail anyway.
+pub fn existing_ids<D: AsRef<Path>>(directory: D) -> HashSet<Integer> {
+    let Ok(entries) = read_dir(directory) else {
+        return HashSet::new();
+    };
+
+    entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter_map(|name| name.parse::<Partial<'_>>().ok())
+        .filter_map(|partial| match partial.id {
+            Id::Integer(id) => Some(id),
+            Id::String(_) => None,
+        })
+        .collect()
+}
+
+/// Represents reasons to skip pull requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Skip {
+    /// The fragment with the same ID already exists.
+    Exists,
+    /// The type can not be inferred from labels.
+    NoType,
+    /// The number does not fit into fragment IDs.
+    TooLarge,
+}
+
+impl Skip {
+    /// Returns the string representation of [`Self`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Exists => "fragment already exists",
+            Self::NoType => "no labels mapped to types",
+            Self::TooLarge => "number is too large",
+        }
+    }
+}
+
+/// Returns the fragment for the given pull request, or the reason to skip it.
+pub fn fragment(
+    workspace: &Workspace<'_>,
+    pull_request: &PullRequest,
+    existing: &HashSet<Integer>,
+) -> Result<Fragment<'static>, Skip> {
+    let config = &workspace.config;
+
+    let number = Integer::try_from(pull_request.number).map_err(|_| Skip::TooLarge)?;
+
+    if existing.contains(&number) {
+        return Err(Skip::Exists);
+    }
+
+    let type_name = config
+        .forge
+        .type_for(&pull_request.labels, &config.order)
+        .ok_or(Skip::NoType)?;
+
+    let partial = Partial::new(Id::integer(number), Cow::Owned(type_name.to_owned()))
+        .with_extension(Some(Cow::Borrowed(EXTENSION)));
+
+    Ok(Fragment::owned(partial, pull_request.title.clone()))
+}
+
+/// Imports pull requests merged since the given tag or date as fragments.
+///
+/// Each pull request gets its fragment, with its number as the ID, its title as the content
+/// and its type inferred from labels (see `forge.labels`). Pull requests that already have
+/// fragments or labels of which are not mapped are skipped and reported.
+///
+/// If `add` is set, created fragments are added via `git`.
+///
+/// If `offline` is set, nothing is imported and [`OfflineWarning`] is reported instead.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if resolving `since` fails, requesting the forge API fails
+/// or creating fragments fails.
+pub fn import(
+    workspace: &Workspace<'_>,
+    since: Option<&str>,
+    add: bool,
+    offline: bool,
+) -> Result<(), Error> {
+    let client = Client::from_workspace(workspace, offline).map_err(Error::forge)?;
+
+    if client.is_offline() {
+        report::warn(OfflineWarning::new("importing pull requests"));
+
+        return Ok(());
+    }
+
+    let since = resolve_since(&workspace.config.git, since)?;
+
+    info!(
+        "importing pull requests merged since {}",
+        format_time(since)
+    );
+
+    let pull_requests = client.merged_pull_requests(since).map_err(Error::forge)?;
+
+    debug!("found {} merged pull request(s)", pull_requests.len());
+
+    let directory = workspace.config.paths.directory.as_ref();
+
+    let existing = existing_ids(directory);
+
+    let mut created = 0;
+    let mut skipped = 0;
+
+    for pull_request in pull_requests {
+        let number = pull_request.number;
+
+        match fragment(workspace, &pull_request, &existing) {
+            Ok(fragment) => {
+                let path = fragment.save(directory).map_err(Error::save)?;
+
+                if add {
+                    git::add(&workspace.config.git, once(&path))
+                        .and_then(git::success)
+                        .map_err(Error::git)?;
+                }
+
+                println!("created {}", path.display());
+
+                created += 1;
+            }
+            Err(skip) => {
+                println!("skipped #{number}: {}", skip.as_str());
+
+                skipped += 1;
+            }
+        }
+    }
+
+    info!("created {created} fragment(s), skipped {skipped} pull request(s)");
+
+    Ok(())
+}
@@ -0,0 +1,197 @@
+//! Listing fragments.
+//!
+//! The [`list`] function implements the `list` subcommand, which lists pending fragments
+//! along with their metadata, either as text or as JSON for external tools (for instance,
+//! dashboards and bots reminding about stale fragments).
+
+use std::{fs::metadata, path::PathBuf};
+
+use clap::ValueEnum;
+use miette::Diagnostic;
+use serde::Serialize;
+use thiserror::Error;
+use time::OffsetDateTime;
+use tracing::debug;
+
+use crate::{
+    builder::{Builder, CollectError, InitError},
+    date::today,
+    exit::Code,
+    fragment::{FragmentFile, Id},
+    workspace::Workspace,
+};
+
+/// Represents errors that can occur when serializing fragments.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to serialize fragments")]
+#[diagnostic(
+    code(changelogging::commands::list::serialize),
+    help("this is likely a bug, please report it")
+)]
+pub struct SerializeError(#[from] pub serde_json::Error);
+
+/// Represents sources of errors that can occur when listing.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Initialization errors.
+    Init(#[from] InitError),
+    /// Collect errors.
+    Collect(#[from] CollectError),
+    /// Serialize errors.
+    Serialize(#[from] SerializeError),
+}
+
+/// Represents errors that can occur when listing.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to list fragments")]
+#[diagnostic(
+    code(changelogging::commands::list),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`CollectError`].
+    pub fn collect(error: CollectError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`SerializeError`].
+    pub fn serialize(error: SerializeError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`SerializeError`] and constructs [`Self`] from it.
+    pub fn new_serialize(error: serde_json::Error) -> Self {
+        Self::serialize(SerializeError(error))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Collect(error) => error.exit_code(),
+            ErrorSource::Serialize(_) => Code::Failure,
+        }
+    }
+}
+
+/// Represents output formats of listed fragments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, ValueEnum)]
+pub enum Format {
+    /// One `{path}: {first line}` line per fragment.
+    #[default]
+    Text,
+    /// The JSON array of fragments.
+    Json,
+}
+
+/// Represents listed fragments.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Listed {
+    /// The ID of the fragment.
+    pub id: Id<'static>,
+    /// The type of the fragment.
+    #[serde(rename = "type")]
+    pub type_name: String,
+    /// The path to the fragment.
+    pub path: PathBuf,
+    /// The last modification time of the fragment, if available.
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub modified: Option<OffsetDateTime>,
+    /// The author of the fragment, specified as `author` in front matter, if any.
+    pub author: Option<String>,
+    /// The first line of the fragment content.
+    pub first_line: String,
+}
+
+impl Listed {
+    /// Constructs [`Self`] from the given fragment file, returning [`None`]
+    /// if the fragment can not be parsed.
+    pub fn from_file(file: &FragmentFile) -> Option<Self> {
+        let fragment = match file.fragment() {
+            Ok(fragment) => fragment,
+            Err(error) => {
+                debug!("skipped `{}`: {}", file.path.display(), error.source);
+
+                return None;
+            }
+        };
+
+        let modified = metadata(&file.path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .map(OffsetDateTime::from);
+
+        let first_line = fragment
+            .content
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or_default()
+            .trim()
+            .to_owned();
+
+        Some(Self {
+            id: fragment.partial.id.clone().into_owned(),
+            type_name: fragment.partial.type_name.clone().into_owned(),
+            path: file.path.clone(),
+            modified,
+            author: fragment.author().map(str::to_owned),
+            first_line,
+        })
+    }
+}
+
+const SEPARATOR: &str = ": ";
+
+/// Lists fragments in the given format, sorted by their file names.
+///
+/// Files that can not be parsed into fragments are skipped (see `check` for finding them).
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if initializing the builder, collecting fragments
+/// or serializing them fails.
+pub fn list(workspace: Workspace<'_>, format: Format) -> Result<(), Error> {
+    let builder = Builder::from_workspace(workspace, today()).map_err(Error::init)?;
+
+    let files = builder.read_files().map_err(Error::collect)?;
+
+    let listed: Vec<_> = files.iter().filter_map(Listed::from_file).collect();
+
+    match format {
+        Format::Text => {
+            for fragment in &listed {
+                println!(
+                    "{}{SEPARATOR}{}",
+                    fragment.path.display(),
+                    fragment.first_line
+                );
+            }
+        }
+        Format::Json => {
+            let string = serde_json::to_string_pretty(&listed).map_err(Error::new_serialize)?;
+
+            println!("{string}");
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,168 @@
+//! Generating manual pages.
+//!
+//! The [`man`] function implements the hidden `man` subcommand.
+
+use std::{
+    fs::{create_dir_all, write},
+    path::{Path, PathBuf},
+};
+
+use clap::Command;
+use clap_mangen::Man;
+use miette::Diagnostic;
+use thiserror::Error;
+use tracing::info;
+
+use crate::exit::Code;
+
+/// Represents errors that can occur when creating directories.
+#[derive(Debug, Error, Diagnostic)]
+#[error("creating directory failed")]
+#[diagnostic(
+    code(changelogging::commands::man::create_directory),
+    help("make sure the target directory is accessible")
+)]
+pub struct CreateDirectoryError(#[from] pub std::io::Error);
+
+/// Represents errors that can occur when rendering manual pages.
+#[derive(Debug, Error, Diagnostic)]
+#[error("rendering failed")]
+#[diagnostic(
+    code(changelogging::commands::man::render),
+    help("this is likely a bug, please report it")
+)]
+pub struct RenderError(#[from] pub std::io::Error);
+
+/// Represents errors that can occur when writing manual pages.
+#[derive(Debug, Error, Diagnostic)]
+#[error("writing failed")]
+#[diagnostic(
+    code(changelogging::commands::man::write),
+    help("make sure the target directory is accessible")
+)]
+pub struct WriteError(#[from] pub std::io::Error);
+
+/// Represents sources of errors that can occur when generating manual pages.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Create directory errors.
+    CreateDirectory(#[from] CreateDirectoryError),
+    /// Render errors.
+    Render(#[from] RenderError),
+    /// Write errors.
+    Write(#[from] WriteError),
+}
+
+/// Represents errors that can occur when generating manual pages.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to generate manual pages into `{path}`")]
+#[diagnostic(
+    code(changelogging::commands::man),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+    /// The path provided.
+    pub path: PathBuf,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+
+    /// Constructs [`Self`] from [`CreateDirectoryError`].
+    pub fn create_directory(error: CreateDirectoryError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`RenderError`].
+    pub fn render(error: RenderError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`WriteError`].
+    pub fn write(error: WriteError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`CreateDirectoryError`] and constructs [`Self`] from it.
+    pub fn new_create_directory(error: std::io::Error, path: PathBuf) -> Self {
+        Self::create_directory(CreateDirectoryError(error), path)
+    }
+
+    /// Constructs [`RenderError`] and constructs [`Self`] from it.
+    pub fn new_render(error: std::io::Error, path: PathBuf) -> Self {
+        Self::render(RenderError(error), path)
+    }
+
+    /// Constructs [`WriteError`] and constructs [`Self`] from it.
+    pub fn new_write(error: std::io::Error, path: PathBuf) -> Self {
+        Self::write(WriteError(error), path)
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        Code::Io
+    }
+}
+
+/// The section of manual pages for user commands.
+pub const SECTION: &str = "1";
+
+/// The name of the built-in `help` subcommand, which is skipped.
+pub const HELP: &str = "help";
+
+fn render(command: &Command, directory: &Path) -> Result<(), Error> {
+    // subcommands of built commands are displayed like `changelogging-build`
+
+    let name = command
+        .get_display_name()
+        .unwrap_or_else(|| command.get_name());
+
+    let path = directory.join(format!("{name}.{SECTION}"));
+
+    let mut buffer = Vec::new();
+
+    Man::new(command.clone())
+        .section(SECTION)
+        .render(&mut buffer)
+        .map_err(|error| Error::new_render(error, path.clone()))?;
+
+    write(&path, buffer).map_err(|error| Error::new_write(error, path.clone()))?;
+
+    info!("generated `{}`", path.display());
+
+    for subcommand in command.get_subcommands() {
+        if subcommand.is_hide_set() || subcommand.get_name() == HELP {
+            continue;
+        }
+
+        render(subcommand, directory)?;
+    }
+
+    Ok(())
+}
+
+/// Generates manual pages for the given command and each of its subcommands,
+/// writing them into the given directory.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if creating the directory, rendering or writing pages fails.
+pub fn man<P: AsRef<Path>>(mut command: Command, directory: P) -> Result<(), Error> {
+    let directory = directory.as_ref();
+
+    create_dir_all(directory)
+        .map_err(|error| Error::new_create_directory(error, directory.to_owned()))?;
+
+    command.build();
+
+    render(&command, directory)
+}
@@ -0,0 +1,432 @@
+//! Migrating changelogs to fragments.
+//!
+//! The [`split`] function implements the `migrate split` subcommand, which recreates fragments
+//! from bullets of the existing changelog entry, for instance, to re-open the release cycle
+//! and rebuild its entry with `changelogging`.
+//!
+//! Types are detected by section titles (see [`type_for`]) on the best-effort basis,
+//! and IDs are taken from references (like `(#13)` or `([#13](...))`) ending bullets,
+//! falling back to string IDs derived from versions. Sections of unknown types are skipped,
+//! reporting [`UnknownSectionWarning`].
+
+use std::{
+    borrow::Cow,
+    collections::HashSet,
+    fs::create_dir_all,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+use tracing::{debug, info};
+
+use crate::{
+    builder::{Builder, InitError, WriteError},
+    changelog::{self, find},
+    commands::create::EXTENSION,
+    config::Config,
+    date::today,
+    exit::Code,
+    fragment::{Fragment, Id, Integer, Partial, SaveError},
+    report,
+    version::slug,
+    workspace::Workspace,
+};
+
+/// Represents errors that occur when the entry of the given version is not found.
+#[derive(Debug, Error, Diagnostic)]
+#[error("entry of version `{version}` not found")]
+#[diagnostic(
+    code(changelogging::commands::migrate::entry_not_found),
+    help("make sure the changelog contains the entry of this version")
+)]
+pub struct EntryNotFoundError {
+    /// The version provided.
+    pub version: String,
+}
+
+impl EntryNotFoundError {
+    /// Constructs [`Self`].
+    pub fn new(version: String) -> Self {
+        Self { version }
+    }
+}
+
+/// Represents errors that can occur when creating directories.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to create `{path}`")]
+#[diagnostic(
+    code(changelogging::commands::migrate::create_directory),
+    help("check whether the directory is accessible")
+)]
+pub struct CreateDirectoryError {
+    /// The underlying I/O error.
+    #[source]
+    pub source: std::io::Error,
+    /// The path to the directory.
+    pub path: PathBuf,
+}
+
+impl CreateDirectoryError {
+    /// Constructs [`Self`].
+    pub fn new(source: std::io::Error, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+}
+
+/// Represents warnings emitted when section titles do not match any types.
+#[derive(Debug, Error, Diagnostic)]
+#[error("section `{title}` does not match any type")]
+#[diagnostic(
+    code(changelogging::commands::migrate::unknown_section),
+    severity(Warning),
+    help("create fragments of this section manually, or rename it after one of the types")
+)]
+pub struct UnknownSectionWarning {
+    /// The title of the section.
+    pub title: String,
+}
+
+impl UnknownSectionWarning {
+    /// Constructs [`Self`].
+    pub fn new(title: String) -> Self {
+        Self { title }
+    }
+}
+
+/// Represents sources of errors that can occur when migrating.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Initialization errors.
+    Init(#[from] InitError),
+    /// Read and write errors.
+    Write(#[from] WriteError),
+    /// Entry not found errors.
+    EntryNotFound(#[from] EntryNotFoundError),
+    /// Create directory errors.
+    CreateDirectory(#[from] CreateDirectoryError),
+    /// Save errors.
+    Save(#[from] SaveError),
+}
+
+/// Represents errors that can occur when migrating.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to migrate")]
+#[diagnostic(
+    code(changelogging::commands::migrate),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`WriteError`].
+    pub fn write(error: WriteError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`EntryNotFoundError`].
+    pub fn entry_not_found(error: EntryNotFoundError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`CreateDirectoryError`].
+    pub fn create_directory(error: CreateDirectoryError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`SaveError`].
+    pub fn save(error: SaveError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`EntryNotFoundError`] and constructs [`Self`] from it.
+    pub fn new_entry_not_found(version: String) -> Self {
+        Self::entry_not_found(EntryNotFoundError::new(version))
+    }
+
+    /// Constructs [`CreateDirectoryError`] and constructs [`Self`] from it.
+    pub fn new_create_directory(error: std::io::Error, path: PathBuf) -> Self {
+        Self::create_directory(CreateDirectoryError::new(error, path))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Write(error) => error.exit_code(),
+            ErrorSource::EntryNotFound(_) => Code::Usage,
+            ErrorSource::CreateDirectory(_) | ErrorSource::Save(_) => Code::Io,
+        }
+    }
+}
+
+/// Finds the type of the section with the given title, comparing it (case-insensitively)
+/// with headings, titles and names of types, in this order.
+///
+/// Types included in `order` take precedence over the other ones.
+pub fn type_for(config: &Config<'_>, title: &str) -> Option<String> {
+    let types = config.types_with_defaults();
+
+    let mut names: Vec<_> = types.keys().collect();
+
+    // sort the names included in `order` first, keeping their order
+
+    let position = |name: &str| {
+        config
+            .order
+            .iter()
+            .position(|other| other == name)
+            .unwrap_or(usize::MAX)
+    };
+
+    names.sort_by(|this, that| {
+        position(this)
+            .cmp(&position(that))
+            .then_with(|| this.cmp(that))
+    });
+
+    let title = title.trim();
+
+    names
+        .into_iter()
+        .find(|name| {
+            let type_ = &types[*name];
+
+            [
+                type_.heading().as_ref(),
+                type_.title.as_ref(),
+                name.as_ref(),
+            ]
+            .into_iter()
+            .any(|candidate| candidate.eq_ignore_ascii_case(title))
+        })
+        .map(|name| name.clone().into_owned())
+}
+
+const SPACE: char = ' ';
+const PARAGRAPH_SEPARATOR: &str = "\n\n";
+
+/// Collects items (starting with the given `bullet` character) from the given string.
+///
+/// Unlike [`bullets`], paragraphs of items (that is, indented lines following blank ones)
+/// are kept, separated by blank lines, while lines within paragraphs are joined.
+///
+/// [`bullets`]: changelog::bullets
+pub fn items(string: &str, bullet: char) -> Vec<String> {
+    let mut items = Vec::new();
+
+    let mut current: Option<Vec<String>> = None;
+
+    let mut blank = false;
+
+    for line in string.lines() {
+        let item = line
+            .strip_prefix(bullet)
+            .and_then(|rest| rest.strip_prefix(SPACE));
+
+        if let Some(rest) = item {
+            items.extend(
+                current
+                    .take()
+                    .map(|paragraphs| paragraphs.join(PARAGRAPH_SEPARATOR)),
+            );
+
+            current = Some(vec![rest.trim().to_owned()]);
+        } else if line.trim().is_empty() {
+            blank = true;
+
+            continue;
+        } else if line.starts_with(char::is_whitespace) {
+            if let Some(paragraphs) = current.as_mut() {
+                if blank {
+                    paragraphs.push(String::new());
+                }
+
+                if let Some(paragraph) = paragraphs.last_mut() {
+                    if !paragraph.is_empty() {
+                        paragraph.push(SPACE);
+                    }
+
+                    paragraph.push_str(line.trim());
+                }
+            }
+        } else {
+            items.extend(
+                current
+                    .take()
+                    .map(|paragraphs| paragraphs.join(PARAGRAPH_SEPARATOR)),
+            );
+        }
+
+        blank = false;
+    }
+
+    items.extend(current.map(|paragraphs| paragraphs.join(PARAGRAPH_SEPARATOR)));
+
+    items
+}
+
+const REFERENCE_OPEN: &str = " (";
+const REFERENCE_CLOSE: char = ')';
+const LINK_OPEN: char = '[';
+const NUMBER_SIGN: char = '#';
+
+/// Splits the given bullet into the integer ID referenced at its end, if any, and the content.
+///
+/// References are parenthesized and start with either `#` or `[#`, for instance, `(#13)`
+/// or `([#13](https://github.com/nekitdev/changelogging/pull/13))`.
+pub fn split_reference(bullet: &str) -> (Option<Integer>, &str) {
+    let found = bullet
+        .strip_suffix(REFERENCE_CLOSE)
+        .and_then(|rest| rest.rfind(REFERENCE_OPEN).map(|index| (index, rest)))
+        .and_then(|(index, rest)| {
+            let inner = &rest[index + REFERENCE_OPEN.len()..];
+
+            let inner = inner.strip_prefix(LINK_OPEN).unwrap_or(inner);
+
+            let digits: String = inner
+                .strip_prefix(NUMBER_SIGN)?
+                .chars()
+                .take_while(char::is_ascii_digit)
+                .collect();
+
+            digits.parse().ok().map(|id| (id, index))
+        });
+
+    match found {
+        Some((id, index)) => (Some(id), bullet[..index].trim_end()),
+        None => (None, bullet),
+    }
+}
+
+/// Recreates fragments from the given entry body, returning them along with the titles
+/// of sections whose types are not found.
+///
+/// Fragments referencing IDs already taken by fragments of the same type are given
+/// string IDs derived from the given version instead.
+pub fn fragments(
+    builder: &Builder<'_>,
+    body: &str,
+    version: &str,
+) -> (Vec<Fragment<'static>>, Vec<String>) {
+    let config = &builder.config;
+
+    let heading = builder.section_heading();
+
+    let bullet = config.indents.bullet;
+
+    let prefix = slug(version);
+
+    let mut fragments = Vec::new();
+
+    let mut unknown = Vec::new();
+
+    let mut taken = HashSet::new();
+
+    let mut count = 0;
+
+    for section in changelog::entries(body, &heading) {
+        if section.title == config.contributors.title {
+            debug!("skipped the `{}` section", section.title);
+
+            continue;
+        }
+
+        let Some(type_name) = type_for(config, section.title) else {
+            unknown.push(section.title.to_owned());
+
+            continue;
+        };
+
+        for text in items(section.body, bullet) {
+            let (integer, content) = split_reference(&text);
+
+            let id = match integer {
+                Some(integer) if taken.insert((integer, type_name.clone())) => Id::integer(integer),
+                _ => {
+                    count += 1;
+
+                    Id::owned(format!("{prefix}-{count}"))
+                }
+            };
+
+            let partial = Partial::new(id, Cow::Owned(type_name.clone()))
+                .with_extension(Some(Cow::Borrowed(EXTENSION)));
+
+            fragments.push(Fragment::owned(partial, content.to_owned()));
+        }
+    }
+
+    (fragments, unknown)
+}
+
+/// Splits the entry of the given version into fragments, saving them into the fragments
+/// directory (created if needed). Existing fragments are never overwritten.
+///
+/// If `remove` is set, the entry is removed from the changelog, so that it can be rebuilt.
+///
+/// Returns the paths to the fragments created.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if initializing the builder, reading or writing the changelog,
+/// creating the fragments directory or saving fragments fails, as well as if the entry
+/// of the given version is not found.
+pub fn split(workspace: Workspace<'_>, version: &str, remove: bool) -> Result<Vec<PathBuf>, Error> {
+    let builder = Builder::from_workspace(workspace, today()).map_err(Error::init)?;
+
+    let contents = builder.read_changelog().map_err(Error::write)?;
+
+    let entry = find(&contents.string, &builder.entry_heading(), version)
+        .ok_or_else(|| Error::new_entry_not_found(version.to_owned()))?;
+
+    let (fragments, unknown) = self::fragments(&builder, entry.body, version);
+
+    for title in unknown {
+        report::warn(UnknownSectionWarning::new(title));
+    }
+
+    let directory: &Path = builder.config.paths.directory.as_ref();
+
+    create_dir_all(directory)
+        .map_err(|error| Error::new_create_directory(error, directory.to_owned()))?;
+
+    let mut paths = Vec::new();
+
+    for fragment in fragments {
+        let path = fragment.save(directory).map_err(Error::save)?;
+
+        info!("created `{}`", path.display());
+
+        paths.push(path);
+    }
+
+    if remove {
+        info!("removing the entry `{}`", entry.title);
+
+        let string = changelog::remove(&contents.string, &[entry]);
+
+        builder
+            .write_changelog(&string, contents.line_ending)
+            .map_err(Error::write)?;
+    }
+
+    Ok(paths)
+}
@@ -1,5 +1,22 @@
 //! `changelogging` subcommands.
 
 pub mod build;
+pub mod cache;
+pub mod check;
+pub mod config;
 pub mod create;
+pub mod doctor;
+pub mod fixtures;
+#[cfg(feature = "forge")]
+pub mod import;
+pub mod list;
+pub mod man;
+pub mod migrate;
+pub mod normalize;
+pub mod open;
 pub mod preview;
+pub mod render;
+pub mod resolve;
+pub mod rollback;
+pub mod variables;
+pub mod verify;
@@ -0,0 +1,410 @@
+//! Normalizing fragments.
+//!
+//! The [`normalize`] function implements the `normalize` subcommand, which rewrites fragment files
+//! into their canonical form: byte order marks are stripped, line endings are normalized,
+//! contents are trimmed (and end with exactly one new line), zeros are stripped from IDs
+//! and the extension is enforced.
+//!
+//! Front matter and priority markers are kept as they are, so that normalized fragments
+//! are parsed into exactly the same fragments as before.
+//!
+//! Files that are not fragments (or can not be read) are left intact, see `check` for that.
+
+use std::{
+    collections::HashSet,
+    fs::{read_to_string, remove_file, rename, write},
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+use tracing::{debug, info};
+
+use crate::{
+    builder::{Builder, CollectError, InitError},
+    commands::create::EXTENSION,
+    date::today,
+    exit::Code,
+    fragment::{path_name, split_front_matter, InvalidExtensionError, Partial, FRONT_MATTER},
+    text::{normalize as normalize_line_endings, strip_bom},
+    workspace::Workspace,
+};
+
+/// Represents errors that occur when normalized names are taken by other files.
+#[derive(Debug, Error, Diagnostic)]
+#[error("can not rename `{path}` to `{target}` as it already exists")]
+#[diagnostic(
+    code(changelogging::commands::normalize::exists),
+    help("remove or rename either of the fragments")
+)]
+pub struct ExistsError {
+    /// The path to the fragment.
+    pub path: PathBuf,
+    /// The normalized path to the fragment.
+    pub target: PathBuf,
+}
+
+impl ExistsError {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, target: PathBuf) -> Self {
+        Self { path, target }
+    }
+}
+
+/// Represents errors that occur when writing normalized fragments fails.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to write `{path}`")]
+#[diagnostic(
+    code(changelogging::commands::normalize::write),
+    help("make sure the fragments directory is accessible")
+)]
+pub struct WriteError {
+    /// The underlying I/O error.
+    pub source: std::io::Error,
+    /// The path to the fragment.
+    pub path: PathBuf,
+}
+
+impl WriteError {
+    /// Constructs [`Self`].
+    pub fn new(source: std::io::Error, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+}
+
+/// Represents errors that occur when checking finds fragments that are not normalized.
+#[derive(Debug, Error, Diagnostic)]
+#[error("{count} fragment(s) are not normalized")]
+#[diagnostic(
+    code(changelogging::commands::normalize::unnormalized),
+    help("run `changelogging normalize` to normalize them")
+)]
+pub struct UnnormalizedError {
+    /// The number of fragments that are not normalized.
+    pub count: usize,
+}
+
+impl UnnormalizedError {
+    /// Constructs [`Self`].
+    pub fn new(count: usize) -> Self {
+        Self { count }
+    }
+}
+
+/// Represents sources of errors that can occur when normalizing.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Invalid extension errors.
+    InvalidExtension(#[from] InvalidExtensionError),
+    /// Initialization errors.
+    Init(#[from] InitError),
+    /// Collect errors.
+    Collect(#[from] CollectError),
+    /// Exists errors.
+    Exists(#[from] ExistsError),
+    /// Write errors.
+    Write(#[from] WriteError),
+    /// Unnormalized errors.
+    Unnormalized(#[from] UnnormalizedError),
+}
+
+/// Represents errors that can occur when normalizing.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to normalize")]
+#[diagnostic(
+    code(changelogging::commands::normalize),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`InvalidExtensionError`].
+    pub fn invalid_extension(error: InvalidExtensionError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`CollectError`].
+    pub fn collect(error: CollectError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`ExistsError`].
+    pub fn exists(error: ExistsError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`WriteError`].
+    pub fn write(error: WriteError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`UnnormalizedError`].
+    pub fn unnormalized(error: UnnormalizedError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`InvalidExtensionError`] and constructs [`Self`] from it.
+    pub fn new_invalid_extension(extension: String) -> Self {
+        Self::invalid_extension(InvalidExtensionError::new(extension))
+    }
+
+    /// Constructs [`ExistsError`] and constructs [`Self`] from it.
+    pub fn new_exists(path: PathBuf, target: PathBuf) -> Self {
+        Self::exists(ExistsError::new(path, target))
+    }
+
+    /// Constructs [`WriteError`] and constructs [`Self`] from it.
+    pub fn new_write(error: std::io::Error, path: PathBuf) -> Self {
+        Self::write(WriteError::new(error, path))
+    }
+
+    /// Constructs [`UnnormalizedError`] and constructs [`Self`] from it.
+    pub fn new_unnormalized(count: usize) -> Self {
+        Self::unnormalized(UnnormalizedError::new(count))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::InvalidExtension(_) => Code::Usage,
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Collect(error) => error.exit_code(),
+            ErrorSource::Exists(_) | ErrorSource::Unnormalized(_) => Code::Validation,
+            ErrorSource::Write(_) => Code::Io,
+        }
+    }
+}
+
+/// Represents options of normalizing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Options {
+    /// The extension to enforce, defaulting to [`EXTENSION`].
+    pub extension: Option<String>,
+    /// Whether to only report fragments that are not normalized, failing if there are any.
+    pub check: bool,
+}
+
+/// Represents changes made to fragments when normalizing them.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Change {
+    /// The path to the fragment.
+    pub path: PathBuf,
+    /// The normalized path to the fragment, if it is renamed.
+    pub target: Option<PathBuf>,
+    /// The normalized contents of the fragment, if they are changed.
+    pub contents: Option<String>,
+}
+
+impl Change {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, target: Option<PathBuf>, contents: Option<String>) -> Self {
+        Self {
+            path,
+            target,
+            contents,
+        }
+    }
+
+    /// Returns the path the fragment ends up at.
+    pub fn destination(&self) -> &Path {
+        self.target.as_deref().unwrap_or(&self.path)
+    }
+
+    /// Returns the human-readable description of [`Self`].
+    pub fn describe(&self) -> String {
+        let path = self.path.display();
+
+        match (&self.target, &self.contents) {
+            (Some(target), Some(_)) => format!("`{path}` -> `{}` (contents)", target.display()),
+            (Some(target), None) => format!("`{path}` -> `{}`", target.display()),
+            _ => format!("`{path}` (contents)"),
+        }
+    }
+}
+
+/// Returns the canonical contents of the given fragment string.
+///
+/// Returns [`None`] if front matter is not terminated, as such fragments can not be parsed.
+pub fn canonical_contents(string: &str) -> Option<String> {
+    let string = normalize_line_endings(strip_bom(string));
+
+    let (front_matter, content) = split_front_matter(&string).ok()?;
+
+    let content = content.trim();
+
+    let contents = match front_matter {
+        Some(front_matter) => {
+            let front_matter = front_matter.trim();
+
+            if front_matter.is_empty() {
+                format!("{FRONT_MATTER}\n{FRONT_MATTER}\n\n{content}\n")
+            } else {
+                format!("{FRONT_MATTER}\n{front_matter}\n{FRONT_MATTER}\n\n{content}\n")
+            }
+        }
+        None => format!("{content}\n"),
+    };
+
+    Some(contents)
+}
+
+/// Finds changes needed to normalize the fragment at the given path, enforcing the extension.
+///
+/// Returns [`None`] if the fragment is already normalized, as well as if it is not a fragment
+/// or can not be read.
+pub fn change(path: &Path, extension: &str) -> Option<Change> {
+    let name = path_name(path)?;
+
+    let Ok(partial) = name.parse::<Partial<'_>>() else {
+        debug!("skipped `{}`: not a fragment", path.display());
+
+        return None;
+    };
+
+    let string = match read_to_string(path) {
+        Ok(string) => string,
+        Err(error) => {
+            debug!("skipped `{}`: {error}", path.display());
+
+            return None;
+        }
+    };
+
+    let Some(contents) = canonical_contents(&string) else {
+        debug!("skipped `{}`: unterminated front matter", path.display());
+
+        return None;
+    };
+
+    let normalized = partial
+        .with_extension(Some(extension.to_owned().into()))
+        .name();
+
+    let target = (normalized != name).then(|| path.with_file_name(normalized));
+
+    let contents = (contents != string).then_some(contents);
+
+    if target.is_none() && contents.is_none() {
+        return None;
+    }
+
+    Some(Change::new(path.to_owned(), target, contents))
+}
+
+/// Checks that the given changes do not rename fragments to paths that either exist
+/// or are the targets of other changes, so that nothing is overwritten.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if any target is taken.
+pub fn check_targets(changes: &[Change]) -> Result<(), Error> {
+    let mut targets = HashSet::new();
+
+    for change in changes {
+        if let Some(target) = change.target.as_deref() {
+            if target.exists() || !targets.insert(target) {
+                return Err(Error::new_exists(change.path.clone(), target.to_owned()));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies the given change, writing normalized contents and renaming the fragment.
+///
+/// See [`check_targets`] for checking changes before applying them.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if writing or renaming fails.
+pub fn apply(change: &Change) -> Result<(), Error> {
+    let path = change.path.as_path();
+
+    let destination = change.destination();
+
+    match &change.contents {
+        Some(contents) => {
+            write(destination, contents)
+                .map_err(|error| Error::new_write(error, destination.to_owned()))?;
+
+            if change.target.is_some() {
+                remove_file(path).map_err(|error| Error::new_write(error, path.to_owned()))?;
+            }
+        }
+        None => {
+            rename(path, destination).map_err(|error| Error::new_write(error, path.to_owned()))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Normalizes fragments, reporting the changes made.
+///
+/// If `check` is set, nothing is written; fragments that are not normalized are reported instead.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if the extension is invalid, or initializing the builder,
+/// listing fragments or applying changes fails. Also returned if `check` is set
+/// and there are fragments that are not normalized.
+pub fn normalize(workspace: Workspace<'_>, options: Options) -> Result<(), Error> {
+    let extension = options.extension.as_deref().unwrap_or(EXTENSION);
+
+    if extension.is_empty() || extension.contains('.') {
+        return Err(Error::new_invalid_extension(extension.to_owned()));
+    }
+
+    let builder = Builder::from_workspace(workspace, today()).map_err(Error::init)?;
+
+    let changes: Vec<_> = builder
+        .entries()
+        .map_err(Error::collect)?
+        .iter()
+        .filter_map(|path| change(path, extension))
+        .collect();
+
+    if changes.is_empty() {
+        println!("nothing to normalize");
+
+        return Ok(());
+    }
+
+    if options.check {
+        for change in &changes {
+            println!("would normalize {}", change.describe());
+        }
+
+        return Err(Error::new_unnormalized(changes.len()));
+    }
+
+    check_targets(&changes)?;
+
+    for change in &changes {
+        apply(change)?;
+
+        println!("normalized {}", change.describe());
+    }
+
+    info!("normalized {} fragment(s)", changes.len());
+
+    Ok(())
+}
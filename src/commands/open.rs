@@ -0,0 +1,274 @@
+//! Opening files in the editor.
+//!
+//! The [`open`] function implements the `open` subcommand, which opens the changelog,
+//! the configuration file or fragments in the editor (see `create.editor` in [`config`]).
+//!
+//! [`config`]: crate::config
+
+use std::path::PathBuf;
+
+use itertools::Itertools;
+use miette::Diagnostic;
+use thiserror::Error;
+use tracing::info;
+
+use crate::{
+    builder::{Builder, CollectError, InitError},
+    commands::create::{is_interactive, open_editor},
+    date::today,
+    exit::Code,
+    fragment::{path_name, Id, InvalidIdError, Partial},
+    workspace::Workspace,
+};
+
+/// Represents errors that occur when no fragments with the given ID are found.
+#[derive(Debug, Error, Diagnostic)]
+#[error("no fragments with ID `{id}` found")]
+#[diagnostic(
+    code(changelogging::commands::open::not_found),
+    help("check the ID and the fragments directory")
+)]
+pub struct NotFoundError {
+    /// The ID given.
+    pub id: String,
+}
+
+impl NotFoundError {
+    /// Constructs [`Self`].
+    pub fn new(id: String) -> Self {
+        Self { id }
+    }
+}
+
+/// Represents errors that occur when multiple fragments with the given ID are found.
+#[derive(Debug, Error, Diagnostic)]
+#[error("multiple fragments with ID `{id}` found")]
+#[diagnostic(
+    code(changelogging::commands::open::ambiguous),
+    help("open one of these instead: {paths}")
+)]
+pub struct AmbiguousError {
+    /// The ID given.
+    pub id: String,
+    /// The paths to the fragments found, joined together.
+    pub paths: String,
+}
+
+impl AmbiguousError {
+    /// Constructs [`Self`].
+    pub fn new(id: String, paths: String) -> Self {
+        Self { id, paths }
+    }
+}
+
+/// Represents errors that can occur when starting editors.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to open `{path}` in the editor")]
+#[diagnostic(
+    code(changelogging::commands::open::edit),
+    help("check your default editor configuration")
+)]
+pub struct EditError {
+    /// The underlying I/O error.
+    pub source: std::io::Error,
+    /// The path to the file opened.
+    pub path: PathBuf,
+}
+
+impl EditError {
+    /// Constructs [`Self`].
+    pub fn new(source: std::io::Error, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+}
+
+/// Represents errors that occur when the editor is requested in non-interactive mode.
+#[derive(Debug, Error, Diagnostic)]
+#[error("can not open the editor in non-interactive mode")]
+#[diagnostic(
+    code(changelogging::commands::open::non_interactive),
+    help("run the command in the terminal")
+)]
+pub struct NonInteractiveError;
+
+/// Represents sources of errors that can occur when opening files.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Initialization errors.
+    Init(#[from] InitError),
+    /// Collect errors.
+    Collect(#[from] CollectError),
+    /// Invalid ID errors.
+    InvalidId(#[from] InvalidIdError),
+    /// Not found errors.
+    NotFound(#[from] NotFoundError),
+    /// Ambiguous errors.
+    Ambiguous(#[from] AmbiguousError),
+    /// Edit errors.
+    Edit(#[from] EditError),
+    /// Non-interactive errors.
+    NonInteractive(#[from] NonInteractiveError),
+}
+
+/// Represents errors that can occur when opening files.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to open")]
+#[diagnostic(
+    code(changelogging::commands::open),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`CollectError`].
+    pub fn collect(error: CollectError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`InvalidIdError`].
+    pub fn invalid_id(error: InvalidIdError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`NotFoundError`].
+    pub fn not_found(error: NotFoundError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`AmbiguousError`].
+    pub fn ambiguous(error: AmbiguousError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`EditError`].
+    pub fn edit(error: EditError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`NonInteractiveError`].
+    pub fn non_interactive(error: NonInteractiveError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`NotFoundError`] and constructs [`Self`] from it.
+    pub fn new_not_found(id: String) -> Self {
+        Self::not_found(NotFoundError::new(id))
+    }
+
+    /// Constructs [`AmbiguousError`] and constructs [`Self`] from it.
+    pub fn new_ambiguous(id: String, paths: String) -> Self {
+        Self::ambiguous(AmbiguousError::new(id, paths))
+    }
+
+    /// Constructs [`EditError`] and constructs [`Self`] from it.
+    pub fn new_edit(error: std::io::Error, path: PathBuf) -> Self {
+        Self::edit(EditError::new(error, path))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Collect(error) => error.exit_code(),
+            ErrorSource::InvalidId(_)
+            | ErrorSource::NotFound(_)
+            | ErrorSource::Ambiguous(_)
+            | ErrorSource::NonInteractive(_) => Code::Usage,
+            ErrorSource::Edit(_) => Code::Failure,
+        }
+    }
+}
+
+/// Represents targets to open.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Target {
+    /// The changelog, that is, `paths.output`.
+    Changelog,
+    /// The configuration file at the given path.
+    Config(PathBuf),
+    /// The fragment with the given ID.
+    Fragment(String),
+}
+
+const SEPARATOR: &str = ", ";
+
+/// Finds the fragment with the given ID in the fragments directory of the given builder.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if the ID is invalid, listing fragments fails,
+/// or there is either no or more than one fragment with the given ID.
+pub fn find(builder: &Builder<'_>, id: &str) -> Result<PathBuf, Error> {
+    let parsed: Id<'_> = id.parse().map_err(Error::invalid_id)?;
+
+    let paths: Vec<PathBuf> = builder
+        .entries()
+        .map_err(Error::collect)?
+        .into_iter()
+        .filter(|path| {
+            path_name(path)
+                .and_then(|name| name.parse::<Partial<'_>>().ok())
+                .is_some_and(|partial| partial.id == parsed)
+        })
+        .collect();
+
+    match paths.as_slice() {
+        [] => Err(Error::new_not_found(id.to_owned())),
+        [path] => Ok(path.clone()),
+        _ => {
+            let joined = paths
+                .iter()
+                .map(|path| format!("`{}`", path.display()))
+                .join(SEPARATOR);
+
+            Err(Error::new_ambiguous(id.to_owned(), joined))
+        }
+    }
+}
+
+/// Opens the given target in the editor (or the default one, see `create.editor`).
+///
+/// Editing fails fast with [`NonInteractiveError`] unless [`is_interactive`].
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if resolving the target fails (see [`find`]),
+/// as well as if starting the editor fails.
+pub fn open(workspace: Workspace<'_>, target: Target) -> Result<(), Error> {
+    if !is_interactive() {
+        return Err(Error::non_interactive(NonInteractiveError));
+    }
+
+    let editor = workspace.config.create.editor.clone();
+
+    let path = match target {
+        Target::Changelog => workspace.config.paths.output.clone().into_owned(),
+        Target::Config(path) => path,
+        Target::Fragment(id) => {
+            let builder = Builder::from_workspace(workspace, today()).map_err(Error::init)?;
+
+            find(&builder, &id)?
+        }
+    };
+
+    info!("opening `{}`", path.display());
+
+    open_editor(editor.as_deref(), &path).map_err(|error| Error::new_edit(error, path))
+}
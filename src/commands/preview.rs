@@ -1,16 +1,70 @@
 //! Previewing changelog entries.
 //!
-//! The [`preview`] function implements the `preview` subcommand.
+//! The [`preview`] function implements the `preview` subcommand,
+//! while [`watch`] implements its `--watch` mode.
+//!
+//! When the version to compare against is given, the pending entry is compared with
+//! the released entry of that version, showing bullets added and removed.
+//!
+//! In the summary mode, only the number of fragments per section is shown,
+//! along with the kind of release suggested.
+//!
+//! The entry can also be passed to GitHub Actions, see [`Mode::GitHub`],
+//! or copied to the clipboard (with the `clipboard` feature), see `Mode::Clipboard`.
+
+use std::{
+    io::{stdout, IsTerminal},
+    path::{Path, PathBuf},
+    sync::mpsc::{channel, Receiver},
+    time::Duration,
+};
 
-use miette::Diagnostic;
+use anstyle::{AnsiColor, Color, Style};
+use miette::{Diagnostic, Report};
+use notify::{recommended_watcher, Event, RecursiveMode, Watcher};
 use thiserror::Error;
+use time::Date;
+use tracing::{debug, info};
 
 use crate::{
-    builder::{BuildError, Builder, InitError},
+    builder::{BuildError, Builder, InitError, WriteError},
+    changelog::{diff, entries, find, Change, Entry},
     date::{parse, today},
+    exit::Code,
+    github,
+    pretty::render,
+    version::suggest,
     workspace::Workspace,
 };
 
+/// Represents errors that can occur when watching for changes.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to watch for changes")]
+#[diagnostic(
+    code(changelogging::commands::preview::watch),
+    help("make sure the paths watched are accessible")
+)]
+pub struct WatchError(#[from] pub notify::Error);
+
+/// Represents errors that can occur when the entry to compare against is not found.
+#[derive(Debug, Error, Diagnostic)]
+#[error("entry for `{version}` not found")]
+#[diagnostic(
+    code(changelogging::commands::preview::entry_not_found),
+    help("make sure the changelog contains the entry for the given version")
+)]
+pub struct EntryNotFoundError {
+    /// The version provided.
+    pub version: String,
+}
+
+impl EntryNotFoundError {
+    /// Constructs [`Self`].
+    pub fn new(version: String) -> Self {
+        Self { version }
+    }
+}
+
 /// Represents sources of errors that can occur during changelog entry previewing.
 #[derive(Debug, Error, Diagnostic)]
 #[error(transparent)]
@@ -22,6 +76,17 @@ pub enum ErrorSource {
     Init(#[from] InitError),
     /// Build errors.
     Build(#[from] BuildError),
+    /// Watch errors.
+    Watch(#[from] WatchError),
+    /// Changelog reading errors.
+    Changelog(#[from] WriteError),
+    /// Entry not found errors.
+    EntryNotFound(#[from] EntryNotFoundError),
+    /// GitHub Actions errors.
+    GitHub(#[from] github::Error),
+    /// Clipboard errors.
+    #[cfg(feature = "clipboard")]
+    Clipboard(#[from] crate::clipboard::Error),
 }
 
 /// Represents errors that can occur during changelog entry previewing.
@@ -60,22 +125,396 @@ impl Error {
     pub fn build(error: BuildError) -> Self {
         Self::new(error.into())
     }
+
+    /// Constructs [`Self`] from [`WatchError`].
+    pub fn watch(error: WatchError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`WriteError`].
+    pub fn changelog(error: WriteError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`EntryNotFoundError`].
+    pub fn entry_not_found(error: EntryNotFoundError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::github::Error
+    pub fn github(error: github::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::clipboard::Error
+    #[cfg(feature = "clipboard")]
+    pub fn clipboard(error: crate::clipboard::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`EntryNotFoundError`] and constructs [`Self`] from it.
+    pub fn new_entry_not_found(version: String) -> Self {
+        Self::entry_not_found(EntryNotFoundError::new(version))
+    }
+
+    /// Constructs [`WatchError`] and constructs [`Self`] from it.
+    pub fn new_watch(error: notify::Error) -> Self {
+        Self::watch(WatchError(error))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Date(_) => Code::Usage,
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Build(error) => error.exit_code(),
+            ErrorSource::Watch(_) => Code::Io,
+            ErrorSource::Changelog(error) => error.exit_code(),
+            ErrorSource::EntryNotFound(_) => Code::Usage,
+            ErrorSource::GitHub(error) => error.exit_code(),
+            #[cfg(feature = "clipboard")]
+            ErrorSource::Clipboard(error) => error.exit_code(),
+        }
+    }
+}
+
+/// Represents modes of previewing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Mode<'m> {
+    /// Show the entry.
+    #[default]
+    Entry,
+    /// Compare the entry with the released entry of the given version.
+    Against(&'m str),
+    /// Show the number of fragments per section and the kind of release suggested.
+    Summary,
+    /// Show the entry, also setting the GitHub Actions output with the given name to it
+    /// and appending it to the step summary, see [`github`].
+    GitHub(&'m str),
+    /// Show the entry, also copying it to the clipboard, see [`clipboard`].
+    ///
+    /// [`clipboard`]: crate::clipboard
+    #[cfg(feature = "clipboard")]
+    Clipboard,
 }
 
 /// Previews changelog entries.
 ///
+/// If `pretty` is set, the entry is rendered with terminal styling, provided that `color` is set.
+///
+/// If `timings` is set, how long collecting and rendering took is printed to `stderr`,
+/// along with the number of fragments processed (see [`Timings`]).
+///
+/// See [`Mode`] for what is previewed.
+///
+/// [`Timings`]: crate::timings::Timings
+///
 /// # Errors
 ///
-/// Returns [`struct@Error`] if parsing the date, initializing the builder or previewing fails.
-pub fn preview<S: AsRef<str>>(workspace: Workspace<'_>, date: Option<S>) -> Result<(), Error> {
-    let date = match date {
-        Some(string) => parse(string).map_err(Error::date)?,
-        None => today(),
+/// Returns [`struct@Error`] if parsing the date, initializing the builder or previewing fails,
+/// as well as if reading the changelog fails or the entry to compare against is not found.
+pub fn preview<S: AsRef<str>>(
+    workspace: Workspace<'_>,
+    date: Option<S>,
+    pretty: bool,
+    color: bool,
+    mode: Mode<'_>,
+    timings: bool,
+) -> Result<(), Error> {
+    let date = parse_or_today(date)?;
+
+    show(workspace, date, pretty, color, mode, timings)
+}
+
+fn parse_or_today<S: AsRef<str>>(date: Option<S>) -> Result<Date, Error> {
+    match date {
+        Some(string) => parse(string).map_err(Error::date),
+        None => Ok(today()),
+    }
+}
+
+fn show(
+    workspace: Workspace<'_>,
+    date: Date,
+    pretty: bool,
+    color: bool,
+    mode: Mode<'_>,
+    timings: bool,
+) -> Result<(), Error> {
+    let mut builder = Builder::from_workspace(workspace, date).map_err(Error::init)?;
+
+    if timings {
+        builder = builder.with_timings();
+    }
+
+    let result = match mode {
+        Mode::Against(version) => compare(&builder, version, pretty && color),
+        Mode::Summary => summarize(&builder),
+        _ => show_entry(&builder, pretty, color, mode),
     };
 
-    let builder = Builder::from_workspace(workspace, date).map_err(Error::init)?;
+    if let Some(timings) = builder.timings.as_ref() {
+        eprintln!("{timings}");
+    }
+
+    result
+}
+
+fn show_entry(
+    builder: &Builder<'_>,
+    pretty: bool,
+    color: bool,
+    mode: Mode<'_>,
+) -> Result<(), Error> {
+    let entry = builder.build_entry().map_err(Error::changelog)?;
+
+    if pretty && color {
+        println!("{}", render(&entry, &builder.config.indents));
+    } else {
+        println!("{entry}");
+    }
+
+    if let Mode::GitHub(name) = mode {
+        info!("setting the `{name}` output");
+
+        github::set_output(name, &entry).map_err(Error::github)?;
+
+        if github::append_summary(&entry).map_err(Error::github)? {
+            info!("appended the entry to the step summary");
+        }
+    }
+
+    #[cfg(feature = "clipboard")]
+    if let Mode::Clipboard = mode {
+        crate::clipboard::copy(&entry).map_err(Error::clipboard)?;
 
-    builder.preview().map_err(Error::build)?;
+        info!("copied the entry to the clipboard");
+    }
+
+    Ok(())
+}
+
+const OLD: &str = "---";
+const NEW: &str = "+++";
+
+const KEPT: char = ' ';
+const ADDED: char = '+';
+const REMOVED: char = '-';
+
+const ADDED_STYLE: Style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Green)));
+const REMOVED_STYLE: Style = Style::new().fg_color(Some(Color::Ansi(AnsiColor::Red)));
+
+fn line(marker: char, bullet: char, text: &str, style: Option<Style>) -> String {
+    let string = format!("{marker} {bullet} {text}");
+
+    match style {
+        Some(style) => format!("{}{string}{}", style.render(), style.render_reset()),
+        None => string,
+    }
+}
+
+fn compare(builder: &Builder<'_>, version: &str, styled: bool) -> Result<(), Error> {
+    let contents = builder.read_changelog().map_err(Error::changelog)?;
+
+    let heading = builder.entry_heading();
+
+    let old = find(&contents.string, &heading, version)
+        .ok_or_else(|| Error::new_entry_not_found(version.to_owned()))?;
+
+    let built = builder.build_entry().map_err(Error::changelog)?;
+
+    let new = entries(&built, &heading)
+        .into_iter()
+        .next()
+        .unwrap_or(Entry::new("", &built, 0, built.len()));
+
+    let bullet = builder.config.indents.bullet;
+
+    println!("{OLD} {}", old.title);
+    println!("{NEW} {}", new.title);
+
+    for change in diff(&old.bullets(bullet), &new.bullets(bullet)) {
+        let string = match change {
+            Change::Kept(text) => line(KEPT, bullet, &text, None),
+            Change::Added(text) => line(ADDED, bullet, &text, styled.then_some(ADDED_STYLE)),
+            Change::Removed(text) => line(REMOVED, bullet, &text, styled.then_some(REMOVED_STYLE)),
+        };
+
+        println!("{string}");
+    }
+
+    Ok(())
+}
+
+const SEPARATOR: &str = ": ";
+
+fn summarize(builder: &Builder<'_>) -> Result<(), Error> {
+    let collection = builder
+        .collect()
+        .map_err(|error| Error::build(BuildError::collect(error)))?;
+
+    let sections = collection.sections();
+
+    let types = builder.config.types_with_defaults();
+
+    // only the types included in `order` are built
+
+    let counted: Vec<(&str, &str, usize)> = builder
+        .config
+        .order
+        .iter()
+        .filter_map(|name| {
+            types
+                .get(name)
+                .zip(sections.get(name))
+                .map(|found| (name, found))
+        })
+        .filter(|(_, (_, fragments))| !fragments.is_empty())
+        .map(|(name, (type_, fragments))| (name.as_ref(), type_.title.as_ref(), fragments.len()))
+        .collect();
+
+    for (_, title, count) in &counted {
+        println!("{title}{SEPARATOR}{count}");
+    }
+
+    let suggested = suggest(counted.iter().map(|(name, _, _)| *name));
+
+    match suggested {
+        Some(bump) => println!("{} release suggested", bump.as_str()),
+        None => println!("no release needed"),
+    }
+
+    Ok(())
+}
+
+/// The current directory, watched when the configuration file is not known.
+pub const CURRENT: &str = ".";
+
+/// The duration to wait for more changes before previewing again.
+pub const DEBOUNCE: Duration = Duration::from_millis(100);
+
+const CLEAR: &str = "\x1b[2J\x1b[H";
+
+fn clear() {
+    if stdout().is_terminal() {
+        print!("{CLEAR}");
+    }
+}
+
+fn wait(receiver: &Receiver<notify::Result<Event>>) -> bool {
+    loop {
+        let Ok(result) = receiver.recv() else {
+            return false;
+        };
+
+        let event = match result {
+            Ok(event) => event,
+            Err(error) => {
+                eprintln!("{:?}", Report::new(WatchError(error)));
+
+                continue;
+            }
+        };
+
+        if !event.kind.is_access() {
+            for path in &event.paths {
+                debug!("changed `{}`", path.display());
+            }
+
+            break;
+        }
+    }
+
+    // wait for the changes to settle
+
+    while receiver.recv_timeout(DEBOUNCE).is_ok() {}
+
+    true
+}
+
+/// Previews changelog entries, watching for changes and previewing again on every change.
+///
+/// The workspace is loaded via `load` before every preview, so that configuration changes
+/// are picked up. The `config` is the path to the configuration file, if it is known;
+/// otherwise the current directory is watched.
+///
+/// Errors that occur during loading, previewing and watching are reported without stopping.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if parsing the date or creating the watcher fails.
+pub fn watch<S, E, F>(
+    mut load: F,
+    config: Option<&Path>,
+    date: Option<S>,
+    pretty: bool,
+    color: bool,
+    mode: Mode<'_>,
+    timings: bool,
+) -> Result<(), Error>
+where
+    S: AsRef<str>,
+    E: Diagnostic + Send + Sync + 'static,
+    F: FnMut() -> Result<Workspace<'static>, E>,
+{
+    let date = parse_or_today(date)?;
+
+    let (sender, receiver) = channel();
+
+    let mut watcher = recommended_watcher(sender).map_err(Error::new_watch)?;
+
+    // watch the parent directory, since editors tend to replace files on save
+
+    let parent = config
+        .and_then(Path::parent)
+        .filter(|path| !path.as_os_str().is_empty())
+        .unwrap_or(Path::new(CURRENT));
+
+    // errors are reported without stopping, as the paths might become watchable later
+
+    if let Err(error) = watcher.watch(parent, RecursiveMode::NonRecursive) {
+        eprintln!("{:?}", Report::new(WatchError(error)));
+    }
+
+    let mut directory: Option<PathBuf> = None;
+
+    loop {
+        clear();
+
+        match load() {
+            Ok(workspace) => {
+                let current = workspace.config.paths.directory.clone().into_owned();
+
+                if directory.as_ref() != Some(&current) {
+                    if let Some(previous) = directory.take() {
+                        let _ = watcher.unwatch(&previous);
+                    }
+
+                    info!("watching `{}`", current.display());
+
+                    // the directory is watched again on the next reload in case of errors
+
+                    match watcher.watch(&current, RecursiveMode::Recursive) {
+                        Ok(()) => directory = Some(current),
+                        Err(error) => eprintln!("{:?}", Report::new(WatchError(error))),
+                    }
+                }
+
+                if let Err(error) = show(workspace, date, pretty, color, mode, timings) {
+                    eprintln!("{:?}", Report::new(error));
+                }
+            }
+            Err(error) => eprintln!("{:?}", Report::new(error)),
+        }
+
+        if !wait(&receiver) {
+            break;
+        }
+    }
 
     Ok(())
 }
@@ -0,0 +1,159 @@
+//! Rendering example entries.
+//!
+//! The [`render`] function implements the `render` subcommand, which renders the entry
+//! with one made-up fragment through the configured formats, so that templates can be
+//! iterated on without creating fragment files.
+
+use std::borrow::Cow;
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::{
+    builder::{render_entry, RenderEntryError},
+    commands::variables::{EXAMPLE_CONTENT, EXAMPLE_ID, EXAMPLE_TYPE},
+    date::{parse, today},
+    exit::Code,
+    fragment::{Fragment, ParseError, Partial},
+    workspace::Workspace,
+};
+
+/// Represents errors that occur when types of fragments are not included in `order`.
+#[derive(Debug, Error, Diagnostic)]
+#[error("type `{type_name}` is not included in `order`")]
+#[diagnostic(
+    code(changelogging::commands::render::unordered),
+    help("use one of the types listed in `order`")
+)]
+pub struct UnorderedError {
+    /// The type of the fragment.
+    pub type_name: String,
+}
+
+impl UnorderedError {
+    /// Constructs [`Self`].
+    pub fn new(type_name: String) -> Self {
+        Self { type_name }
+    }
+}
+
+/// Represents sources of errors that can occur when rendering.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Date parsing errors.
+    Date(#[from] crate::date::Error),
+    /// Parse errors.
+    Parse(#[from] ParseError),
+    /// Unordered errors.
+    Unordered(#[from] UnorderedError),
+    /// Render entry errors.
+    Render(#[from] RenderEntryError),
+}
+
+/// Represents errors that can occur when rendering.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to render")]
+#[diagnostic(
+    code(changelogging::commands::render),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::date::Error
+    pub fn date(error: crate::date::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`ParseError`].
+    pub fn parse(error: ParseError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`UnorderedError`].
+    pub fn unordered(error: UnorderedError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`RenderEntryError`].
+    pub fn render(error: RenderEntryError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`UnorderedError`] and constructs [`Self`] from it.
+    pub fn new_unordered(type_name: String) -> Self {
+        Self::unordered(UnorderedError::new(type_name))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Date(_) | ErrorSource::Parse(_) | ErrorSource::Unordered(_) => Code::Usage,
+            ErrorSource::Render(error) => error.exit_code(),
+        }
+    }
+}
+
+/// Returns the name of the example fragment, that is, `{id}.{type}`.
+pub fn example_name() -> String {
+    format!("{EXAMPLE_ID}.{EXAMPLE_TYPE}")
+}
+
+/// Renders the entry with the fragment of the given name (`{id}.{type}`, optionally followed
+/// by the extension) and content, printing it to `stdout`.
+///
+/// The name and the content default to the ones of the example fragment, while the date
+/// defaults to today. Nothing is read from the fragments directory.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if parsing the date or the name fails, the type is not included
+/// in `order`, as well as if compiling templates or rendering fails.
+pub fn render(
+    workspace: Workspace<'_>,
+    name: Option<&str>,
+    content: Option<&str>,
+    date: Option<&str>,
+) -> Result<(), Error> {
+    let date = match date {
+        Some(string) => parse(string).map_err(Error::date)?,
+        None => today(),
+    };
+
+    let example = example_name();
+
+    let name = name.unwrap_or(&example);
+
+    let partial: Partial<'_> = name.parse().map_err(Error::parse)?;
+
+    let type_name = workspace.config.canonical_type(&partial.type_name);
+
+    if !workspace.config.order.iter().any(|name| name == type_name) {
+        return Err(Error::new_unordered(type_name.to_owned()));
+    }
+
+    let content = content.unwrap_or(EXAMPLE_CONTENT);
+
+    let fragment = Fragment::new(partial, Cow::Borrowed(content));
+
+    let entry = render_entry(workspace.config, workspace.context, [fragment], date)
+        .map_err(Error::render)?;
+
+    println!("{entry}");
+
+    Ok(())
+}
@@ -0,0 +1,528 @@
+//! Resolving changelog conflicts.
+//!
+//! The [`resolve`] function implements the `resolve` subcommand, which resolves merge conflicts
+//! in changelogs. Concurrent release branches frequently conflict right after the start marker,
+//! as both of them insert entries there.
+//!
+//! Both sides of conflicts are reconstructed, their entries are merged (entries present
+//! on both sides are kept once) and then ordered by their versions, from the newest.
+//! Conflicts outside of entries (that is, before the first one) are left for manual resolution.
+//!
+//! Link reference definitions ending changelogs are merged separately, see [`merge`].
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use semver::Version;
+use thiserror::Error;
+use tracing::{debug, info};
+
+use crate::{
+    builder::{Builder, InitError, WriteError},
+    changelog::{definition_label, entries, reference_label, split_definitions},
+    date::today,
+    exit::Code,
+    git,
+    workspace::Workspace,
+};
+
+/// Represents errors that occur when conflict markers are not terminated.
+#[derive(Debug, Error, Diagnostic)]
+#[error("unterminated conflict markers in `{path}`")]
+#[diagnostic(
+    code(changelogging::commands::resolve::malformed),
+    help("make sure every `{OURS}` marker is followed by `{SEPARATOR}` and `{END}` ones")
+)]
+pub struct MalformedError {
+    /// The path to the changelog.
+    pub path: PathBuf,
+}
+
+impl MalformedError {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+/// Represents errors that occur when conflicts are found outside of entries.
+#[derive(Debug, Error, Diagnostic)]
+#[error("conflicts outside of entries in `{path}`")]
+#[diagnostic(
+    code(changelogging::commands::resolve::outside),
+    help("resolve conflicts before the first entry manually")
+)]
+pub struct OutsideError {
+    /// The path to the changelog.
+    pub path: PathBuf,
+}
+
+impl OutsideError {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+/// Represents errors that occur when entries do not mention any versions.
+#[derive(Debug, Error, Diagnostic)]
+#[error("can not find the version of entry `{title}`")]
+#[diagnostic(
+    code(changelogging::commands::resolve::unversioned),
+    help("entries are ordered by versions that follow semantic versioning; resolve manually")
+)]
+pub struct UnversionedError {
+    /// The title of the entry.
+    pub title: String,
+}
+
+impl UnversionedError {
+    /// Constructs [`Self`].
+    pub fn new(title: String) -> Self {
+        Self { title }
+    }
+}
+
+/// Represents errors that occur when entries of the same version differ between sides.
+#[derive(Debug, Error, Diagnostic)]
+#[error("entries of version `{version}` differ between sides")]
+#[diagnostic(
+    code(changelogging::commands::resolve::diverged),
+    help("resolve the entry manually")
+)]
+pub struct DivergedError {
+    /// The version of the entries.
+    pub version: Version,
+}
+
+impl DivergedError {
+    /// Constructs [`Self`].
+    pub fn new(version: Version) -> Self {
+        Self { version }
+    }
+}
+
+/// Represents sources of errors that can occur when resolving.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Initialization errors.
+    Init(#[from] InitError),
+    /// Read and write errors.
+    Write(#[from] WriteError),
+    /// Malformed errors.
+    Malformed(#[from] MalformedError),
+    /// Outside errors.
+    Outside(#[from] OutsideError),
+    /// Unversioned errors.
+    Unversioned(#[from] UnversionedError),
+    /// Diverged errors.
+    Diverged(#[from] DivergedError),
+    /// `git` errors.
+    Git(#[from] git::Error),
+}
+
+/// Represents errors that can occur when resolving.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to resolve conflicts")]
+#[diagnostic(
+    code(changelogging::commands::resolve),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`WriteError`].
+    pub fn write(error: WriteError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`MalformedError`].
+    pub fn malformed(error: MalformedError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`OutsideError`].
+    pub fn outside(error: OutsideError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`UnversionedError`].
+    pub fn unversioned(error: UnversionedError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`DivergedError`].
+    pub fn diverged(error: DivergedError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::git::Error
+    pub fn git(error: git::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`MalformedError`] and constructs [`Self`] from it.
+    pub fn new_malformed(path: PathBuf) -> Self {
+        Self::malformed(MalformedError::new(path))
+    }
+
+    /// Constructs [`OutsideError`] and constructs [`Self`] from it.
+    pub fn new_outside(path: PathBuf) -> Self {
+        Self::outside(OutsideError::new(path))
+    }
+
+    /// Constructs [`UnversionedError`] and constructs [`Self`] from it.
+    pub fn new_unversioned(title: String) -> Self {
+        Self::unversioned(UnversionedError::new(title))
+    }
+
+    /// Constructs [`DivergedError`] and constructs [`Self`] from it.
+    pub fn new_diverged(version: Version) -> Self {
+        Self::diverged(DivergedError::new(version))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Write(error) => error.exit_code(),
+            ErrorSource::Malformed(_)
+            | ErrorSource::Outside(_)
+            | ErrorSource::Unversioned(_)
+            | ErrorSource::Diverged(_) => Code::Validation,
+            ErrorSource::Git(_) => Code::Git,
+        }
+    }
+}
+
+/// The marker that starts our side of conflicts.
+pub const OURS: &str = "<<<<<<<";
+
+/// The marker that starts the common ancestor of conflicts (in the `diff3` style).
+pub const BASE: &str = "|||||||";
+
+/// The marker that separates our side of conflicts from their side.
+pub const SEPARATOR: &str = "=======";
+
+/// The marker that ends conflicts.
+pub const END: &str = ">>>>>>>";
+
+const NEW_LINE: char = '\n';
+
+const NEW_LINE_STR: &str = "\n";
+
+const DOUBLE_NEW_LINE: &str = "\n\n";
+
+fn is_marker(line: &str, marker: &str) -> bool {
+    line.strip_prefix(marker)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(char::is_whitespace))
+}
+
+fn is_label(line: &str, label: &str) -> bool {
+    definition_label(line).is_some_and(|existing| existing.eq_ignore_ascii_case(label))
+}
+
+/// Represents both sides of conflicting contents.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Sides {
+    /// The contents with conflicts resolved in favor of our side.
+    pub ours: String,
+    /// The contents with conflicts resolved in favor of their side.
+    pub theirs: String,
+    /// The number of conflicts.
+    pub conflicts: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Both,
+    Ours,
+    Base,
+    Theirs,
+}
+
+/// Splits the given contents into both sides of conflicts.
+///
+/// Common ancestors (in the `diff3` style) are dropped.
+/// Returns [`None`] if conflict markers are not terminated.
+pub fn split(contents: &str) -> Option<Sides> {
+    let mut sides = Sides::default();
+
+    let mut state = State::Both;
+
+    for line in contents.split_inclusive(NEW_LINE) {
+        state = match state {
+            State::Both if is_marker(line, OURS) => {
+                sides.conflicts += 1;
+
+                State::Ours
+            }
+            State::Ours if is_marker(line, BASE) => State::Base,
+            State::Ours | State::Base if is_marker(line, SEPARATOR) => State::Theirs,
+            State::Theirs if is_marker(line, END) => State::Both,
+            State::Both => {
+                sides.ours.push_str(line);
+                sides.theirs.push_str(line);
+
+                state
+            }
+            State::Ours => {
+                sides.ours.push_str(line);
+
+                state
+            }
+            State::Base => state,
+            State::Theirs => {
+                sides.theirs.push_str(line);
+
+                state
+            }
+        };
+    }
+
+    (state == State::Both).then_some(sides)
+}
+
+/// Merges entries of both sides, ordering them by their versions, from the newest.
+///
+/// Blocks of link reference definitions ending both sides (see [`split_definitions`]) are merged
+/// into one, keeping each label once (preferring our side). Definitions of entries follow
+/// the order of the entries, while other ones (like the unreleased one) are placed first.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if the sides differ before their first entries, if any entry
+/// does not mention its version, or if entries of the same version differ between sides.
+pub fn merge(builder: &Builder<'_>, path: &Path, sides: &Sides) -> Result<String, Error> {
+    let heading = builder.entry_heading();
+
+    let mut prefixes = Vec::new();
+
+    let mut merged = BTreeMap::new();
+
+    let mut definitions: Vec<&str> = Vec::new();
+
+    for side in [sides.ours.as_str(), sides.theirs.as_str()] {
+        let (contents, block) = split_definitions(side);
+
+        for line in block.lines().map(str::trim_end) {
+            let Some(label) = definition_label(line) else {
+                continue;
+            };
+
+            let exists = definitions.iter().any(|existing| is_label(existing, label));
+
+            if !exists {
+                definitions.push(line);
+            }
+        }
+
+        let found = entries(contents, &heading);
+
+        let prefix = found
+            .first()
+            .map_or(contents, |entry| &contents[..entry.start]);
+
+        prefixes.push(prefix);
+
+        for entry in found {
+            let version = entry
+                .version()
+                .ok_or_else(|| Error::new_unversioned(entry.title.to_owned()))?;
+
+            let string = contents[entry.start..entry.end].trim_end();
+
+            match merged.get(&version) {
+                Some(&(existing, _)) if existing != string => {
+                    return Err(Error::new_diverged(version));
+                }
+                Some(_) => debug!("entry `{}` is present on both sides", entry.title),
+                None => {
+                    merged.insert(version, (string, reference_label(entry.title)));
+                }
+            }
+        }
+    }
+
+    if prefixes.windows(2).any(|pair| pair[0] != pair[1]) {
+        return Err(Error::new_outside(path.to_owned()));
+    }
+
+    let mut string = prefixes.first().copied().unwrap_or_default().to_owned();
+
+    for (index, (entry, _)) in merged.values().rev().enumerate() {
+        if index > 0 {
+            string.push_str(DOUBLE_NEW_LINE);
+        }
+
+        string.push_str(entry);
+    }
+
+    if !merged.is_empty() {
+        string.push(NEW_LINE);
+    }
+
+    if !definitions.is_empty() {
+        let position = |line: &&str| {
+            merged
+                .values()
+                .rev()
+                .position(|(_, label)| label.is_some_and(|label| is_label(line, label)))
+        };
+
+        definitions.sort_by_key(position);
+
+        string.truncate(string.trim_end().len());
+
+        string.push_str(DOUBLE_NEW_LINE);
+        string.push_str(&definitions.join(NEW_LINE_STR));
+        string.push(NEW_LINE);
+    }
+
+    Ok(string)
+}
+
+/// Resolves conflicts in the changelog, merging entries of both sides, see [`merge`].
+///
+/// If `stage` is set, the resolved changelog is staged, marking conflicts as resolved.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when initializing the builder, reading or writing the changelog
+/// or staging it fails, as well as when conflicts can not be resolved.
+pub fn resolve(workspace: Workspace<'_>, stage: bool) -> Result<(), Error> {
+    let builder = Builder::from_workspace(workspace, today()).map_err(Error::init)?;
+
+    let path = builder.config.paths.output.as_ref();
+
+    let contents = builder.read_changelog().map_err(Error::write)?;
+
+    let sides = split(&contents.string).ok_or_else(|| Error::new_malformed(path.to_owned()))?;
+
+    if sides.conflicts == 0 {
+        println!("no conflicts found in `{}`", path.display());
+
+        return Ok(());
+    }
+
+    let string = merge(&builder, path, &sides)?;
+
+    builder
+        .write_changelog(&string, contents.line_ending)
+        .map_err(Error::write)?;
+
+    println!(
+        "resolved {} conflict(s) in `{}`",
+        sides.conflicts,
+        path.display()
+    );
+
+    if stage {
+        info!("staging `{}`", path.display());
+
+        git::add(&builder.config.git, [path])
+            .and_then(git::success)
+            .map_err(Error::git)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, path::Path};
+
+    use time::macros::date;
+
+    use super::{merge, split};
+    use crate::{builder::Builder, config::Config, context::Context};
+
+    const CONFLICTED: &str = "\
+# Changelog
+
+<<<<<<< ours
+## [1.2.0] - 2024-03-01
+
+- Ours.
+
+=======
+## [1.1.1] - 2024-02-15
+
+- Theirs.
+
+>>>>>>> theirs
+## [1.1.0] - 2024-02-01
+
+- Base.
+
+<<<<<<< ours
+[Unreleased]: https://example.com/compare/v1.2.0...HEAD
+[1.2.0]: https://example.com/compare/v1.1.0...v1.2.0
+=======
+[Unreleased]: https://example.com/compare/v1.1.1...HEAD
+[1.1.1]: https://example.com/compare/v1.1.0...v1.1.1
+>>>>>>> theirs
+[1.1.0]: https://example.com/compare/v1.0.0...v1.1.0
+";
+
+    const RESOLVED: &str = "\
+# Changelog
+
+## [1.2.0] - 2024-03-01
+
+- Ours.
+
+## [1.1.1] - 2024-02-15
+
+- Theirs.
+
+## [1.1.0] - 2024-02-01
+
+- Base.
+
+[Unreleased]: https://example.com/compare/v1.2.0...HEAD
+[1.2.0]: https://example.com/compare/v1.1.0...v1.2.0
+[1.1.1]: https://example.com/compare/v1.1.0...v1.1.1
+[1.1.0]: https://example.com/compare/v1.0.0...v1.1.0
+";
+
+    #[test]
+    fn merge_definitions() {
+        let context = Context {
+            name: Cow::Borrowed("changelogging"),
+            version: Cow::Borrowed("1.2.0"),
+            url: None,
+            extra: Default::default(),
+        };
+
+        let builder = Builder::new(context, Config::default(), date!(2024 - 03 - 01)).unwrap();
+
+        let sides = split(CONFLICTED).unwrap();
+
+        let merged = merge(&builder, Path::new("CHANGELOG.md"), &sides).unwrap();
+
+        assert_eq!(merged, RESOLVED);
+    }
+}
@@ -0,0 +1,297 @@
+//! Rolling back builds.
+//!
+//! The [`rollback`] function implements the `rollback` subcommand, which undoes the last build:
+//! changelogs are restored from backups made by `build` (see [`backup`]), or, if explicitly
+//! requested, the newest entries are removed from them when there are no backups.
+//! Fragments recorded as removed by the build are restored via `git`.
+//!
+//! [`backup`]: crate::backup
+
+use std::path::PathBuf;
+
+use miette::Diagnostic;
+use thiserror::Error;
+use tracing::info;
+
+use crate::{
+    backup::{self, Backup, RestoreError},
+    builder::{Builder, InitError, WriteError},
+    date::today,
+    exit::Code,
+    git::{self, HEAD},
+    workspace::Workspace,
+};
+
+/// Represents errors that occur when there is nothing to roll back.
+#[derive(Debug, Error, Diagnostic)]
+#[error("no entries found in `{path}`")]
+#[diagnostic(
+    code(changelogging::commands::rollback::no_entries),
+    help("there are no entries to remove")
+)]
+pub struct NoEntriesError {
+    /// The path to the changelog.
+    pub path: PathBuf,
+}
+
+impl NoEntriesError {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+/// Represents errors that occur when there is no backup to restore.
+#[derive(Debug, Error, Diagnostic)]
+#[error("no backup of `{path}` found")]
+#[diagnostic(
+    code(changelogging::commands::rollback::no_backup),
+    help("pass `--remove-newest` to remove the newest entry instead")
+)]
+pub struct NoBackupError {
+    /// The path to the changelog.
+    pub path: PathBuf,
+}
+
+impl NoBackupError {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
+}
+
+/// Represents sources of errors that can occur when rolling back.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Initialization errors.
+    Init(#[from] InitError),
+    /// Restore errors.
+    Restore(#[from] RestoreError),
+    /// Read and write errors.
+    Write(#[from] WriteError),
+    /// No entries errors.
+    NoEntries(#[from] NoEntriesError),
+    /// No backup errors.
+    NoBackup(#[from] NoBackupError),
+    /// `git` errors.
+    Git(#[from] git::Error),
+    /// Profile errors.
+    Profile(#[from] crate::workspace::ProfileError),
+}
+
+/// Represents errors that can occur when rolling back.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to roll back")]
+#[diagnostic(
+    code(changelogging::commands::rollback),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`RestoreError`].
+    pub fn restore(error: RestoreError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`WriteError`].
+    pub fn write(error: WriteError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`NoEntriesError`].
+    pub fn no_entries(error: NoEntriesError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`NoBackupError`].
+    pub fn no_backup(error: NoBackupError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::git::Error
+    pub fn git(error: git::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`ProfileError`].
+    ///
+    /// [`ProfileError`]: crate::workspace::ProfileError
+    pub fn profile(error: crate::workspace::ProfileError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`NoEntriesError`] and constructs [`Self`] from it.
+    pub fn new_no_entries(path: PathBuf) -> Self {
+        Self::no_entries(NoEntriesError::new(path))
+    }
+
+    /// Constructs [`NoBackupError`] and constructs [`Self`] from it.
+    pub fn new_no_backup(path: PathBuf) -> Self {
+        Self::no_backup(NoBackupError::new(path))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Restore(_) => Code::Io,
+            ErrorSource::Write(error) => error.exit_code(),
+            ErrorSource::NoEntries(_) | ErrorSource::NoBackup(_) => Code::Validation,
+            ErrorSource::Git(_) => Code::Git,
+            ErrorSource::Profile(_) => Code::Config,
+        }
+    }
+}
+
+/// Represents options of rolling back.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// Whether to stage the changelog.
+    pub stage: bool,
+    /// Whether to leave fragments as they are.
+    pub keep_fragments: bool,
+    /// The revision to restore fragments from, defaulting to [`HEAD`].
+    pub revision: Option<String>,
+    /// Whether to remove the newest entries from changelogs without backups.
+    pub remove_newest: bool,
+}
+
+// restores the changelog (and the archive, if any) of the given builder,
+// returning the paths restored
+
+fn restore(builder: &Builder<'_>, remove_newest: bool) -> Result<Vec<PathBuf>, Error> {
+    let path = builder.config.paths.output.as_ref();
+
+    let backup = Backup::new(builder.project_path(backup::DIRECTORY));
+
+    let mut paths = Vec::new();
+
+    if let Some(archive) = builder.config.paths.archive.as_deref() {
+        if backup.restore(archive).map_err(Error::restore)? {
+            println!("restored `{}` from the backup", archive.display());
+
+            paths.push(archive.to_owned());
+        }
+    }
+
+    if backup.restore(path).map_err(Error::restore)? {
+        println!("restored `{}` from the backup", path.display());
+    } else if !remove_newest {
+        return Err(Error::new_no_backup(path.to_owned()));
+    } else {
+        info!(
+            "no backup of `{}` found, removing the newest entry",
+            path.display()
+        );
+
+        let contents = builder.read_changelog().map_err(Error::write)?;
+
+        let string = builder
+            .remove_newest(&contents.string)
+            .ok_or_else(|| Error::new_no_entries(path.to_owned()))?;
+
+        builder
+            .write_changelog(&string, contents.line_ending)
+            .map_err(Error::write)?;
+
+        println!("removed the newest entry from `{}`", path.display());
+    }
+
+    paths.push(path.to_owned());
+
+    Ok(paths)
+}
+
+/// Rolls back the last build.
+///
+/// The changelog is restored from the backup, which is then removed. If there is no backup,
+/// [`NoBackupError`] is returned, unless `remove_newest` is set, in which case the newest entry
+/// is removed from the changelog instead, see [`Builder::remove_newest`].
+/// Additional changelogs (see `outputs` in [`config`]) and archives (see `paths.archive`)
+/// are restored the same way.
+///
+/// Unless `keep_fragments` is set, fragments recorded as removed by the build
+/// (see [`Backup::record`]) are restored as of `revision` (the build is expected not to be
+/// committed yet, otherwise the revision before it should be given). The record is removed
+/// either way.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when initializing the builder, restoring the backup, reading
+/// or writing the changelog fails, as well as when there is nothing to roll back.
+/// Also returned if `git` fails or applying profiles listed in `outputs` fails.
+///
+/// [`config`]: crate::config
+pub fn rollback(workspace: Workspace<'_>, options: Options) -> Result<(), Error> {
+    let date = today();
+
+    let outputs = workspace.outputs().map_err(Error::profile)?;
+
+    let builder = Builder::from_workspace(workspace, date).map_err(Error::init)?;
+
+    let mut paths = restore(&builder, options.remove_newest)?;
+
+    for workspace in outputs {
+        let builder = Builder::from_workspace(workspace, date).map_err(Error::init)?;
+
+        paths.extend(restore(&builder, options.remove_newest)?);
+    }
+
+    // files absent before the build are removed, so there is nothing to stage
+
+    paths.retain(|path| path.exists());
+
+    if options.stage {
+        info!("staging {} file(s)", paths.len());
+
+        git::add(&builder.config.git, &paths)
+            .and_then(git::success)
+            .map_err(Error::git)?;
+    }
+
+    let removed = Backup::new(builder.project_path(backup::DIRECTORY))
+        .take_removed()
+        .map_err(Error::restore)?;
+
+    if options.keep_fragments {
+        return Ok(());
+    }
+
+    let revision = options.revision.as_deref().unwrap_or(HEAD);
+
+    let paths: Vec<PathBuf> = removed.into_iter().filter(|path| !path.exists()).collect();
+
+    if paths.is_empty() {
+        info!("no fragments to restore");
+
+        return Ok(());
+    }
+
+    git::restore(&builder.config.git, revision, &paths)
+        .and_then(git::success)
+        .map_err(Error::git)?;
+
+    println!("restored {} fragment(s) from `{revision}`", paths.len());
+
+    Ok(())
+}
@@ -0,0 +1,167 @@
+//! Listing template variables.
+//!
+//! The [`variables`] function implements the `variables` subcommand.
+
+use std::borrow::Cow;
+
+use handlebars::JsonValue;
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::{
+    builder::{Builder, CollectError, InitError, FRAGMENT, TITLE},
+    date::today,
+    exit::Code,
+    fragment::{Fragment, Id, Integer, Partial},
+    workspace::Workspace,
+};
+
+/// Represents sources of errors that can occur when listing variables.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Initialization errors.
+    Init(#[from] InitError),
+    /// Collect errors.
+    Collect(#[from] CollectError),
+}
+
+/// Represents errors that can occur when listing variables.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to list variables")]
+#[diagnostic(
+    code(changelogging::commands::variables),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`CollectError`].
+    pub fn collect(error: CollectError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Collect(error) => error.exit_code(),
+        }
+    }
+}
+
+/// The ID of the example fragment, used when there are no fragments.
+pub const EXAMPLE_ID: Integer = 13;
+
+/// The type of the example fragment, used when there are no fragments.
+pub const EXAMPLE_TYPE: &str = "feature";
+
+/// The content of the example fragment, used when there are no fragments.
+pub const EXAMPLE_CONTENT: &str = "Added cool features!";
+
+/// Returns the example fragment, used when there are no fragments.
+pub fn example<'e>() -> Fragment<'e> {
+    let partial = Partial::new(Id::integer(EXAMPLE_ID), Cow::Borrowed(EXAMPLE_TYPE));
+
+    Fragment::borrowed(partial, EXAMPLE_CONTENT)
+}
+
+const DOT: char = '.';
+
+fn flatten(prefix: &str, value: &JsonValue, variables: &mut Vec<(String, String)>) {
+    match value {
+        JsonValue::Object(object) if !object.is_empty() => {
+            for (key, inner) in object {
+                let name = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}{DOT}{key}")
+                };
+
+                flatten(&name, inner, variables);
+            }
+        }
+        _ => variables.push((prefix.to_owned(), value.to_string())),
+    }
+}
+
+fn print(name: &str, value: &JsonValue) {
+    let mut variables = Vec::new();
+
+    flatten("", value, &mut variables);
+
+    variables.sort();
+
+    println!("{name}:");
+
+    for (variable, example) in variables {
+        println!("  {variable} = {example}");
+    }
+}
+
+/// Lists variables available to the `title` and `fragment` templates,
+/// along with example values from the current workspace.
+///
+/// The example fragment is the first one with an integer ID found (following `order`),
+/// and front matter keys of all fragments are included. If there are no such fragments,
+/// the example is made up.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if initializing the builder or collecting fragments fails.
+pub fn variables(workspace: Workspace<'_>) -> Result<(), Error> {
+    let builder = Builder::from_workspace(workspace, today()).map_err(Error::init)?;
+
+    let collection = builder.collect().map_err(Error::collect)?;
+
+    let sections = collection.sections();
+
+    let fragments: Vec<&Fragment<'_>> = builder
+        .config
+        .order
+        .iter()
+        .filter_map(|name| sections.get(name))
+        .flatten()
+        .collect();
+
+    let mut example = fragments
+        .iter()
+        .find(|fragment| fragment.partial.id.is_integer())
+        .map(|fragment| (*fragment).clone())
+        .unwrap_or_else(example);
+
+    // include front matter keys of every fragment, keeping the example values first
+
+    for fragment in &fragments {
+        for (key, value) in &fragment.meta {
+            example
+                .meta
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+    }
+
+    print(TITLE, &builder.title_data());
+
+    println!();
+
+    print(FRAGMENT, &builder.fragment_data(&example));
+
+    Ok(())
+}
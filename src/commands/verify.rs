@@ -0,0 +1,274 @@
+//! Verifying changelog entries.
+//!
+//! The [`verify`] function implements the `verify` subcommand, which rebuilds the entry
+//! of the given version from fragments and compares it with the released one, for instance,
+//! to audit that release notes match their sources.
+//!
+//! Released entries are looked up in the changelog, falling back to the archive (if any).
+//! Since fragments are usually removed once released, they can be read as of any revision
+//! via `git` (for instance, the parent of the release commit).
+
+use std::borrow::Cow;
+
+use miette::Diagnostic;
+use thiserror::Error;
+use tracing::info;
+
+use crate::{
+    builder::{BuildError, Builder, CollectError, InitError, WriteError},
+    changelog::{diff, entries, find, Change, Entry},
+    date::{parse, today},
+    exit::Code,
+    workspace::Workspace,
+};
+
+/// Represents errors that occur when the entry of the given version is not found.
+#[derive(Debug, Error, Diagnostic)]
+#[error("entry of version `{version}` not found")]
+#[diagnostic(
+    code(changelogging::commands::verify::entry_not_found),
+    help("make sure either the changelog or the archive contains the entry of this version")
+)]
+pub struct EntryNotFoundError {
+    /// The version provided.
+    pub version: String,
+}
+
+impl EntryNotFoundError {
+    /// Constructs [`Self`].
+    pub fn new(version: String) -> Self {
+        Self { version }
+    }
+}
+
+/// Represents errors that occur when released entries differ from rebuilt ones.
+#[derive(Debug, Error, Diagnostic)]
+#[error("entry of version `{version}` does not match its fragments")]
+#[diagnostic(
+    code(changelogging::commands::verify::mismatch),
+    help("see the differences above; make sure the fragments are read as of the release")
+)]
+pub struct MismatchError {
+    /// The version provided.
+    pub version: String,
+}
+
+impl MismatchError {
+    /// Constructs [`Self`].
+    pub fn new(version: String) -> Self {
+        Self { version }
+    }
+}
+
+/// Represents sources of errors that can occur when verifying.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Date parsing errors.
+    Date(#[from] crate::date::Error),
+    /// Initialization errors.
+    Init(#[from] InitError),
+    /// Changelog reading errors.
+    Changelog(#[from] WriteError),
+    /// Collect errors.
+    Collect(#[from] CollectError),
+    /// Build errors.
+    Build(#[from] BuildError),
+    /// Entry not found errors.
+    EntryNotFound(#[from] EntryNotFoundError),
+    /// Mismatch errors.
+    Mismatch(#[from] MismatchError),
+}
+
+/// Represents errors that can occur when verifying.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to verify")]
+#[diagnostic(
+    code(changelogging::commands::verify),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::date::Error
+    pub fn date(error: crate::date::Error) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`InitError`].
+    pub fn init(error: InitError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`WriteError`].
+    pub fn changelog(error: WriteError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`CollectError`].
+    pub fn collect(error: CollectError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`BuildError`].
+    pub fn build(error: BuildError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`EntryNotFoundError`].
+    pub fn entry_not_found(error: EntryNotFoundError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`MismatchError`].
+    pub fn mismatch(error: MismatchError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`EntryNotFoundError`] and constructs [`Self`] from it.
+    pub fn new_entry_not_found(version: String) -> Self {
+        Self::entry_not_found(EntryNotFoundError::new(version))
+    }
+
+    /// Constructs [`MismatchError`] and constructs [`Self`] from it.
+    pub fn new_mismatch(version: String) -> Self {
+        Self::mismatch(MismatchError::new(version))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Date(_) | ErrorSource::EntryNotFound(_) => Code::Usage,
+            ErrorSource::Init(_) => Code::Config,
+            ErrorSource::Changelog(error) => error.exit_code(),
+            ErrorSource::Collect(_) => Code::Io,
+            ErrorSource::Build(error) => error.exit_code(),
+            ErrorSource::Mismatch(_) => Code::Validation,
+        }
+    }
+}
+
+/// Represents options of verifying.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Options {
+    /// The version of the entry to verify, defaulting to the one of the context.
+    pub version: Option<String>,
+    /// The date to rebuild the entry with, defaulting to the one of the released entry.
+    pub date: Option<String>,
+    /// The revision to read fragments from instead of the working tree, if any.
+    pub fragments_ref: Option<String>,
+}
+
+const OLD: &str = "---";
+const NEW: &str = "+++";
+
+const KEPT: char = ' ';
+const ADDED: char = '+';
+const REMOVED: char = '-';
+
+fn show(old: &Entry<'_>, new: &Entry<'_>, bullet: char) {
+    println!("{OLD} {}", old.title);
+    println!("{NEW} {}", new.title);
+
+    for change in diff(&old.bullets(bullet), &new.bullets(bullet)) {
+        let (marker, text) = match change {
+            Change::Kept(text) => (KEPT, text),
+            Change::Added(text) => (ADDED, text),
+            Change::Removed(text) => (REMOVED, text),
+        };
+
+        println!("{marker} {bullet} {text}");
+    }
+}
+
+/// Rebuilds the entry of the given version from fragments and compares it with the released one
+/// (found either in the changelog or in the archive), printing the differences, if any.
+///
+/// If the date is not given, the date mentioned in the title of the released entry is used,
+/// defaulting to today.
+///
+/// If `fragments_ref` is given, fragments are read from the fragments directory as of
+/// that revision via `git` instead of the working tree.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if parsing the date, initializing the builder, reading
+/// the changelog or the archive, collecting fragments or building fails, as well as
+/// if the entry is not found or does not match the rebuilt one.
+pub fn verify(mut workspace: Workspace<'_>, options: Options) -> Result<(), Error> {
+    if let Some(version) = options.version {
+        workspace.context.version = Cow::Owned(version);
+    }
+
+    let version = workspace.context.version.clone().into_owned();
+
+    let builder = Builder::from_workspace(workspace, today()).map_err(Error::init)?;
+
+    let heading = builder.entry_heading();
+
+    let changelog = builder.read_changelog().map_err(Error::changelog)?.string;
+
+    let archive;
+
+    // look the released entry up in the changelog, falling back to the archive
+
+    let old = match find(&changelog, &heading, &version) {
+        Some(entry) => entry,
+        None => {
+            archive = builder
+                .read_archive()
+                .map_err(Error::changelog)?
+                .unwrap_or_default();
+
+            find(&archive, &heading, &version)
+                .ok_or_else(|| Error::new_entry_not_found(version.clone()))?
+        }
+    };
+
+    let date = match options.date {
+        Some(string) => parse(string).map_err(Error::date)?,
+        None => old.date().unwrap_or(builder.date),
+    };
+
+    let builder = builder.at(date);
+
+    let files = match options.fragments_ref.as_deref() {
+        Some(revision) => builder.read_files_at(revision),
+        None => builder.read_files(),
+    }
+    .map_err(Error::collect)?;
+
+    let built = builder
+        .build_from(&builder.collect_from(&files), &[])
+        .map_err(Error::build)?;
+
+    let new = entries(&built, &heading)
+        .into_iter()
+        .next()
+        .unwrap_or(Entry::new("", &built, 0, built.len()));
+
+    if old.title == new.title && old.body.trim() == new.body.trim() {
+        println!("entry `{}` matches its fragments", old.title);
+
+        return Ok(());
+    }
+
+    info!("entry `{}` does not match its fragments", old.title);
+
+    show(&old, &new, builder.config.indents.bullet);
+
+    Err(Error::new_mismatch(version))
+}
@@ -11,7 +11,18 @@
 //! This section is optional, so are its fields (see defaults for more information):
 //!
 //! - `directory` is the directory containing fragments;
-//! - `output` is the file containing the changelog.
+//! - `output` is the file containing the changelog;
+//! - `archive` is the file to move entries dropped from the changelog to (see `retain`);
+//! - `relative_to` specifies what the paths above are relative to, either `config`
+//!   (the directory containing the config file) or `current` (the current directory).
+//!
+//! Paths are normalized (see [`normalize_path`]), so forward slashes can be used
+//! regardless of the platform.
+//!
+//! By default, relative paths are resolved against the directory containing the config file,
+//! so that `changelogging -C ../project/changelogging.toml build` works as expected.
+//! Setting `relative_to` to `current` restores resolving them against the current directory.
+//! Paths given on the command line (like `--changelog`) are always relative to the current one.
 //!
 //! Here is an example of this section:
 //!
@@ -40,6 +51,350 @@
 //!
 //! This field is represented as the `start` field of [`Config`].
 //!
+//! ## `create_output`
+//!
+//! The `create_output` field specifies whether to create the *changelog* in case it does not exist.
+//! The new changelog is rendered from the `skeleton` format (see `formats`).
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! create_output = true
+//! ```
+//!
+//! This field is represented as the `create_output` field of [`Config`].
+//!
+//! ## `follow_symlinks`
+//!
+//! The `follow_symlinks` field specifies whether to follow symbolic links
+//! found in the fragments directory (which is the default). Otherwise, symbolic links are skipped.
+//! Directories and special files are always skipped.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! follow_symlinks = false
+//! ```
+//!
+//! This field is represented as the `follow_symlinks` field of [`Config`].
+//!
+//! ## `line_ending`
+//!
+//! The `line_ending` field specifies which line endings to use when writing the *changelog*:
+//!
+//! - `lf` uses `\n`;
+//! - `crlf` uses `\r\n`;
+//! - `preserve` uses the dominant line ending of the existing changelog.
+//!
+//! Regardless of this field, byte order marks are stripped and line endings
+//! are normalized when reading *fragments* and the changelog.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! line_ending = "crlf"
+//! ```
+//!
+//! This field is represented as the `line_ending` field of [`Config`].
+//!
+//! ## `render_content`
+//!
+//! The `render_content` field specifies whether to render contents of *fragments* as templates,
+//! before rendering the fragments themselves using the `fragment` format.
+//!
+//! Within contents, one can use fields of [`Context`] and [`Fragment`], including front matter
+//! available as `meta` (for instance, `{{meta.issue}}`). This allows writing
+//! `see {{url}}/docs` instead of hardcoding URLs.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! render_content = true
+//! ```
+//!
+//! This field is represented as the `render_content` field of [`Config`].
+//!
+//! ## `escape`
+//!
+//! The `escape` field specifies how values substituted into templates (using double braces,
+//! like `{{content}}`) are escaped:
+//!
+//! - `none` does not escape values;
+//! - `html` escapes `<`, `>`, `&`, `"`, `'`, `` ` `` and `=` as HTML entities,
+//!   so that angle brackets embedded in fragments render safely;
+//! - tables map characters to their replacements.
+//!
+//! Values substituted using triple braces (like `{{{content}}}`) are never escaped.
+//! Different outputs (see `outputs`) can use different escaping, as profiles can override it.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here are examples of this field:
+//!
+//! ```toml
+//! escape = "html"
+//! ```
+//!
+//! ```toml
+//! [escape]
+//! "<" = "\\<"
+//! ">" = "\\>"
+//! ```
+//!
+//! This field is represented as the `escape` field of [`Config`], see also [`Escape`].
+//!
+//! ## `cache`
+//!
+//! The `cache` field specifies whether to cache bullets rendered from *fragments*
+//! in `.changelogging/cache` (next to the config file), so that unchanged fragments
+//! are not rendered again.
+//! This is mostly useful for large fragment sets that are previewed repeatedly.
+//!
+//! The cache can be removed using the `cache clear` command. Consider adding `.changelogging`
+//! to `.gitignore` when enabling this field.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! cache = true
+//! ```
+//!
+//! This field is represented as the `cache` field of [`Config`], see also [`cache`].
+//!
+//! ## `ids`
+//!
+//! The `ids` field specifies how *fragments* get their IDs:
+//!
+//! - `external` means IDs come from elsewhere, usually pull requests or issues;
+//! - `sequence` means IDs are assigned by `create --auto` from the counter stored
+//!   in the fragments directory (see [`counter`]), for projects without issue trackers.
+//!
+//! Since sequential IDs do not refer to anything, the default `fragment` format
+//! omits them when `ids` is `sequence` (see [`Config::fragment_format`]).
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! ids = "sequence"
+//! ```
+//!
+//! This field is represented as the `ids` field of [`Config`].
+//!
+//! ## `language`
+//!
+//! The `language` field specifies the language of the default type titles and of the message
+//! used when there are no significant changes. Supported languages are `en` (default), `de`,
+//! `es`, `fr`, `it`, `pt` and `ru`.
+//!
+//! Titles specified in `types` and the message specified in `no_changes` always take precedence
+//! over the built-in translations.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! language = "de"
+//! ```
+//!
+//! This field is represented as the `language` field of [`Config`], see also [`Language`].
+//!
+//! ## `no_changes`
+//!
+//! The `no_changes` field specifies the message written to entries without fragments.
+//!
+//! This field is optional, defaulting to the translation of `No significant changes.`
+//! into the configured `language`.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! no_changes = "Nothing to see here."
+//! ```
+//!
+//! This field is represented as the `no_changes` field of [`Config`].
+//!
+//! ## `fold_prereleases`
+//!
+//! The `fold_prereleases` field specifies whether to fold prerelease entries (for instance,
+//! `1.2.0-rc.1` and `1.2.0-rc.2`) into the entry of the final release (`1.2.0`) when building it.
+//!
+//! Bullets of prerelease entries are merged into the matching sections of the new entry,
+//! oldest first, and the prerelease entries are removed from the changelog.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! fold_prereleases = true
+//! ```
+//!
+//! This field is represented as the `fold_prereleases` field of [`Config`].
+//!
+//! ## `version`
+//!
+//! The `version` field specifies how to validate the version of the project:
+//!
+//! - `any` accepts any version;
+//! - `semver` requires the version to follow [semantic versioning](https://semver.org/).
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! version = "semver"
+//! ```
+//!
+//! This field is represented as the `version` field of [`Config`], see also [`Policy`].
+//!
+//! ## `retain`
+//!
+//! The `retain` field specifies the number of the newest entries to keep in the *changelog*.
+//! When writing, entries beyond this number are dropped or, if `paths.archive` is set,
+//! moved to the archive (after its start marker, if present, or at the beginning otherwise).
+//!
+//! Note that entries extend up until the next entry, so anything following the last entry
+//! is considered a part of it.
+//!
+//! This field is optional, and all entries are kept by default.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! retain = 20
+//!
+//! [paths]
+//! archive = "docs/HISTORY.md"
+//! ```
+//!
+//! This field is represented as the `retain` field of [`Config`].
+//!
+//! ## `outputs`
+//!
+//! The `outputs` field specifies the names of profiles (see [`workspace`]) to build
+//! additional *changelogs* from, along with the main one, in one `build` run.
+//! This allows keeping, for instance, the full changelog along with the compact one
+//! containing features and fixes only, without them drifting apart.
+//!
+//! Each profile is applied to the base settings (its own `outputs` are ignored), so it usually
+//! overrides `paths.output`, as well as `order` or `formats`. Fragments are removed
+//! (see `build --remove`) only once all changelogs are written.
+//!
+//! This field is optional, and no additional changelogs are built by default.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! outputs = ["compact"]
+//!
+//! [profile.compact]
+//! order = ["feature", "fix"]
+//!
+//! [profile.compact.paths]
+//! output = "RELEASES.md"
+//!
+//! [profile.compact.formats]
+//! fragment = "{{content}}"
+//! ```
+//!
+//! This field is represented as the `outputs` field of [`Config`].
+//!
+//! ## `areas`
+//!
+//! The `areas` field specifies how to group bullets within sections by the `area` field
+//! specified in front matter of *fragments*:
+//!
+//! - `none` does not group bullets;
+//! - `labels` emits bold sub-labels (like `**CLI:**`) before bullets of each area;
+//! - `nested` emits bullets of each area as nested lists.
+//!
+//! Areas are sorted alphabetically, and bullets without areas come first.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! areas = "labels"
+//! ```
+//!
+//! This field is represented as the `areas` field of [`Config`], see also [`Grouping`].
+//!
+//! ## `dates`
+//!
+//! The `dates` field specifies where dates of *fragments* come from:
+//!
+//! - `front-matter` uses the `date` field specified in front matter, if any;
+//! - `git` also falls back to the date of the commit that added the fragment,
+//!   while fragments that are not committed yet get the date of the entry.
+//!
+//! Dates are available as `fragment_date` to the `fragment` format (see `formats`),
+//! for instance, `{{content}} ({{fragment_date}})`.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! dates = "git"
+//! ```
+//!
+//! This field is represented as the `dates` field of [`Config`], see also [`Dates`].
+//!
+//! ## `sort`
+//!
+//! The `sort` field specifies how to sort bullets within sections, after priorities:
+//!
+//! - `id` sorts bullets by IDs of *fragments*;
+//! - `date` sorts bullets by dates of fragments (see `dates`), oldest first,
+//!   followed by fragments without dates.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! sort = "date"
+//! ```
+//!
+//! This field is represented as the `sort` field of [`Config`], see also [`Sort`].
+//!
+//! ## `anchors`
+//!
+//! The `anchors` field specifies which anchors to append to entry headings,
+//! so that documentation sites can link to specific versions:
+//!
+//! - `none` does not append anchors;
+//! - `html` appends HTML anchors, like `<a id="v1-2-0"></a>`;
+//! - `attribute` appends heading attributes, like `{#v1-2-0}`, supported by some renderers.
+//!
+//! Anchors are derived from versions, the same way as the `slug` helper does it,
+//! see [`slug`] for more information.
+//!
+//! This field is optional, and its default value can be found in defaults.
+//!
+//! Here is an example of this field:
+//!
+//! ```toml
+//! anchors = "html"
+//! ```
+//!
+//! This field is represented as the `anchors` field of [`Config`], see also [`Anchors`].
+//!
 //! ## `levels`
 //!
 //! The `levels` section is used to tell `changelogging` which heading levels to use.
@@ -86,18 +441,220 @@
 //!
 //! - `title` specifies the format to use for rendering titles.
 //! - `fragment` specifies the format to use for rendering fragments.
+//! - `skeleton` specifies the format to use for rendering new changelogs (see `create_output`).
+//! - `reference` specifies the format to use for rendering link reference definitions.
+//! - `security` specifies the format to use for rendering fragments of the `security` type.
+//! - `compare` specifies the format to use for rendering URLs that titles link to.
+//! - `unreleased` specifies the format to use for rendering the URL of unreleased changes.
+//!
+//! All fields of [`Context`] (plus `date`, `prerelease`, `is_prerelease`, `major`, `minor`,
+//! `patch`, `build_metadata` and `commit`) are available as formatting arguments within `title`.
+//! Here, `prerelease` is the prerelease part of the version (for instance, `rc.1` in `1.2.0-rc.1`),
+//! or `null` for stable versions, while `major`, `minor` and `patch` are `null` unless the version
+//! follows semantic versioning. Similarly, `build_metadata` is the build metadata of the version
+//! (for instance, `abc1234` in `1.2.0+abc1234`), while `commit` is the short hash of the current
+//! commit (`HEAD`) found via `git`, for projects that stamp releases with the commits they were cut
+//! from; both are `null` if unavailable.
+//! Within `fragment`, `reference` and `security`, one can use fields of [`Context`]
+//! and [`Fragment`].
+//! Within `skeleton`, one can use fields of [`Context`] (plus `start`).
 //!
-//! All fields of [`Context`] (plus `date`) are available as formatting arguments within `title`.
-//! Within `fragment`, one can use fields of [`Context`] and [`Fragment`].
+//! All formats can also use the `slug` helper, which derives slugs from versions
+//! (see [`slug`]): `{{slug}}` gives the slug of `version`, while `{{slug "1.2.0"}}`
+//! gives the slug of the version given.
 //!
 //! ```toml
 //! [formats]
 //! title = "[{{version}}]({{url}}/tree/v{{version}}) ({{date}})"
 //! fragment = "{{content}} ([#{{id}}]({{url}}/pull/{{id}}))"
+//! skeleton = "# Changelog\n\n{{start}}"
+//! ```
+//!
+//! Since templates are rendered in strict mode, referring to missing front matter fields
+//! is an error. Because of this, `fragment`, `reference` and fragment contents (see
+//! `render_content`) are also given the following booleans, which are always present
+//! and are meant to be used within `{{#if}}` blocks:
+//!
+//! - `has_id` is `true` if the ID of the fragment is an integer (rather than a string one);
+//! - `has_area` is `true` if `area` is specified in front matter;
+//! - `has_author` is `true` if `author` is specified (as a non-empty string) in front matter;
+//! - `has_date` is `true` if the fragment has a date (available as `fragment_date`, see `dates`);
+//! - `is_breaking` is `true` if `breaking = true` is specified in front matter;
+//! - `has_cve`, `has_severity` and `has_advisory_url` are `true` if `cve`, `severity`
+//!   and `advisory_url` are specified (as non-empty strings) in front matter, respectively.
+//!
+//! These booleans (along with `fragment_date`) are part of the stable template interface,
+//! so they will not be renamed or removed without a major version bump.
+//!
+//! ```toml
+//! [formats]
+//! fragment = "{{#if is_breaking}}**Breaking:** {{/if}}{{content}}{{#if has_id}} (#{{id}}){{/if}}{{#if has_author}} by {{meta.author}}{{/if}}"
+//! ```
+//!
+//! Any other front matter fields are available as they are under `meta` (for instance,
+//! `{{meta.issue}}`), except that dates and times are given as strings (like `2024-01-15`).
+//! Fields that only some fragments specify can be checked via `{{#if meta.issue}}`,
+//! or accessed via the `default` helper, which gives the fallback (the empty string
+//! unless provided) in case the field is missing or `null`:
+//!
+//! ```toml
+//! [formats]
+//! fragment = "{{content}} (#{{id}}){{#if meta.issue}}, fixes #{{meta.issue}}{{/if}}"
+//! reference = "[#{{id}}]: {{default meta.link \"https://example.com\"}}"
+//! ```
+//!
+//! The `reference` format has no default value. When it is provided, the reference definitions
+//! of fragments with integer IDs are appended to the end of each entry, which allows
+//! keeping bullets short by using reference-style links:
+//!
+//! ```toml
+//! [formats]
+//! fragment = "{{content}} ([#{{id}}])"
+//! reference = "[#{{id}}]: {{url}}/pull/{{id}}"
+//! ```
+//!
+//! The `security` format has no default value either. When it is provided, fragments
+//! of the `security` type (see `types`) are rendered with it instead of `fragment`,
+//! regardless of whether their IDs are integers. Security fragments can specify `cve`
+//! (like `CVE-2024-12345`), `severity` (one of `low`, `medium`, `high` and `critical`)
+//! and `advisory_url` in front matter, which `check` requires and validates:
+//!
+//! ```toml
+//! [formats]
+//! security = "{{#if has_severity}}**{{meta.severity}}:** {{/if}}{{content}}{{#if has_cve}} ({{#if has_advisory_url}}[{{meta.cve}}]({{meta.advisory_url}}){{else}}{{meta.cve}}{{/if}}){{/if}}"
+//! ```
+//!
+//! Neither `compare` nor `unreleased` have default values. When `compare` is provided
+//! and titles start with reference links (like `[1.2.0] - 2024-05-01`), the block of link
+//! reference definitions at the end of the changelog is maintained when building:
+//! the definition of the new entry is added (or replaced), and so is the `[Unreleased]` one
+//! if `unreleased` is provided, while other definitions are kept as they are.
+//!
+//! Both formats can use fields of [`Context`], along with `label` (the label of the entry,
+//! or `Unreleased`) and `previous` (the label of the previous entry, or `null` if there is
+//! none), as well as `has_previous`:
+//!
+//! ```toml
+//! [formats]
+//! title = "[{{version}}] - {{date}}"
+//! compare = "{{#if has_previous}}{{url}}/compare/v{{previous}}...v{{label}}{{else}}{{url}}/releases/tag/v{{label}}{{/if}}"
+//! unreleased = "{{url}}/compare/v{{previous}}...HEAD"
 //! ```
 //!
 //! This section is represented by the [`Formats`] structure.
 //!
+//! ## `create`
+//!
+//! The `create` section configures the `create` command.
+//!
+//! This section is optional, so are its fields (see defaults for more information):
+//!
+//! - `edit` specifies whether to open the editor by default (`--no-edit` overrides it),
+//!   provided that the content is not given and the session is interactive;
+//! - `editor` specifies the editor command to use instead of the default one,
+//!   which is resolved using `VISUAL` and `EDITOR` environment variables.
+//!
+//! Here is an example of this section:
+//!
+//! ```toml
+//! [create]
+//! edit = true
+//! editor = "code --wait"
+//! ```
+//!
+//! This section is represented by the [`Create`] structure.
+//!
+//! ## `contributors`
+//!
+//! The `contributors` section configures the *Contributors* section appended to entries,
+//! which lists authors (and co-authors, specified via `Co-authored-by` trailers) of commits.
+//!
+//! This section is optional, so are its fields (see defaults for more information):
+//!
+//! - `enabled` specifies whether to append the section;
+//! - `since` specifies which commits to consider, either `fragments` (commits that touched
+//!   fragments included in the entry) or `tag` (commits since the last tag);
+//! - `title` specifies the title of the section;
+//! - `format` specifies the format of each contributor, which can use fields of [`Context`]
+//!   along with `name`, `email` and `first` (see below);
+//! - `first` specifies the format of contributors making their first contribution,
+//!   that is, having no commits reachable from the last tag.
+//!
+//! When `first` is not provided, first-time contributors are not highlighted.
+//!
+//! Here is an example of this section:
+//!
+//! ```toml
+//! [contributors]
+//! enabled = true
+//! since = "tag"
+//! title = "Contributors"
+//! format = "{{name}} <{{email}}>"
+//! first = "{{name}} made their first contribution"
+//! ```
+//!
+//! This section is represented by the [`Contributors`] structure.
+//!
+//! ## `forge`
+//!
+//! The `forge` section configures access to the forge API (GitHub or GitLab), which is used
+//! by network-backed features, such as `create --from-pr`.
+//!
+//! This section is optional, so are its fields:
+//!
+//! - `host` specifies the forge, either `github` or `gitlab`; inferred from `context.url`
+//!   when not provided, falling back to `github`;
+//! - `repository` specifies the repository, for instance `nekitdev/changelogging`;
+//!   inferred from the path of `context.url` when not provided;
+//! - `api` specifies the base URL of the API; inferred from `host` and `context.url`
+//!   when not provided, which makes self-hosted instances work out of the box;
+//! - `token_env` specifies the environment variable containing the token;
+//! - `labels` maps forge labels to fragment types, for instance `"kind/bug" = "fix"`.
+//!
+//! Tokens are never stored in the configuration. Instead, they are discovered from the variable
+//! named by `token_env`, then `CHANGELOGGING_TOKEN`, and then `GITHUB_TOKEN` or `GH_TOKEN`
+//! for GitHub and `GITLAB_TOKEN` for GitLab. Without any token, requests are unauthenticated.
+//!
+//! Here is an example of this section:
+//!
+//! ```toml
+//! [forge]
+//! host = "gitlab"
+//! repository = "nekitdev/changelogging"
+//! api = "https://gitlab.example.com/api/v4"
+//! token_env = "CI_JOB_TOKEN"
+//!
+//! [forge.labels]
+//! "kind/bug" = "fix"
+//! "kind/feature" = "feature"
+//! ```
+//!
+//! Labels are used to infer types of fragments created from pull requests. When several labels
+//! are mapped, the type that comes first in `order` wins.
+//!
+//! This section is represented by the [`Forge`] structure.
+//!
+//! ## `git`
+//!
+//! The `git` section configures how `git` is invoked, which is useful in sandboxed environments.
+//!
+//! This section is optional, so are its fields (see defaults for more information):
+//!
+//! - `executable` specifies the `git` executable to run;
+//! - `extra_args` specifies arguments to pass before subcommands.
+//!
+//! The environment is passed through, so `GIT_DIR`, `GIT_WORK_TREE` and worktrees are respected.
+//!
+//! Here is an example of this section:
+//!
+//! ```toml
+//! [git]
+//! executable = "/usr/bin/git"
+//! extra_args = ["-c", "core.hooksPath=/dev/null"]
+//! ```
+//!
+//! This section is represented by the [`Git`] structure.
+//!
 //! ## `wrap`
 //!
 //! The `wrap` field specifies the line length to use when wrapping entries.
@@ -132,6 +689,10 @@
 //! The `types` section specifies the *mapping* of *types* to their *titles*.
 //! This section behaves slightly differently than others. Instead of using `types` directly,
 //! the mapping specified extends the default mapping. (see defaults for the default mapping).
+//! The default mapping depends on the configured `language`.
+//!
+//! Type names can not be empty or contain dots and whitespace. Types specified in `types`
+//! but absent from `order` are reported, since their fragments are never built.
 //!
 //! Here is an example of this section:
 //!
@@ -146,15 +707,81 @@
 //! internal = "Internal"
 //! ```
 //!
-//! This section is represented as the `types` field of [`Config`].
+//! Types can also be specified as tables, which allows providing the `prefix` (for instance,
+//! an emoji) along with its `placement`, which is either `heading` (default) or `bullets`:
+//!
+//! ```toml
+//! [types]
+//! feature = { title = "Features", prefix = "✨" }
+//! fix = { title = "Fixes", prefix = "🐛", placement = "bullets" }
+//! ```
+//!
+//! Since some types (for instance, dependency updates) can dominate entries, their fragments
+//! can be grouped together via `collapse`, which is either `none` (default), `bullet` (join
+//! fragments into one bullet) or `details` (wrap bullets in the collapsed `<details>` block):
+//!
+//! ```toml
+//! [types]
+//! dependencies = { title = "Dependencies", collapse = "details" }
+//! ```
+//!
+//! Setting `collapsed = true` wraps sections in `<details>` blocks regardless of `collapse`,
+//! summarized by their titles and the number of changes, keeping the headings as they are:
+//!
+//! ```toml
+//! [types]
+//! internal = { title = "Internal", collapsed = true }
+//! ```
+//!
+//! Types can be given `aliases`, so that fragments named after any of them (for instance,
+//! `13.bugfix.md`) are included in the sections of the types they alias:
+//!
+//! ```toml
+//! [types]
+//! fix = { title = "Fixes", aliases = ["bugfix", "bug"] }
+//! ```
+//!
+//! Aliases follow the same rules as type names, and can neither coincide with names
+//! of types (including the default ones) nor be shared between types.
+//!
+//! Fragments of specific types can be rendered with their own `format` instead of the one
+//! in `formats` (taking precedence over both `fragment` and `security`), regardless of whether
+//! their IDs are integers. The same variables as in `formats.fragment` are available:
+//!
+//! ```toml
+//! [types]
+//! docs = { title = "Documentation", format = "{{content}}{{#if meta.docs}} ([docs]({{meta.docs}})){{/if}}" }
+//! ```
 //!
+//! This section is represented as the `types` field of [`Config`], see also [`Type`].
+//!
+//! ## Overrides
+//!
+//! Some of the values above can be overridden for one run, without editing the configuration.
+//! See [`Overrides`] for more information.
+//!
+//! [`cache`]: crate::cache
+//! [`counter`]: crate::counter
 //! [`context`]: crate::context
 //! [`Context`]: crate::context::Context
 //! [`Fragment`]: crate::fragment::Fragment
+//! [`Language`]: crate::language::Language
+//! [`slug`]: crate::version::slug
+//! [`workspace`]: crate::workspace
+
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    iter::once,
+    num::NonZeroUsize,
+    path::{Component, Path, PathBuf},
+};
 
-use std::{borrow::Cow, collections::HashMap, num::NonZeroUsize, path::Path};
+use miette::Diagnostic;
+use serde::{Deserialize, Deserializer, Serialize};
+use thiserror::Error;
 
-use serde::{Deserialize, Serialize};
+use crate::{git::GIT, language::Language, version::Policy};
 
 /// Marks the location in the changelog to start writing entries after.
 pub type Start<'s> = Cow<'s, str>;
@@ -162,16 +789,295 @@ pub type Start<'s> = Cow<'s, str>;
 /// The default `start` value.
 pub const DEFAULT_START: &str = "<!-- changelogging: start -->";
 
+/// The default `create_output` value.
+pub const DEFAULT_CREATE_OUTPUT: bool = false;
+
+/// The default `follow_symlinks` value.
+pub const DEFAULT_FOLLOW_SYMLINKS: bool = true;
+
+/// Represents line endings to use when writing changelogs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LineEnding {
+    /// Use `\n`.
+    Lf,
+    /// Use `\r\n`.
+    Crlf,
+    /// Use the dominant line ending of the existing changelog.
+    #[default]
+    Preserve,
+}
+
+/// The default `render_content` value.
+pub const DEFAULT_RENDER_CONTENT: bool = false;
+
+/// The default `cache` value.
+pub const DEFAULT_CACHE: bool = false;
+
+/// Maps characters to their replacements, used when escaping.
+pub type Replacements = BTreeMap<char, String>;
+
+/// Represents ways to escape values substituted into templates,
+/// deserialized either from modes or tables of replacements.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "EscapeRepr", into = "EscapeRepr")]
+pub enum Escape {
+    /// Do not escape values.
+    #[default]
+    None,
+    /// Escape values as HTML.
+    Html,
+    /// Replace characters according to the given table.
+    Custom(Replacements),
+}
+
+impl Escape {
+    /// Escapes the given string according to [`Self`].
+    ///
+    /// Note that HTML escaping is implemented by the template engine, so this function
+    /// only applies custom replacements, returning other strings as they are.
+    pub fn replace(&self, string: &str) -> String {
+        let Self::Custom(replacements) = self else {
+            return string.to_owned();
+        };
+
+        let mut escaped = String::with_capacity(string.len());
+
+        for character in string.chars() {
+            match replacements.get(&character) {
+                Some(replacement) => escaped.push_str(replacement),
+                None => escaped.push(character),
+            }
+        }
+
+        escaped
+    }
+}
+
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum EscapeMode {
+    None,
+    Html,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum EscapeRepr {
+    Mode(EscapeMode),
+    Custom(Replacements),
+}
+
+impl From<EscapeRepr> for Escape {
+    fn from(repr: EscapeRepr) -> Self {
+        match repr {
+            EscapeRepr::Mode(EscapeMode::None) => Self::None,
+            EscapeRepr::Mode(EscapeMode::Html) => Self::Html,
+            EscapeRepr::Custom(replacements) => Self::Custom(replacements),
+        }
+    }
+}
+
+impl From<Escape> for EscapeRepr {
+    fn from(escape: Escape) -> Self {
+        match escape {
+            Escape::None => Self::Mode(EscapeMode::None),
+            Escape::Html => Self::Mode(EscapeMode::Html),
+            Escape::Custom(replacements) => Self::Custom(replacements),
+        }
+    }
+}
+
+/// The default `escape` value.
+pub const DEFAULT_ESCAPE: Escape = Escape::None;
+
+/// Represents ways of assigning IDs to fragments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Ids {
+    /// IDs come from elsewhere, usually pull requests or issues.
+    #[default]
+    External,
+    /// IDs are assigned sequentially from the counter.
+    Sequence,
+}
+
+impl Ids {
+    /// Checks whether [`Self`] is [`Sequence`].
+    ///
+    /// [`Sequence`]: Self::Sequence
+    pub fn is_sequence(self) -> bool {
+        matches!(self, Self::Sequence)
+    }
+}
+
+/// The default `ids` value.
+pub const DEFAULT_IDS: Ids = Ids::External;
+
+/// The default `fold_prereleases` value.
+pub const DEFAULT_FOLD_PRERELEASES: bool = false;
+
+/// The default `version` value.
+pub const DEFAULT_VERSION: Policy = Policy::Any;
+
+/// Represents ways to group bullets within sections by areas.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Grouping {
+    /// Do not group bullets.
+    #[default]
+    None,
+    /// Emit bold sub-labels before bullets of each area.
+    Labels,
+    /// Emit bullets of each area as nested lists.
+    Nested,
+}
+
+/// Represents numbers of entries to retain.
+pub type Retain = NonZeroUsize;
+
+/// The default `areas` value.
+pub const DEFAULT_AREAS: Grouping = Grouping::None;
+
+/// Represents sources of fragment dates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Dates {
+    /// Use the `date` specified in front matter.
+    #[default]
+    FrontMatter,
+    /// Fall back to the date of the commit that added the fragment.
+    Git,
+}
+
+/// The default `dates` value.
+pub const DEFAULT_DATES: Dates = Dates::FrontMatter;
+
+/// Represents ways to sort bullets within sections.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Sort {
+    /// Sort by fragment IDs.
+    #[default]
+    Id,
+    /// Sort by fragment dates.
+    Date,
+}
+
+/// The default `sort` value.
+pub const DEFAULT_SORT: Sort = Sort::Id;
+
+/// Represents styles of anchors appended to entry headings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Anchors {
+    /// Do not append anchors.
+    #[default]
+    None,
+    /// Append HTML anchors, like `<a id="v1-2-0"></a>`.
+    Html,
+    /// Append heading attributes, like `{#v1-2-0}`.
+    Attribute,
+}
+
+impl Anchors {
+    /// Returns the anchor with the given slug, if any.
+    pub fn anchor(self, slug: &str) -> Option<String> {
+        match self {
+            Self::None => None,
+            Self::Html => Some(format!("<a id=\"{slug}\"></a>")),
+            Self::Attribute => Some(format!("{{#{slug}}}")),
+        }
+    }
+}
+
+/// The default `anchors` value.
+pub const DEFAULT_ANCHORS: Anchors = Anchors::None;
+
+/// The default `language` value.
+pub const DEFAULT_LANGUAGE: Language = Language::En;
+
+/// The default `line_ending` value.
+pub const DEFAULT_LINE_ENDING: LineEnding = LineEnding::Preserve;
+
+/// The current directory, used when normalizing empty paths.
+pub const CURRENT_DIRECTORY: &str = ".";
+
+/// Normalizes the given path.
+///
+/// Separators are converted to the ones native to the platform (forward slashes are
+/// accepted everywhere), redundant separators and `.` components are removed.
+///
+/// The `..` components are collapsed lexically (without accessing the file system),
+/// except for leading ones of relative paths; climbing above the root is not possible.
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => match normalized.components().next_back() {
+                Some(Component::Normal(_)) => {
+                    normalized.pop();
+                }
+                Some(Component::RootDir) => {}
+                _ => normalized.push(component),
+            },
+            _ => normalized.push(component),
+        }
+    }
+
+    if normalized.as_os_str().is_empty() {
+        PathBuf::from(CURRENT_DIRECTORY)
+    } else {
+        normalized
+    }
+}
+
+fn deserialize_path<'de, 'p, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Cow<'p, Path>, D::Error> {
+    PathBuf::deserialize(deserializer).map(|path| Cow::Owned(normalize_path(&path)))
+}
+
+fn deserialize_optional_path<'de, 'p, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<Cow<'p, Path>>, D::Error> {
+    Option::<PathBuf>::deserialize(deserializer)
+        .map(|option| option.map(|path| Cow::Owned(normalize_path(&path))))
+}
+
 /// Specifies fragment directories and changelog files.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
 pub struct Paths<'p> {
     /// The directory to fetch fragments from.
+    #[serde(deserialize_with = "deserialize_path")]
     pub directory: Cow<'p, Path>,
     /// The file to write entries to.
+    #[serde(deserialize_with = "deserialize_path")]
     pub output: Cow<'p, Path>,
+    /// The file to move dropped entries to, if any.
+    #[serde(deserialize_with = "deserialize_optional_path")]
+    pub archive: Option<Cow<'p, Path>>,
+    /// What the paths are relative to.
+    pub relative_to: RelativeTo,
+}
+
+/// Represents what relative paths in [`Paths`] are resolved against.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RelativeTo {
+    /// Resolve paths against the directory containing the config file.
+    #[default]
+    Config,
+    /// Resolve paths against the current directory.
+    Current,
 }
 
+/// The default `paths.relative_to` value.
+pub const DEFAULT_RELATIVE_TO: RelativeTo = RelativeTo::Config;
+
 /// The default `paths.directory` value.
 pub const DEFAULT_DIRECTORY: &str = "changes";
 
@@ -182,11 +1088,45 @@ impl Default for Paths<'_> {
     fn default() -> Self {
         let directory = Cow::Borrowed(Path::new(DEFAULT_DIRECTORY));
         let output = Cow::Borrowed(Path::new(DEFAULT_OUTPUT));
+        let archive = None;
+        let relative_to = DEFAULT_RELATIVE_TO;
 
-        Self { directory, output }
+        Self {
+            directory,
+            output,
+            archive,
+            relative_to,
+        }
     }
 }
 
+impl Paths<'_> {
+    /// Checks whether paths are resolved against the directory containing the config file.
+    pub fn is_relative_to_config(&self) -> bool {
+        self.relative_to == RelativeTo::Config
+    }
+
+    /// Resolves relative paths against the given root (the directory containing the config file),
+    /// unless `relative_to` is `current`. Absolute paths are left as they are.
+    pub fn resolve(&mut self, root: &Path) {
+        if !self.is_relative_to_config() {
+            return;
+        }
+
+        self.directory = Cow::Owned(resolve_path(root, &self.directory));
+        self.output = Cow::Owned(resolve_path(root, &self.output));
+
+        if let Some(archive) = self.archive.as_mut() {
+            *archive = Cow::Owned(resolve_path(root, archive));
+        }
+    }
+}
+
+/// Resolves the given path against the given root, normalizing the result.
+pub fn resolve_path(root: &Path, path: &Path) -> PathBuf {
+    normalize_path(&root.join(path))
+}
+
 /// Represents heading levels.
 pub type Level = NonZeroUsize;
 
@@ -215,53 +1155,278 @@ impl Default for Levels {
     }
 }
 
-/// Specifies characters to use for headings and indentation.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// Specifies characters to use for headings and indentation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Indents {
+    /// The character to use for headings.
+    pub heading: char,
+    /// The character to use for indentation.
+    pub bullet: char,
+}
+
+/// The default `indents.heading` value.
+pub const DEFAULT_HEADING: char = '#';
+
+/// The default `indents.bullet` value.
+pub const DEFAULT_BULLET: char = '-';
+
+impl Default for Indents {
+    fn default() -> Self {
+        let heading = DEFAULT_HEADING;
+        let bullet = DEFAULT_BULLET;
+
+        Self { heading, bullet }
+    }
+}
+
+/// Defines formats to use for rendering titles and fragments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Formats<'f> {
+    /// The format to use for rendering titles.
+    pub title: Cow<'f, str>,
+    /// The format to use for rendering fragments.
+    pub fragment: Cow<'f, str>,
+    /// The format to use for rendering new changelogs.
+    pub skeleton: Cow<'f, str>,
+    /// The format to use for rendering link reference definitions, if any.
+    pub reference: Option<Cow<'f, str>>,
+    /// The format to use for rendering security fragments instead of `fragment`, if any.
+    pub security: Option<Cow<'f, str>>,
+    /// The format to use for rendering URLs of link reference definitions of entries, if any.
+    pub compare: Option<Cow<'f, str>>,
+    /// The format to use for rendering the URL of the `Unreleased` definition, if any.
+    pub unreleased: Option<Cow<'f, str>>,
+}
+
+/// The default `formats.title` value.
+pub const DEFAULT_TITLE: &str = "{{version}} ({{date}})";
+
+/// The default `formats.fragment` value.
+pub const DEFAULT_FRAGMENT: &str = "{{content}} (#{{id}})";
+
+/// The default `formats.fragment` value used when `ids` is `sequence`, which omits IDs.
+pub const DEFAULT_SEQUENCE_FRAGMENT: &str = "{{content}}";
+
+/// The default `formats.skeleton` value.
+pub const DEFAULT_SKELETON: &str = "# Changelog\n\n{{start}}";
+
+impl Default for Formats<'_> {
+    fn default() -> Self {
+        let title = Cow::Borrowed(DEFAULT_TITLE);
+        let fragment = Cow::Borrowed(DEFAULT_FRAGMENT);
+        let skeleton = Cow::Borrowed(DEFAULT_SKELETON);
+        let reference = None;
+        let security = None;
+        let compare = None;
+        let unreleased = None;
+
+        Self {
+            title,
+            fragment,
+            skeleton,
+            reference,
+            security,
+            compare,
+            unreleased,
+        }
+    }
+}
+
+/// The default `create.edit` value.
+pub const DEFAULT_EDIT: bool = false;
+
+/// Configures the `create` command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Create<'c> {
+    /// Whether to open the editor by default.
+    pub edit: bool,
+    /// The editor command to use, if any.
+    pub editor: Option<Cow<'c, str>>,
+}
+
+impl Default for Create<'_> {
+    fn default() -> Self {
+        let edit = DEFAULT_EDIT;
+        let editor = None;
+
+        Self { edit, editor }
+    }
+}
+
+/// Represents which commits to consider when finding contributors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Since {
+    /// Commits that touched fragments included in the entry.
+    #[default]
+    Fragments,
+    /// Commits since the last tag.
+    Tag,
+}
+
+/// The default `contributors.enabled` value.
+pub const DEFAULT_CONTRIBUTORS_ENABLED: bool = false;
+
+/// The default `contributors.since` value.
+pub const DEFAULT_SINCE: Since = Since::Fragments;
+
+/// The default `contributors.title` value.
+pub const DEFAULT_CONTRIBUTORS_TITLE: &str = "Contributors";
+
+/// The default `contributors.format` value.
+pub const DEFAULT_CONTRIBUTOR: &str = "{{name}}";
+
+/// Configures the *Contributors* section.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Contributors<'c> {
+    /// Whether to append the section.
+    pub enabled: bool,
+    /// Which commits to consider.
+    pub since: Since,
+    /// The title of the section.
+    pub title: Cow<'c, str>,
+    /// The format of each contributor.
+    pub format: Cow<'c, str>,
+    /// The format of first-time contributors, if any.
+    pub first: Option<Cow<'c, str>>,
+}
+
+impl Default for Contributors<'_> {
+    fn default() -> Self {
+        let enabled = DEFAULT_CONTRIBUTORS_ENABLED;
+        let since = DEFAULT_SINCE;
+        let title = Cow::Borrowed(DEFAULT_CONTRIBUTORS_TITLE);
+        let format = Cow::Borrowed(DEFAULT_CONTRIBUTOR);
+        let first = None;
+
+        Self {
+            enabled,
+            since,
+            title,
+            format,
+            first,
+        }
+    }
+}
+
+/// Represents forges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Host {
+    /// GitHub.
+    GitHub,
+    /// GitLab.
+    GitLab,
+}
+
+/// The string that URLs of GitLab instances usually contain.
+pub const GITLAB: &str = "gitlab";
+
+impl Host {
+    /// Infers the forge from the given project URL.
+    ///
+    /// URLs containing [`GITLAB`] are assumed to point to GitLab, while any other URL
+    /// is assumed to point to GitHub.
+    pub fn infer(url: &str) -> Self {
+        if url.to_lowercase().contains(GITLAB) {
+            Self::GitLab
+        } else {
+            Self::GitHub
+        }
+    }
+
+    /// Returns the string representation of [`Self`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::GitHub => "github",
+            Self::GitLab => "gitlab",
+        }
+    }
+}
+
+/// Maps forge labels to fragment types.
+pub type Labels<'l> = HashMap<Cow<'l, str>, Cow<'l, str>>;
+
+/// Configures access to the forge API.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
-pub struct Indents {
-    /// The character to use for headings.
-    pub heading: char,
-    /// The character to use for indentation.
-    pub bullet: char,
+pub struct Forge<'f> {
+    /// The forge, if provided.
+    pub host: Option<Host>,
+    /// The repository, if provided.
+    pub repository: Option<Cow<'f, str>>,
+    /// The base URL of the API, if provided.
+    pub api: Option<Cow<'f, str>>,
+    /// The environment variable containing the token, if provided.
+    pub token_env: Option<Cow<'f, str>>,
+    /// The mapping of labels to types.
+    pub labels: Labels<'f>,
 }
 
-/// The default `indents.heading` value.
-pub const DEFAULT_HEADING: char = '#';
-
-/// The default `indents.bullet` value.
-pub const DEFAULT_BULLET: char = '-';
-
-impl Default for Indents {
-    fn default() -> Self {
-        let heading = DEFAULT_HEADING;
-        let bullet = DEFAULT_BULLET;
+impl Forge<'_> {
+    /// Infers the type from the given labels, using [`Labels`].
+    ///
+    /// If several labels are mapped, the type that comes first in `order` is returned,
+    /// with types not found in `order` coming last. Returns [`None`] if no label is mapped.
+    pub fn type_for<S: AsRef<str>>(&self, labels: &[S], order: &Order<'_>) -> Option<&str> {
+        labels
+            .iter()
+            .filter_map(|label| self.labels.get(label.as_ref()))
+            .map(AsRef::as_ref)
+            .enumerate()
+            .min_by_key(|&(index, type_name)| {
+                let position = order
+                    .iter()
+                    .position(|name| name == type_name)
+                    .unwrap_or(order.len());
 
-        Self { heading, bullet }
+                (position, index)
+            })
+            .map(|(_, type_name)| type_name)
     }
 }
 
-/// Defines formats to use for rendering titles and fragments.
+/// Configures how `git` is invoked.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(default)]
-pub struct Formats<'f> {
-    /// The format to use for rendering titles.
-    pub title: Cow<'f, str>,
-    /// The format to use for rendering fragments.
-    pub fragment: Cow<'f, str>,
+pub struct Git<'g> {
+    /// The `git` executable to run.
+    pub executable: Cow<'g, str>,
+    /// The arguments to pass before subcommands.
+    pub extra_args: Vec<Cow<'g, str>>,
 }
 
-/// The default `formats.title` value.
-pub const DEFAULT_TITLE: &str = "{{version}} ({{date}})";
+impl Git<'_> {
+    /// Converts [`Self`] into the owned version.
+    pub fn into_owned(self) -> Git<'static> {
+        let executable = Cow::Owned(self.executable.into_owned());
 
-/// The default `formats.fragment` value.
-pub const DEFAULT_FRAGMENT: &str = "{{content}} (#{{id}})";
+        let extra_args = self
+            .extra_args
+            .into_iter()
+            .map(|arg| Cow::Owned(arg.into_owned()))
+            .collect();
 
-impl Default for Formats<'_> {
+        Git {
+            executable,
+            extra_args,
+        }
+    }
+}
+
+impl Default for Git<'_> {
     fn default() -> Self {
-        let title = Cow::Borrowed(DEFAULT_TITLE);
-        let fragment = Cow::Borrowed(DEFAULT_FRAGMENT);
+        let executable = Cow::Borrowed(GIT);
+        let extra_args = Vec::new();
 
-        Self { title, fragment }
+        Self {
+            executable,
+            extra_args,
+        }
     }
 }
 
@@ -274,6 +1439,12 @@ pub const DEFAULT_WRAP: Wrap = Wrap::new(100).unwrap();
 /// Defines which types to include, and in what order to do so.
 pub type Order<'o> = Vec<Cow<'o, str>>;
 
+/// Defines names of profiles to build additional changelogs from.
+pub type Outputs<'o> = Vec<Cow<'o, str>>;
+
+/// The name of the type of security fragments, see [`Config::security_format_for`].
+pub const SECURITY_TYPE: &str = "security";
+
 /// Returns the default `order` value.
 pub fn default_order() -> Vec<&'static str> {
     vec![
@@ -291,8 +1462,182 @@ fn into_order(vec: Vec<&str>) -> Order<'_> {
     vec.into_iter().map(Cow::Borrowed).collect()
 }
 
+/// Represents where type prefixes are placed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Placement {
+    /// Prepend the prefix to the section heading.
+    #[default]
+    Heading,
+    /// Prepend the prefix to each bullet of the section.
+    Bullets,
+}
+
+/// Represents how fragments of types are rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Collapse {
+    /// Render each fragment as its own bullet.
+    #[default]
+    None,
+    /// Join all fragments into one bullet.
+    Bullet,
+    /// Wrap bullets in the collapsed `<details>` block.
+    Details,
+}
+
+impl Collapse {
+    /// Checks whether fragments are rendered as they are.
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+}
+
+/// Represents types, deserialized either from titles or tables.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "TypeRepr<'t>", into = "TypeRepr<'t>")]
+pub struct Type<'t> {
+    /// The title of the type.
+    pub title: Cow<'t, str>,
+    /// The prefix of the type, if any.
+    pub prefix: Option<Cow<'t, str>>,
+    /// Where to place the prefix.
+    pub placement: Placement,
+    /// How to render fragments.
+    pub collapse: Collapse,
+    /// Whether to wrap sections in `<details>` blocks.
+    pub collapsed: bool,
+    /// The alternative names of the type.
+    pub aliases: Vec<Cow<'t, str>>,
+    /// The format to use for rendering fragments of the type instead of `fragment`, if any.
+    pub format: Option<Cow<'t, str>>,
+}
+
+impl<'t> Type<'t> {
+    /// Constructs [`Self`] with the given title only.
+    pub fn new(title: Cow<'t, str>) -> Self {
+        let prefix = None;
+        let placement = Placement::default();
+        let collapse = Collapse::default();
+        let collapsed = false;
+        let aliases = Vec::new();
+        let format = None;
+
+        Self {
+            title,
+            prefix,
+            placement,
+            collapse,
+            collapsed,
+            aliases,
+            format,
+        }
+    }
+
+    /// Checks whether the type has the given alias.
+    pub fn has_alias(&self, alias: &str) -> bool {
+        self.aliases.iter().any(|name| name == alias)
+    }
+
+    /// Returns the prefix to place according to the given placement, if any.
+    pub fn prefix_for(&self, placement: Placement) -> Option<&str> {
+        self.prefix
+            .as_deref()
+            .filter(|_| self.placement == placement)
+    }
+
+    /// Checks whether sections are wrapped in `<details>` blocks, that is, whether
+    /// `collapsed` is set or `collapse` is [`Collapse::Details`].
+    pub fn is_collapsed(&self) -> bool {
+        self.collapsed || self.collapse == Collapse::Details
+    }
+
+    /// Returns the title, prefixed if the prefix is placed in the heading.
+    pub fn heading(&self) -> Cow<'_, str> {
+        match self.prefix_for(Placement::Heading) {
+            Some(prefix) => Cow::Owned(format!("{prefix} {}", self.title)),
+            None => Cow::Borrowed(self.title.as_ref()),
+        }
+    }
+}
+
+fn is_false(value: &bool) -> bool {
+    !value
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+enum TypeRepr<'t> {
+    Title(Cow<'t, str>),
+    Table {
+        title: Cow<'t, str>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prefix: Option<Cow<'t, str>>,
+        #[serde(default)]
+        placement: Placement,
+        #[serde(default, skip_serializing_if = "Collapse::is_none")]
+        collapse: Collapse,
+        #[serde(default, skip_serializing_if = "is_false")]
+        collapsed: bool,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        aliases: Vec<Cow<'t, str>>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        format: Option<Cow<'t, str>>,
+    },
+}
+
+impl<'t> From<TypeRepr<'t>> for Type<'t> {
+    fn from(repr: TypeRepr<'t>) -> Self {
+        match repr {
+            TypeRepr::Title(title) => Self::new(title),
+            TypeRepr::Table {
+                title,
+                prefix,
+                placement,
+                collapse,
+                collapsed,
+                aliases,
+                format,
+            } => Self {
+                title,
+                prefix,
+                placement,
+                collapse,
+                collapsed,
+                aliases,
+                format,
+            },
+        }
+    }
+}
+
+impl<'t> From<Type<'t>> for TypeRepr<'t> {
+    fn from(type_: Type<'t>) -> Self {
+        // serialize plain types as titles, like they are usually written
+
+        if type_.prefix.is_none()
+            && type_.collapse.is_none()
+            && !type_.collapsed
+            && type_.aliases.is_empty()
+            && type_.format.is_none()
+        {
+            Self::Title(type_.title)
+        } else {
+            Self::Table {
+                title: type_.title,
+                prefix: type_.prefix,
+                placement: type_.placement,
+                collapse: type_.collapse,
+                collapsed: type_.collapsed,
+                aliases: type_.aliases,
+                format: type_.format,
+            }
+        }
+    }
+}
+
 /// Specifies the mapping of types to their titles.
-pub type Types<'t> = HashMap<Cow<'t, str>, Cow<'t, str>>;
+pub type Types<'t> = HashMap<Cow<'t, str>, Type<'t>>;
 
 /// Represents configurations.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -302,12 +1647,54 @@ pub struct Config<'c> {
     pub paths: Paths<'c>,
     /// The `start` field.
     pub start: Start<'c>,
+    /// The `create_output` field.
+    pub create_output: bool,
+    /// The `follow_symlinks` field.
+    pub follow_symlinks: bool,
+    /// The `line_ending` field.
+    pub line_ending: LineEnding,
+    /// The `render_content` field.
+    pub render_content: bool,
+    /// The `escape` field.
+    pub escape: Escape,
+    /// The `cache` field.
+    pub cache: bool,
+    /// The `ids` field.
+    pub ids: Ids,
+    /// The `language` field.
+    pub language: Language,
+    /// The `no_changes` field.
+    pub no_changes: Option<Cow<'c, str>>,
+    /// The `fold_prereleases` field.
+    pub fold_prereleases: bool,
+    /// The `version` field.
+    pub version: Policy,
+    /// The `areas` field.
+    pub areas: Grouping,
+    /// The `dates` field.
+    pub dates: Dates,
+    /// The `sort` field.
+    pub sort: Sort,
+    /// The `anchors` field.
+    pub anchors: Anchors,
+    /// The `retain` field.
+    pub retain: Option<Retain>,
+    /// The `outputs` field.
+    pub outputs: Outputs<'c>,
     /// The `levels` section.
     pub levels: Levels,
     /// The `indents` section.
     pub indents: Indents,
     /// The `formats` section.
     pub formats: Formats<'c>,
+    /// The `create` section.
+    pub create: Create<'c>,
+    /// The `contributors` section.
+    pub contributors: Contributors<'c>,
+    /// The `forge` section.
+    pub forge: Forge<'c>,
+    /// The `git` section.
+    pub git: Git<'c>,
     /// The `wrap` field.
     pub wrap: Wrap,
     /// The `order` field.
@@ -316,33 +1703,17 @@ pub struct Config<'c> {
     pub types: Types<'c>,
 }
 
-macro_rules! hash_map {
-    ($($key: expr => $value: expr),* $(,)?) => {
-        ::std::collections::HashMap::from([
-            $(
-                ($key, $value),
-            )*
-        ])
-    }
-}
-
 /// Returns the default `types` value.
+///
+/// See [`Language::default_types`] for translated values.
 pub fn default_types() -> HashMap<&'static str, &'static str> {
-    hash_map! {
-        "security" => "Security",
-        "feature" => "Features",
-        "change" => "Changes",
-        "fix" => "Fixes",
-        "deprecation" => "Deprecations",
-        "removal" => "Removals",
-        "internal" => "Internal",
-    }
+    DEFAULT_LANGUAGE.default_types()
 }
 
 fn into_types<'t>(hash_map: HashMap<&'t str, &'t str>) -> Types<'t> {
     hash_map
         .into_iter()
-        .map(|(name, title)| (Cow::Borrowed(name), Cow::Borrowed(title)))
+        .map(|(name, title)| (Cow::Borrowed(name), Type::new(Cow::Borrowed(title))))
         .collect()
 }
 
@@ -352,24 +1723,89 @@ impl Default for Config<'_> {
 
         let start = Cow::Borrowed(DEFAULT_START);
 
+        let create_output = DEFAULT_CREATE_OUTPUT;
+
+        let follow_symlinks = DEFAULT_FOLLOW_SYMLINKS;
+
+        let line_ending = DEFAULT_LINE_ENDING;
+
+        let render_content = DEFAULT_RENDER_CONTENT;
+
+        let escape = DEFAULT_ESCAPE;
+
+        let cache = DEFAULT_CACHE;
+
+        let ids = DEFAULT_IDS;
+
+        let language = DEFAULT_LANGUAGE;
+
+        let no_changes = None;
+
+        let fold_prereleases = DEFAULT_FOLD_PRERELEASES;
+
+        let version = DEFAULT_VERSION;
+
+        let areas = DEFAULT_AREAS;
+
+        let dates = DEFAULT_DATES;
+
+        let sort = DEFAULT_SORT;
+
+        let anchors = DEFAULT_ANCHORS;
+
+        let retain = None;
+
+        let outputs = Outputs::new();
+
         let levels = Levels::default();
 
         let indents = Indents::default();
 
         let formats = Formats::default();
 
+        let create = Create::default();
+
+        let contributors = Contributors::default();
+
+        let forge = Forge::default();
+
+        let git = Git::default();
+
         let wrap = DEFAULT_WRAP;
 
         let order = into_order(default_order());
 
-        let types = into_types(default_types());
+        // default types depend on `language`, see `types_with_defaults`
+
+        let types = Types::new();
 
         Self {
             paths,
             start,
+            create_output,
+            follow_symlinks,
+            line_ending,
+            render_content,
+            escape,
+            cache,
+            ids,
+            language,
+            no_changes,
+            fold_prereleases,
+            version,
+            areas,
+            dates,
+            sort,
+            anchors,
+            retain,
+            outputs,
             levels,
             indents,
             formats,
+            create,
+            contributors,
+            forge,
+            git,
             wrap,
             order,
             types,
@@ -412,10 +1848,520 @@ impl Config<'_> {
 impl Config<'_> {
     /// Returns `types` with defaults included.
     pub fn types_with_defaults(&self) -> Types<'_> {
-        let mut types_with_defaults = into_types(default_types());
+        let mut types_with_defaults = into_types(self.language.default_types());
 
         types_with_defaults.extend(self.types.clone());
 
         types_with_defaults
     }
+
+    /// Returns the name of the type that has the given alias, if any.
+    pub fn alias_of(&self, alias: &str) -> Option<&str> {
+        self.types
+            .iter()
+            .find(|(_, type_)| type_.has_alias(alias))
+            .map(|(name, _)| name.as_ref())
+    }
+
+    /// Resolves the given type name, which can also be an alias, into the name of the type.
+    ///
+    /// Names that are not aliases are returned as they are.
+    pub fn canonical_type<'n>(&'n self, name: &'n str) -> &'n str {
+        self.alias_of(name).unwrap_or(name)
+    }
+
+    /// Suggests the type name or alias closest to the given unknown type name, if any
+    /// is close enough (see [`distance`]), breaking ties alphabetically.
+    pub fn suggest_type(&self, name: &str) -> Option<String> {
+        let types = self.types_with_defaults();
+
+        let maximum = (name.chars().count() / SUGGEST_RATIO).max(1);
+
+        types
+            .iter()
+            .flat_map(|(type_name, type_)| once(type_name).chain(&type_.aliases))
+            .map(|known| (distance(name, known), known))
+            .filter(|(distance, _)| *distance <= maximum)
+            .min()
+            .map(|(_, known)| known.clone().into_owned())
+    }
+
+    /// Returns the `fragment` format, which defaults to [`DEFAULT_SEQUENCE_FRAGMENT`]
+    /// instead of [`DEFAULT_FRAGMENT`] when `ids` is `sequence`.
+    pub fn fragment_format(&self) -> &str {
+        let fragment = self.formats.fragment.as_ref();
+
+        if self.ids.is_sequence() && fragment == DEFAULT_FRAGMENT {
+            DEFAULT_SEQUENCE_FRAGMENT
+        } else {
+            fragment
+        }
+    }
+
+    /// Returns the `format` of the given type (or the type it aliases), if configured.
+    pub fn type_format(&self, type_name: &str) -> Option<&str> {
+        self.types
+            .get(self.canonical_type(type_name))
+            .and_then(|type_| type_.format.as_deref())
+    }
+
+    /// Returns the `security` format if it is configured and the given type
+    /// (or the type it aliases) is [`SECURITY_TYPE`].
+    pub fn security_format_for(&self, type_name: &str) -> Option<&str> {
+        self.formats
+            .security
+            .as_deref()
+            .filter(|_| self.canonical_type(type_name) == SECURITY_TYPE)
+    }
+
+    /// Returns `no_changes`, defaulting to the one of the configured `language`.
+    pub fn no_changes_or_default(&self) -> &str {
+        self.no_changes
+            .as_deref()
+            .unwrap_or_else(|| self.language.no_changes())
+    }
+}
+
+/// Represents errors that can occur when `order` contains duplicates.
+#[derive(Debug, Error, Diagnostic)]
+#[error("type `{name}` is duplicated in `order`")]
+#[diagnostic(
+    code(changelogging::config::duplicate_order),
+    help("remove duplicate entries from `order`")
+)]
+pub struct DuplicateOrderError {
+    /// The name of the type.
+    pub name: String,
+}
+
+impl DuplicateOrderError {
+    /// Constructs [`Self`].
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+/// Represents errors that can occur when type names are invalid.
+#[derive(Debug, Error, Diagnostic)]
+#[error("invalid type name `{name}`")]
+#[diagnostic(
+    code(changelogging::config::invalid_type_name),
+    help("type names can not be empty or contain dots and whitespace")
+)]
+pub struct InvalidTypeNameError {
+    /// The name of the type.
+    pub name: String,
+}
+
+impl InvalidTypeNameError {
+    /// Constructs [`Self`].
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+/// Represents errors that can occur when aliases coincide with type names or other aliases.
+#[derive(Debug, Error, Diagnostic)]
+#[error("alias `{alias}` conflicts with another type or alias")]
+#[diagnostic(
+    code(changelogging::config::conflicting_alias),
+    help("aliases must differ from type names and can not be shared between types")
+)]
+pub struct ConflictingAliasError {
+    /// The alias.
+    pub alias: String,
+}
+
+impl ConflictingAliasError {
+    /// Constructs [`Self`].
+    pub fn new(alias: String) -> Self {
+        Self { alias }
+    }
+}
+
+/// Represents sources of errors that can occur when checking types.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum TypesErrorSource {
+    /// Duplicate order errors.
+    DuplicateOrder(#[from] DuplicateOrderError),
+    /// Invalid type name errors.
+    InvalidTypeName(#[from] InvalidTypeNameError),
+    /// Conflicting alias errors.
+    ConflictingAlias(#[from] ConflictingAliasError),
+}
+
+/// Represents errors that can occur when checking types.
+#[derive(Debug, Error, Diagnostic)]
+#[error("invalid types configuration")]
+#[diagnostic(
+    code(changelogging::config::types),
+    help("see the report for more information")
+)]
+pub struct TypesError {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: TypesErrorSource,
+}
+
+impl TypesError {
+    /// Constructs [`Self`].
+    pub fn new(source: TypesErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`DuplicateOrderError`].
+    pub fn duplicate_order(error: DuplicateOrderError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`InvalidTypeNameError`].
+    pub fn invalid_type_name(error: InvalidTypeNameError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`ConflictingAliasError`].
+    pub fn conflicting_alias(error: ConflictingAliasError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`DuplicateOrderError`] and constructs [`Self`] from it.
+    pub fn new_duplicate_order(name: String) -> Self {
+        Self::duplicate_order(DuplicateOrderError::new(name))
+    }
+
+    /// Constructs [`InvalidTypeNameError`] and constructs [`Self`] from it.
+    pub fn new_invalid_type_name(name: String) -> Self {
+        Self::invalid_type_name(InvalidTypeNameError::new(name))
+    }
+
+    /// Constructs [`ConflictingAliasError`] and constructs [`Self`] from it.
+    pub fn new_conflicting_alias(alias: String) -> Self {
+        Self::conflicting_alias(ConflictingAliasError::new(alias))
+    }
+}
+
+/// Represents warnings emitted when types are specified in `types` but absent from `order`.
+#[derive(Debug, Error, Diagnostic)]
+#[error("type `{name}` is not included in `order`")]
+#[diagnostic(
+    code(changelogging::config::unordered_type),
+    severity(Warning),
+    help("fragments of this type are never built; add it to `order`")
+)]
+pub struct UnorderedTypeWarning {
+    /// The name of the type.
+    pub name: String,
+}
+
+impl UnorderedTypeWarning {
+    /// Constructs [`Self`].
+    pub fn new(name: String) -> Self {
+        Self { name }
+    }
+}
+
+/// The ratio of lengths of unknown type names to the maximum distance of suggestions.
+pub const SUGGEST_RATIO: usize = 3;
+
+/// Computes the edit (Levenshtein) distance between the given strings, that is, the minimum
+/// number of characters to insert, delete or substitute to turn one string into the other.
+pub fn distance(this: &str, that: &str) -> usize {
+    let that: Vec<char> = that.chars().collect();
+
+    let mut previous: Vec<usize> = (0..=that.len()).collect();
+
+    for (index, this_character) in this.chars().enumerate() {
+        let mut current = vec![index + 1];
+
+        for (that_index, that_character) in that.iter().enumerate() {
+            let cost = usize::from(this_character != *that_character);
+
+            let value = (previous[that_index] + cost)
+                .min(previous[that_index + 1] + 1)
+                .min(current[that_index] + 1);
+
+            current.push(value);
+        }
+
+        previous = current;
+    }
+
+    previous[that.len()]
+}
+
+/// Checks whether the given type name is valid, that is, non-empty and free of dots and whitespace.
+pub fn is_valid_type_name(name: &str) -> bool {
+    !name.is_empty()
+        && !name
+            .chars()
+            .any(|character| character == '.' || character.is_whitespace())
+}
+
+impl Config<'_> {
+    /// Checks `order` and `types` for misconfigurations,
+    /// returning warnings about types specified in `types` but absent from `order`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TypesError`] if `order` contains duplicates, any type name or alias is invalid,
+    /// or aliases conflict with type names or each other.
+    pub fn check_types(&self) -> Result<Vec<UnorderedTypeWarning>, TypesError> {
+        let mut seen = HashSet::new();
+
+        for name in &self.order {
+            if !is_valid_type_name(name) {
+                return Err(TypesError::new_invalid_type_name(name.clone().into_owned()));
+            }
+
+            if !seen.insert(name) {
+                return Err(TypesError::new_duplicate_order(name.clone().into_owned()));
+            }
+        }
+
+        let mut names: Vec<_> = self.types.keys().collect();
+
+        names.sort();
+
+        let mut warnings = Vec::new();
+
+        for name in names {
+            if !is_valid_type_name(name) {
+                return Err(TypesError::new_invalid_type_name(name.clone().into_owned()));
+            }
+
+            if !seen.contains(name) {
+                warnings.push(UnorderedTypeWarning::new(name.clone().into_owned()));
+            }
+        }
+
+        let types = self.types_with_defaults();
+
+        let mut names: Vec<_> = types.keys().collect();
+
+        names.sort();
+
+        let mut aliases = HashSet::new();
+
+        for name in names {
+            for alias in &types[name].aliases {
+                if !is_valid_type_name(alias) {
+                    return Err(TypesError::new_invalid_type_name(
+                        alias.clone().into_owned(),
+                    ));
+                }
+
+                if types.contains_key(alias) || !aliases.insert(alias) {
+                    return Err(TypesError::new_conflicting_alias(
+                        alias.clone().into_owned(),
+                    ));
+                }
+            }
+        }
+
+        Ok(warnings)
+    }
+}
+
+/// Represents overrides of individual configuration values.
+///
+/// Overrides take precedence over values found in configuration files.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Overrides<'o> {
+    /// Overrides `paths.directory`.
+    pub directory: Option<Cow<'o, Path>>,
+    /// Overrides `paths.output`.
+    pub output: Option<Cow<'o, Path>>,
+    /// Overrides `start`.
+    pub start: Option<Start<'o>>,
+    /// Overrides `wrap`.
+    pub wrap: Option<Wrap>,
+}
+
+/// The `paths.directory` key.
+pub const DIRECTORY_KEY: &str = "paths.directory";
+
+/// The `paths.output` key.
+pub const OUTPUT_KEY: &str = "paths.output";
+
+/// The `start` key.
+pub const START_KEY: &str = "start";
+
+/// The `wrap` key.
+pub const WRAP_KEY: &str = "wrap";
+
+impl Overrides<'_> {
+    /// Returns the dotted keys of the values that are overridden.
+    pub fn keys(&self) -> Vec<&'static str> {
+        let mut keys = Vec::new();
+
+        if self.directory.is_some() {
+            keys.push(DIRECTORY_KEY);
+        }
+
+        if self.output.is_some() {
+            keys.push(OUTPUT_KEY);
+        }
+
+        if self.start.is_some() {
+            keys.push(START_KEY);
+        }
+
+        if self.wrap.is_some() {
+            keys.push(WRAP_KEY);
+        }
+
+        keys
+    }
+
+    /// Checks whether the value with the given dotted key is overridden.
+    pub fn contains(&self, key: &str) -> bool {
+        self.keys().contains(&key)
+    }
+}
+
+impl<'c> Config<'c> {
+    /// Applies the given [`Overrides`] to [`Self`].
+    pub fn apply(&mut self, overrides: Overrides<'c>) {
+        if let Some(directory) = overrides.directory {
+            self.paths.directory = Cow::Owned(normalize_path(&directory));
+        }
+
+        if let Some(output) = overrides.output {
+            self.paths.output = Cow::Owned(normalize_path(&output));
+        }
+
+        if let Some(start) = overrides.start {
+            self.start = start;
+        }
+
+        if let Some(wrap) = overrides.wrap {
+            self.wrap = wrap;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use super::{normalize_path, resolve_path, Paths, RelativeTo};
+
+    fn normalize(path: &str) -> PathBuf {
+        normalize_path(Path::new(path))
+    }
+
+    #[test]
+    fn normalize_current() {
+        assert_eq!(
+            normalize("./changes/./13.feature.md"),
+            Path::new("changes/13.feature.md")
+        );
+        assert_eq!(normalize("changes//"), Path::new("changes"));
+        assert_eq!(normalize("."), Path::new("."));
+        assert_eq!(normalize(""), Path::new("."));
+    }
+
+    #[test]
+    fn normalize_parent() {
+        assert_eq!(normalize("docs/../CHANGELOG.md"), Path::new("CHANGELOG.md"));
+        assert_eq!(normalize("a/b/../../c/./d/.."), Path::new("c"));
+        assert_eq!(normalize("changes/.."), Path::new("."));
+    }
+
+    #[test]
+    fn normalize_leading_parent() {
+        assert_eq!(normalize("../CHANGELOG.md"), Path::new("../CHANGELOG.md"));
+        assert_eq!(normalize("a/../../b"), Path::new("../b"));
+        assert_eq!(normalize("../../a/../b"), Path::new("../../b"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn normalize_absolute() {
+        assert_eq!(
+            normalize("/project/./changes/../CHANGELOG.md"),
+            Path::new("/project/CHANGELOG.md")
+        );
+        assert_eq!(
+            normalize("/project/../../CHANGELOG.md"),
+            Path::new("/CHANGELOG.md")
+        );
+        assert_eq!(normalize("/.."), Path::new("/"));
+    }
+
+    #[test]
+    fn resolve_relative() {
+        let root = Path::new("project");
+
+        assert_eq!(
+            resolve_path(root, Path::new("CHANGELOG.md")),
+            Path::new("project/CHANGELOG.md")
+        );
+        assert_eq!(
+            resolve_path(root, Path::new("./changes/")),
+            Path::new("project/changes")
+        );
+        assert_eq!(
+            resolve_path(root, Path::new("../CHANGELOG.md")),
+            Path::new("CHANGELOG.md")
+        );
+        assert_eq!(
+            resolve_path(root, Path::new("../../CHANGELOG.md")),
+            Path::new("../CHANGELOG.md")
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn resolve_absolute() {
+        let root = Path::new("/home/project");
+
+        assert_eq!(
+            resolve_path(root, Path::new("/tmp/CHANGELOG.md")),
+            Path::new("/tmp/CHANGELOG.md")
+        );
+        assert_eq!(
+            resolve_path(root, Path::new("../../../CHANGELOG.md")),
+            Path::new("/CHANGELOG.md")
+        );
+    }
+
+    fn paths(relative_to: RelativeTo) -> Paths<'static> {
+        Paths {
+            archive: Some(Path::new("docs/../ARCHIVE.md").to_owned().into()),
+            relative_to,
+            ..Paths::default()
+        }
+    }
+
+    #[test]
+    fn resolve_relative_to_config() {
+        let mut paths = paths(RelativeTo::Config);
+
+        paths.resolve(Path::new("project"));
+
+        assert_eq!(paths.directory, Path::new("project/changes"));
+        assert_eq!(paths.output, Path::new("project/CHANGELOG.md"));
+        assert_eq!(
+            paths.archive.as_deref(),
+            Some(Path::new("project/ARCHIVE.md"))
+        );
+    }
+
+    #[test]
+    fn resolve_relative_to_current() {
+        let mut paths = paths(RelativeTo::Current);
+
+        paths.resolve(Path::new("project"));
+
+        assert_eq!(paths.directory, Path::new("changes"));
+        assert_eq!(paths.output, Path::new("CHANGELOG.md"));
+        assert_eq!(
+            paths.archive.as_deref(),
+            Some(Path::new("docs/../ARCHIVE.md"))
+        );
+    }
 }
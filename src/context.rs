@@ -6,7 +6,7 @@
 //!
 //! - `name` is the name of the project;
 //! - `version` is the version of the project;
-//! - `url` is the URL of the project.
+//! - `url` is the URL of the project (optional, unless templates reference it).
 //!
 //! Here is an example of this section:
 //!
@@ -17,11 +17,36 @@
 //! url = "https://github.com/nekitdev/changelogging"
 //! ```
 //!
+//! Any other keys are collected into `extra` and exposed to templates alongside the fields above,
+//! for instance, to include release codenames or additional links:
+//!
+//! ```toml
+//! [context]
+//! name = "changelogging"
+//! version = "0.7.0"
+//! url = "https://github.com/nekitdev/changelogging"
+//! version_tag = "v0.7.0"
+//! codename = "Bootstrap"
+//! ```
+//!
+//! With the context above, `{{codename}}` and `{{version_tag}}` can be used in templates.
+//!
+//! The section (or any of its fields) can also be supplied by the separate `context.toml` file,
+//! for instance, generated by release pipelines; see [`workspace`] for more information.
+//!
 //! This section is represented by the [`Context`] structure.
+//!
+//! [`workspace`]: crate::workspace
 
 use std::borrow::Cow;
 
+use miette::Diagnostic;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use toml::{Table, Value};
+
+/// Represents extra fields of contexts.
+pub type Extra = crate::table::Table;
 
 /// Represents project contexts.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -30,6 +55,79 @@ pub struct Context<'c> {
     pub name: Cow<'c, str>,
     /// The version of the project.
     pub version: Cow<'c, str>,
-    /// The URL of the project.
-    pub url: Cow<'c, str>,
+    /// The URL of the project, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<Cow<'c, str>>,
+    /// The extra fields of the project.
+    ///
+    /// This field is flattened during (de)serialization.
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+/// Represents errors that can occur when the `context` section is missing.
+#[derive(Debug, Error, Diagnostic)]
+#[error("missing `[context]` section")]
+#[diagnostic(
+    code(changelogging::context::missing),
+    help("add the `[context]` section with `name` and `version`")
+)]
+pub struct MissingError;
+
+/// Represents errors that can occur when required fields of the `context` section are missing.
+#[derive(Debug, Error, Diagnostic)]
+#[error("missing `context.{field}` field")]
+#[diagnostic(
+    code(changelogging::context::missing_field),
+    help("add `{field}` to the `[context]` section")
+)]
+pub struct MissingFieldError {
+    /// The name of the field.
+    pub field: &'static str,
+}
+
+impl MissingFieldError {
+    /// Constructs [`Self`].
+    pub fn new(field: &'static str) -> Self {
+        Self { field }
+    }
+}
+
+/// Represents errors that can occur when the `context` section is incomplete.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum Error {
+    /// Missing section errors.
+    Missing(#[from] MissingError),
+    /// Missing field errors.
+    MissingField(#[from] MissingFieldError),
+}
+
+/// The `context` key.
+pub const CONTEXT: &str = "context";
+
+/// The required fields of the `context` section.
+pub const REQUIRED: [&str; 2] = ["name", "version"];
+
+/// Checks that the `context` section along with its required fields is present
+/// in the given workspace table.
+///
+/// This is used to provide better diagnostics when deserializing workspaces fails.
+///
+/// # Errors
+///
+/// Returns [`enum@Error`] if the section or any of its required fields is missing.
+pub fn check(table: &Table) -> Result<(), Error> {
+    let Some(Value::Table(context)) = table.get(CONTEXT) else {
+        return Err(MissingError.into());
+    };
+
+    for field in REQUIRED {
+        if !context.contains_key(field) {
+            return Err(MissingFieldError::new(field).into());
+        }
+    }
+
+    Ok(())
 }
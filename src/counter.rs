@@ -0,0 +1,233 @@
+//! Counting fragment IDs.
+//!
+//! When `ids` is set to `sequence` (see [`config`]), fragments created with `create --auto`
+//! are assigned monotonically increasing IDs, the last one of which is stored in the [`COUNTER`]
+//! file placed in the fragments directory. The counter should be committed along with fragments.
+//!
+//! The next ID is always greater than both the stored one and the integer IDs of fragments
+//! found in the directory, so IDs do not collide even if the counter is missing or outdated.
+//!
+//! [`config`]: crate::config
+
+use std::{
+    fs::{create_dir_all, read_dir, read_to_string, write},
+    io::ErrorKind,
+    num::ParseIntError,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+use tracing::debug;
+
+use crate::{
+    exit::Code,
+    fragment::{path_name, Id, Integer, Partial},
+};
+
+/// The name of the counter file placed in the fragments directory.
+pub const COUNTER: &str = ".changeloggingcounter";
+
+/// Represents errors that can occur when reading counters.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to read the counter")]
+#[diagnostic(
+    code(changelogging::counter::read),
+    help("check whether the counter file is accessible")
+)]
+pub struct ReadError(#[from] pub std::io::Error);
+
+/// Represents errors that can occur when parsing counters.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to parse the counter")]
+#[diagnostic(
+    code(changelogging::counter::parse),
+    help("the counter file must contain the last fragment ID")
+)]
+pub struct ParseError(#[from] pub ParseIntError);
+
+/// Represents errors that can occur when writing counters.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to write the counter")]
+#[diagnostic(
+    code(changelogging::counter::write),
+    help("make sure the fragments directory is accessible")
+)]
+pub struct WriteError(#[from] pub std::io::Error);
+
+/// Represents errors that can occur when the counter overflows.
+#[derive(Debug, Error, Diagnostic)]
+#[error("the counter overflowed")]
+#[diagnostic(
+    code(changelogging::counter::overflow),
+    help("fragment IDs must fit into unsigned 32-bit integers")
+)]
+pub struct OverflowError;
+
+/// Represents sources of errors that can occur when counting.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Read errors.
+    Read(#[from] ReadError),
+    /// Parse errors.
+    Parse(#[from] ParseError),
+    /// Write errors.
+    Write(#[from] WriteError),
+    /// Overflow errors.
+    Overflow(#[from] OverflowError),
+}
+
+/// Represents errors that can occur when counting.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to count in `{path}`")]
+#[diagnostic(
+    code(changelogging::counter::count),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+    /// The path to the counter.
+    pub path: PathBuf,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+
+    /// Constructs [`Self`] from [`ReadError`].
+    pub fn read(error: ReadError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`ParseError`].
+    pub fn parse(error: ParseError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`WriteError`].
+    pub fn write(error: WriteError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`OverflowError`].
+    pub fn overflow(error: OverflowError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`ReadError`] and constructs [`Self`] from it.
+    pub fn new_read(error: std::io::Error, path: PathBuf) -> Self {
+        Self::read(ReadError(error), path)
+    }
+
+    /// Constructs [`ParseError`] and constructs [`Self`] from it.
+    pub fn new_parse(error: ParseIntError, path: PathBuf) -> Self {
+        Self::parse(ParseError(error), path)
+    }
+
+    /// Constructs [`WriteError`] and constructs [`Self`] from it.
+    pub fn new_write(error: std::io::Error, path: PathBuf) -> Self {
+        Self::write(WriteError(error), path)
+    }
+
+    /// Constructs [`OverflowError`] and constructs [`Self`] from it.
+    pub fn new_overflow(path: PathBuf) -> Self {
+        Self::overflow(OverflowError, path)
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match self.source {
+            ErrorSource::Parse(_) | ErrorSource::Overflow(_) => Code::Validation,
+            _ => Code::Io,
+        }
+    }
+}
+
+/// Represents counters of fragment IDs.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Counter {
+    /// The fragments directory.
+    pub directory: PathBuf,
+}
+
+impl Counter {
+    /// Constructs [`Self`].
+    pub fn new(directory: PathBuf) -> Self {
+        Self { directory }
+    }
+
+    /// Returns the path to the counter file.
+    pub fn path(&self) -> PathBuf {
+        self.directory.join(COUNTER)
+    }
+
+    /// Reads the last ID from the counter file, returning zero if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if reading or parsing the counter fails.
+    pub fn last(&self) -> Result<Integer, Error> {
+        let path = self.path();
+
+        match read_to_string(&path) {
+            Ok(string) => string
+                .trim()
+                .parse()
+                .map_err(|error| Error::new_parse(error, path)),
+            Err(error) if error.kind() == ErrorKind::NotFound => Ok(0),
+            Err(error) => Err(Error::new_read(error, path)),
+        }
+    }
+
+    /// Finds the greatest integer ID of fragments in the directory, if any.
+    ///
+    /// Entries that can not be read are skipped.
+    pub fn greatest(&self) -> Option<Integer> {
+        let entries = read_dir(&self.directory).ok()?;
+
+        entries
+            .filter_map(Result::ok)
+            .filter_map(|entry| integer_id(&entry.path()))
+            .max()
+    }
+
+    /// Returns the next ID, storing it in the counter file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if reading, parsing or writing the counter fails,
+    /// or if the next ID overflows.
+    pub fn next(&self) -> Result<Integer, Error> {
+        let path = self.path();
+
+        let last = self.last()?.max(self.greatest().unwrap_or_default());
+
+        let next = last
+            .checked_add(1)
+            .ok_or_else(|| Error::new_overflow(path.clone()))?;
+
+        create_dir_all(&self.directory)
+            .and_then(|()| write(&path, format!("{next}\n")))
+            .map_err(|error| Error::new_write(error, path))?;
+
+        debug!("assigned the ID `{next}`");
+
+        Ok(next)
+    }
+}
+
+fn integer_id(path: &Path) -> Option<Integer> {
+    let partial: Partial<'_> = path_name(path)?.parse().ok()?;
+
+    match partial.id {
+        Id::Integer(value) => Some(value),
+        Id::String(_) => None,
+    }
+}
@@ -26,6 +26,9 @@ impl Error {
 }
 
 /// Returns the current [`Date`].
+///
+/// This requires the system clock, which is unavailable on `wasm32-unknown-unknown`
+/// (calling this function panics there); pass dates explicitly on such targets.
 pub fn today() -> Date {
     OffsetDateTime::now_utc().date()
 }
@@ -1,13 +1,24 @@
 //! Discovering workspaces.
-
-use std::{env::current_dir, path::PathBuf};
+//!
+//! The [`discover`] function looks for workspaces in the current directory, while
+//! [`discover_in`] looks for them in the given one, which is useful when embedding.
+//!
+//! Context files next to discovered files are used as well, see [`workspace`].
+//!
+//! [`workspace`]: crate::workspace
+
+use std::{
+    env::current_dir,
+    path::{Path, PathBuf},
+};
 
 use miette::Diagnostic;
 use thiserror::Error;
+use tracing::{debug, info};
 
 use crate::{
     load::load,
-    workspace::{PyProject, Workspace},
+    workspace::{PyProject, Source, Workspace},
 };
 
 /// Represents errors that can occur when fetching the current directory fails.
@@ -150,17 +161,96 @@ pub const PYPROJECT: &str = "pyproject.toml";
 /// Returns [`struct@Error`] if fetching the current directory, checking the existence
 /// or loading the workspace fails. Also returned when no workspace can be found.
 pub fn discover() -> Result<Workspace<'static>, Error> {
-    let mut path = current_dir().map_err(Error::new_current_directory)?;
+    discover_with_path().map(|(workspace, _)| workspace)
+}
+
+/// Similar to [`discover`], except the path to the file used is also returned.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] in the same cases as [`discover`].
+pub fn discover_with_path() -> Result<(Workspace<'static>, PathBuf), Error> {
+    let directory = current_dir().map_err(Error::new_current_directory)?;
+
+    find(directory, None)
+}
+
+/// Similar to [`discover`], except the `context` section is supplied (or overridden)
+/// by the given context file, see [`Workspace::load_with_context`].
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] in the same cases as [`discover`], as well as if loading
+/// the context file fails.
+pub fn discover_with_context<P: AsRef<Path>>(context: P) -> Result<Workspace<'static>, Error> {
+    let directory = current_dir().map_err(Error::new_current_directory)?;
+
+    find(directory, Some(context.as_ref())).map(|(workspace, _)| workspace)
+}
+
+/// Discovers workspaces in the given directory, returning the workspace
+/// along with the path to the file it was found in.
+///
+/// This function looks for the same files as [`discover`] does. Relative paths in the workspace
+/// are resolved against the given directory (see [`Workspace::resolve`]), so that they
+/// can be used regardless of the current directory.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if checking the existence or loading the workspace fails.
+/// Also returned when no workspace can be found.
+pub fn discover_in<P: AsRef<Path>>(directory: P) -> Result<(Workspace<'static>, PathBuf), Error> {
+    let directory = directory.as_ref();
+
+    let (mut workspace, path) = find(directory.to_owned(), None)?;
+
+    if !directory.as_os_str().is_empty() {
+        workspace.resolve(directory.to_owned());
+    }
+
+    Ok((workspace, path))
+}
+
+fn find(
+    directory: PathBuf,
+    context: Option<&Path>,
+) -> Result<(Workspace<'static>, PathBuf), Error> {
+    let mut path = directory;
 
     // try `changelogging.toml`
 
     path.push(CHANGELOGGING);
 
+    debug!("looking for `{}`", path.display());
+
     if path
         .try_exists()
         .map_err(|error| Error::new_existence(error, path.clone()))?
     {
-        return load(path.as_path()).map_err(Error::workspace);
+        let workspace: Workspace<'_> = match context {
+            Some(context) => Workspace::load_with_context(&path, context),
+            None => load(path.as_path()),
+        }
+        .map_err(Error::workspace)?;
+
+        let source = Source::Changelogging(path.clone());
+
+        info!("using {source}");
+
+        // users are often unsure which file is used when both are present
+
+        path.set_file_name(PYPROJECT);
+
+        if path.is_file() {
+            info!(
+                "ignoring `{}`, as `{CHANGELOGGING}` takes precedence",
+                path.display()
+            );
+        }
+
+        path.set_file_name(CHANGELOGGING);
+
+        return Ok((workspace.with_source(source), path));
     }
 
     // try `pyproject.toml` if it contains `tool.changelogging`
@@ -169,15 +259,27 @@ pub fn discover() -> Result<Workspace<'static>, Error> {
 
     path.push(PYPROJECT);
 
+    debug!("looking for `{}`", path.display());
+
     if path
         .try_exists()
         .map_err(|error| Error::new_existence(error, path.clone()))?
     {
-        let pyproject: PyProject<'_> = load(path.as_path()).map_err(Error::workspace)?;
+        let pyproject: PyProject<'_> = match context {
+            Some(context) => PyProject::load_with_context(&path, context),
+            None => load(path.as_path()),
+        }
+        .map_err(Error::workspace)?;
 
         if let Some(workspace) = pyproject.into_workspace() {
-            return Ok(workspace);
+            let source = Source::PyProject(path.clone());
+
+            info!("using {source}");
+
+            return Ok((workspace.with_source(source), path));
         }
+
+        debug!("`{}` does not contain `tool.changelogging`", path.display());
     }
 
     // not found
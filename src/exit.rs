@@ -0,0 +1,43 @@
+//! Exit codes.
+//!
+//! The [`Code`] enumeration defines the exit codes of `changelogging`,
+//! one per failure class, so that scripts can branch on the type of failure.
+
+use std::process::ExitCode;
+
+/// Represents exit codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Code {
+    /// Success.
+    Success = 0,
+    /// Failures that do not belong to any other class.
+    Failure = 1,
+    /// Invalid usage, reported by the argument parser.
+    Usage = 2,
+    /// Configuration errors, including invalid templates.
+    Config = 3,
+    /// No fragments found where at least one is required.
+    NoFragments = 4,
+    /// Validation failures, for instance invalid fragment names or problems found by `doctor`.
+    Validation = 5,
+    /// `git` failures.
+    Git = 6,
+    /// I/O failures.
+    Io = 7,
+    /// Network failures, for instance failed requests to the forge API.
+    Network = 8,
+}
+
+impl Code {
+    /// Returns the numeric value of [`Self`].
+    pub fn value(self) -> u8 {
+        self as u8
+    }
+}
+
+impl From<Code> for ExitCode {
+    fn from(code: Code) -> Self {
+        Self::from(code.value())
+    }
+}
@@ -0,0 +1,723 @@
+//! Forge API clients.
+//!
+//! This module provides [`Client`] that talks to the GitHub and GitLab APIs on behalf of
+//! network-backed features, so that they do not need to handle authentication themselves.
+//!
+//! The [`Remote`] (that is, the forge, its API and the repository) is resolved from the `forge`
+//! section, falling back to `context.url`, while tokens are discovered via [`token`].
+//!
+//! Responses are cached for the lifetime of the client, so that the same resource
+//! is requested at most once per run.
+//!
+//! Clients constructed in offline mode never send requests, returning [`OfflineError`] instead;
+//! callers are expected to check [`Client::is_offline`] to degrade gracefully.
+//!
+//! This module is only available with the `forge` feature enabled (which it is by default).
+
+use std::{cell::RefCell, collections::HashMap, env::var, time::Duration};
+
+use miette::Diagnostic;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use thiserror::Error;
+use time::{format_description::well_known::Rfc3339, OffsetDateTime, UtcOffset};
+use tracing::debug;
+use ureq::Agent;
+
+use crate::{
+    config::{Forge, Host},
+    exit::Code,
+    network::OFFLINE,
+    workspace::Workspace,
+};
+
+/// The environment variable containing the token for any forge.
+pub const TOKEN: &str = "CHANGELOGGING_TOKEN";
+
+/// The environment variables containing GitHub tokens, in order of precedence.
+pub const GITHUB_TOKENS: [&str; 2] = ["GITHUB_TOKEN", "GH_TOKEN"];
+
+/// The environment variables containing GitLab tokens, in order of precedence.
+pub const GITLAB_TOKENS: [&str; 1] = ["GITLAB_TOKEN"];
+
+/// The host of GitHub.
+pub const GITHUB_HOST: &str = "github.com";
+
+/// The base URL of the GitHub API.
+pub const GITHUB_API: &str = "https://api.github.com";
+
+/// The path of the API of GitHub Enterprise instances.
+pub const GITHUB_ENTERPRISE_API: &str = "api/v3";
+
+/// The path of the API of GitLab instances.
+pub const GITLAB_API: &str = "api/v4";
+
+/// The `User-Agent` of requests.
+pub const USER_AGENT: &str = concat!("changelogging/", env!("CARGO_PKG_VERSION"));
+
+/// The timeout of requests.
+pub const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The `Authorization` header.
+pub const AUTHORIZATION: &str = "Authorization";
+
+/// The `Accept` header.
+pub const ACCEPT: &str = "Accept";
+
+/// The media type of responses.
+pub const JSON: &str = "application/json";
+
+/// The number of items per page when listing.
+pub const PER_PAGE: usize = 100;
+
+/// The maximum number of pages requested when listing.
+pub const MAX_PAGES: usize = 100;
+
+const SCHEME: &str = "://";
+const SLASH: char = '/';
+const GIT: &str = ".git";
+const ENCODED_SLASH: &str = "%2F";
+
+/// The status code of responses to requests for missing resources.
+pub const NOT_FOUND: u16 = 404;
+
+/// Represents errors that occur when the repository can not be determined.
+#[derive(Debug, Error, Diagnostic)]
+#[error("can not determine the forge repository")]
+#[diagnostic(
+    code(changelogging::forge::missing_repository),
+    help("set `forge.repository` or `context.url`")
+)]
+pub struct MissingRepositoryError;
+
+/// Represents errors that can occur when sending requests.
+#[derive(Debug, Error, Diagnostic)]
+#[error("request to `{url}` failed")]
+#[diagnostic(
+    code(changelogging::forge::request),
+    help("check the network connection, the repository and the token")
+)]
+pub struct RequestError {
+    /// The underlying request error.
+    #[source]
+    pub source: ureq::Error,
+    /// The URL requested.
+    pub url: String,
+}
+
+impl RequestError {
+    /// Constructs [`Self`].
+    pub fn new(source: ureq::Error, url: String) -> Self {
+        Self { source, url }
+    }
+}
+
+/// Represents errors that occur when responses can not be understood.
+#[derive(Debug, Error, Diagnostic)]
+#[error("unexpected response from `{url}`")]
+#[diagnostic(
+    code(changelogging::forge::unexpected),
+    help("check whether `forge.host` and `forge.api` are correct")
+)]
+pub struct UnexpectedError {
+    /// The underlying deserialization error.
+    #[source]
+    pub source: serde_json::Error,
+    /// The URL requested.
+    pub url: String,
+}
+
+impl UnexpectedError {
+    /// Constructs [`Self`].
+    pub fn new(source: serde_json::Error, url: String) -> Self {
+        Self { source, url }
+    }
+}
+
+/// Represents errors that occur when requests are attempted in offline mode.
+#[derive(Debug, Error, Diagnostic)]
+#[error("can not request `{url}` in offline mode")]
+#[diagnostic(
+    code(changelogging::forge::offline),
+    help("drop `--offline` and unset `{OFFLINE}` to allow network access")
+)]
+pub struct OfflineError {
+    /// The URL that would be requested.
+    pub url: String,
+}
+
+impl OfflineError {
+    /// Constructs [`Self`].
+    pub fn new(url: String) -> Self {
+        Self { url }
+    }
+}
+
+/// Represents sources of errors that can occur when using the forge API.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Missing repository errors.
+    MissingRepository(#[from] MissingRepositoryError),
+    /// Request errors.
+    Request(#[from] RequestError),
+    /// Unexpected response errors.
+    Unexpected(#[from] UnexpectedError),
+    /// Offline errors.
+    Offline(#[from] OfflineError),
+}
+
+/// Represents errors that can occur when using the forge API.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to use the forge API")]
+#[diagnostic(
+    code(changelogging::forge),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`MissingRepositoryError`].
+    pub fn missing_repository(error: MissingRepositoryError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`RequestError`].
+    pub fn request(error: RequestError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`UnexpectedError`].
+    pub fn unexpected(error: UnexpectedError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`OfflineError`].
+    pub fn offline(error: OfflineError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`RequestError`] and constructs [`Self`] from it.
+    pub fn new_request(error: ureq::Error, url: String) -> Self {
+        Self::request(RequestError::new(error, url))
+    }
+
+    /// Constructs [`UnexpectedError`] and constructs [`Self`] from it.
+    pub fn new_unexpected(error: serde_json::Error, url: String) -> Self {
+        Self::unexpected(UnexpectedError::new(error, url))
+    }
+
+    /// Constructs [`OfflineError`] and constructs [`Self`] from it.
+    pub fn new_offline(url: String) -> Self {
+        Self::offline(OfflineError::new(url))
+    }
+
+    /// Checks whether the resource requested does not exist, that is, whether the response
+    /// has the [`NOT_FOUND`] status.
+    pub fn is_not_found(&self) -> bool {
+        matches!(
+            &self.source,
+            ErrorSource::Request(RequestError {
+                source: ureq::Error::StatusCode(NOT_FOUND),
+                ..
+            })
+        )
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::MissingRepository(_) => Code::Config,
+            ErrorSource::Request(_) | ErrorSource::Unexpected(_) | ErrorSource::Offline(_) => {
+                Code::Network
+            }
+        }
+    }
+}
+
+/// Splits the given project URL into its host and path, dropping the scheme,
+/// trailing slashes and the `.git` suffix.
+pub fn split_url(url: &str) -> Option<(&str, &str)> {
+    let url = url.split_once(SCHEME).map_or(url, |(_, rest)| rest);
+
+    let (host, path) = url.split_once(SLASH)?;
+
+    let path = path.trim_end_matches(SLASH);
+    let path = path.strip_suffix(GIT).unwrap_or(path);
+
+    if host.is_empty() || path.is_empty() {
+        None
+    } else {
+        Some((host, path))
+    }
+}
+
+/// Returns the base URL of the API of the given forge, hosted at the given host.
+pub fn default_api(host: Host, server: &str) -> String {
+    match host {
+        Host::GitHub if server == GITHUB_HOST => GITHUB_API.to_owned(),
+        Host::GitHub => format!("https://{server}/{GITHUB_ENTERPRISE_API}"),
+        Host::GitLab => format!("https://{server}/{GITLAB_API}"),
+    }
+}
+
+/// Represents remotes, that is, forges along with their APIs and repositories.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Remote {
+    /// The forge.
+    pub host: Host,
+    /// The base URL of the API.
+    pub api: String,
+    /// The repository.
+    pub repository: String,
+}
+
+impl Remote {
+    /// Constructs [`Self`].
+    pub fn new(host: Host, api: String, repository: String) -> Self {
+        Self {
+            host,
+            api,
+            repository,
+        }
+    }
+
+    /// Resolves [`Self`] from the given `forge` section and project URL.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`MissingRepositoryError`] if the repository is neither configured
+    /// nor can be inferred from the URL.
+    pub fn resolve(forge: &Forge<'_>, url: Option<&str>) -> Result<Self, MissingRepositoryError> {
+        let split = url.and_then(split_url);
+
+        let host = forge
+            .host
+            .or_else(|| url.map(Host::infer))
+            .unwrap_or(Host::GitHub);
+
+        let repository = forge
+            .repository
+            .as_deref()
+            .or_else(|| split.map(|(_, path)| path))
+            .ok_or(MissingRepositoryError)?
+            .to_owned();
+
+        let api = match forge.api.as_deref() {
+            Some(api) => api.trim_end_matches(SLASH).to_owned(),
+            None => match (host, split) {
+                (_, Some((server, _))) => default_api(host, server),
+                (Host::GitHub, None) => GITHUB_API.to_owned(),
+                (Host::GitLab, None) => return Err(MissingRepositoryError),
+            },
+        };
+
+        Ok(Self::new(host, api, repository))
+    }
+
+    /// Returns the URL of the given API path.
+    pub fn url(&self, path: &str) -> String {
+        format!("{api}/{path}", api = self.api)
+    }
+
+    /// Returns the API path of the repository.
+    pub fn repository_path(&self) -> String {
+        match self.host {
+            Host::GitHub => format!("repos/{}", self.repository),
+            Host::GitLab => format!("projects/{}", self.repository.replace(SLASH, ENCODED_SLASH)),
+        }
+    }
+
+    /// Returns the API path of the pull request (or merge request) with the given number.
+    pub fn pull_request_path(&self, number: u64) -> String {
+        let repository = self.repository_path();
+
+        match self.host {
+            Host::GitHub => format!("{repository}/pulls/{number}"),
+            Host::GitLab => format!("{repository}/merge_requests/{number}"),
+        }
+    }
+
+    /// Returns the API path of the issue with the given number.
+    ///
+    /// On GitHub, pull requests are also accessible as issues.
+    pub fn issue_path(&self, number: u64) -> String {
+        format!("{}/issues/{number}", self.repository_path())
+    }
+
+    /// Returns the API path of the given page of pull requests (or merge requests)
+    /// updated since the given time, most recently updated first.
+    pub fn pull_requests_path(&self, since: OffsetDateTime, page: usize) -> String {
+        let repository = self.repository_path();
+
+        match self.host {
+            Host::GitHub => format!(
+                "{repository}/pulls?state=closed&sort=updated&direction=desc\
+                &per_page={PER_PAGE}&page={page}"
+            ),
+            Host::GitLab => format!(
+                "{repository}/merge_requests?state=merged&order_by=updated_at&sort=desc\
+                &updated_after={since}&per_page={PER_PAGE}&page={page}",
+                since = format_time(since),
+            ),
+        }
+    }
+}
+
+/// Formats the given time according to RFC 3339, in UTC.
+pub fn format_time(time: OffsetDateTime) -> String {
+    time.to_offset(UtcOffset::UTC)
+        .format(&Rfc3339)
+        .unwrap_or_default()
+}
+
+/// Parses the given time according to RFC 3339.
+pub fn parse_time(string: &str) -> Option<OffsetDateTime> {
+    OffsetDateTime::parse(string, &Rfc3339).ok()
+}
+
+/// Returns the environment variables to discover tokens from, in order of precedence.
+pub fn token_variables(host: Host, token_env: Option<&str>) -> Vec<&str> {
+    let specific: &[&str] = match host {
+        Host::GitHub => &GITHUB_TOKENS,
+        Host::GitLab => &GITLAB_TOKENS,
+    };
+
+    token_env
+        .into_iter()
+        .chain([TOKEN])
+        .chain(specific.iter().copied())
+        .collect()
+}
+
+/// Discovers the token for the given forge.
+///
+/// The variable named by `token_env` is checked first, then [`TOKEN`], and then either
+/// [`GITHUB_TOKENS`] or [`GITLAB_TOKENS`], depending on the forge. Empty values are ignored.
+pub fn token(host: Host, token_env: Option<&str>) -> Option<String> {
+    token_variables(host, token_env)
+        .into_iter()
+        .find_map(|name| var(name).ok().filter(|value| !value.is_empty()))
+}
+
+/// Represents pull requests (or merge requests, in GitLab terms).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PullRequest {
+    /// The number of the pull request.
+    pub number: u64,
+    /// The title of the pull request.
+    pub title: String,
+    /// The labels of the pull request.
+    pub labels: Vec<String>,
+    /// The username of the author, if known.
+    pub author: Option<String>,
+    /// The time the pull request was merged at, if it was.
+    pub merged_at: Option<String>,
+}
+
+impl PullRequest {
+    /// Checks whether the pull request was merged.
+    pub fn is_merged(&self) -> bool {
+        self.merged_at.is_some()
+    }
+
+    /// Checks whether the pull request was merged after the given time.
+    pub fn is_merged_after(&self, since: OffsetDateTime) -> bool {
+        self.merged_at
+            .as_deref()
+            .and_then(parse_time)
+            .is_some_and(|merged_at| merged_at > since)
+    }
+}
+
+#[derive(Deserialize)]
+struct GitHubLabel {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubUser {
+    login: String,
+}
+
+#[derive(Deserialize)]
+struct GitHubPullRequest {
+    number: u64,
+    title: String,
+    #[serde(default)]
+    labels: Vec<GitHubLabel>,
+    user: Option<GitHubUser>,
+    merged_at: Option<String>,
+    updated_at: Option<String>,
+}
+
+impl GitHubPullRequest {
+    fn is_updated_after(&self, since: OffsetDateTime) -> bool {
+        self.updated_at
+            .as_deref()
+            .and_then(parse_time)
+            .is_none_or(|updated_at| updated_at > since)
+    }
+}
+
+impl From<GitHubPullRequest> for PullRequest {
+    fn from(pull_request: GitHubPullRequest) -> Self {
+        Self {
+            number: pull_request.number,
+            title: pull_request.title,
+            labels: pull_request
+                .labels
+                .into_iter()
+                .map(|label| label.name)
+                .collect(),
+            author: pull_request.user.map(|user| user.login),
+            merged_at: pull_request.merged_at,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+#[derive(Deserialize)]
+struct GitLabMergeRequest {
+    iid: u64,
+    title: String,
+    #[serde(default)]
+    labels: Vec<String>,
+    author: Option<GitLabUser>,
+    merged_at: Option<String>,
+}
+
+impl From<GitLabMergeRequest> for PullRequest {
+    fn from(merge_request: GitLabMergeRequest) -> Self {
+        Self {
+            number: merge_request.iid,
+            title: merge_request.title,
+            labels: merge_request.labels,
+            author: merge_request.author.map(|author| author.username),
+            merged_at: merge_request.merged_at,
+        }
+    }
+}
+
+/// Represents forge API clients.
+#[derive(Debug)]
+pub struct Client {
+    /// The remote to talk to.
+    pub remote: Remote,
+    token: Option<String>,
+    offline: bool,
+    agent: Agent,
+    cache: RefCell<HashMap<String, Value>>,
+}
+
+impl Client {
+    /// Constructs [`Self`].
+    ///
+    /// If `offline` is set, the client never sends requests.
+    pub fn new(remote: Remote, token: Option<String>, offline: bool) -> Self {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(TIMEOUT))
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+
+        Self {
+            remote,
+            token,
+            offline,
+            agent,
+            cache: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// Constructs [`Self`] from the given workspace, resolving the [`Remote`]
+    /// and discovering the [`token`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the repository can not be determined.
+    pub fn from_workspace(workspace: &Workspace<'_>, offline: bool) -> Result<Self, Error> {
+        let forge = &workspace.config.forge;
+
+        let remote = Remote::resolve(forge, workspace.context.url.as_deref())
+            .map_err(Error::missing_repository)?;
+
+        let token = token(remote.host, forge.token_env.as_deref());
+
+        if token.is_none() {
+            debug!("no token found, sending unauthenticated requests");
+        }
+
+        Ok(Self::new(remote, token, offline))
+    }
+
+    /// Checks whether the client has the token.
+    pub fn is_authenticated(&self) -> bool {
+        self.token.is_some()
+    }
+
+    /// Checks whether the client is in offline mode.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Fetches the JSON value at the given API path, caching it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the request fails or the response is not JSON.
+    /// Also returned in offline mode, unless the value is cached.
+    pub fn get(&self, path: &str) -> Result<Value, Error> {
+        let url = self.remote.url(path);
+
+        if let Some(value) = self.cache.borrow().get(&url) {
+            debug!("using cached `{url}`");
+
+            return Ok(value.clone());
+        }
+
+        if self.offline {
+            return Err(Error::new_offline(url));
+        }
+
+        debug!("requesting `{url}`");
+
+        let mut request = self.agent.get(&url).header(ACCEPT, JSON);
+
+        if let Some(token) = self.token.as_deref() {
+            request = request.header(AUTHORIZATION, format!("Bearer {token}"));
+        }
+
+        let value: Value = request
+            .call()
+            .and_then(|mut response| response.body_mut().read_json())
+            .map_err(|error| Error::new_request(error, url.clone()))?;
+
+        self.cache.borrow_mut().insert(url, value.clone());
+
+        Ok(value)
+    }
+
+    /// Fetches the value at the given API path and deserializes it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the request fails or the response is unexpected.
+    pub fn get_as<T: DeserializeOwned>(&self, path: &str) -> Result<T, Error> {
+        let value = self.get(path)?;
+
+        serde_json::from_value(value)
+            .map_err(|error| Error::new_unexpected(error, self.remote.url(path)))
+    }
+
+    /// Fetches the pull request (or merge request) with the given number.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the request fails or the response is unexpected.
+    pub fn pull_request(&self, number: u64) -> Result<PullRequest, Error> {
+        let path = self.remote.pull_request_path(number);
+
+        let pull_request = match self.remote.host {
+            Host::GitHub => self.get_as::<GitHubPullRequest>(&path)?.into(),
+            Host::GitLab => self.get_as::<GitLabMergeRequest>(&path)?.into(),
+        };
+
+        Ok(pull_request)
+    }
+
+    /// Checks whether the pull request (or merge request) or the issue
+    /// with the given number exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if any request fails for reasons other than
+    /// the resource not existing.
+    pub fn reference_exists(&self, number: u64) -> Result<bool, Error> {
+        // merge requests and issues are numbered separately on GitLab
+
+        let paths = match self.remote.host {
+            Host::GitHub => vec![self.remote.issue_path(number)],
+            Host::GitLab => vec![
+                self.remote.pull_request_path(number),
+                self.remote.issue_path(number),
+            ],
+        };
+
+        for path in paths {
+            match self.get(&path) {
+                Ok(_) => return Ok(true),
+                Err(error) if error.is_not_found() => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(false)
+    }
+
+    /// Lists pull requests (or merge requests) merged after the given time,
+    /// ordered by their numbers.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if any request fails or any response is unexpected.
+    pub fn merged_pull_requests(&self, since: OffsetDateTime) -> Result<Vec<PullRequest>, Error> {
+        let mut pull_requests = Vec::new();
+
+        for page in 1..=MAX_PAGES {
+            let path = self.remote.pull_requests_path(since, page);
+
+            // pages are ordered by update times, so there is nothing to find past stale pages
+
+            let (count, done) = match self.remote.host {
+                Host::GitHub => {
+                    let page = self.get_as::<Vec<GitHubPullRequest>>(&path)?;
+
+                    let count = page.len();
+
+                    let done = !page.iter().any(|item| item.is_updated_after(since));
+
+                    pull_requests.extend(page.into_iter().map(PullRequest::from));
+
+                    (count, done)
+                }
+                Host::GitLab => {
+                    let page = self.get_as::<Vec<GitLabMergeRequest>>(&path)?;
+
+                    let count = page.len();
+
+                    pull_requests.extend(page.into_iter().map(PullRequest::from));
+
+                    (count, false)
+                }
+            };
+
+            if done || count < PER_PAGE {
+                break;
+            }
+        }
+
+        pull_requests.retain(|pull_request| pull_request.is_merged_after(since));
+
+        pull_requests.sort_by_key(|pull_request| pull_request.number);
+
+        pull_requests.dedup_by_key(|pull_request| pull_request.number);
+
+        Ok(pull_requests)
+    }
+}
@@ -1,9 +1,48 @@
 //! Changelog fragments.
+//!
+//! Fragments can start with the *front matter*, which is TOML enclosed in `+++` lines:
+//!
+//! ```md
+//! +++
+//! issue = 42
+//! +++
+//!
+//! Added cool features!
+//! ```
+//!
+//! The front matter is stored in the [`meta`] field of fragments.
+//!
+//! Fragments are sorted by their IDs within sections, unless they specify the integer
+//! `priority` in front matter, in which case fragments with higher priorities come first.
+//! Alternatively, contents can start with `!` markers followed by whitespace,
+//! each of them adding one to the priority (unless it is given in front matter):
+//!
+//! ```md
+//! !! Added the headline feature!
+//! ```
+//!
+//! Bullets can also be grouped by the `area` specified in front matter (see `areas`
+//! in [`config`]).
+//!
+//! Fragments are loaded via [`FragmentFile`], which reads each file once and lends its name
+//! and contents to the fragment parsed from it.
+//!
+//! Fragments can also be constructed programmatically via [`FragmentBuilder`]
+//! (see [`Fragment::builder`]), optionally validating their types against the configuration,
+//! and saved back to disk via [`Fragment::save`], which derives canonical file names.
+//!
+//! [`meta`]: Fragment::meta
+//! [`config`]: crate::config
 
+#[cfg(feature = "fs")]
+use std::fs::{read_to_string, write, File};
+#[cfg(feature = "fs")]
+use std::io::Write;
 use std::{
     borrow::Cow,
-    collections::HashMap,
-    fs::read_to_string,
+    cmp::Ordering,
+    collections::{BTreeSet, HashMap},
+    fmt,
     path::{Path, PathBuf},
     str::FromStr,
 };
@@ -11,14 +50,23 @@ use std::{
 use miette::Diagnostic;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use time::{Date, Month};
 
+#[cfg(feature = "fs")]
 use crate::load::Load;
+#[cfg(feature = "async")]
+use crate::load::LoadAsync;
+use crate::{
+    config::Config,
+    date::parse_str,
+    text::{normalize, BOM},
+};
 
 /// Represents integer IDs of fragments.
 pub type Integer = u32;
 
 /// Represents fragment IDs.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Id<'i> {
     /// Integer fragment ID.
@@ -44,6 +92,42 @@ impl<'i> Id<'i> {
     }
 }
 
+impl<'i> Id<'i> {
+    /// Parses the given string into [`Self`], borrowing string IDs from it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InvalidIdError`] if the string is neither an integer (consisting of ASCII digits,
+    /// possibly zero-padded) nor a non-empty string prefixed with [`STRING_PREFIX`].
+    pub fn parse_borrowed(string: &'i str) -> Result<Self, InvalidIdError> {
+        let error = || InvalidIdError::new(string.to_owned());
+
+        if let Some(stripped) = string.strip_prefix(STRING_PREFIX) {
+            if stripped.is_empty() {
+                return Err(error());
+            }
+
+            return Ok(Self::borrowed(stripped));
+        }
+
+        // `parse` accepts leading signs, which are not allowed here
+
+        if string.is_empty() || !string.bytes().all(|byte| byte.is_ascii_digit()) {
+            return Err(error());
+        }
+
+        string.parse().map(Self::integer).map_err(|_| error())
+    }
+
+    /// Converts [`Self`] into the owned version.
+    pub fn into_owned(self) -> Id<'static> {
+        match self {
+            Self::Integer(value) => Id::Integer(value),
+            Self::String(string) => Id::owned(string.into_owned()),
+        }
+    }
+}
+
 impl Id<'_> {
     /// Checks if [`Self`] is [`Integer`].
     pub fn is_integer(&self) -> bool {
@@ -60,13 +144,15 @@ impl FromStr for Id<'_> {
     type Err = InvalidIdError;
 
     fn from_str(string: &str) -> Result<Self, Self::Err> {
-        if let Some(stripped) = string.strip_prefix(STRING_PREFIX) {
-            Ok(Self::owned(stripped.to_owned()))
-        } else {
-            string
-                .parse()
-                .map(Self::integer)
-                .map_err(|_| Self::Err::new(string.to_owned()))
+        Id::parse_borrowed(string).map(Id::into_owned)
+    }
+}
+
+impl fmt::Display for Id<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Integer(value) => value.fmt(formatter),
+            Self::String(string) => write!(formatter, "{STRING_PREFIX}{string}"),
         }
     }
 }
@@ -102,6 +188,34 @@ pub const STRING_PREFIX: char = '~';
 )]
 pub struct UnexpectedEofError;
 
+/// Represents errors that can occur when parsing fragment types.
+#[derive(Debug, Error, Diagnostic)]
+#[error("invalid type `{type_name}`")]
+#[diagnostic(
+    code(changelogging::fragment::invalid_type),
+    help("fragment types must be non-empty and must not start with digits")
+)]
+pub struct InvalidTypeError {
+    /// The type that is invalid.
+    pub type_name: String,
+}
+
+impl InvalidTypeError {
+    /// Constructs [`Self`].
+    pub fn new(type_name: String) -> Self {
+        Self { type_name }
+    }
+}
+
+/// Represents errors that can occur when names contain empty segments.
+#[derive(Debug, Error, Diagnostic)]
+#[error("empty segment")]
+#[diagnostic(
+    code(changelogging::fragment::empty_segment),
+    help("make sure the name contains neither consecutive nor trailing dots")
+)]
+pub struct EmptySegmentError;
+
 /// Represents sources of errors that can occur while parsing into [`Partial`].
 #[derive(Debug, Error, Diagnostic)]
 #[error(transparent)]
@@ -111,6 +225,10 @@ pub enum ParseErrorSource {
     InvalidId(#[from] InvalidIdError),
     /// Unexpected EOF errors.
     UnexpectedEof(#[from] UnexpectedEofError),
+    /// Invalid type errors.
+    InvalidType(#[from] InvalidTypeError),
+    /// Empty segment errors.
+    EmptySegment(#[from] EmptySegmentError),
 }
 
 /// Represents errors that can occur while parsing into [`Partial`].
@@ -145,6 +263,16 @@ impl ParseError {
         Self::new(error.into(), name)
     }
 
+    /// Constructs [`Self`] from [`InvalidTypeError`].
+    pub fn invalid_type(error: InvalidTypeError, name: String) -> Self {
+        Self::new(error.into(), name)
+    }
+
+    /// Constructs [`Self`] from [`EmptySegmentError`].
+    pub fn empty_segment(error: EmptySegmentError, name: String) -> Self {
+        Self::new(error.into(), name)
+    }
+
     /// Constructs [`InvalidIdError`] and constructs [`Self`] from it.
     pub fn new_invalid_id(string: String, name: String) -> Self {
         Self::invalid_id(InvalidIdError::new(string), name)
@@ -154,44 +282,175 @@ impl ParseError {
     pub fn new_unexpected_eof(name: String) -> Self {
         Self::unexpected_eof(UnexpectedEofError, name)
     }
+
+    /// Constructs [`InvalidTypeError`] and constructs [`Self`] from it.
+    pub fn new_invalid_type(type_name: String, name: String) -> Self {
+        Self::invalid_type(InvalidTypeError::new(type_name), name)
+    }
+
+    /// Constructs [`EmptySegmentError`] and constructs [`Self`] from it.
+    pub fn new_empty_segment(name: String) -> Self {
+        Self::empty_segment(EmptySegmentError, name)
+    }
 }
 
-/// Represents partial fragments.
+/// Represents partial fragments, parsed from fragment names.
+///
+/// Names are dot-separated segments, following this grammar:
+///
+/// ```text
+/// name = id "." type *("." segment) ["." extension]
+/// id = 1*digit / "~" 1*character
+/// type = (character except digit) *character
+/// ```
+///
+/// That is, names start with IDs, which are either integers (possibly zero-padded,
+/// so `0042` is the same as `42`) or strings prefixed with [`STRING_PREFIX`], followed by types,
+/// which can not start with digits (so names like `1.2.3.feature.md` are rejected instead of
+/// being parsed into the type `2`).
+///
+/// The last segment after the type is the extension, while the ones in between
+/// are captured as extra segments. For instance, `13.feature.2.md` has the extra segment `2`
+/// and the `md` extension. No segment can be empty.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Partial<'p> {
     /// The ID of the fragment.
     pub id: Id<'p>,
     /// The type of the fragment.
     pub type_name: Cow<'p, str>,
+    /// The extra segments between the type and the extension.
+    #[serde(default)]
+    pub segments: Vec<Cow<'p, str>>,
+    /// The extension of the fragment, if any.
+    #[serde(default)]
+    pub extension: Option<Cow<'p, str>>,
 }
 
 impl<'p> Partial<'p> {
-    /// Constructs [`Self`].
+    /// Constructs [`Self`] without extra segments and the extension.
     pub fn new(id: Id<'p>, type_name: Cow<'p, str>) -> Self {
-        Self { id, type_name }
+        Self {
+            id,
+            type_name,
+            segments: Vec::new(),
+            extension: None,
+        }
     }
-}
 
-const DOT: char = '.';
+    /// Replaces the extra segments of [`Self`] with the given ones.
+    #[must_use]
+    pub fn with_segments(self, segments: Vec<Cow<'p, str>>) -> Self {
+        Self { segments, ..self }
+    }
 
-impl FromStr for Partial<'_> {
-    type Err = ParseError;
+    /// Replaces the extension of [`Self`] with the given one.
+    #[must_use]
+    pub fn with_extension(self, extension: Option<Cow<'p, str>>) -> Self {
+        Self { extension, ..self }
+    }
 
-    fn from_str(name: &str) -> Result<Self, Self::Err> {
+    /// Parses the given name into [`Self`], borrowing from it.
+    ///
+    /// See [`Self`] for the grammar of names.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ParseError`] if the ID is invalid, the type is missing or invalid,
+    /// or any of the segments is empty.
+    pub fn parse_borrowed(name: &'p str) -> Result<Self, ParseError> {
         let mut split = name.split(DOT);
 
         let id = split
             .next()
-            .ok_or_else(|| ParseError::new_unexpected_eof(name.to_owned()))?
-            .parse()
-            .map_err(|error| ParseError::invalid_id(error, name.to_owned()))?;
+            .ok_or_else(|| ParseError::new_unexpected_eof(name.to_owned()))
+            .and_then(|string| {
+                Id::parse_borrowed(string)
+                    .map_err(|error| ParseError::invalid_id(error, name.to_owned()))
+            })?;
 
         let type_name = split
             .next()
-            .ok_or_else(|| ParseError::new_unexpected_eof(name.to_owned()))?
-            .to_owned();
+            .ok_or_else(|| ParseError::new_unexpected_eof(name.to_owned()))?;
+
+        if !is_valid_type(type_name) {
+            return Err(ParseError::new_invalid_type(
+                type_name.to_owned(),
+                name.to_owned(),
+            ));
+        }
 
-        Ok(Self::new(id, Cow::Owned(type_name)))
+        let mut segments: Vec<Cow<'p, str>> = split.map(Cow::Borrowed).collect();
+
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return Err(ParseError::new_empty_segment(name.to_owned()));
+        }
+
+        let extension = segments.pop();
+
+        Ok(Self::new(id, Cow::Borrowed(type_name))
+            .with_segments(segments)
+            .with_extension(extension))
+    }
+
+    /// Returns the canonical name of [`Self`], which parses back into it.
+    ///
+    /// For instance, the partial parsed from `0013.feature.md` is named `13.feature.md`.
+    pub fn name(&self) -> String {
+        let mut name = format!(
+            "{id}{DOT}{type_name}",
+            id = self.id,
+            type_name = self.type_name
+        );
+
+        for segment in self.segments.iter().chain(self.extension.as_ref()) {
+            name.push(DOT);
+            name.push_str(segment);
+        }
+
+        name
+    }
+
+    /// Converts [`Self`] into the owned version.
+    pub fn into_owned(self) -> Partial<'static> {
+        let segments = self
+            .segments
+            .into_iter()
+            .map(|segment| Cow::Owned(segment.into_owned()))
+            .collect();
+
+        let extension = self
+            .extension
+            .map(|extension| Cow::Owned(extension.into_owned()));
+
+        Partial::new(
+            self.id.into_owned(),
+            Cow::Owned(self.type_name.into_owned()),
+        )
+        .with_segments(segments)
+        .with_extension(extension)
+    }
+}
+
+fn is_valid_type(type_name: &str) -> bool {
+    type_name
+        .chars()
+        .next()
+        .is_some_and(|character| !character.is_ascii_digit())
+}
+
+const DOT: char = '.';
+
+impl FromStr for Partial<'_> {
+    type Err = ParseError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Partial::parse_borrowed(name).map(Partial::into_owned)
+    }
+}
+
+impl fmt::Display for Partial<'_> {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.name().fmt(formatter)
     }
 }
 
@@ -236,6 +495,14 @@ pub fn path_name(path: &Path) -> Option<&str> {
     path.file_name().and_then(|os_string| os_string.to_str())
 }
 
+/// The prefix of hidden files, like `.gitkeep`, which are never fragments.
+pub const HIDDEN: char = '.';
+
+/// Checks whether the given path points to the hidden file, see [`HIDDEN`].
+pub fn is_hidden(path: &Path) -> bool {
+    path_name(path).is_some_and(|name| name.starts_with(HIDDEN))
+}
+
 /// Represents errors that can occur when reading files.
 #[derive(Debug, Error, Diagnostic)]
 #[error("read failed")]
@@ -254,6 +521,26 @@ pub struct ReadError(#[from] pub std::io::Error);
 )]
 pub struct InvalidUtf8Error;
 
+/// Represents errors that can occur when parsing front matter.
+///
+/// The underlying error is boxed, since it is large.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to parse front matter")]
+#[diagnostic(
+    code(changelogging::fragment::front_matter),
+    help("front matter must be valid TOML")
+)]
+pub struct FrontMatterError(#[from] pub Box<toml::de::Error>);
+
+/// Represents errors that can occur when front matter is not terminated.
+#[derive(Debug, Error, Diagnostic)]
+#[error("unterminated front matter")]
+#[diagnostic(
+    code(changelogging::fragment::unterminated_front_matter),
+    help("front matter must be closed with the `+++` line")
+)]
+pub struct UnterminatedFrontMatterError;
+
 /// Represents sources of errors that can occur when loading [`Fragment`] values.
 #[derive(Debug, Error, Diagnostic)]
 #[error(transparent)]
@@ -265,6 +552,10 @@ pub enum ErrorSource {
     Parse(#[from] ParseError),
     /// Read errors.
     Read(#[from] ReadError),
+    /// Front matter errors.
+    FrontMatter(#[from] FrontMatterError),
+    /// Unterminated front matter errors.
+    UnterminatedFrontMatter(#[from] UnterminatedFrontMatterError),
 }
 
 /// Represents errors that can occur when loading [`Fragment`] values.
@@ -304,6 +595,16 @@ impl Error {
         Self::new(error.into(), path)
     }
 
+    /// Constructs [`Self`] from [`FrontMatterError`].
+    pub fn front_matter(error: FrontMatterError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`UnterminatedFrontMatterError`].
+    pub fn unterminated_front_matter(error: UnterminatedFrontMatterError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
     /// Constructs [`InvalidUtf8Error`] and constructs [`Self`] from it.
     pub fn new_invalid_utf8(path: PathBuf) -> Self {
         Self::invalid_utf8(InvalidUtf8Error, path)
@@ -313,10 +614,202 @@ impl Error {
     pub fn new_read(error: std::io::Error, path: PathBuf) -> Self {
         Self::read(ReadError(error), path)
     }
+
+    /// Constructs [`FrontMatterError`] and constructs [`Self`] from it.
+    pub fn new_front_matter(error: toml::de::Error, path: PathBuf) -> Self {
+        Self::front_matter(FrontMatterError(Box::new(error)), path)
+    }
+
+    /// Constructs [`UnterminatedFrontMatterError`] and constructs [`Self`] from it.
+    pub fn new_unterminated_front_matter(path: PathBuf) -> Self {
+        Self::unterminated_front_matter(UnterminatedFrontMatterError, path)
+    }
+}
+
+/// Represents errors that can occur when serializing front matter.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to serialize front matter")]
+#[diagnostic(
+    code(changelogging::fragment::serialize),
+    help("front matter must be representable as TOML")
+)]
+pub struct SerializeError(#[from] pub toml::ser::Error);
+
+/// Represents errors that can occur when writing files.
+#[derive(Debug, Error, Diagnostic)]
+#[error("write failed")]
+#[diagnostic(
+    code(changelogging::fragment::write),
+    help("check that the file does not already exist and the directory is accessible")
+)]
+pub struct WriteError(#[from] pub std::io::Error);
+
+/// Represents sources of errors that can occur when saving [`Fragment`] values.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum SaveErrorSource {
+    /// Serialize errors.
+    Serialize(#[from] SerializeError),
+    /// Write errors.
+    Write(#[from] WriteError),
+}
+
+/// Represents errors that can occur when saving [`Fragment`] values.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to save `{path}`")]
+#[diagnostic(
+    code(changelogging::fragment::save),
+    help("see the report for more information")
+)]
+pub struct SaveError {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: SaveErrorSource,
+    /// The path provided.
+    pub path: PathBuf,
+}
+
+impl SaveError {
+    /// Constructs [`Self`].
+    pub fn new(source: SaveErrorSource, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+
+    /// Constructs [`Self`] from [`SerializeError`].
+    pub fn serialize(error: SerializeError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`Self`] from [`WriteError`].
+    pub fn write(error: WriteError, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
+    /// Constructs [`WriteError`] and constructs [`Self`] from it.
+    pub fn new_write(error: std::io::Error, path: PathBuf) -> Self {
+        Self::write(WriteError(error), path)
+    }
+}
+
+/// Represents fragment metadata, specified in front matter.
+pub type Meta = crate::table::Table;
+
+/// The delimiter of front matter.
+pub const FRONT_MATTER: &str = "+++";
+
+/// The `area` literal, used as the front matter key.
+pub const AREA: &str = "area";
+
+/// The `priority` literal, used as the front matter key.
+pub const PRIORITY: &str = "priority";
+
+/// The `date` literal, used as the front matter key.
+pub const DATE: &str = "date";
+
+/// The `author` literal, used as the front matter key.
+pub const AUTHOR: &str = "author";
+
+/// The `breaking` literal, used as the front matter key.
+pub const BREAKING: &str = "breaking";
+
+/// The `cve` literal, used as the front matter key.
+pub const CVE: &str = "cve";
+
+/// The `severity` literal, used as the front matter key.
+pub const SEVERITY: &str = "severity";
+
+/// The `advisory_url` literal, used as the front matter key.
+pub const ADVISORY_URL: &str = "advisory_url";
+
+/// The severities that security fragments can specify, from the lowest.
+pub const SEVERITIES: [&str; 4] = ["low", "medium", "high", "critical"];
+
+/// The prefix of CVE IDs.
+pub const CVE_PREFIX: &str = "CVE-";
+
+const CVE_YEAR_DIGITS: usize = 4;
+const CVE_MIN_NUMBER_DIGITS: usize = 4;
+
+const CVE_SEPARATOR: char = '-';
+
+/// Checks whether the given string is a valid CVE ID, like `CVE-2024-12345`.
+///
+/// CVE IDs consist of the `CVE-` prefix, the four-digit year and the number of at least
+/// four digits, separated by dashes.
+pub fn is_cve(string: &str) -> bool {
+    let Some((year, number)) = string
+        .strip_prefix(CVE_PREFIX)
+        .and_then(|rest| rest.split_once(CVE_SEPARATOR))
+    else {
+        return false;
+    };
+
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+
+    year.len() == CVE_YEAR_DIGITS
+        && number.len() >= CVE_MIN_NUMBER_DIGITS
+        && is_digits(year)
+        && is_digits(number)
+}
+
+/// The marker that increases priorities of fragments.
+pub const MARKER: char = '!';
+
+/// Represents priorities of fragments.
+pub type Priority = i64;
+
+/// Splits the given content into the priority given by the leading markers and the rest.
+///
+/// Markers are only recognized when followed by whitespace, so that contents like `!important`
+/// are left intact.
+pub fn split_markers(content: &str) -> (Priority, &str) {
+    let rest = content.trim_start_matches(MARKER);
+
+    let count = content.len() - rest.len();
+
+    if count == 0 || !rest.starts_with(char::is_whitespace) {
+        return (0, content);
+    }
+
+    let priority = Priority::try_from(count).unwrap_or(Priority::MAX);
+
+    (priority, rest.trim_start())
+}
+
+/// Splits the given string (with normalized line endings) into front matter and content.
+///
+/// Returns [`None`] as front matter if the string does not start with the delimiter line.
+///
+/// # Errors
+///
+/// Returns [`UnterminatedFrontMatterError`] if the front matter is not closed.
+pub fn split_front_matter(
+    string: &str,
+) -> Result<(Option<&str>, &str), UnterminatedFrontMatterError> {
+    let Some(rest) = string
+        .strip_prefix(FRONT_MATTER)
+        .and_then(|rest| rest.strip_prefix('\n'))
+    else {
+        return Ok((None, string));
+    };
+
+    let mut offset = 0;
+
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == FRONT_MATTER {
+            return Ok((Some(&rest[..offset]), &rest[offset + line.len()..]));
+        }
+
+        offset += line.len();
+    }
+
+    Err(UnterminatedFrontMatterError)
 }
 
 /// Represents fragments.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Fragment<'f> {
     /// The partial fragment.
     ///
@@ -325,12 +818,143 @@ pub struct Fragment<'f> {
     pub partial: Partial<'f>,
     /// The fragment content.
     pub content: Cow<'f, str>,
+    /// The fragment metadata, specified in front matter.
+    #[serde(default)]
+    pub meta: Meta,
 }
 
 impl<'f> Fragment<'f> {
     /// Constructs [`Self`].
     pub fn new(partial: Partial<'f>, content: Cow<'f, str>) -> Self {
-        Self { partial, content }
+        let meta = Meta::new();
+
+        Self {
+            partial,
+            content,
+            meta,
+        }
+    }
+
+    /// Replaces the metadata of [`Self`] with the given one.
+    #[must_use]
+    pub fn with_meta(self, meta: Meta) -> Self {
+        Self { meta, ..self }
+    }
+
+    /// Returns the area of [`Self`], specified as `area` in front matter.
+    ///
+    /// Non-string areas are ignored.
+    pub fn area(&self) -> Option<&str> {
+        self.meta.get(AREA).and_then(toml::Value::as_str)
+    }
+
+    /// Returns the priority of [`Self`], specified as `priority` in front matter.
+    ///
+    /// Missing and non-integer priorities are considered to be zero.
+    pub fn priority(&self) -> Priority {
+        self.meta
+            .get(PRIORITY)
+            .and_then(toml::Value::as_integer)
+            .unwrap_or_default()
+    }
+
+    /// Returns the author of [`Self`], specified as `author` in front matter.
+    ///
+    /// Non-string and empty authors are ignored.
+    pub fn author(&self) -> Option<&str> {
+        self.meta
+            .get(AUTHOR)
+            .and_then(toml::Value::as_str)
+            .filter(|author| !author.is_empty())
+    }
+
+    /// Checks whether [`Self`] is breaking, specified as `breaking` in front matter.
+    ///
+    /// Missing and non-boolean values are considered to be `false`.
+    pub fn is_breaking(&self) -> bool {
+        self.meta
+            .get(BREAKING)
+            .and_then(toml::Value::as_bool)
+            .unwrap_or_default()
+    }
+
+    fn meta_str(&self, key: &str) -> Option<&str> {
+        self.meta
+            .get(key)
+            .and_then(toml::Value::as_str)
+            .filter(|string| !string.is_empty())
+    }
+
+    /// Returns the CVE ID of [`Self`], specified as `cve` in front matter.
+    ///
+    /// Non-string and empty IDs are ignored, while invalid ones are returned as they are
+    /// (see [`is_cve`]).
+    pub fn cve(&self) -> Option<&str> {
+        self.meta_str(CVE)
+    }
+
+    /// Returns the severity of [`Self`], specified as `severity` in front matter.
+    ///
+    /// Non-string and empty severities are ignored, while unknown ones are returned
+    /// as they are (see [`SEVERITIES`]).
+    pub fn severity(&self) -> Option<&str> {
+        self.meta_str(SEVERITY)
+    }
+
+    /// Returns the advisory URL of [`Self`], specified as `advisory_url` in front matter.
+    ///
+    /// Non-string and empty URLs are ignored.
+    pub fn advisory_url(&self) -> Option<&str> {
+        self.meta_str(ADVISORY_URL)
+    }
+
+    /// Returns the date of [`Self`], specified as `date` in front matter.
+    ///
+    /// Dates are either TOML dates (like `date = 2024-01-15`) or strings
+    /// in the `YYYY-MM-DD` format, while invalid ones are ignored.
+    pub fn date(&self) -> Option<Date> {
+        match self.meta.get(DATE)? {
+            toml::Value::Datetime(datetime) => {
+                let date = datetime.date?;
+
+                let month = Month::try_from(date.month).ok()?;
+
+                Date::from_calendar_date(date.year.into(), month, date.day).ok()
+            }
+            toml::Value::String(string) => parse_str(string).ok(),
+            _ => None,
+        }
+    }
+
+    /// Compares fragments by their priorities (higher ones come first), then by their dates
+    /// (older ones come first, followed by fragments without dates) and then like [`compare`].
+    ///
+    /// This is used to sort fragments within sections when sorting by dates.
+    ///
+    /// [`compare`]: Self::compare
+    pub fn compare_by_date(&self, other: &Self) -> Ordering {
+        other
+            .priority()
+            .cmp(&self.priority())
+            .then_with(|| match (self.date(), other.date()) {
+                (Some(this), Some(that)) => this.cmp(&that),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            })
+            .then_with(|| self.compare(other))
+    }
+
+    /// Compares fragments by their priorities (higher ones come first), then by their partials
+    /// and then by their contents, ignoring other metadata.
+    ///
+    /// This is used to sort fragments within sections.
+    pub fn compare(&self, other: &Self) -> Ordering {
+        other
+            .priority()
+            .cmp(&self.priority())
+            .then_with(|| self.partial.cmp(&other.partial))
+            .then_with(|| self.content.cmp(&other.content))
     }
 
     /// Constructs [`Self`] with the owned content.
@@ -342,31 +966,768 @@ impl<'f> Fragment<'f> {
     pub fn borrowed(partial: Partial<'f>, content: &'f str) -> Self {
         Self::new(partial, Cow::Borrowed(content))
     }
-}
 
-impl Load for Fragment<'_> {
-    type Error = Error;
+    /// Converts [`Self`] into the owned version.
+    pub fn into_owned(self) -> Fragment<'static> {
+        Fragment::owned(self.partial.into_owned(), self.content.into_owned()).with_meta(self.meta)
+    }
 
-    fn load<P: AsRef<Path>>(path: P) -> Result<Self, Self::Error> {
-        let path = path.as_ref();
+    /// Returns the canonical file name of [`Self`], see [`Partial::name`].
+    pub fn name(&self) -> String {
+        self.partial.name()
+    }
+
+    /// Returns the contents of the file of [`Self`], that is, the content
+    /// preceded by front matter, unless the metadata is empty.
+    ///
+    /// The contents are parsed back into the same content and metadata.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SerializeError`] if the metadata can not be serialized.
+    pub fn file_contents(&self) -> Result<String, SerializeError> {
+        let content = self.content.trim();
+
+        if self.meta.is_empty() {
+            return Ok(format!("{content}\n"));
+        }
+
+        let front_matter = toml::to_string(&self.meta)?;
+
+        Ok(format!(
+            "{FRONT_MATTER}\n{front_matter}{FRONT_MATTER}\n\n{content}\n"
+        ))
+    }
 
-        let name = path_name(path).ok_or_else(|| Self::Error::new_invalid_utf8(path.to_owned()))?;
+    /// Saves [`Self`] to the file with the canonical name (see [`name`]) in the given directory,
+    /// returning the path to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SaveError`] if serializing front matter fails or the file can not be created
+    /// (including when it already exists) or written to.
+    ///
+    /// [`name`]: Self::name
+    #[cfg(feature = "fs")]
+    pub fn save<D: AsRef<Path>>(&self, directory: D) -> Result<PathBuf, SaveError> {
+        let path = directory.as_ref().join(self.name());
 
-        let info = name
-            .parse()
-            .map_err(|error| Self::Error::parse(error, path.to_owned()))?;
+        let string = self
+            .file_contents()
+            .map_err(|error| SaveError::serialize(error, path.clone()))?;
 
-        let content = read_to_string(path)
-            .map_err(|error| Self::Error::new_read(error, path.to_owned()))?
-            .trim()
-            .to_owned();
+        File::options()
+            .create_new(true)
+            .write(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(string.as_bytes()))
+            .map_err(|error| SaveError::new_write(error, path.clone()))?;
 
-        Ok(Self::new(info, Cow::Owned(content)))
+        Ok(path)
     }
 }
 
-/// Represents arrays of fragments.
-pub type Fragments<'f> = [Fragment<'f>];
-
-/// Represents sections.
-pub type Sections<'s> = HashMap<Cow<'s, str>, Vec<Fragment<'s>>>;
+/// Writes the given fragment to the given path, replacing the file if it exists.
+///
+/// Unlike [`Fragment::save`], the name of the file is not derived from the fragment.
+///
+/// # Errors
+///
+/// Returns [`SaveError`] if serializing front matter or writing fails.
+#[cfg(feature = "fs")]
+pub fn write_fragment<P: AsRef<Path>>(path: P, fragment: &Fragment<'_>) -> Result<(), SaveError> {
+    let path = path.as_ref();
+
+    let string = fragment
+        .file_contents()
+        .map_err(|error| SaveError::serialize(error, path.to_owned()))?;
+
+    write(path, string).map_err(|error| SaveError::new_write(error, path.to_owned()))
+}
+
+impl Fragment<'_> {
+    /// Returns [`FragmentBuilder`] for constructing owned fragments programmatically.
+    pub fn builder() -> FragmentBuilder {
+        FragmentBuilder::new()
+    }
+
+    /// Validates the type of [`Self`] against the given configuration, resolving aliases.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] if the type is not known (that is, neither configured in `types`
+    /// nor one of the defaults) or not included in `order`.
+    pub fn validate_type(&self, config: &Config<'_>) -> Result<(), BuildError> {
+        let type_name = config.canonical_type(&self.partial.type_name);
+
+        if !config.types_with_defaults().contains_key(type_name) {
+            return Err(BuildError::new_unknown_type(type_name.to_owned()));
+        }
+
+        if !config.order.iter().any(|name| name == type_name) {
+            return Err(BuildError::new_unordered_type(type_name.to_owned()));
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents errors that can occur when required fields are not given to [`FragmentBuilder`].
+#[derive(Debug, Error, Diagnostic)]
+#[error("missing `{field}`")]
+#[diagnostic(
+    code(changelogging::fragment::missing),
+    help("fragments require both IDs and types")
+)]
+pub struct MissingError {
+    /// The name of the missing field.
+    pub field: &'static str,
+}
+
+impl MissingError {
+    /// Constructs [`Self`].
+    pub fn new(field: &'static str) -> Self {
+        Self { field }
+    }
+}
+
+/// Represents errors that can occur when fragment types are not known to the configuration.
+#[derive(Debug, Error, Diagnostic)]
+#[error("unknown type `{type_name}`")]
+#[diagnostic(
+    code(changelogging::fragment::unknown_type),
+    help("use one of the types configured in `types`")
+)]
+pub struct UnknownTypeError {
+    /// The unknown type.
+    pub type_name: String,
+}
+
+impl UnknownTypeError {
+    /// Constructs [`Self`].
+    pub fn new(type_name: String) -> Self {
+        Self { type_name }
+    }
+}
+
+/// Represents errors that can occur when fragment types are not included in `order`.
+#[derive(Debug, Error, Diagnostic)]
+#[error("type `{type_name}` is not included in `order`")]
+#[diagnostic(
+    code(changelogging::fragment::unordered_type),
+    help("use one of the types listed in `order`")
+)]
+pub struct UnorderedTypeError {
+    /// The type that is not included in `order`.
+    pub type_name: String,
+}
+
+impl UnorderedTypeError {
+    /// Constructs [`Self`].
+    pub fn new(type_name: String) -> Self {
+        Self { type_name }
+    }
+}
+
+/// Represents errors that can occur when fragment extensions are invalid.
+#[derive(Debug, Error, Diagnostic)]
+#[error("invalid extension `{extension}`")]
+#[diagnostic(
+    code(changelogging::fragment::invalid_extension),
+    help("extensions must be non-empty and must not contain dots")
+)]
+pub struct InvalidExtensionError {
+    /// The extension that is invalid.
+    pub extension: String,
+}
+
+impl InvalidExtensionError {
+    /// Constructs [`Self`].
+    pub fn new(extension: String) -> Self {
+        Self { extension }
+    }
+}
+
+/// Represents sources of errors that can occur when building fragments.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum BuildErrorSource {
+    /// Missing field errors.
+    Missing(#[from] MissingError),
+    /// Invalid ID errors.
+    InvalidId(#[from] InvalidIdError),
+    /// Invalid type errors.
+    InvalidType(#[from] InvalidTypeError),
+    /// Unknown type errors.
+    UnknownType(#[from] UnknownTypeError),
+    /// Unordered type errors.
+    UnorderedType(#[from] UnorderedTypeError),
+    /// Invalid extension errors.
+    InvalidExtension(#[from] InvalidExtensionError),
+}
+
+/// Represents errors that can occur when building fragments.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to build the fragment")]
+#[diagnostic(
+    code(changelogging::fragment::build),
+    help("see the report for more information")
+)]
+pub struct BuildError {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: BuildErrorSource,
+}
+
+impl BuildError {
+    /// Constructs [`Self`].
+    pub fn new(source: BuildErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`MissingError`].
+    pub fn missing(error: MissingError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`InvalidIdError`].
+    pub fn invalid_id(error: InvalidIdError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`InvalidTypeError`].
+    pub fn invalid_type(error: InvalidTypeError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`UnknownTypeError`].
+    pub fn unknown_type(error: UnknownTypeError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`UnorderedTypeError`].
+    pub fn unordered_type(error: UnorderedTypeError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`InvalidExtensionError`].
+    pub fn invalid_extension(error: InvalidExtensionError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`MissingError`] and constructs [`Self`] from it.
+    pub fn new_missing(field: &'static str) -> Self {
+        Self::missing(MissingError::new(field))
+    }
+
+    /// Constructs [`InvalidIdError`] and constructs [`Self`] from it.
+    pub fn new_invalid_id(string: String) -> Self {
+        Self::invalid_id(InvalidIdError::new(string))
+    }
+
+    /// Constructs [`InvalidTypeError`] and constructs [`Self`] from it.
+    pub fn new_invalid_type(type_name: String) -> Self {
+        Self::invalid_type(InvalidTypeError::new(type_name))
+    }
+
+    /// Constructs [`UnknownTypeError`] and constructs [`Self`] from it.
+    pub fn new_unknown_type(type_name: String) -> Self {
+        Self::unknown_type(UnknownTypeError::new(type_name))
+    }
+
+    /// Constructs [`UnorderedTypeError`] and constructs [`Self`] from it.
+    pub fn new_unordered_type(type_name: String) -> Self {
+        Self::unordered_type(UnorderedTypeError::new(type_name))
+    }
+
+    /// Constructs [`InvalidExtensionError`] and constructs [`Self`] from it.
+    pub fn new_invalid_extension(extension: String) -> Self {
+        Self::invalid_extension(InvalidExtensionError::new(extension))
+    }
+}
+
+/// The name of the ID field.
+pub const ID: &str = "id";
+
+/// The name of the type field.
+pub const TYPE: &str = "type";
+
+/// Builds owned fragments programmatically, see [`Fragment::builder`].
+///
+/// For instance, `Fragment::builder().id(13).type_name("feature").content("...").build()`
+/// builds the fragment with the same ID and type as the one parsed from `13.feature.md`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FragmentBuilder {
+    id: Option<Id<'static>>,
+    type_name: Option<String>,
+    extension: Option<String>,
+    content: String,
+    meta: Meta,
+}
+
+impl FragmentBuilder {
+    /// Constructs [`Self`] without any fields set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the integer ID.
+    #[must_use]
+    pub fn id(self, id: Integer) -> Self {
+        self.with_id(Id::integer(id))
+    }
+
+    /// Sets the string ID, which is given without [`STRING_PREFIX`].
+    #[must_use]
+    pub fn string_id<S: Into<String>>(self, id: S) -> Self {
+        self.with_id(Id::owned(id.into()))
+    }
+
+    /// Sets the ID.
+    #[must_use]
+    pub fn with_id(self, id: Id<'_>) -> Self {
+        let id = Some(id.into_owned());
+
+        Self { id, ..self }
+    }
+
+    /// Sets the type.
+    #[must_use]
+    pub fn type_name<S: Into<String>>(self, type_name: S) -> Self {
+        let type_name = Some(type_name.into());
+
+        Self { type_name, ..self }
+    }
+
+    /// Sets the extension, used when saving fragments (see [`Fragment::save`]).
+    #[must_use]
+    pub fn extension<S: Into<String>>(self, extension: S) -> Self {
+        let extension = Some(extension.into());
+
+        Self { extension, ..self }
+    }
+
+    /// Sets the content.
+    #[must_use]
+    pub fn content<S: Into<String>>(self, content: S) -> Self {
+        let content = content.into();
+
+        Self { content, ..self }
+    }
+
+    /// Sets the metadata, as if it were specified in front matter.
+    #[must_use]
+    pub fn meta(self, meta: Meta) -> Self {
+        Self { meta, ..self }
+    }
+
+    /// Sets the metadata value with the given key, as if it were specified in front matter.
+    #[must_use]
+    pub fn meta_value<K: Into<String>, V: Into<toml::Value>>(mut self, key: K, value: V) -> Self {
+        self.meta.insert(key.into(), value.into());
+
+        self
+    }
+
+    /// Builds the fragment.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] if either the ID or the type is missing,
+    /// the string ID is empty, or the type or the extension is invalid.
+    pub fn build(self) -> Result<Fragment<'static>, BuildError> {
+        let id = self.id.ok_or_else(|| BuildError::new_missing(ID))?;
+
+        if let Id::String(string) = &id {
+            if string.is_empty() || string.contains(DOT) {
+                return Err(BuildError::new_invalid_id(format!(
+                    "{STRING_PREFIX}{string}"
+                )));
+            }
+        }
+
+        let type_name = self
+            .type_name
+            .ok_or_else(|| BuildError::new_missing(TYPE))?;
+
+        if !is_valid_type(&type_name) || type_name.contains(DOT) {
+            return Err(BuildError::new_invalid_type(type_name));
+        }
+
+        if let Some(extension) = &self.extension {
+            if extension.is_empty() || extension.contains(DOT) {
+                return Err(BuildError::new_invalid_extension(extension.clone()));
+            }
+        }
+
+        let partial =
+            Partial::new(id, Cow::Owned(type_name)).with_extension(self.extension.map(Cow::Owned));
+
+        Ok(Fragment::owned(partial, self.content).with_meta(self.meta))
+    }
+
+    /// Builds the fragment, validating its type against the given configuration,
+    /// see [`Fragment::validate_type`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`BuildError`] if building fails (see [`build`]) or the type is either unknown
+    /// or not included in `order`.
+    ///
+    /// [`build`]: Self::build
+    pub fn build_for(self, config: &Config<'_>) -> Result<Fragment<'static>, BuildError> {
+        let fragment = self.build()?;
+
+        fragment.validate_type(config)?;
+
+        Ok(fragment)
+    }
+}
+
+fn valid_name(path: &Path) -> Result<&str, Error> {
+    let name = path_name(path).ok_or_else(|| Error::new_invalid_utf8(path.to_owned()))?;
+
+    validate_str(name).map_err(|error| Error::parse(error, path.to_owned()))?;
+
+    Ok(name)
+}
+
+fn clean(mut string: String) -> String {
+    if string.starts_with(BOM) {
+        string.drain(..BOM.len_utf8());
+    }
+
+    match normalize(&string) {
+        Cow::Owned(normalized) => normalized,
+        Cow::Borrowed(_) => string,
+    }
+}
+
+/// Represents fragment files, which own their names and contents once,
+/// lending them to fragments parsed from them (see [`fragment`]).
+///
+/// This avoids allocating strings for every field of every fragment,
+/// which matters when processing thousands of fragments.
+///
+/// [`fragment`]: Self::fragment
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FragmentFile {
+    /// The path to the file.
+    pub path: PathBuf,
+    /// The name of the file.
+    pub name: String,
+    /// The contents of the file, without the byte order mark and with normalized line endings.
+    pub string: String,
+}
+
+impl FragmentFile {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, name: String, string: String) -> Self {
+        Self { path, name, string }
+    }
+
+    /// Constructs [`Self`] from the given path and contents, validating the name
+    /// of the file, stripping the byte order mark and normalizing line endings.
+    ///
+    /// This allows processing fragments kept in memory, without touching the filesystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the name is invalid.
+    pub fn from_string<P: AsRef<Path>>(path: P, string: String) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        let name = valid_name(path)?;
+
+        Ok(Self::new(path.to_owned(), name.to_owned(), clean(string)))
+    }
+
+    /// Reads the fragment file from the given path.
+    ///
+    /// The name of the file is validated before reading it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the name is invalid or reading the file fails.
+    #[cfg(feature = "fs")]
+    pub fn read<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        let name = valid_name(path)?;
+
+        let string =
+            read_to_string(path).map_err(|error| Error::new_read(error, path.to_owned()))?;
+
+        Ok(Self::new(path.to_owned(), name.to_owned(), clean(string)))
+    }
+
+    /// Reads the fragment file from the given path asynchronously.
+    ///
+    /// The name of the file is validated before reading it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the name is invalid or reading the file fails.
+    #[cfg(feature = "async")]
+    pub async fn read_async<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        let name = valid_name(path)?;
+
+        let string = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|error| Error::new_read(error, path.to_owned()))?;
+
+        Ok(Self::new(path.to_owned(), name.to_owned(), clean(string)))
+    }
+
+    /// Parses the fragment, borrowing its type, string ID and content from [`Self`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the name is invalid or parsing front matter fails.
+    pub fn fragment(&self) -> Result<Fragment<'_>, Error> {
+        let path = self.path.as_path();
+
+        let partial = Partial::parse_borrowed(&self.name)
+            .map_err(|error| Error::parse(error, path.to_owned()))?;
+
+        let (front_matter, content) = split_front_matter(&self.string)
+            .map_err(|error| Error::unterminated_front_matter(error, path.to_owned()))?;
+
+        let mut meta = front_matter
+            .map(str::parse::<Meta>)
+            .transpose()
+            .map_err(|error| Error::new_front_matter(error, path.to_owned()))?
+            .unwrap_or_default();
+
+        let (priority, content) = split_markers(content.trim());
+
+        // priorities given in front matter take precedence over markers
+
+        if priority != 0 && !meta.contains_key(PRIORITY) {
+            meta.insert(PRIORITY.to_owned(), priority.into());
+        }
+
+        Ok(Fragment::borrowed(partial, content).with_meta(meta))
+    }
+}
+
+#[cfg(feature = "fs")]
+impl Load for FragmentFile {
+    type Error = Error;
+
+    fn load<P: AsRef<Path>>(path: P) -> Result<Self, Self::Error> {
+        Self::read(path)
+    }
+}
+
+#[cfg(feature = "async")]
+impl LoadAsync for FragmentFile {
+    type Error = Error;
+
+    async fn load_async<P: AsRef<Path> + Send>(path: P) -> Result<Self, Self::Error> {
+        Self::read_async(path).await
+    }
+}
+
+#[cfg(feature = "fs")]
+impl Load for Fragment<'_> {
+    type Error = Error;
+
+    fn load<P: AsRef<Path>>(path: P) -> Result<Self, Self::Error> {
+        let file = FragmentFile::read(path)?;
+
+        file.fragment().map(Fragment::into_owned)
+    }
+}
+
+/// Represents arrays of fragments.
+pub type Fragments<'f> = [Fragment<'f>];
+
+/// Represents selections of fragments by their IDs, used to build subsets of pending
+/// fragments (for instance, when backporting).
+///
+/// Fragments are selected if `only` is either not given or contains their IDs,
+/// unless `exclude` contains them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Selection {
+    /// The IDs of fragments to select, if given.
+    pub only: Option<BTreeSet<Id<'static>>>,
+    /// The IDs of fragments to exclude.
+    pub exclude: BTreeSet<Id<'static>>,
+}
+
+impl Selection {
+    /// Constructs [`Self`].
+    pub fn new(only: Option<BTreeSet<Id<'static>>>, exclude: BTreeSet<Id<'static>>) -> Self {
+        Self { only, exclude }
+    }
+
+    /// Checks whether [`Self`] selects every fragment.
+    pub fn is_all(&self) -> bool {
+        self.only.is_none() && self.exclude.is_empty()
+    }
+
+    /// Checks whether [`Self`] selects fragments with the given ID.
+    pub fn includes(&self, id: &Id<'_>) -> bool {
+        let id = id.clone().into_owned();
+
+        self.only.as_ref().is_none_or(|only| only.contains(&id)) && !self.exclude.contains(&id)
+    }
+}
+
+/// Represents sections.
+pub type Sections<'s> = HashMap<Cow<'s, str>, Vec<Fragment<'s>>>;
+
+/// Groups the given fragments into sections by their types, sorting fragments of each section
+/// (see [`Fragment::compare`]).
+pub fn sections<'s, I: IntoIterator<Item = Fragment<'s>>>(fragments: I) -> Sections<'s> {
+    let mut sections = Sections::new();
+
+    for fragment in fragments {
+        sections
+            .entry(fragment.partial.type_name.clone())
+            .or_default()
+            .push(fragment);
+    }
+
+    sections
+        .values_mut()
+        .for_each(|section| section.sort_by(Fragment::compare));
+
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{borrow::Cow, path::Path};
+
+    use super::{is_hidden, is_valid_path, Id, ParseErrorSource, Partial};
+
+    fn parse(name: &str) -> Partial<'_> {
+        Partial::parse_borrowed(name).unwrap()
+    }
+
+    fn parse_error(name: &str) -> ParseErrorSource {
+        Partial::parse_borrowed(name).unwrap_err().source
+    }
+
+    #[test]
+    fn parse_integer_id() {
+        let partial = parse("13.feature.md");
+
+        assert_eq!(partial.id, Id::integer(13));
+        assert_eq!(partial.type_name, "feature");
+        assert!(partial.segments.is_empty());
+        assert_eq!(partial.extension.as_deref(), Some("md"));
+    }
+
+    #[test]
+    fn parse_padded_integer_id() {
+        assert_eq!(parse("0013.feature.md").id, Id::integer(13));
+    }
+
+    #[test]
+    fn parse_string_id() {
+        let partial = parse("~issue.feature.md");
+
+        assert_eq!(partial.id, Id::borrowed("issue"));
+        assert!(matches!(partial.id, Id::String(Cow::Borrowed(_))));
+    }
+
+    #[test]
+    fn parse_string_id_of_digits() {
+        assert_eq!(parse("~13.feature.md").id, Id::borrowed("13"));
+    }
+
+    #[test]
+    fn parse_extra_segments() {
+        let partial = parse("13.feature.2.draft.md");
+
+        assert_eq!(partial.segments, ["2", "draft"]);
+        assert_eq!(partial.extension.as_deref(), Some("md"));
+    }
+
+    #[test]
+    fn parse_missing_extension() {
+        let partial = parse("13.feature");
+
+        assert!(partial.segments.is_empty());
+        assert_eq!(partial.extension, None);
+    }
+
+    #[test]
+    fn parse_missing_type() {
+        assert!(matches!(
+            parse_error("13"),
+            ParseErrorSource::UnexpectedEof(_)
+        ));
+    }
+
+    #[test]
+    fn parse_invalid_type() {
+        assert!(matches!(
+            parse_error("1.2.3.feature.md"),
+            ParseErrorSource::InvalidType(_)
+        ));
+    }
+
+    #[test]
+    fn parse_empty_segments() {
+        for name in ["13.feature..md", "13.feature.md.", "13.feature."] {
+            assert!(matches!(
+                parse_error(name),
+                ParseErrorSource::EmptySegment(_)
+            ));
+        }
+    }
+
+    #[test]
+    fn parse_empty_type() {
+        assert!(matches!(
+            parse_error("13..md"),
+            ParseErrorSource::InvalidType(_)
+        ));
+    }
+
+    #[test]
+    fn parse_invalid_ids() {
+        for name in [
+            "+13.feature.md",
+            "-13.feature.md",
+            "~.feature.md",
+            ".feature.md",
+        ] {
+            assert!(matches!(parse_error(name), ParseErrorSource::InvalidId(_)));
+        }
+    }
+
+    #[test]
+    fn hidden_files() {
+        let path = Path::new(".13.feature.md");
+
+        assert!(is_hidden(path));
+        assert!(!is_valid_path(path));
+
+        assert!(!is_hidden(Path::new("13.feature.md")));
+    }
+
+    #[test]
+    fn display_round_trip() {
+        for (name, expected) in [
+            ("13.feature.md", "13.feature.md"),
+            ("0013.feature.md", "13.feature.md"),
+            ("~issue.fix.2.md", "~issue.fix.2.md"),
+            ("13.feature", "13.feature"),
+        ] {
+            let partial = parse(name);
+            let string = partial.to_string();
+
+            assert_eq!(string, expected);
+            assert_eq!(string.parse::<Partial<'_>>().unwrap(), partial);
+        }
+    }
+}
@@ -1,38 +1,153 @@
 //! `git` functionality.
+//!
+//! Functions running `git` are only available with the `git` feature (enabled by `cli`),
+//! while [`Author`] and [`struct@Error`] are always available.
+//!
+//! Commands are built via [`command`], which uses the executable and extra arguments
+//! from the `git` section of the configuration (see [`Git`]). The environment is inherited,
+//! so `GIT_DIR`, `GIT_WORK_TREE` and worktrees are respected.
+//!
+//! [`Git`]: crate::config::Git
 
+use std::process::ExitStatus;
+#[cfg(feature = "git")]
 use std::{
-    path::Path,
-    process::{Command, ExitStatus},
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
 };
 
+#[cfg(feature = "git")]
+use itertools::Itertools;
 use miette::Diagnostic;
+use serde::Serialize;
 use thiserror::Error;
 
+#[cfg(feature = "git")]
+use crate::config::Git;
+
 /// Represents `git` errors.
 #[derive(Debug, Error, Diagnostic)]
 #[error("git error")]
 #[diagnostic(code(changelogging::git), help("make sure git is present"))]
 pub struct Error(#[from] pub std::io::Error);
 
-/// The `git` command.
+/// The default `git` executable.
 pub const GIT: &str = "git";
 /// The `add` subcommand.
 pub const ADD: &str = "add";
 /// The `rm` (remove) subcommand.
 pub const REMOVE: &str = "rm";
+/// The `log` subcommand.
+pub const LOG: &str = "log";
+/// The `describe` subcommand.
+pub const DESCRIBE: &str = "describe";
+/// The `diff` subcommand.
+pub const DIFF: &str = "diff";
+/// The `checkout` subcommand.
+pub const CHECKOUT: &str = "checkout";
+/// The `ls-tree` subcommand.
+pub const LS_TREE: &str = "ls-tree";
+/// The `cat-file` subcommand.
+pub const CAT_FILE: &str = "cat-file";
+
+/// The `rev-parse` subcommand.
+pub const REV_PARSE: &str = "rev-parse";
+
+/// The `blob` object type.
+pub const BLOB: &str = "blob";
+
+/// The `--version` flag.
+pub const VERSION: &str = "--version";
 
 /// The `-f` (force) flag.
 pub const FORCE: &str = "-f";
 /// The `-q` (quiet) flag.
 pub const QUIET: &str = "-q";
 
+/// The `--tags` flag.
+pub const TAGS: &str = "--tags";
+/// The `--abbrev=0` flag, used to show tags only.
+pub const NO_ABBREV: &str = "--abbrev=0";
+
+/// The `--short` option.
+pub const SHORT: &str = "--short";
+
+/// The `--cached` flag.
+pub const CACHED: &str = "--cached";
+/// The `--name-only` flag.
+pub const NAME_ONLY: &str = "--name-only";
+/// The `--relative` flag, used to output paths relative to the current directory.
+pub const RELATIVE: &str = "--relative";
+/// The `--diff-filter=ACMR` flag, used to include added, copied, modified and renamed files.
+pub const EXISTING: &str = "--diff-filter=ACMR";
+/// The `-z` flag, used to separate paths with null characters.
+pub const NULL: &str = "-z";
+
+/// The `--diff-filter=A` flag, used to include added files only.
+pub const ADDED: &str = "--diff-filter=A";
+
+/// The `-1` flag, used to show one commit only.
+pub const ONE: &str = "-1";
+/// The format of `git log`, which outputs committer timestamps.
+pub const TIMESTAMP_FORMAT: &str = "--format=%ct";
+
+/// The format of `git log`, which outputs committer timestamps preceded by [`RECORD`].
+pub const ADDED_FORMAT: &str = "--format=%x1e%ct";
+
+/// The `HEAD` revision.
+pub const HEAD: &str = "HEAD";
+
+/// The separator of revisions and paths.
+pub const SEPARATOR: &str = "--";
+
+/// The format of `git log`, which outputs author names, emails and co-authors
+/// separated by [`UNIT`] and terminated by [`RECORD`].
+pub const AUTHORS_FORMAT: &str =
+    "--format=%aN%x1f%aE%x1f%(trailers:key=Co-authored-by,valueonly,separator=%x1f)%x1e";
+
+/// The unit separator.
+pub const UNIT: char = '\x1f';
+/// The record separator.
+pub const RECORD: char = '\x1e';
+
+/// Checks whether the given status of `git` is successful.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if `git` exited unsuccessfully.
+pub fn success(status: ExitStatus) -> Result<(), Error> {
+    if status.success() {
+        Ok(())
+    } else {
+        let message = format!("`git` exited with {status}");
+
+        Err(Error(std::io::Error::other(message)))
+    }
+}
+
+/// Builds `git` commands according to the given configuration, that is, runs the `executable`
+/// with `extra_args` passed before subcommands.
+#[cfg(feature = "git")]
+pub fn command(config: &Git<'_>) -> Command {
+    let mut command = Command::new(config.executable.as_ref());
+
+    command.args(config.extra_args.iter().map(AsRef::<str>::as_ref));
+
+    command
+}
+
 /// Adds paths from the provided iterator via `git add`.
 ///
 /// # Errors
 ///
 /// Returns [`struct@Error`] when the command fails to execute.
-pub fn add<P: AsRef<Path>, I: IntoIterator<Item = P>>(iterator: I) -> Result<ExitStatus, Error> {
-    let mut command = Command::new(GIT);
+#[cfg(feature = "git")]
+pub fn add<P: AsRef<Path>, I: IntoIterator<Item = P>>(
+    config: &Git<'_>,
+    iterator: I,
+) -> Result<ExitStatus, Error> {
+    let mut command = command(config);
 
     command.arg(ADD);
 
@@ -48,8 +163,12 @@ pub fn add<P: AsRef<Path>, I: IntoIterator<Item = P>>(iterator: I) -> Result<Exi
 /// # Errors
 ///
 /// Returns [`struct@Error`] when the command fails to execute.
-pub fn remove<P: AsRef<Path>, I: IntoIterator<Item = P>>(iterator: I) -> Result<ExitStatus, Error> {
-    let mut command = Command::new(GIT);
+#[cfg(feature = "git")]
+pub fn remove<P: AsRef<Path>, I: IntoIterator<Item = P>>(
+    config: &Git<'_>,
+    iterator: I,
+) -> Result<ExitStatus, Error> {
+    let mut command = command(config);
 
     command.arg(REMOVE).arg(FORCE).arg(QUIET);
 
@@ -59,3 +178,388 @@ pub fn remove<P: AsRef<Path>, I: IntoIterator<Item = P>>(iterator: I) -> Result<
 
     command.status().map_err(Error)
 }
+
+/// Checks whether `git` is available by running `git --version` silently.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute.
+#[cfg(feature = "git")]
+pub fn version(config: &Git<'_>) -> Result<ExitStatus, Error> {
+    command(config)
+        .arg(VERSION)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map_err(Error)
+}
+
+/// Represents commit authors.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct Author {
+    /// The name of the author.
+    pub name: String,
+    /// The email of the author.
+    pub email: String,
+}
+
+impl Author {
+    /// Constructs [`Self`].
+    pub fn new(name: String, email: String) -> Self {
+        Self { name, email }
+    }
+
+    /// Parses [`Self`] from strings like `Name <email>`, as found in `Co-authored-by` trailers.
+    pub fn parse(string: &str) -> Option<Self> {
+        let (name, rest) = string.split_once('<')?;
+
+        let email = rest.strip_suffix('>')?;
+
+        Some(Self::new(name.trim().to_owned(), email.trim().to_owned()))
+    }
+
+    /// Returns the key identifying [`Self`], that is, the lowercase email.
+    pub fn key(&self) -> String {
+        self.email.to_lowercase()
+    }
+}
+
+#[cfg(feature = "git")]
+fn parse_authors(output: &str) -> Vec<Author> {
+    let mut authors = Vec::new();
+
+    for record in output.split(RECORD) {
+        let mut units = record.trim().split(UNIT);
+
+        let (Some(name), Some(email)) = (units.next(), units.next()) else {
+            continue;
+        };
+
+        authors.push(Author::new(name.to_owned(), email.to_owned()));
+
+        authors.extend(units.filter_map(Author::parse));
+    }
+
+    authors
+}
+
+/// Finds authors and co-authors of commits in the given range (or the entire history)
+/// that touched any of the given paths (or any paths at all).
+///
+/// Authors are deduplicated by their emails and sorted by their names.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute or exits unsuccessfully.
+#[cfg(feature = "git")]
+pub fn authors<P: AsRef<Path>, I: IntoIterator<Item = P>>(
+    config: &Git<'_>,
+    range: Option<&str>,
+    paths: I,
+) -> Result<Vec<Author>, Error> {
+    let mut command = command(config);
+
+    command.arg(LOG).arg(AUTHORS_FORMAT);
+
+    if let Some(range) = range {
+        command.arg(range);
+    }
+
+    command.arg(SEPARATOR);
+
+    for path in paths {
+        command.arg(path.as_ref());
+    }
+
+    let output = command.stderr(Stdio::null()).output().map_err(Error)?;
+
+    success(output.status)?;
+
+    let string = String::from_utf8_lossy(&output.stdout);
+
+    let authors = parse_authors(&string)
+        .into_iter()
+        .unique_by(Author::key)
+        .sorted_by(|this, that| this.name.cmp(&that.name))
+        .collect();
+
+    Ok(authors)
+}
+
+/// Finds the last tag reachable from `HEAD`, if any.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute.
+#[cfg(feature = "git")]
+pub fn last_tag(config: &Git<'_>) -> Result<Option<String>, Error> {
+    let output = command(config)
+        .arg(DESCRIBE)
+        .arg(TAGS)
+        .arg(NO_ABBREV)
+        .arg(HEAD)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(Error)?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let tag = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+
+    Ok(Some(tag).filter(|tag| !tag.is_empty()))
+}
+
+/// Finds the short hash of the current commit (that is, `HEAD`).
+///
+/// Returns [`None`] if there are no commits yet (or if the project is not a repository).
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute.
+#[cfg(feature = "git")]
+pub fn head(config: &Git<'_>) -> Result<Option<String>, Error> {
+    let output = command(config)
+        .arg(REV_PARSE)
+        .arg(SHORT)
+        .arg(HEAD)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(Error)?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_owned();
+
+    Ok(Some(hash).filter(|hash| !hash.is_empty()))
+}
+
+/// Finds the committer timestamp (in seconds since the Unix epoch) of the given revision.
+///
+/// Returns [`None`] if the revision does not exist.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute.
+#[cfg(feature = "git")]
+pub fn timestamp(config: &Git<'_>, revision: &str) -> Result<Option<i64>, Error> {
+    let output = command(config)
+        .arg(LOG)
+        .arg(ONE)
+        .arg(TIMESTAMP_FORMAT)
+        .arg(revision)
+        .arg(SEPARATOR)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(Error)?;
+
+    if !output.status.success() {
+        return Ok(None);
+    }
+
+    let timestamp = String::from_utf8_lossy(&output.stdout).trim().parse().ok();
+
+    Ok(timestamp)
+}
+
+/// Checks which of the given authors make their first contribution after the given revision,
+/// that is, have not authored (or co-authored) any commits reachable from it.
+///
+/// If there is no revision, every author is considered to make their first contribution.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute or exits unsuccessfully.
+#[cfg(feature = "git")]
+pub fn first_time(
+    config: &Git<'_>,
+    authors: &[Author],
+    revision: Option<&str>,
+) -> Result<Vec<bool>, Error> {
+    let Some(revision) = revision else {
+        return Ok(vec![true; authors.len()]);
+    };
+
+    let previous: HashSet<String> = self::authors::<&Path, _>(config, Some(revision), [])?
+        .iter()
+        .map(Author::key)
+        .collect();
+
+    let first = authors
+        .iter()
+        .map(|author| !previous.contains(&author.key()))
+        .collect();
+
+    Ok(first)
+}
+
+/// Finds when files in the given directory were added, that is, committer timestamps
+/// (in seconds since the Unix epoch) of the newest commits that added them,
+/// keyed by paths relative to the current directory.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute or exits unsuccessfully.
+#[cfg(feature = "git")]
+pub fn added(config: &Git<'_>, directory: &Path) -> Result<HashMap<PathBuf, i64>, Error> {
+    let output = command(config)
+        .arg(LOG)
+        .arg(ADDED)
+        .arg(ADDED_FORMAT)
+        .arg(NAME_ONLY)
+        .arg(RELATIVE)
+        .arg(SEPARATOR)
+        .arg(directory)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(Error)?;
+
+    success(output.status)?;
+
+    let string = String::from_utf8_lossy(&output.stdout);
+
+    let mut added = HashMap::new();
+
+    for record in string.split(RECORD) {
+        let mut lines = record.lines();
+
+        let Some(Ok(timestamp)) = lines.next().map(|line| line.trim().parse::<i64>()) else {
+            continue;
+        };
+
+        // commits are listed from the newest, so the newest additions are kept
+
+        for line in lines.filter(|line| !line.is_empty()) {
+            added.entry(PathBuf::from(line)).or_insert(timestamp);
+        }
+    }
+
+    Ok(added)
+}
+
+/// Restores paths from the provided iterator as of the given revision via `git checkout`,
+/// both in the index and in the working tree.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute.
+#[cfg(feature = "git")]
+pub fn restore<P: AsRef<Path>, I: IntoIterator<Item = P>>(
+    config: &Git<'_>,
+    revision: &str,
+    iterator: I,
+) -> Result<ExitStatus, Error> {
+    let mut command = command(config);
+
+    command
+        .arg(CHECKOUT)
+        .arg(QUIET)
+        .arg(revision)
+        .arg(SEPARATOR);
+
+    for path in iterator {
+        command.arg(path.as_ref());
+    }
+
+    command.status().map_err(Error)
+}
+
+/// Lists files (that is, blobs) in the given directory as of the given revision,
+/// relative to the current directory, along with their object names.
+///
+/// Files in subdirectories are not listed.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute or exits unsuccessfully,
+/// for instance, if the revision does not exist.
+#[cfg(feature = "git")]
+pub fn tree(
+    config: &Git<'_>,
+    revision: &str,
+    directory: &Path,
+) -> Result<Vec<(PathBuf, String)>, Error> {
+    // the trailing separator makes `git` list the contents of the directory
+
+    let output = command(config)
+        .arg(LS_TREE)
+        .arg(NULL)
+        .arg(revision)
+        .arg(SEPARATOR)
+        .arg(directory.join(""))
+        .stderr(Stdio::null())
+        .output()
+        .map_err(Error)?;
+
+    success(output.status)?;
+
+    // each entry is `{mode} {type} {object}\t{path}`
+
+    let files = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter_map(|entry| {
+            let (info, path) = entry.split_once('\t')?;
+
+            let mut parts = info.split_whitespace();
+
+            let (_, type_name, object) = (parts.next()?, parts.next()?, parts.next()?);
+
+            (type_name == BLOB).then(|| (PathBuf::from(path), object.to_owned()))
+        })
+        .collect();
+
+    Ok(files)
+}
+
+/// Reads the contents of the blob with the given object name.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute or exits unsuccessfully.
+#[cfg(feature = "git")]
+pub fn blob(config: &Git<'_>, object: &str) -> Result<Vec<u8>, Error> {
+    let output = command(config)
+        .arg(CAT_FILE)
+        .arg(BLOB)
+        .arg(object)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(Error)?;
+
+    success(output.status)?;
+
+    Ok(output.stdout)
+}
+
+/// Lists staged files that exist (that is, were added, copied, modified or renamed),
+/// relative to the current directory.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] when the command fails to execute or exits unsuccessfully.
+#[cfg(feature = "git")]
+pub fn staged(config: &Git<'_>) -> Result<Vec<PathBuf>, Error> {
+    let output = command(config)
+        .arg(DIFF)
+        .arg(CACHED)
+        .arg(NAME_ONLY)
+        .arg(RELATIVE)
+        .arg(EXISTING)
+        .arg(NULL)
+        .stderr(Stdio::null())
+        .output()
+        .map_err(Error)?;
+
+    success(output.status)?;
+
+    let paths = String::from_utf8_lossy(&output.stdout)
+        .split('\0')
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .collect();
+
+    Ok(paths)
+}
@@ -0,0 +1,189 @@
+//! GitHub Actions integration.
+//!
+//! This module provides [`set_output`] that appends multi-line values to the file
+//! pointed to by `GITHUB_OUTPUT`, and [`append_summary`] that appends Markdown
+//! to the file pointed to by `GITHUB_STEP_SUMMARY`.
+//!
+//! Multi-line values are delimited using the `{name}<<{delimiter}` syntax, where
+//! the delimiter is guaranteed not to occur in the value itself.
+
+use std::{
+    env::var_os,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+use crate::exit::Code;
+
+/// The environment variable pointing to the file to append outputs to.
+pub const OUTPUT: &str = "GITHUB_OUTPUT";
+
+/// The environment variable pointing to the file to append the step summary to.
+pub const STEP_SUMMARY: &str = "GITHUB_STEP_SUMMARY";
+
+/// The base of delimiters of multi-line values.
+pub const DELIMITER: &str = "CHANGELOGGING_EOF";
+
+/// Represents errors that occur when the environment variable is not set.
+#[derive(Debug, Error, Diagnostic)]
+#[error("`{name}` is not set")]
+#[diagnostic(
+    code(changelogging::github::missing_variable),
+    help("make sure to run within GitHub Actions")
+)]
+pub struct MissingVariableError {
+    /// The name of the environment variable.
+    pub name: &'static str,
+}
+
+impl MissingVariableError {
+    /// Constructs [`Self`].
+    pub fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+}
+
+/// Represents errors that can occur when appending to files.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to append to `{path}`")]
+#[diagnostic(
+    code(changelogging::github::append),
+    help("check whether the file is accessible")
+)]
+pub struct AppendError {
+    /// The underlying I/O error.
+    pub source: std::io::Error,
+    /// The path to the file.
+    pub path: PathBuf,
+}
+
+impl AppendError {
+    /// Constructs [`Self`].
+    pub fn new(source: std::io::Error, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+}
+
+/// Represents sources of errors that can occur when integrating with GitHub Actions.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Missing variable errors.
+    MissingVariable(#[from] MissingVariableError),
+    /// Append errors.
+    Append(#[from] AppendError),
+}
+
+/// Represents errors that can occur when integrating with GitHub Actions.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to integrate with GitHub Actions")]
+#[diagnostic(
+    code(changelogging::github),
+    help("see the report for more information")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`MissingVariableError`].
+    pub fn missing_variable(error: MissingVariableError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`AppendError`].
+    pub fn append(error: AppendError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`MissingVariableError`] and constructs [`Self`] from it.
+    pub fn new_missing_variable(name: &'static str) -> Self {
+        Self::missing_variable(MissingVariableError::new(name))
+    }
+
+    /// Constructs [`AppendError`] and constructs [`Self`] from it.
+    pub fn new_append(error: std::io::Error, path: PathBuf) -> Self {
+        Self::append(AppendError::new(error, path))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::MissingVariable(_) => Code::Usage,
+            ErrorSource::Append(_) => Code::Io,
+        }
+    }
+}
+
+/// Returns the delimiter that does not occur in the given value.
+pub fn delimiter(value: &str) -> String {
+    let mut delimiter = DELIMITER.to_owned();
+
+    let mut count = 0;
+
+    while value.contains(&delimiter) {
+        count += 1;
+
+        delimiter = format!("{DELIMITER}_{count}");
+    }
+
+    delimiter
+}
+
+/// Formats the given output, delimiting its value.
+pub fn format_output(name: &str, value: &str) -> String {
+    let delimiter = delimiter(value);
+
+    format!("{name}<<{delimiter}\n{value}\n{delimiter}\n")
+}
+
+fn append(path: &Path, string: &str) -> Result<(), Error> {
+    let mut file = File::options()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|error| Error::new_append(error, path.to_owned()))?;
+
+    write!(file, "{string}").map_err(|error| Error::new_append(error, path.to_owned()))
+}
+
+/// Sets the output with the given name to the given (possibly multi-line) value.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if `GITHUB_OUTPUT` is not set or appending to the file fails.
+pub fn set_output(name: &str, value: &str) -> Result<(), Error> {
+    let path = var_os(OUTPUT).ok_or_else(|| Error::new_missing_variable(OUTPUT))?;
+
+    append(Path::new(&path), &format_output(name, value))
+}
+
+/// Appends the given Markdown to the step summary, if `GITHUB_STEP_SUMMARY` is set.
+///
+/// Returns whether the summary was appended to.
+///
+/// # Errors
+///
+/// Returns [`struct@Error`] if appending to the file fails.
+pub fn append_summary(markdown: &str) -> Result<bool, Error> {
+    let Some(path) = var_os(STEP_SUMMARY) else {
+        return Ok(false);
+    };
+
+    append(Path::new(&path), &format!("{markdown}\n"))?;
+
+    Ok(true)
+}
@@ -0,0 +1,135 @@
+//! Built-in translations.
+//!
+//! The [`Language`] enumeration provides translations of the default type titles
+//! and the message used when there are no significant changes.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Represents languages with built-in translations.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// English.
+    #[default]
+    En,
+    /// German.
+    De,
+    /// Spanish.
+    Es,
+    /// French.
+    Fr,
+    /// Italian.
+    It,
+    /// Portuguese.
+    Pt,
+    /// Russian.
+    Ru,
+}
+
+macro_rules! titles {
+    (
+        $security: expr,
+        $feature: expr,
+        $change: expr,
+        $fix: expr,
+        $deprecation: expr,
+        $removal: expr,
+        $internal: expr $(,)?
+    ) => {
+        ::std::collections::HashMap::from([
+            ("security", $security),
+            ("feature", $feature),
+            ("change", $change),
+            ("fix", $fix),
+            ("deprecation", $deprecation),
+            ("removal", $removal),
+            ("internal", $internal),
+        ])
+    };
+}
+
+impl Language {
+    /// Returns the default `types` value translated to [`Self`].
+    pub fn default_types(self) -> HashMap<&'static str, &'static str> {
+        match self {
+            Self::En => titles!(
+                "Security",
+                "Features",
+                "Changes",
+                "Fixes",
+                "Deprecations",
+                "Removals",
+                "Internal",
+            ),
+            Self::De => titles!(
+                "Sicherheit",
+                "Funktionen",
+                "Änderungen",
+                "Fehlerbehebungen",
+                "Veraltungen",
+                "Entfernungen",
+                "Intern",
+            ),
+            Self::Es => titles!(
+                "Seguridad",
+                "Funcionalidades",
+                "Cambios",
+                "Correcciones",
+                "Obsolescencias",
+                "Eliminaciones",
+                "Interno",
+            ),
+            Self::Fr => titles!(
+                "Sécurité",
+                "Fonctionnalités",
+                "Changements",
+                "Corrections",
+                "Dépréciations",
+                "Suppressions",
+                "Interne",
+            ),
+            Self::It => titles!(
+                "Sicurezza",
+                "Funzionalità",
+                "Modifiche",
+                "Correzioni",
+                "Deprecazioni",
+                "Rimozioni",
+                "Interno",
+            ),
+            Self::Pt => titles!(
+                "Segurança",
+                "Funcionalidades",
+                "Alterações",
+                "Correções",
+                "Descontinuações",
+                "Remoções",
+                "Interno",
+            ),
+            Self::Ru => titles!(
+                "Безопасность",
+                "Новые возможности",
+                "Изменения",
+                "Исправления",
+                "Устаревшее",
+                "Удалённое",
+                "Внутреннее",
+            ),
+        }
+    }
+
+    /// Returns the message used when there are no significant changes, translated to [`Self`].
+    pub fn no_changes(self) -> &'static str {
+        match self {
+            Self::En => "No significant changes.",
+            Self::De => "Keine nennenswerten Änderungen.",
+            Self::Es => "Sin cambios significativos.",
+            Self::Fr => "Aucun changement significatif.",
+            Self::It => "Nessuna modifica significativa.",
+            Self::Pt => "Nenhuma alteração significativa.",
+            Self::Ru => "Нет значимых изменений.",
+        }
+    }
+}
@@ -27,8 +27,15 @@
 //! - *type*, for instance *change* or *feature*;
 //! - *content*, which gets written to the changelog.
 //!
-//! In `changelogging`, fragments are files which have names starting with `{id}.{name}`
-//! and contain fragment contents.
+//! In `changelogging`, fragments are files which have names starting with `{id}.{type}`
+//! and contain fragment contents. IDs are either integers (possibly zero-padded) or strings
+//! prefixed with `~`, while types can not start with digits. The last segment after the type
+//! is the extension, and the ones in between are kept as extra segments, for instance,
+//! `13.feature.2.md` (see [`Partial`] for the full grammar).
+//!
+//! Files in the fragments directory can be excluded using the `.changeloggingignore` file
+//! placed there, which follows the `.gitignore` syntax. Ignored files are neither built
+//! nor removed.
 //!
 //! ## Entries
 //!
@@ -58,7 +65,20 @@
 //! - `--help (-h)` displays help information.
 //! - `--version (-V)` shows this application's version.
 //! - `--directory (-D)` changes the directory before doing anything.
-//! - `--config (-C)` specifies the configuration file to use.
+//! - `--config (-C)` specifies the configuration file to use; paths in it are relative to it
+//!   (see `paths.relative_to` in [`config`]).
+//! - `--context` supplies (or overrides) the `context` section with the given file instead of
+//!   `context.toml` next to the config file (see [`workspace`]), for instance, to inject versions
+//!   generated by release pipelines.
+//! - `--verbose (-v)` shows what `changelogging` is doing (can be repeated for more details).
+//! - `--quiet (-q)` hides everything but errors.
+//! - `--profile (-P)` applies the profile with the given name (see [`workspace`]).
+//! - `--fragments-dir` overrides `paths.directory` for this run.
+//! - `--changelog` overrides `paths.output` for this run.
+//! - `--wrap` overrides `wrap` for this run.
+//! - `--start` overrides `start` for this run.
+//! - `--offline` guarantees that the network is never accessed (so does setting
+//!   the `CHANGELOGGING_OFFLINE` variable); network-backed features are skipped with warnings.
 //!
 //! ## `create`
 //!
@@ -99,8 +119,96 @@
 //! Here are the options (except for [globals](#globals)) that `create` supports:
 //!
 //! - `--content (-c)` passes the content of the fragment through the argument.
-//! - `--edit (-e)` opens the default editor to enter the fragment's contents.
-//! - `--add (-a)` adds the fragment file via `git`.
+//! - `--edit (-e)` opens the editor to enter the fragment's contents.
+//! - `--no-edit` never opens the editor, even if `create.edit` is set.
+//! - `--add (-a)` adds the fragment file via `git` (along with the counter, if `--auto` is used).
+//! - `--auto` assigns the next ID from the counter, given `ids = "sequence"` (see [`config`]).
+//! - `--from-pr` uses the title of the pull request with the fragment ID as the content,
+//!   fetching it from the forge API (see `forge` in [`config`]).
+//!
+//! For instance, this fetches the title of the pull request `#42`:
+//!
+//! ```console
+//! $ changelogging create --from-pr 42.feature.md
+//! ```
+//!
+//! The type can be omitted, in which case it is inferred from labels of the pull request
+//! mapped via `forge.labels`, creating `42.fix.md` given the `"kind/bug" = "fix"` mapping:
+//!
+//! ```console
+//! $ changelogging create --from-pr 42
+//! ```
+//!
+//! Projects without issue trackers can use sequential IDs instead, passing `{type}`
+//! (or `{type}.{extension}`) to `--auto`, which creates `1.feature.md`, then `2.feature.md`
+//! and so on:
+//!
+//! ```console
+//! $ changelogging create --auto feature
+//! ```
+//!
+//! Network-backed features such as `--from-pr` require the `forge` feature,
+//! which is enabled by `cli`.
+//!
+//! The editor can be configured via `create.editor` (see [`config`]). When `stdin` or `stdout`
+//! is not a terminal (for instance, in CI), editing fails immediately instead of hanging.
+//!
+//! When editing fragments without content, the editor is pre-populated from the template
+//! of their type, if there is one, that is, `{directory}/templates/{type}.md`:
+//!
+//! ```md
+//! Fixed the vulnerability in {{name}} {{version}}.
+//!
+//! **Severity:** ...
+//! ```
+//!
+//! Templates are rendered with the same variables as in the `fragment` format.
+//!
+//! ## `check`
+//!
+//! The `check` command validates fragments without building anything, reporting invalid names,
+//! unknown (or unordered) types, fragments that can not be loaded and empty ones:
+//!
+//! ```console
+//! $ changelogging check
+//! changes/13.feat.md: unknown type `feat`
+//! ```
+//!
+//! Once the `security` format is configured (see `formats` in [`config`]), security fragments
+//! are also required to specify either `cve` or `advisory_url` in front matter, and `cve`,
+//! `advisory_url` and `severity` are validated when specified:
+//!
+//! ```console
+//! $ changelogging check
+//! changes/64.security.md: security fragment specifies neither `cve` nor `advisory_url`
+//! ```
+//!
+//! Here are the options (except for [globals](#globals)) that `check` supports:
+//!
+//! - `--staged (-s)` checks staged files (in the fragments directory) only.
+//! - `--format (-f)` outputs findings either as `text` (default) or as `json`.
+//! - `--references` checks that integer IDs reference existing pull requests or issues
+//!   on the forge (see `forge` in [`config`]), caching the ones that do.
+//! - `--links` checks links in the entry that would be built, or in the whole changelog
+//!   given `--links changelog`, reporting broken ones (requires the `links` feature).
+//! - `--stale` flags fragments at least as old as the given age (for instance, `90d` or `12w`),
+//!   which often indicate abandoned changes or missed releases. Fragments are aged by
+//!   the commits that added them, or by their modification times if they are not committed yet.
+//! - `--entries (-e)` checks that changelog entries are ordered from the newest (by versions,
+//!   or by dates for entries without versions) and that their headings match the title format
+//!   (see `formats` in [`config`]), reporting the first out-of-order and malformed headings.
+//!
+//! Specific files can also be passed as arguments. `check` fails if any problems are found.
+//!
+//! It can be used with [pre-commit](https://pre-commit.com/) like so:
+//!
+//! ```yaml
+//! repos:
+//!   - repo: https://github.com/nekitdev/changelogging
+//!     rev: v0.7.0
+//!     hooks:
+//!       - id: changelogging-check
+//! ```
 //!
 //! ## `preview`
 //!
@@ -126,6 +234,39 @@
 //! Here are the options (except for [globals](#globals)) that `preview` supports:
 //!
 //! - `--date (-d)` specifies the date to use instead of today.
+//! - `--pretty (-p)` renders the entry with terminal styling.
+//! - `--no-color` disables terminal styling (so does setting the `NO_COLOR` variable).
+//! - `--watch (-w)` watches fragments and configuration, previewing again on every change.
+//! - `--against (-a)` compares the pending entry with the released entry of the given version,
+//!   showing bullets added (`+`) and removed (`-`).
+//! - `--summary (-c)`, also available as `--count`, shows the number of fragments per section
+//!   and the kind of release suggested, instead of the entry itself.
+//! - `--github-output NAME` sets the GitHub Actions output with the given name to the entry
+//!   (appending the delimited value to `GITHUB_OUTPUT`), also appending the entry to the step
+//!   summary if `GITHUB_STEP_SUMMARY` is set.
+//! - `--clipboard` copies the entry (without terminal styling) to the system clipboard,
+//!   which requires the `clipboard` feature.
+//! - `--timings` prints how long collecting and rendering took to `stderr`,
+//!   along with the number of fragments processed.
+//!
+//! The kind of release is suggested based on types of fragments: `removal` suggests the major
+//! release, `feature`, `change` and `deprecation` suggest the minor one, while any other type
+//! suggests the patch one:
+//!
+//! ```console
+//! $ changelogging preview --summary
+//! Features: 2
+//! Fixes: 1
+//! minor release suggested
+//! ```
+//!
+//! Entries can be previewed from any fragments directory without editing the configuration
+//! via the global `--fragments-dir` option (note that `--directory (-D)` changes the current
+//! directory instead), for instance, when reviewing branches checked out elsewhere:
+//!
+//! ```console
+//! $ changelogging preview --fragments-dir ../other/changes
+//! ```
 //!
 //! ## `build`
 //!
@@ -139,12 +280,406 @@
 //!
 //! You can also see the [rendered] version.
 //!
+//! When titles use reference links (like `## [1.2.0] - 2024-05-01`) and the `compare` format
+//! is provided (see `formats` in [`config`]), `build` also maintains link reference definitions
+//! at the end of the changelog, adding the one of the new entry and updating `[Unreleased]`:
+//!
+//! ```markdown
+//! [Unreleased]: https://github.com/nekitdev/changelogging/compare/v1.2.0...HEAD
+//! [1.2.0]: https://github.com/nekitdev/changelogging/compare/v1.1.0...v1.2.0
+//! [1.1.0]: https://github.com/nekitdev/changelogging/compare/v1.0.0...v1.1.0
+//! ```
+//!
 //! Here are the options (except for [globals](#globals)) that `build` supports:
 //!
 //! - `--date (-d)` specifies the date to use instead of today.
 //! - `--stage (-s)` stages the updated changelog via `git`.
 //! - `--remove (-r)` removes all fragment files with `git`.
+//! - `--insert-marker` inserts the start marker into the changelog if it is missing.
+//! - `--require-fragments` fails if there are no fragments to build.
+//! - `--consolidate-prereleases` merges bullets of prerelease entries of the version being built
+//!   (for instance, `1.2.0-rc.1` and `1.2.0-beta.2` when building `1.2.0`) into the new entry,
+//!   replacing them (same as setting `fold_prereleases`).
+//! - `--amend (-a)` replaces the existing entry of the version being built (if any)
+//!   with the new one instead of adding another one, preserving everything else; for instance,
+//!   when fragments are added after the initial build but before tagging (keep fragments
+//!   by omitting `--remove`).
+//! - `--output (-o)` writes the resulting changelog to the given path instead of `paths.output`
+//!   (`-` writes it to `stdout`), leaving the changelog intact (so `--remove` is not allowed).
+//! - `--notes-file (-n)` also writes the entry alone to the given path, for instance,
+//!   to be used as release notes (parent directories are created if needed).
+//! - `--notes-only` writes release notes only, leaving the changelog intact
+//!   (so `--remove` is not allowed).
+//! - `--report json` prints the report of what happened to `stdout`: the entry built,
+//!   fragments included and skipped (along with the reasons why), paths written and removed.
+//! - `--no-lock` builds without acquiring `.changelogging.lock`, which otherwise guards
+//!   against concurrent builds (the lock file contains the ID of the process holding it).
+//! - `--fragments-ref` reads fragments from the given revision via `git` instead of the working
+//!   tree, while writing to the changelog of the current branch; for instance,
+//!   `--fragments-ref release/1.x` builds notes of backports that only exist on the release
+//!   branch (such fragments are never removed, so `--remove` is not allowed).
+//! - `--only` builds only the fragments with the given comma-separated IDs
+//!   (for instance, `--only 123,145,160`), so that patch releases can cherry-pick pending changes;
+//!   `--remove` then removes only these fragments.
+//! - `--exclude` leaves out the fragments with the given comma-separated IDs.
+//! - `--timings` prints how long collecting, rendering and writing took to `stderr`,
+//!   along with the number of fragments processed; for instance, to diagnose slow builds
+//!   on network filesystems:
+//!
+//! ```console
+//! $ changelogging build --timings
+//! collecting: 1.42ms
+//! rendering: 3.08ms
+//! writing: 215.30µs
+//! total: 4.72ms (7 fragment(s))
+//! ```
+//!
+//! In case the changelog does not contain the start marker, `build` warns about it,
+//! since the entry is then written at the very beginning of the changelog.
+//!
+//! If `retain` is set, only the newest entries are kept in the changelog, while the older ones
+//! are dropped or moved to `paths.archive`.
+//!
+//! If `outputs` is set (see [`config`]), additional changelogs are built from the given profiles
+//! and written along with the main one, for instance, the compact changelog of features and fixes.
+//!
+//! Before writing changelogs and archives, `build` backs them up to `.changelogging/backup`
+//! (next to the config file, ignored by `git`), recording the fragments it removes there as well.
+//!
+//! ## `rollback`
+//!
+//! The `rollback` command undoes the last build, restoring the changelog (and the archive)
+//! from the backup and fragments removed by the build via `git`:
+//!
+//! ```console
+//! $ changelogging rollback
+//! restored `CHANGELOG.md` from the backup
+//! restored 7 fragment(s) from `HEAD`
+//! ```
+//!
+//! Here are the options that `rollback` supports:
+//!
+//! - `--stage (-s)` stages the restored changelog via `git`.
+//! - `--keep-fragments (-k)` leaves fragments as they are.
+//! - `--revision` restores fragments from the given revision instead of `HEAD`,
+//!   for instance, `HEAD~1` in case the build is already committed.
+//! - `--remove-newest` removes the newest entry from changelogs that are not backed up,
+//!   which fails otherwise. Fragments are only restored if recorded in the backup.
+//!
+//! ## `resolve`
+//!
+//! The `resolve` command resolves merge conflicts in the changelog, which concurrent release
+//! branches frequently run into right after the start marker. Entries of both sides are merged
+//! (keeping the ones present on both sides once) and ordered by their versions, from the newest:
+//!
+//! ```console
+//! $ changelogging resolve
+//! resolved 1 conflict(s) in `CHANGELOG.md`
+//! ```
+//!
+//! Conflicts before the first entry, entries without versions (following semantic versioning)
+//! and entries of the same version that differ between sides are left for manual resolution.
+//!
+//! Here are the options that `resolve` supports:
+//!
+//! - `--stage (-s)` stages the resolved changelog via `git`, marking conflicts as resolved.
+//!
+//! ## `config show`
+//!
+//! The `config show` command prints the effective configuration (that is, defaults merged
+//! with the configuration file) as TOML:
+//!
+//! ```console
+//! $ changelogging config show --only-changed --origin
+//! [context]
+//! name = "changelogging"  # config
+//! url = "https://github.com/nekitdev/changelogging"  # config
+//! version = "0.7.0"  # config
+//!
+//! [formats]
+//! fragment = "{{content}} ([#{{id}}]({{url}}/pull/{{id}}))"  # config
+//! title = "[{{version}}]({{url}}/tree/v{{version}}) ({{date}})"  # config
+//! ```
+//!
+//! Here are the options (except for [globals](#globals)) that `config show` supports:
+//!
+//! - `--only-changed` shows only the values that are set (even to the defaults) or overridden.
+//! - `--origin` annotates each value with where it came from.
+//!
+//! ## `config validate`
+//!
+//! The `config validate` command validates the configuration without building anything:
+//! templates are compiled and rendered against the example fragment (catching unknown variables),
+//! every type in `order` is checked to have the title, and configured paths are checked to exist.
+//!
+//! ```console
+//! $ changelogging config validate
+//! configuration is valid
+//! ```
+//!
+//! ## `doctor`
+//!
+//! The `doctor` command diagnoses common setup problems, such as missing configuration,
+//! missing fragments directories, missing or duplicated start markers, fragments of unknown
+//! types, invalid templates and `git` being unavailable:
+//!
+//! ```console
+//! $ changelogging doctor
+//! no problems found
+//! ```
+//!
+//! Every problem is reported along with the hint on how to fix it.
+//!
+//! ## `variables`
+//!
+//! The `variables` command lists variables available to the `title` and `fragment` templates,
+//! along with example values taken from the current workspace:
+//!
+//! ```console
+//! $ changelogging variables
+//! title:
+//!   build_metadata = null
+//!   commit = "1a2b3c4"
+//!   date = "YYYY-MM-DD"
+//!   is_prerelease = false
+//!   major = 0
+//!   minor = 7
+//!   name = "changelogging"
+//!   patch = 0
+//!   prerelease = null
+//!   url = "https://github.com/nekitdev/changelogging"
+//!   version = "0.7.0"
+//!
+//! fragment:
+//!   content = "Added cool features!"
+//!   id = 13
+//!   meta = {}
+//!   name = "changelogging"
+//!   type_name = "feature"
+//!   url = "https://github.com/nekitdev/changelogging"
+//!   version = "0.7.0"
+//! ```
+//!
+//! Variables of the `fragment` template are also available to the `reference` template,
+//! as well as to fragment contents (see `render_content`). Front matter keys found in fragments
+//! are listed as `meta.{key}`.
+//!
+//! ## `render`
+//!
+//! The `render` command renders the entry with one made-up fragment through the configured
+//! formats, without reading or creating any fragment files, which helps iterating on templates:
+//!
+//! ```console
+//! $ changelogging render --fragment 13.feature --content "Example"
+//! ## [0.7.0](https://github.com/nekitdev/changelogging/tree/v0.7.0) (YYYY-MM-DD)
+//!
+//! ### Features
+//!
+//! - Example ([#13](https://github.com/nekitdev/changelogging/pull/13))
+//! ```
+//!
+//! Here are the options (except for [globals](#globals)) that `render` supports:
+//!
+//! - `--fragment (-f)` specifies the name of the fragment, `13.feature` by default.
+//! - `--content (-c)` specifies the content of the fragment.
+//! - `--date (-d)` specifies the date to use instead of today.
+//!
+//! ## `normalize`
+//!
+//! The `normalize` command rewrites fragment files into their canonical form, stripping byte order
+//! marks and zeros from IDs, normalizing line endings, trimming contents and enforcing
+//! the extension, while keeping front matter and priority markers intact:
+//!
+//! ```console
+//! $ changelogging normalize
+//! normalized `changes/0013.feature.md` -> `changes/13.feature.md`
+//! normalized `changes/64.security.md` (contents)
+//! ```
+//!
+//! Here are the options (except for [globals](#globals)) that `normalize` supports:
+//!
+//! - `--extension (-e)` specifies the extension to enforce, `md` by default.
+//! - `--check` only reports fragments that are not normalized, failing if there are any.
+//!
+//! ## `list`
+//!
+//! The `list` command lists pending fragments along with the first lines of their contents:
+//!
+//! ```console
+//! $ changelogging list
+//! changes/13.feature.md: Added cool features!
+//! changes/64.change.md: Changed some things!
+//! ```
+//!
+//! Here are the options (except for [globals](#globals)) that `list` supports:
+//!
+//! - `--format (-f)` specifies the format to list fragments in, either `text` (the default)
+//!   or `json`, which includes IDs, types, paths, modification times (in RFC 3339 format),
+//!   authors (specified as `author` in front matter, if any) and first lines of fragments.
+//!
+//! ## `open`
+//!
+//! The `open` command opens the changelog, the config file or the fragment with the given ID
+//! in the editor (see `create.editor` in [`config`]):
+//!
+//! ```console
+//! $ changelogging open changelog
+//! $ changelogging open config
+//! $ changelogging open fragment 13
+//! ```
+//!
+//! Fragments are looked up in the fragments directory, and opening fails if the ID
+//! is shared by multiple fragments (for instance, `13.feature.md` and `13.fix.md`).
+//!
+//! ## `cache clear`
+//!
+//! The `cache clear` command removes the cache of rendered fragments (see `cache` in [`config`]):
+//!
+//! ```console
+//! $ changelogging cache clear
+//! cleared `.changelogging/cache`
+//! ```
+//!
+//! ## `import-prs`
+//!
+//! The `import-prs` command creates fragments for pull requests merged since the given tag
+//! (or date), using their numbers as IDs, titles as contents and labels to infer types
+//! (see `forge.labels` in [`config`]):
+//!
+//! ```console
+//! $ changelogging import-prs --since v0.6.0
+//! skipped #12: no labels mapped to types
+//! created changes/13.feature.md
+//! ```
+//!
+//! Pull requests that already have fragments or labels of which are not mapped are skipped.
+//!
+//! Here are the options (except for [globals](#globals)) that `import-prs` supports:
+//!
+//! - `--since (-s)` specifies the tag (or any other revision) or the date in `YYYY-MM-DD` format
+//!   to import pull requests merged since; defaults to the last tag.
+//! - `--add (-a)` adds the fragment files via `git`.
+//!
+//! This command requires the `forge` feature, which is enabled by `cli`.
+//!
+//! ## `migrate split`
+//!
+//! The `migrate split` command recreates fragments from bullets of the changelog entry
+//! of the given version, so that the release cycle can be re-opened and rebuilt:
+//!
+//! ```console
+//! $ changelogging migrate split 0.7.0 --remove
+//! created 7 fragment(s)
+//! ```
+//!
+//! Types are detected by section titles, which are compared with headings, titles and names
+//! of types; sections that do not match any type are skipped with warnings. IDs are taken
+//! from references ending bullets (for instance, `(#13)` or `([#13](...))`, which are removed
+//! from contents), falling back to string IDs derived from the version (like `~v0-7-0-1`).
+//! Existing fragments are never overwritten.
+//!
+//! Here are the options (except for [globals](#globals)) that `migrate split` supports:
+//!
+//! - `--remove (-r)` removes the entry from the changelog, so that it can be rebuilt
+//!   (otherwise, use `build --amend` to replace it).
+//!
+//! ## `verify`
+//!
+//! The `verify` command rebuilds the entry of the given version from fragments and compares
+//! it with the released one (found in the changelog or, failing that, in the archive),
+//! for instance, to audit that release notes match their sources:
+//!
+//! ```console
+//! $ changelogging verify --version 0.7.0 --fragments-ref v0.7.0~1
+//! entry `0.7.0 (2024-05-01)` matches its fragments
+//! ```
+//!
+//! Differences are shown the same way `preview --against` shows them, in which case
+//! `verify` fails with the validation exit code.
+//!
+//! Here are the options (except for [globals](#globals)) that `verify` supports:
+//!
+//! - `--version` verifies the entry of this version instead of the one from the context;
+//! - `--date (-d)` rebuilds the entry with this date instead of the one in its title;
+//! - `--fragments-ref` reads fragments from this revision via `git` instead of the working
+//!   tree, which is useful since fragments are usually removed once released.
+//!
+//! ## `man`
+//!
+//! The hidden `man` command generates manual pages for `changelogging` and each of its
+//! subcommands, writing them into the given directory. It is intended for packagers:
+//!
+//! ```console
+//! $ changelogging man target/man
+//! ```
+//!
+//! ## `generate-fixtures`
+//!
+//! The hidden `generate-fixtures` command fills the fragments directory (or the given one)
+//! with synthesized fragments of types included in `order`, which is intended for benchmarking
+//! and for reproducing performance problems without sharing actual fragments:
+//!
+//! ```console
+//! $ changelogging generate-fixtures --count 5000 /tmp/fixtures
+//! $ changelogging --fragments-dir /tmp/fixtures preview --timings
+//! ```
+//!
+//! Fragments are synthesized deterministically, so the same `--seed` (`0` by default)
+//! and `--count (-n)` (`1000` by default) always produce the same fragments.
+//! Existing fragments are never overwritten.
+//!
+//! ## Exit codes
+//!
+//! `changelogging` exits with distinct codes depending on the class of failure
+//! (see [`exit`] for more information):
+//!
+//! - `0` on success;
+//! - `1` on failures that do not belong to any other class;
+//! - `2` on invalid usage, for instance invalid dates;
+//! - `3` on configuration errors, including invalid templates;
+//! - `4` when no fragments are found where at least one is required;
+//! - `5` on validation failures, for instance invalid fragment names or problems found by `doctor`;
+//! - `6` on `git` failures;
+//! - `7` on I/O failures;
+//! - `8` on network failures, for instance failed requests to the forge API.
+//!
+//! # Features
+//!
+//! - `cli` (default) provides the application (see [`app`]) along with its commands,
+//!   pulling in the command-line dependencies; it enables `git` and `forge`.
+//! - `fs` allows accessing the filesystem, that is, loading workspaces and fragments,
+//!   collecting fragments from directories, writing changelogs and caching.
+//! - `git` allows running `git`, which is used to stage files and to find contributors;
+//!   it enables `fs`.
+//! - `forge` provides network-backed features, see [`forge`]; it is enabled by `cli`,
+//!   so that library consumers only pull in the network dependencies when asking for them.
+//! - `async` provides asynchronous loading (see `LoadAsync`) and collecting
+//!   (see `Builder::collect_async`) via `tokio`, for embedding `changelogging`
+//!   in services that must not block the runtime on file I/O; it enables `fs`.
+//! - `clipboard` provides `preview --clipboard`, copying entries to the system clipboard
+//!   via `arboard`; it enables `cli`.
+//! - `links` provides `check --links`, verifying links in entries and changelogs
+//!   (see `links`); it enables `cli`.
+//!
+//! Library consumers that only need the core (configuration, fragments and the builder)
+//! can disable the default features:
+//!
+//! ```toml
+//! [dependencies.changelogging]
+//! version = "0.7.0"
+//! default-features = false
+//! ```
+//!
+//! Without any features, the core does not touch the filesystem nor spawn processes,
+//! so it compiles to `wasm32-unknown-unknown`, rendering entries from fragments kept in memory
+//! (see [`render_entry`]).
+//!
+//! For golden (snapshot) tests, [`Builder::build_deterministic`] builds entries from fragments
+//! kept in memory with the injected date, producing byte-stable output across platforms.
 //!
+//! [`Partial`]: crate::fragment::Partial
+//! [`render_entry`]: crate::builder::render_entry
+//! [`Builder::build_deterministic`]: crate::builder::Builder::build_deterministic
 //! [changelog]: https://github.com/nekitdev/changelogging/blob/main/CHANGELOG.md
 //! [readme]: https://github.com/nekitdev/changelogging/blob/main/README.md
 //! [this]: https://github.com/nekitdev/changelogging/blob/main/changelogging.toml
@@ -154,15 +689,47 @@
 #![forbid(unsafe_code)]
 #![deny(missing_docs)]
 
+#[cfg(feature = "cli")]
 pub mod app;
+#[cfg(feature = "fs")]
+pub mod backup;
 pub mod builder;
+#[cfg(feature = "fs")]
+pub mod cache;
+pub mod changelog;
+#[cfg(feature = "clipboard")]
+pub mod clipboard;
+#[cfg(feature = "cli")]
 pub mod commands;
 pub mod config;
 pub mod context;
+#[cfg(feature = "fs")]
+pub mod counter;
 pub mod date;
+#[cfg(feature = "fs")]
 pub mod discover;
+pub mod exit;
+#[cfg(feature = "forge")]
+pub mod forge;
 pub mod fragment;
 pub mod git;
+#[cfg(feature = "fs")]
+pub mod github;
+#[cfg(feature = "fs")]
 pub mod init;
+pub mod language;
+#[cfg(feature = "links")]
+pub mod links;
+#[cfg(feature = "fs")]
 pub mod load;
+#[cfg(feature = "fs")]
+pub mod lock;
+pub mod network;
+#[cfg(feature = "cli")]
+pub mod pretty;
+pub mod report;
+pub mod table;
+pub mod text;
+pub mod timings;
+pub mod version;
 pub mod workspace;
@@ -0,0 +1,151 @@
+//! Checking links.
+//!
+//! This module provides [`extract`] that finds URLs in rendered entries and changelogs,
+//! and [`verify`] that requests them, returning the [`Broken`] ones.
+//!
+//! Links are requested via `HEAD`, falling back to `GET` when servers do not allow `HEAD`.
+//! At most [`CONCURRENCY`] links are requested at once.
+//!
+//! This module is only available with the `links` feature enabled.
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread::scope,
+    time::Duration,
+};
+
+use itertools::Itertools;
+use tracing::debug;
+use ureq::Agent;
+
+use crate::forge::USER_AGENT;
+
+/// The maximum number of links to request at once.
+pub const CONCURRENCY: usize = 8;
+
+/// The timeout of each request.
+pub const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The status code of responses from servers that do not allow the method used.
+pub const METHOD_NOT_ALLOWED: u16 = 405;
+
+/// The prefixes URLs are expected to start with.
+pub const SCHEMES: [&str; 2] = ["https://", "http://"];
+
+// characters that end URLs in Markdown, for instance `[text](url)` or `<url>`
+
+const ENDS: [char; 6] = [')', '>', ']', '"', '\'', '`'];
+
+// punctuation that is more likely to end sentences than URLs
+
+const TRAILING: [char; 5] = ['.', ',', ';', ':', '!'];
+
+fn find_url(string: &str) -> Option<(usize, &str)> {
+    SCHEMES
+        .iter()
+        .filter_map(|scheme| string.find(scheme))
+        .min()
+        .map(|start| {
+            let rest = &string[start..];
+
+            let end = rest
+                .find(|character: char| character.is_whitespace() || ENDS.contains(&character))
+                .unwrap_or(rest.len());
+
+            (start + end, rest[..end].trim_end_matches(TRAILING))
+        })
+}
+
+/// Extracts unique URLs from the given string, in order of appearance.
+pub fn extract(string: &str) -> Vec<&str> {
+    let mut urls = Vec::new();
+
+    let mut rest = string;
+
+    while let Some((end, url)) = find_url(rest) {
+        if !SCHEMES.contains(&url) {
+            urls.push(url);
+        }
+
+        rest = &rest[end..];
+    }
+
+    urls.into_iter().unique().collect()
+}
+
+/// Represents broken links.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Broken {
+    /// The URL of the link.
+    pub url: String,
+    /// The reason the link is considered broken.
+    pub reason: String,
+}
+
+impl Broken {
+    /// Constructs [`Self`].
+    pub fn new(url: String, reason: String) -> Self {
+        Self { url, reason }
+    }
+}
+
+fn agent() -> Agent {
+    Agent::config_builder()
+        .timeout_global(Some(TIMEOUT))
+        .user_agent(USER_AGENT)
+        .build()
+        .into()
+}
+
+/// Checks the link with the given URL, returning the reason it is broken, if it is.
+pub fn check(agent: &Agent, url: &str) -> Option<String> {
+    debug!("checking `{url}`");
+
+    let result = match agent.head(url).call() {
+        Err(ureq::Error::StatusCode(METHOD_NOT_ALLOWED)) => agent.get(url).call(),
+        result => result,
+    };
+
+    result.err().map(|error| error.to_string())
+}
+
+/// Verifies the given URLs, requesting at most [`CONCURRENCY`] at once,
+/// and returns the broken ones, in the order given.
+pub fn verify<S: AsRef<str> + Sync>(urls: &[S]) -> Vec<Broken> {
+    let agent = agent();
+
+    let next = AtomicUsize::new(0);
+
+    let broken = Mutex::new(Vec::new());
+
+    scope(|scope| {
+        for _ in 0..CONCURRENCY.min(urls.len()) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+
+                let Some(url) = urls.get(index) else {
+                    break;
+                };
+
+                let url = url.as_ref();
+
+                if let Some(reason) = check(&agent, url) {
+                    let mut broken = broken.lock().unwrap_or_else(|error| error.into_inner());
+
+                    broken.push((index, Broken::new(url.to_owned(), reason)));
+                }
+            });
+        }
+    });
+
+    broken
+        .into_inner()
+        .unwrap_or_else(|error| error.into_inner())
+        .into_iter()
+        .sorted_by_key(|(index, _)| *index)
+        .map(|(_, broken)| broken)
+        .collect()
+}
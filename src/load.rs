@@ -1,5 +1,9 @@
 //! Loading values from paths.
+//!
+//! With the `async` feature, values can also be loaded asynchronously, see [`LoadAsync`].
 
+#[cfg(feature = "async")]
+use std::future::Future;
 use std::path::Path;
 
 /// Loading values from paths.
@@ -29,3 +33,35 @@ pub trait Load: Sized {
 pub fn load<L: Load, P: AsRef<Path>>(path: P) -> Result<L, L::Error> {
     L::load(path)
 }
+
+/// Loading values from paths asynchronously, without blocking the runtime on file I/O.
+#[cfg(feature = "async")]
+pub trait LoadAsync: Sized {
+    /// The associated error type returned from [`load_async`] on failure.
+    ///
+    /// [`load_async`]: Self::load_async
+    type Error;
+
+    /// Loads the value of this type from the given path asynchronously.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] when loading fails.
+    ///
+    /// [`Error`]: Self::Error
+    fn load_async<P: AsRef<Path> + Send>(
+        path: P,
+    ) -> impl Future<Output = Result<Self, Self::Error>> + Send;
+}
+
+/// Loads the value of the type given from the given path asynchronously.
+///
+/// # Errors
+///
+/// Returns [`Error`] when loading fails.
+///
+/// [`Error`]: LoadAsync::Error
+#[cfg(feature = "async")]
+pub async fn load_async<L: LoadAsync, P: AsRef<Path> + Send>(path: P) -> Result<L, L::Error> {
+    L::load_async(path).await
+}
@@ -0,0 +1,184 @@
+//! Locking builds.
+//!
+//! While building, `build` holds the [`LOCK`] file in the root of the project
+//! (see [`Workspace::project_path`]), which contains the ID of the process,
+//! so that concurrent builds can not interleave reads and writes of the changelog.
+//!
+//! [`Workspace::project_path`]: crate::workspace::Workspace::project_path
+//!
+//! The lock is released (that is, the file is removed) once [`Lock`] is dropped.
+//! If the process is killed, the lock file is left behind and has to be removed manually.
+
+use std::{
+    fs::{read_to_string, remove_file, File},
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+    process,
+};
+
+use miette::Diagnostic;
+use thiserror::Error;
+use tracing::debug;
+
+use crate::exit::Code;
+
+/// The name of the lock file, relative to the project.
+pub const LOCK: &str = ".changelogging.lock";
+
+/// Represents errors that occur when the lock is held by another process.
+#[derive(Debug, Error, Diagnostic)]
+#[error("`{path}` is held by another process ({holder})")]
+#[diagnostic(
+    code(changelogging::lock::locked),
+    help("wait for the other build to finish, or remove the lock file if it is stale")
+)]
+pub struct LockedError {
+    /// The path to the lock file.
+    pub path: PathBuf,
+    /// The ID of the process holding the lock, if known.
+    pub holder: String,
+}
+
+impl LockedError {
+    /// Constructs [`Self`].
+    pub fn new(path: PathBuf, holder: String) -> Self {
+        Self { path, holder }
+    }
+}
+
+/// Represents errors that can occur when creating lock files.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to create `{path}`")]
+#[diagnostic(
+    code(changelogging::lock::create),
+    help("check whether the directory is accessible")
+)]
+pub struct CreateError {
+    /// The underlying I/O error.
+    pub source: std::io::Error,
+    /// The path to the lock file.
+    pub path: PathBuf,
+}
+
+impl CreateError {
+    /// Constructs [`Self`].
+    pub fn new(source: std::io::Error, path: PathBuf) -> Self {
+        Self { source, path }
+    }
+}
+
+/// Represents sources of errors that can occur when locking.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ErrorSource {
+    /// Locked errors.
+    Locked(#[from] LockedError),
+    /// Create errors.
+    Create(#[from] CreateError),
+}
+
+/// Represents errors that can occur when locking.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to lock")]
+#[diagnostic(
+    code(changelogging::lock),
+    help("use `--no-lock` to build without locking")
+)]
+pub struct Error {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ErrorSource,
+}
+
+impl Error {
+    /// Constructs [`Self`].
+    pub fn new(source: ErrorSource) -> Self {
+        Self { source }
+    }
+
+    /// Constructs [`Self`] from [`LockedError`].
+    pub fn locked(error: LockedError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`Self`] from [`CreateError`].
+    pub fn create(error: CreateError) -> Self {
+        Self::new(error.into())
+    }
+
+    /// Constructs [`LockedError`] and constructs [`Self`] from it.
+    pub fn new_locked(path: PathBuf, holder: String) -> Self {
+        Self::locked(LockedError::new(path, holder))
+    }
+
+    /// Constructs [`CreateError`] and constructs [`Self`] from it.
+    pub fn new_create(error: std::io::Error, path: PathBuf) -> Self {
+        Self::create(CreateError::new(error, path))
+    }
+
+    /// Returns the exit [`Code`] corresponding to [`Self`].
+    pub fn exit_code(&self) -> Code {
+        match &self.source {
+            ErrorSource::Locked(_) => Code::Failure,
+            ErrorSource::Create(_) => Code::Io,
+        }
+    }
+}
+
+const UNKNOWN: &str = "unknown";
+
+/// Represents held locks, which are released when dropped.
+#[derive(Debug)]
+pub struct Lock {
+    /// The path to the lock file.
+    pub path: PathBuf,
+}
+
+impl Lock {
+    /// Acquires the lock at the given path, writing the ID of the current process to it.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if the lock is already held or creating the lock file fails.
+    pub fn acquire<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
+        let path = path.as_ref();
+
+        let mut file = match File::options().write(true).create_new(true).open(path) {
+            Ok(file) => file,
+            Err(error) if error.kind() == ErrorKind::AlreadyExists => {
+                let holder = read_to_string(path)
+                    .ok()
+                    .map(|string| string.trim().to_owned())
+                    .filter(|string| !string.is_empty())
+                    .unwrap_or_else(|| UNKNOWN.to_owned());
+
+                return Err(Error::new_locked(path.to_owned(), holder));
+            }
+            Err(error) => return Err(Error::new_create(error, path.to_owned())),
+        };
+
+        let lock = Self {
+            path: path.to_owned(),
+        };
+
+        // the lock is released on failure, as it is dropped
+
+        writeln!(file, "{}", process::id())
+            .map_err(|error| Error::new_create(error, path.to_owned()))?;
+
+        debug!("acquired `{}`", path.display());
+
+        Ok(lock)
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        match remove_file(&self.path) {
+            Ok(()) => debug!("released `{}`", self.path.display()),
+            Err(error) => debug!("failed to release `{}`: {error}", self.path.display()),
+        }
+    }
+}
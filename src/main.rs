@@ -1,11 +1,20 @@
 //! The `changelogging` binary.
 
+use std::process::ExitCode;
+
 use changelogging::app::App;
 use clap::Parser;
-use miette::Result;
+use miette::Report;
+
+fn main() -> ExitCode {
+    match App::parse().run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            let code = error.exit_code();
 
-fn main() -> Result<()> {
-    App::parse().run()?;
+            eprintln!("Error: {:?}", Report::new(error));
 
-    Ok(())
+            code.into()
+        }
+    }
 }
@@ -0,0 +1,49 @@
+//! Network access.
+//!
+//! Network-backed features (for instance, `create --from-pr`) never reach out to the network
+//! when offline mode is enabled, either via the `--offline` option or the [`OFFLINE`] variable.
+//! Instead, they skip whatever they would fetch, reporting [`OfflineWarning`].
+
+use std::env::var_os;
+
+use miette::Diagnostic;
+use thiserror::Error;
+
+/// The environment variable that enables offline mode when set to a truthy value.
+pub const OFFLINE: &str = "CHANGELOGGING_OFFLINE";
+
+/// The values of [`OFFLINE`] that do not enable offline mode.
+pub const FALSY: [&str; 4] = ["0", "false", "no", "off"];
+
+/// Checks whether offline mode is enabled, given the value of the `--offline` option.
+///
+/// Offline mode is enabled when `offline` is set or when the [`OFFLINE`] variable is not empty
+/// and is not one of [`FALSY`] values (case-insensitively).
+pub fn offline_enabled(offline: bool) -> bool {
+    offline
+        || var_os(OFFLINE).is_some_and(|value| {
+            let value = value.to_string_lossy().to_lowercase();
+
+            !value.is_empty() && !FALSY.contains(&value.as_str())
+        })
+}
+
+/// Represents warnings emitted when network-backed features are skipped in offline mode.
+#[derive(Debug, Error, Diagnostic)]
+#[error("offline mode is enabled, skipping {what}")]
+#[diagnostic(
+    code(changelogging::network::offline),
+    severity(Warning),
+    help("drop `--offline` and unset `{OFFLINE}` to allow network access")
+)]
+pub struct OfflineWarning {
+    /// What is skipped.
+    pub what: &'static str,
+}
+
+impl OfflineWarning {
+    /// Constructs [`Self`].
+    pub fn new(what: &'static str) -> Self {
+        Self { what }
+    }
+}
@@ -0,0 +1,147 @@
+//! Rendering entries for terminals.
+//!
+//! This module provides the [`render`] function that styles Markdown entries,
+//! making headings bold, coloring bullets and code, and turning links into
+//! [OSC 8](https://gist.github.com/egmontkob/eb114294efbcd5adb1944c9f3cb5feda) hyperlinks.
+
+use std::env::var_os;
+
+use anstyle::{AnsiColor, Style};
+
+use crate::config::Indents;
+
+/// The environment variable that disables colors when set to a non-empty value.
+pub const NO_COLOR: &str = "NO_COLOR";
+
+/// Checks whether colors are enabled, given the value of the `--no-color` option.
+///
+/// Colors are disabled when `no_color` is set or when the [`NO_COLOR`] variable is not empty.
+pub fn color_enabled(no_color: bool) -> bool {
+    !no_color && var_os(NO_COLOR).is_none_or(|value| value.is_empty())
+}
+
+const SPACE: char = ' ';
+const BACKTICK: char = '`';
+const OPEN_BRACKET: char = '[';
+const LINK_MIDDLE: &str = "](";
+const CLOSE_PARENTHESIS: char = ')';
+
+const HEADING_STYLE: Style = Style::new().bold();
+const MARKER_STYLE: Style = Style::new().dimmed();
+const BULLET_STYLE: Style = Style::new()
+    .fg_color(Some(anstyle::Color::Ansi(AnsiColor::Green)))
+    .bold();
+const CODE_STYLE: Style = Style::new().fg_color(Some(anstyle::Color::Ansi(AnsiColor::Yellow)));
+const LINK_STYLE: Style = Style::new()
+    .fg_color(Some(anstyle::Color::Ansi(AnsiColor::Blue)))
+    .underline();
+
+fn styled(style: Style, string: &str) -> String {
+    format!("{}{string}{}", style.render(), style.render_reset())
+}
+
+fn hyperlink(text: &str, url: &str) -> String {
+    format!(
+        "\x1b]8;;{url}\x1b\\{}\x1b]8;;\x1b\\",
+        styled(LINK_STYLE, text)
+    )
+}
+
+fn inline(string: &str) -> String {
+    let mut output = String::new();
+
+    let mut rest = string;
+
+    while let Some(index) = rest.find([BACKTICK, OPEN_BRACKET]) {
+        let (before, after) = rest.split_at(index);
+
+        output.push_str(before);
+
+        if after.starts_with(BACKTICK) {
+            if let Some(end) = after[1..].find(BACKTICK) {
+                let (code, remaining) = after.split_at(end + 2);
+
+                output.push_str(&styled(CODE_STYLE, code));
+
+                rest = remaining;
+
+                continue;
+            }
+        } else if let Some((text, url, remaining)) = link(after) {
+            output.push_str(&hyperlink(text, url));
+
+            rest = remaining;
+
+            continue;
+        }
+
+        // not a code span or link, so emit the character as-is
+
+        let (character, remaining) = after.split_at(1);
+
+        output.push_str(character);
+
+        rest = remaining;
+    }
+
+    output.push_str(rest);
+
+    output
+}
+
+fn link(string: &str) -> Option<(&str, &str, &str)> {
+    let inner = string.strip_prefix(OPEN_BRACKET)?;
+
+    let middle = inner.find(LINK_MIDDLE)?;
+
+    let (text, after) = inner.split_at(middle);
+
+    let after = &after[LINK_MIDDLE.len()..];
+
+    let end = after.find(CLOSE_PARENTHESIS)?;
+
+    let (url, remaining) = after.split_at(end);
+
+    Some((text, url, &remaining[1..]))
+}
+
+fn line(string: &str, indents: &Indents) -> String {
+    let heading = string
+        .chars()
+        .take_while(|&character| character == indents.heading)
+        .count();
+
+    if heading > 0 && string[heading..].starts_with(SPACE) {
+        let (marker, text) = string.split_at(heading + 1);
+
+        return format!(
+            "{}{}",
+            styled(MARKER_STYLE, marker),
+            styled(HEADING_STYLE, &inline(text))
+        );
+    }
+
+    let mut bullet = String::new();
+
+    bullet.push(indents.bullet);
+    bullet.push(SPACE);
+
+    if let Some(text) = string.strip_prefix(&bullet) {
+        let mut marker = String::new();
+
+        marker.push(indents.bullet);
+
+        return format!("{} {}", styled(BULLET_STYLE, &marker), inline(text));
+    }
+
+    inline(string)
+}
+
+/// Renders the given Markdown entry for terminals, using the given [`Indents`].
+pub fn render(entry: &str, indents: &Indents) -> String {
+    entry
+        .lines()
+        .map(|string| line(string, indents))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,65 @@
+//! Reporting warnings and setting up logging.
+//!
+//! This module provides two notable functions: [`init`] and [`warn`].
+//! Errors can also be reported on one line, see [`chain`].
+
+#[cfg(feature = "cli")]
+use std::io::{stderr, IsTerminal};
+
+use miette::{Diagnostic, Report};
+use tracing::{level_filters::LevelFilter, Level};
+
+/// Returns [`LevelFilter`] corresponding to the given verbosity.
+///
+/// Quiet runs only show errors, while each level of verbosity enables more detailed logs.
+pub fn level(verbose: u8, quiet: bool) -> LevelFilter {
+    if quiet {
+        return LevelFilter::ERROR;
+    }
+
+    match verbose {
+        0 => LevelFilter::WARN,
+        1 => LevelFilter::INFO,
+        2 => LevelFilter::DEBUG,
+        _ => LevelFilter::TRACE,
+    }
+}
+
+/// Initializes logging to `stderr` with the given maximum level.
+///
+/// Does nothing if logging is already initialized.
+#[cfg(feature = "cli")]
+pub fn init(level: LevelFilter) {
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(stderr)
+        .with_ansi(stderr().is_terminal())
+        .with_target(false)
+        .without_time()
+        .try_init();
+}
+
+const SEPARATOR: &str = ": ";
+
+/// Joins the given error and its sources into one line, separating them with colons.
+pub fn chain(error: &dyn std::error::Error) -> String {
+    let mut message = error.to_string();
+
+    let mut source = error.source();
+
+    while let Some(error) = source {
+        message.push_str(SEPARATOR);
+        message.push_str(&error.to_string());
+
+        source = error.source();
+    }
+
+    message
+}
+
+/// Reports the given diagnostic as a warning to `stderr`, unless warnings are disabled.
+pub fn warn<D: Diagnostic + Send + Sync + 'static>(diagnostic: D) {
+    if tracing::enabled!(Level::WARN) {
+        eprintln!("{:?}", Report::new(diagnostic));
+    }
+}
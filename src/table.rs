@@ -0,0 +1,92 @@
+//! Comparing TOML tables.
+//!
+//! This module provides [`Table`], wrapping [`toml::Table`] so that structures containing
+//! arbitrary TOML values (like front matter of fragments or extra fields of contexts)
+//! can implement [`Eq`].
+
+use std::{
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+/// Wraps [`toml::Table`], implementing [`Eq`].
+///
+/// Tables are compared via [`PartialEq`], so tables containing NaN floats
+/// are not equal to themselves, which is the only way [`Eq`] does not hold.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Table(pub toml::Table);
+
+impl Eq for Table {}
+
+impl Table {
+    /// Constructs the empty [`Self`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the wrapped table.
+    pub fn into_inner(self) -> toml::Table {
+        self.0
+    }
+}
+
+impl From<toml::Table> for Table {
+    fn from(table: toml::Table) -> Self {
+        Self(table)
+    }
+}
+
+impl From<Table> for toml::Table {
+    fn from(table: Table) -> Self {
+        table.into_inner()
+    }
+}
+
+impl Deref for Table {
+    type Target = toml::Table;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Table {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl FromIterator<(String, toml::Value)> for Table {
+    fn from_iter<I: IntoIterator<Item = (String, toml::Value)>>(iterable: I) -> Self {
+        Self(iterable.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Table {
+    type Item = (String, toml::Value);
+    type IntoIter = <toml::Table as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'t> IntoIterator for &'t Table {
+    type Item = (&'t String, &'t toml::Value);
+    type IntoIter = <&'t toml::Table as IntoIterator>::IntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl FromStr for Table {
+    type Err = toml::de::Error;
+
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        string.parse().map(Self)
+    }
+}
@@ -0,0 +1,44 @@
+//! Handling byte order marks and line endings.
+//!
+//! This module provides [`strip_bom`], [`normalize`] and [`is_crlf`] used when reading text,
+//! and [`to_crlf`] used when writing it.
+
+use std::borrow::Cow;
+
+/// The byte order mark.
+pub const BOM: char = '\u{feff}';
+
+/// The Unix line ending.
+pub const LF: &str = "\n";
+
+/// The Windows line ending.
+pub const CRLF: &str = "\r\n";
+
+/// Strips the leading byte order mark from the given string, if present.
+pub fn strip_bom(string: &str) -> &str {
+    string.strip_prefix(BOM).unwrap_or(string)
+}
+
+/// Normalizes line endings of the given string to `\n`.
+pub fn normalize(string: &str) -> Cow<'_, str> {
+    if string.contains(CRLF) {
+        Cow::Owned(string.replace(CRLF, LF))
+    } else {
+        Cow::Borrowed(string)
+    }
+}
+
+/// Checks whether `\r\n` is the dominant line ending of the given string.
+///
+/// Ties (including strings without line endings) are resolved in favor of `\n`.
+pub fn is_crlf(string: &str) -> bool {
+    let crlf = string.matches(CRLF).count();
+    let lf = string.matches(LF).count() - crlf;
+
+    crlf > lf
+}
+
+/// Converts line endings of the given normalized string to `\r\n`.
+pub fn to_crlf(string: &str) -> String {
+    string.replace(LF, CRLF)
+}
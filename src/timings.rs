@@ -0,0 +1,125 @@
+//! Measuring builds.
+//!
+//! [`Timings`] accumulate how long each [`Phase`] of building takes, along with the number
+//! of fragments processed. Builders only measure when given timings, see [`with_timings`].
+//!
+//! [`with_timings`]: crate::builder::Builder::with_timings
+
+use std::{
+    cell::Cell,
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// Represents phases of building.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Phase {
+    /// Reading and parsing fragments.
+    Collecting,
+    /// Rendering entries.
+    Rendering,
+    /// Writing changelogs and release notes.
+    Writing,
+}
+
+impl Phase {
+    /// Every phase, in order.
+    pub const ALL: [Self; 3] = [Self::Collecting, Self::Rendering, Self::Writing];
+
+    /// Returns the name of the phase.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Collecting => "collecting",
+            Self::Rendering => "rendering",
+            Self::Writing => "writing",
+        }
+    }
+}
+
+/// Represents accumulated timings of building.
+///
+/// Timings are recorded through shared references, so that builders can record them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Timings {
+    collecting: Cell<Duration>,
+    rendering: Cell<Duration>,
+    writing: Cell<Duration>,
+    fragments: Cell<usize>,
+}
+
+impl Timings {
+    /// Constructs [`Self`] with nothing recorded.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn cell(&self, phase: Phase) -> &Cell<Duration> {
+        match phase {
+            Phase::Collecting => &self.collecting,
+            Phase::Rendering => &self.rendering,
+            Phase::Writing => &self.writing,
+        }
+    }
+
+    /// Returns the time spent in the given phase.
+    pub fn get(&self, phase: Phase) -> Duration {
+        self.cell(phase).get()
+    }
+
+    /// Returns the total time spent in every phase.
+    pub fn total(&self) -> Duration {
+        Phase::ALL.into_iter().map(|phase| self.get(phase)).sum()
+    }
+
+    /// Returns the number of fragments processed.
+    pub fn fragments(&self) -> usize {
+        self.fragments.get()
+    }
+
+    /// Adds the given duration to the given phase.
+    pub fn record(&self, phase: Phase, duration: Duration) {
+        let cell = self.cell(phase);
+
+        cell.set(cell.get() + duration);
+    }
+
+    /// Adds the given number of processed fragments.
+    pub fn count(&self, fragments: usize) {
+        self.fragments.set(self.fragments.get() + fragments);
+    }
+
+    /// Calls the given function, adding the time it takes to the given phase.
+    pub fn time<T, F: FnOnce() -> T>(&self, phase: Phase, function: F) -> T {
+        let start = Instant::now();
+
+        let result = function();
+
+        self.record(phase, start.elapsed());
+
+        result
+    }
+
+    /// Adds everything recorded by the other timings to [`Self`].
+    pub fn merge(&self, other: &Self) {
+        for phase in Phase::ALL {
+            self.record(phase, other.get(phase));
+        }
+
+        self.count(other.fragments());
+    }
+}
+
+impl fmt::Display for Timings {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for phase in Phase::ALL {
+            writeln!(formatter, "{}: {:.2?}", phase.name(), self.get(phase))?;
+        }
+
+        write!(
+            formatter,
+            "total: {:.2?} ({} fragment(s))",
+            self.total(),
+            self.fragments()
+        )
+    }
+}
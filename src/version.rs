@@ -0,0 +1,199 @@
+//! Versions.
+//!
+//! This module provides functions to detect prerelease versions, such as `1.2.0-rc.1`.
+//! Prereleases are denoted by the `-` separator, while the build metadata (that is,
+//! anything after the `+` separator) is ignored.
+//!
+//! It also provides [`Policy`] that specifies how versions are validated, see [`check`],
+//! and [`Bump`] that represents kinds of releases, see [`suggest`].
+
+use miette::Diagnostic;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// The separator of prereleases.
+pub const PRERELEASE: char = '-';
+
+/// The separator of build metadata.
+pub const BUILD: char = '+';
+
+/// Strips the build metadata from the given version.
+pub fn strip_build(version: &str) -> &str {
+    version
+        .split_once(BUILD)
+        .map_or(version, |(version, _)| version)
+}
+
+/// Returns the build metadata of the given version, if any.
+pub fn build_metadata(version: &str) -> Option<&str> {
+    version
+        .split_once(BUILD)
+        .map(|(_, metadata)| metadata)
+        .filter(|metadata| !metadata.is_empty())
+}
+
+/// Splits the given version into the base version and the prerelease, if any.
+pub fn split_prerelease(version: &str) -> (&str, Option<&str>) {
+    let version = strip_build(version);
+
+    match version.split_once(PRERELEASE) {
+        Some((base, prerelease)) if !prerelease.is_empty() => (base, Some(prerelease)),
+        _ => (version, None),
+    }
+}
+
+/// Returns the prerelease of the given version, if any.
+pub fn prerelease(version: &str) -> Option<&str> {
+    split_prerelease(version).1
+}
+
+/// Checks whether the given version is the prerelease.
+pub fn is_prerelease(version: &str) -> bool {
+    prerelease(version).is_some()
+}
+
+/// Checks whether the given version is the prerelease of the `base` version.
+pub fn is_prerelease_of(version: &str, base: &str) -> bool {
+    matches!(split_prerelease(version), (other, Some(_)) if other == strip_build(base))
+}
+
+/// The prefix of slugs.
+pub const SLUG_PREFIX: char = 'v';
+
+/// The separator used in slugs.
+pub const SLUG_SEPARATOR: char = '-';
+
+/// Derives the slug from the given version, suitable for use as the anchor.
+///
+/// The slug is the version prefixed with `v`, lowercased, with runs of characters other than
+/// ASCII letters and digits replaced by `-` (for instance, `1.2.0-rc.1` gives `v1-2-0-rc-1`).
+/// The leading `v` of the version is not duplicated.
+pub fn slug(version: &str) -> String {
+    let version = version.strip_prefix(SLUG_PREFIX).unwrap_or(version);
+
+    let mut slug = String::from(SLUG_PREFIX);
+
+    let mut separated = false;
+
+    for character in version.chars() {
+        if character.is_ascii_alphanumeric() {
+            if separated && slug.len() > 1 {
+                slug.push(SLUG_SEPARATOR);
+            }
+
+            separated = false;
+
+            slug.push(character.to_ascii_lowercase());
+        } else {
+            separated = true;
+        }
+    }
+
+    slug
+}
+
+/// Represents policies of validating versions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Policy {
+    /// Accept any version.
+    #[default]
+    Any,
+    /// Require versions to follow [semantic versioning](https://semver.org/).
+    Semver,
+}
+
+/// Represents errors that can occur when versions do not follow semantic versioning.
+#[derive(Debug, Error, Diagnostic)]
+#[error("version `{version}` does not follow semantic versioning")]
+#[diagnostic(
+    code(changelogging::version::invalid),
+    help("use versions like `1.2.0` or `1.2.0-rc.1`, or set `version` to `any`")
+)]
+pub struct InvalidError {
+    /// The source of this error.
+    #[source]
+    pub source: semver::Error,
+    /// The invalid version.
+    pub version: String,
+}
+
+impl InvalidError {
+    /// Constructs [`Self`].
+    pub fn new(source: semver::Error, version: String) -> Self {
+        Self { source, version }
+    }
+}
+
+/// Parses the given version according to semantic versioning.
+///
+/// # Errors
+///
+/// Returns [`InvalidError`] if the version does not follow semantic versioning.
+pub fn parse(version: &str) -> Result<Version, InvalidError> {
+    Version::parse(version).map_err(|error| InvalidError::new(error, version.to_owned()))
+}
+
+/// The types that suggest major releases.
+pub const MAJOR_TYPES: [&str; 1] = ["removal"];
+
+/// The types that suggest minor releases.
+pub const MINOR_TYPES: [&str; 3] = ["feature", "change", "deprecation"];
+
+/// Represents kinds of releases, ordered by significance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Bump {
+    /// Patch releases.
+    Patch,
+    /// Minor releases.
+    Minor,
+    /// Major releases.
+    Major,
+}
+
+impl Bump {
+    /// Returns the kind of release suggested by fragments of the given type.
+    ///
+    /// Types found in [`MAJOR_TYPES`] and [`MINOR_TYPES`] suggest major and minor releases,
+    /// respectively, while any other type suggests patch releases.
+    pub fn for_type(type_name: &str) -> Self {
+        if MAJOR_TYPES.contains(&type_name) {
+            Self::Major
+        } else if MINOR_TYPES.contains(&type_name) {
+            Self::Minor
+        } else {
+            Self::Patch
+        }
+    }
+
+    /// Returns the string representation of [`Self`].
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        }
+    }
+}
+
+/// Suggests the kind of release given the types of pending fragments, see [`Bump::for_type`].
+///
+/// Returns [`None`] if there are no types, that is, no release is needed.
+pub fn suggest<'t, I: IntoIterator<Item = &'t str>>(type_names: I) -> Option<Bump> {
+    type_names.into_iter().map(Bump::for_type).max()
+}
+
+/// Checks the given version according to the given [`Policy`].
+///
+/// # Errors
+///
+/// Returns [`InvalidError`] if the version is required to follow semantic versioning,
+/// but does not.
+pub fn check(version: &str, policy: Policy) -> Result<(), InvalidError> {
+    match policy {
+        Policy::Any => Ok(()),
+        Policy::Semver => parse(version).map(|_| ()),
+    }
+}
@@ -4,19 +4,66 @@
 //!
 //! See also [`context`] and [`config`].
 //!
+//! # Profiles
+//!
+//! Workspaces can define *profiles*, which are tables under `profile.<name>` that override
+//! the base settings when selected (for instance, with the `--profile` option):
+//!
+//! ```toml
+//! [profile.user]
+//! order = ["security", "feature", "fix"]
+//!
+//! [profile.user.paths]
+//! output = "RELEASES.md"
+//! ```
+//!
+//! Tables are merged recursively, while all other values (including arrays) are replaced.
+//! Paths specified in profiles are resolved the same way as the base ones
+//! (see `paths.relative_to` in [`config`]).
+//!
+//! See [`Workspace::with_profile`] for more information.
+//!
+//! # Context files
+//!
+//! The `context` section can be supplied (or overridden) by the separate [`CONTEXT_FILE`]
+//! next to the config file (or any other file, see [`Workspace::load_with_context`]),
+//! keeping machine-generated information (for instance, versions) out of the main config:
+//!
+//! ```toml
+//! version = "0.8.0"
+//! ```
+//!
+//! Keys of context files (which can also be wrapped into the `context` section)
+//! replace the ones of the `context` section.
+//!
 //! [`context`]: crate::context
 //! [`config`]: crate::config
 
+#[cfg(feature = "fs")]
+use std::fs::read_to_string;
 use std::{
-    fs::read_to_string,
+    collections::HashMap,
+    fmt,
     path::{Path, PathBuf},
 };
 
+use itertools::Itertools;
 use miette::Diagnostic;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use toml::{Table, Value};
 
-use crate::{config::Config, context::Context, load::Load};
+#[cfg(feature = "async")]
+use crate::load::LoadAsync;
+use crate::{
+    config::{resolve_path, Config, RelativeTo},
+    context::Context,
+};
+#[cfg(feature = "fs")]
+use crate::{
+    context::{check, CONTEXT},
+    load::Load,
+};
 
 /// Represents errors that can occur when reading files.
 #[derive(Debug, Error, Diagnostic)]
@@ -28,13 +75,15 @@ use crate::{config::Config, context::Context, load::Load};
 pub struct ReadError(#[from] pub std::io::Error);
 
 /// Represents errors that can occur when parsing TOML configuration into concrete types.
+///
+/// The underlying error is boxed, since it is large.
 #[derive(Debug, Error, Diagnostic)]
 #[error("parsing failed")]
 #[diagnostic(
     code(changelogging::workspace::parse),
     help("check that the configuration is correct")
 )]
-pub struct ParseError(#[from] pub toml::de::Error);
+pub struct ParseError(#[from] pub Box<toml::de::Error>);
 
 /// Represents sources of errors that can occur when loading workspaces.
 #[derive(Debug, Error, Diagnostic)]
@@ -45,6 +94,8 @@ pub enum ErrorSource {
     Read(#[from] ReadError),
     /// Parse errors.
     Parse(#[from] ParseError),
+    /// Context errors.
+    Context(#[from] crate::context::Error),
 }
 
 /// Represents errors that can occur during workspace loading.
@@ -79,6 +130,13 @@ impl Error {
         Self::new(error.into(), path)
     }
 
+    /// Constructs [`Self`] from [`Error`].
+    ///
+    /// [`Error`]: crate::context::Error
+    pub fn context(error: crate::context::Error, path: PathBuf) -> Self {
+        Self::new(error.into(), path)
+    }
+
     /// Constructs [`ReadError`] and constructs [`Self`] from it.
     pub fn new_read(error: std::io::Error, path: PathBuf) -> Self {
         Self::read(ReadError(error), path)
@@ -86,7 +144,189 @@ impl Error {
 
     /// Constructs [`ParseError`] and constructs [`Self`] from it.
     pub fn new_parse(error: toml::de::Error, path: PathBuf) -> Self {
-        Self::parse(ParseError(error), path)
+        Self::parse(ParseError(Box::new(error)), path)
+    }
+}
+
+/// Represents errors that can occur when the selected profile is not defined.
+#[derive(Debug, Error, Diagnostic)]
+#[error("profile not found")]
+#[diagnostic(
+    code(changelogging::workspace::profile_not_found),
+    help("available profiles: {available}")
+)]
+pub struct ProfileNotFoundError {
+    /// The comma-separated list of available profiles.
+    pub available: String,
+}
+
+impl ProfileNotFoundError {
+    /// Constructs [`Self`].
+    pub fn new(available: String) -> Self {
+        Self { available }
+    }
+}
+
+/// Represents errors that can occur when serializing workspaces before applying profiles.
+#[derive(Debug, Error, Diagnostic)]
+#[error("serializing failed")]
+#[diagnostic(
+    code(changelogging::workspace::serialize),
+    help("this is likely a bug, please report it")
+)]
+pub struct SerializeError(#[from] pub toml::ser::Error);
+
+/// Represents errors that can occur when parsing workspaces after applying profiles.
+///
+/// The underlying error is boxed, since it is large.
+#[derive(Debug, Error, Diagnostic)]
+#[error("parsing failed")]
+#[diagnostic(
+    code(changelogging::workspace::profile_parse),
+    help("check that the profile configuration is correct")
+)]
+pub struct ProfileParseError(#[from] pub Box<toml::de::Error>);
+
+/// Represents sources of errors that can occur when applying profiles.
+#[derive(Debug, Error, Diagnostic)]
+#[error(transparent)]
+#[diagnostic(transparent)]
+pub enum ProfileErrorSource {
+    /// Profile not found errors.
+    NotFound(#[from] ProfileNotFoundError),
+    /// Serialize errors.
+    Serialize(#[from] SerializeError),
+    /// Parse errors.
+    Parse(#[from] ProfileParseError),
+}
+
+/// Represents errors that can occur when applying profiles.
+#[derive(Debug, Error, Diagnostic)]
+#[error("failed to apply profile `{name}`")]
+#[diagnostic(
+    code(changelogging::workspace::profile),
+    help("see the report for more information")
+)]
+pub struct ProfileError {
+    /// The source of this error.
+    #[source]
+    #[diagnostic_source]
+    pub source: ProfileErrorSource,
+    /// The name of the profile.
+    pub name: String,
+}
+
+impl ProfileError {
+    /// Constructs [`Self`].
+    pub fn new(source: ProfileErrorSource, name: String) -> Self {
+        Self { source, name }
+    }
+
+    /// Constructs [`Self`] from [`ProfileNotFoundError`].
+    pub fn not_found(error: ProfileNotFoundError, name: String) -> Self {
+        Self::new(error.into(), name)
+    }
+
+    /// Constructs [`Self`] from [`SerializeError`].
+    pub fn serialize(error: SerializeError, name: String) -> Self {
+        Self::new(error.into(), name)
+    }
+
+    /// Constructs [`Self`] from [`ProfileParseError`].
+    pub fn parse(error: ProfileParseError, name: String) -> Self {
+        Self::new(error.into(), name)
+    }
+
+    /// Constructs [`ProfileNotFoundError`] and constructs [`Self`] from it.
+    pub fn new_not_found(available: String, name: String) -> Self {
+        Self::not_found(ProfileNotFoundError::new(available), name)
+    }
+
+    /// Constructs [`SerializeError`] and constructs [`Self`] from it.
+    pub fn new_serialize(error: toml::ser::Error, name: String) -> Self {
+        Self::serialize(SerializeError(error), name)
+    }
+
+    /// Constructs [`ProfileParseError`] and constructs [`Self`] from it.
+    pub fn new_parse(error: toml::de::Error, name: String) -> Self {
+        Self::parse(ProfileParseError(Box::new(error)), name)
+    }
+}
+
+/// Represents profiles, which are tables overriding the base settings.
+pub type Profile = crate::table::Table;
+
+/// Maps names to their [`Profile`] values.
+pub type Profiles = HashMap<String, Profile>;
+
+/// Represents files that workspaces are loaded from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Source {
+    /// Discovered `changelogging.toml` files.
+    Changelogging(PathBuf),
+    /// Discovered `pyproject.toml` files containing the `tool.changelogging` section.
+    PyProject(PathBuf),
+    /// Config files given explicitly, for instance, via `--config`.
+    Config(PathBuf),
+}
+
+impl Source {
+    /// Returns the path to the file.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Changelogging(path) | Self::PyProject(path) | Self::Config(path) => path,
+        }
+    }
+}
+
+#[cfg(feature = "fs")]
+impl Source {
+    /// Reads the table of the file as written, that is, without applying defaults.
+    ///
+    /// For `pyproject.toml` files, the `tool.changelogging` section is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if reading or parsing the file fails.
+    pub fn read_table(&self) -> Result<Table, Error> {
+        let path = self.path();
+
+        let string =
+            read_to_string(path).map_err(|error| Error::new_read(error, path.to_owned()))?;
+
+        let mut table: Table =
+            toml::from_str(&string).map_err(|error| Error::new_parse(error, path.to_owned()))?;
+
+        if let Self::PyProject(_) = self {
+            let inner = match table.remove(TOOL) {
+                Some(Value::Table(mut tools)) => match tools.remove(CHANGELOGGING) {
+                    Some(Value::Table(inner)) => inner,
+                    _ => Table::new(),
+                },
+                _ => Table::new(),
+            };
+
+            return Ok(inner);
+        }
+
+        Ok(table)
+    }
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path().display();
+
+        match self {
+            Self::Changelogging(_) => {
+                write!(formatter, "`{path}` (discovered `changelogging.toml`)")
+            }
+            Self::PyProject(_) => write!(
+                formatter,
+                "`{path}` (discovered `tool.changelogging` in `pyproject.toml`)"
+            ),
+            Self::Config(_) => write!(formatter, "`{path}` (given via `--config`)"),
+        }
     }
 }
 
@@ -100,28 +340,343 @@ pub struct Workspace<'w> {
     /// This field is flattened during (de)serialization.
     #[serde(flatten)]
     pub config: Config<'w>,
+    /// The profiles of the workspace.
+    ///
+    /// This field is never serialized.
+    #[serde(default, skip_serializing)]
+    pub profile: Profiles,
+    /// The directory containing the config file, if paths are resolved against it.
+    ///
+    /// This field is never (de)serialized, see [`resolve`].
+    ///
+    /// [`resolve`]: Self::resolve
+    #[serde(skip)]
+    pub root: Option<PathBuf>,
+    /// The file this workspace was loaded from, if known.
+    ///
+    /// This field is never (de)serialized.
+    #[serde(skip)]
+    pub source: Option<Source>,
 }
 
 impl<'w> Workspace<'w> {
     /// Constructs [`Self`].
     pub fn new(context: Context<'w>, config: Config<'w>) -> Self {
-        Self { context, config }
+        Self::with_profiles(context, config, Profiles::new())
+    }
+
+    /// Constructs [`Self`] with the given profiles.
+    pub fn with_profiles(context: Context<'w>, config: Config<'w>, profile: Profiles) -> Self {
+        Self {
+            context,
+            config,
+            profile,
+            root: None,
+            source: None,
+        }
+    }
+
+    /// Returns the copy of [`Self`] recording the file it was loaded from.
+    #[must_use]
+    pub fn with_source(self, source: Source) -> Self {
+        Self {
+            source: Some(source),
+            ..self
+        }
+    }
+
+    /// Resolves relative paths against the given root, that is, the directory containing
+    /// the config file, remembering it for profiles (see [`Paths::resolve`]).
+    ///
+    /// This should be called once, right after loading.
+    ///
+    /// [`Paths::resolve`]: crate::config::Paths::resolve
+    pub fn resolve(&mut self, root: PathBuf) {
+        self.config.paths.resolve(&root);
+
+        self.root = Some(root);
+    }
+
+    /// Resolves the given path, relative to the project, against the root, if any.
+    ///
+    /// This is used for files managed by `changelogging` itself, like the cache.
+    pub fn project_path<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        project_path(self.root.as_deref(), path.as_ref())
     }
 }
 
+/// Resolves the given path, relative to the project, against the given root, if any,
+/// returning the path as it is otherwise (that is, relative to the current directory).
+pub fn project_path(root: Option<&Path>, path: &Path) -> PathBuf {
+    root.map_or_else(|| path.to_owned(), |root| root.join(path))
+}
+
+/// The `paths` key.
+pub const PATHS: &str = "paths";
+
+/// The `relative_to` key.
+pub const RELATIVE_TO: &str = "relative_to";
+
+/// The keys of paths within the `paths` table.
+pub const PATH_KEYS: [&str; 3] = ["directory", "output", "archive"];
+
+// resolves paths specified in profiles, as the base ones are already resolved
+
+fn resolve_profile(profile: &mut Table, root: &Path, relative_to: RelativeTo) {
+    let Some(Value::Table(paths)) = profile.get_mut(PATHS) else {
+        return;
+    };
+
+    let relative_to = paths
+        .get(RELATIVE_TO)
+        .cloned()
+        .and_then(|value| value.try_into().ok())
+        .unwrap_or(relative_to);
+
+    if relative_to != RelativeTo::Config {
+        return;
+    }
+
+    for key in PATH_KEYS {
+        if let Some(Value::String(string)) = paths.get_mut(key) {
+            if let Some(resolved) = resolve_path(root, Path::new(string.as_str())).to_str() {
+                *string = resolved.to_owned();
+            }
+        }
+    }
+}
+
+fn merge(table: &mut Table, other: Table) {
+    for (key, value) in other {
+        match (table.get_mut(&key), value) {
+            (Some(Value::Table(inner)), Value::Table(other_inner)) => merge(inner, other_inner),
+            (_, value) => {
+                table.insert(key, value);
+            }
+        }
+    }
+}
+
+impl Workspace<'_> {
+    /// Applies the profile with the given name, returning the resulting workspace.
+    ///
+    /// The profile table is merged into the workspace recursively,
+    /// replacing all non-table values (including arrays).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProfileError`] if the profile is not defined or is invalid.
+    pub fn with_profile<S: AsRef<str>>(&self, name: S) -> Result<Workspace<'static>, ProfileError> {
+        let name = name.as_ref();
+
+        let mut profile = self.profile.get(name).cloned().ok_or_else(|| {
+            let available = self.profile.keys().sorted().join(", ");
+
+            ProfileError::new_not_found(available, name.to_owned())
+        })?;
+
+        if let Some(root) = self.root.as_deref() {
+            resolve_profile(&mut profile, root, self.config.paths.relative_to);
+        }
+
+        let mut table = Table::try_from(self)
+            .map_err(|error| ProfileError::new_serialize(error, name.to_owned()))?;
+
+        merge(&mut table, profile.into_inner());
+
+        let mut workspace: Workspace<'static> = Value::Table(table)
+            .try_into()
+            .map_err(|error| ProfileError::new_parse(error, name.to_owned()))?;
+
+        workspace.profile.clone_from(&self.profile);
+
+        workspace.root.clone_from(&self.root);
+
+        workspace.source.clone_from(&self.source);
+
+        Ok(workspace)
+    }
+
+    /// Applies profiles listed in `outputs` (see [`config`]), returning the workspaces
+    /// to build additional changelogs from.
+    ///
+    /// Note that `outputs` of the resulting workspaces are cleared.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ProfileError`] if any of the profiles is not defined or is invalid.
+    ///
+    /// [`config`]: crate::config
+    pub fn outputs(&self) -> Result<Vec<Workspace<'static>>, ProfileError> {
+        self.config
+            .outputs
+            .iter()
+            .map(|name| {
+                let mut workspace = self.with_profile(name)?;
+
+                workspace.config.outputs.clear();
+
+                Ok(workspace)
+            })
+            .collect()
+    }
+}
+
+/// The `tool` key.
+pub const TOOL: &str = "tool";
+
+/// The `changelogging` key.
+pub const CHANGELOGGING: &str = "changelogging";
+
+/// The `context.toml` literal.
+pub const CONTEXT_FILE: &str = "context.toml";
+
+/// Returns the path to [`CONTEXT_FILE`] next to the config file at the given path,
+/// provided that it exists.
+#[cfg(feature = "fs")]
+pub fn context_file(path: &Path) -> Option<PathBuf> {
+    let path = path.with_file_name(CONTEXT_FILE);
+
+    path.is_file().then_some(path)
+}
+
+// context files can also wrap their keys into the `context` section
+
+#[cfg(feature = "fs")]
+fn parse_context(string: &str, path: &Path) -> Result<Table, Error> {
+    let mut table: Table =
+        toml::from_str(string).map_err(|error| Error::new_parse(error, path.to_owned()))?;
+
+    if table.len() == 1 {
+        if let Some(Value::Table(context)) = table.remove(CONTEXT) {
+            return Ok(context);
+        }
+    }
+
+    Ok(table)
+}
+
+#[cfg(feature = "fs")]
+fn read_context(path: &Path) -> Result<Table, Error> {
+    let string = read_to_string(path).map_err(|error| Error::new_read(error, path.to_owned()))?;
+
+    parse_context(&string, path)
+}
+
+#[cfg(feature = "async")]
+async fn read_context_async(path: &Path) -> Result<Option<Table>, Error> {
+    let path = path.with_file_name(CONTEXT_FILE);
+
+    if !tokio::fs::try_exists(&path).await.unwrap_or_default() {
+        return Ok(None);
+    }
+
+    let string = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|error| Error::new_read(error, path.clone()))?;
+
+    parse_context(&string, &path).map(Some)
+}
+
+#[cfg(feature = "fs")]
+fn override_context(table: &mut Table, context: Table) {
+    match table.get_mut(CONTEXT) {
+        Some(Value::Table(existing)) => existing.extend(context),
+        _ => {
+            table.insert(CONTEXT.to_owned(), Value::Table(context));
+        }
+    }
+}
+
+// when parsing fails, check whether it is due to the incomplete context to report it clearly
+
+#[cfg(feature = "fs")]
+fn parse_error(error: toml::de::Error, table: Option<&Table>, path: &Path) -> Error {
+    match table.map(check) {
+        Some(Err(context)) => Error::context(context, path.to_owned()),
+        _ => Error::new_parse(error, path.to_owned()),
+    }
+}
+
+#[cfg(feature = "fs")]
+fn parse_workspace(
+    string: &str,
+    path: &Path,
+    context: Option<Table>,
+) -> Result<Workspace<'static>, Error> {
+    let Some(context) = context else {
+        return toml::from_str(string).map_err(|error| {
+            let table: Option<Table> = toml::from_str(string).ok();
+
+            parse_error(error, table.as_ref(), path)
+        });
+    };
+
+    let mut table: Table =
+        toml::from_str(string).map_err(|error| Error::new_parse(error, path.to_owned()))?;
+
+    override_context(&mut table, context);
+
+    Value::Table(table.clone())
+        .try_into()
+        .map_err(|error| parse_error(error, Some(&table), path))
+}
+
+#[cfg(feature = "fs")]
+impl Workspace<'_> {
+    /// Loads the workspace from the given path, supplying (or overriding) the `context` section
+    /// with the given context file instead of [`CONTEXT_FILE`] next to the config file.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if reading or parsing either of the files fails.
+    pub fn load_with_context<P: AsRef<Path>, C: AsRef<Path>>(
+        path: P,
+        context: C,
+    ) -> Result<Workspace<'static>, Error> {
+        let path = path.as_ref();
+
+        let context = read_context(context.as_ref())?;
+
+        let string =
+            read_to_string(path).map_err(|error| Error::new_read(error, path.to_owned()))?;
+
+        parse_workspace(&string, path, Some(context))
+    }
+}
+
+#[cfg(feature = "fs")]
 impl Load for Workspace<'_> {
     type Error = Error;
 
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, Self::Error> {
         let path = path.as_ref();
 
+        let context = context_file(path)
+            .map(|context| read_context(&context))
+            .transpose()?;
+
         let string =
             read_to_string(path).map_err(|error| Self::Error::new_read(error, path.to_owned()))?;
 
-        let workspace = toml::from_str(&string)
-            .map_err(|error| Self::Error::new_parse(error, path.to_owned()))?;
+        parse_workspace(&string, path, context)
+    }
+}
 
-        Ok(workspace)
+#[cfg(feature = "async")]
+impl LoadAsync for Workspace<'_> {
+    type Error = Error;
+
+    async fn load_async<P: AsRef<Path> + Send>(path: P) -> Result<Self, Self::Error> {
+        let path = path.as_ref();
+
+        let context = read_context_async(path).await?;
+
+        let string = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|error| Self::Error::new_read(error, path.to_owned()))?;
+
+        parse_workspace(&string, path, context)
     }
 }
 
@@ -139,19 +694,98 @@ pub struct PyProject<'p> {
     pub tool: Option<Tools<'p>>,
 }
 
+#[cfg(feature = "fs")]
+fn parse_pyproject(
+    string: &str,
+    path: &Path,
+    context: Option<Table>,
+) -> Result<PyProject<'static>, Error> {
+    fn inner(table: &Table) -> Option<&Table> {
+        table
+            .get(TOOL)
+            .and_then(Value::as_table)
+            .and_then(|tools| tools.get(CHANGELOGGING))
+            .and_then(Value::as_table)
+    }
+
+    let Some(context) = context else {
+        return toml::from_str(string).map_err(|error| {
+            let table: Option<Table> = toml::from_str(string).ok();
+
+            parse_error(error, table.as_ref().and_then(inner), path)
+        });
+    };
+
+    let mut table: Table =
+        toml::from_str(string).map_err(|error| Error::new_parse(error, path.to_owned()))?;
+
+    // the context is only supplied if the `tool.changelogging` section is present
+
+    if let Some(Value::Table(tools)) = table.get_mut(TOOL) {
+        if let Some(Value::Table(workspace)) = tools.get_mut(CHANGELOGGING) {
+            override_context(workspace, context);
+        }
+    }
+
+    Value::Table(table.clone())
+        .try_into()
+        .map_err(|error| parse_error(error, inner(&table), path))
+}
+
+#[cfg(feature = "fs")]
+impl PyProject<'_> {
+    /// Similar to [`Workspace::load_with_context`], except `pyproject.toml` is loaded.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`struct@Error`] if reading or parsing either of the files fails.
+    pub fn load_with_context<P: AsRef<Path>, C: AsRef<Path>>(
+        path: P,
+        context: C,
+    ) -> Result<PyProject<'static>, Error> {
+        let path = path.as_ref();
+
+        let context = read_context(context.as_ref())?;
+
+        let string =
+            read_to_string(path).map_err(|error| Error::new_read(error, path.to_owned()))?;
+
+        parse_pyproject(&string, path, Some(context))
+    }
+}
+
+#[cfg(feature = "fs")]
 impl Load for PyProject<'_> {
     type Error = Error;
 
     fn load<P: AsRef<Path>>(path: P) -> Result<Self, Self::Error> {
         let path = path.as_ref();
 
+        let context = context_file(path)
+            .map(|context| read_context(&context))
+            .transpose()?;
+
         let string =
             read_to_string(path).map_err(|error| Self::Error::new_read(error, path.to_owned()))?;
 
-        let workspace = toml::from_str(&string)
-            .map_err(|error| Self::Error::new_parse(error, path.to_owned()))?;
+        parse_pyproject(&string, path, context)
+    }
+}
+
+#[cfg(feature = "async")]
+impl LoadAsync for PyProject<'_> {
+    type Error = Error;
 
-        Ok(workspace)
+    async fn load_async<P: AsRef<Path> + Send>(path: P) -> Result<Self, Self::Error> {
+        let path = path.as_ref();
+
+        let context = read_context_async(path).await?;
+
+        let string = tokio::fs::read_to_string(path)
+            .await
+            .map_err(|error| Self::Error::new_read(error, path.to_owned()))?;
+
+        parse_pyproject(&string, path, context)
     }
 }
 